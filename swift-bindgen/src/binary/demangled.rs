@@ -0,0 +1,133 @@
+//! Conversion of demangled type names to IR types.
+//!
+//! Field types and generic requirements are recorded as mangled names, which
+//! are demangled by [`swift_sys::mangling`] into a tree of nodes. Types that
+//! bindings can't refer to by name, such as function types and private
+//! types, are not converted.
+
+use crate::ir::{PathComponent, TupleElement, Type, TypePath};
+use swift_sys::mangling::{Node, NodeKind};
+
+/// Converts the demangled type `node` into an IR type, using sugar where
+/// Swift would.
+pub(crate) fn ty(node: &Node) -> Option<Type> {
+    match node.kind() {
+        NodeKind::Type => ty(node.child(0)?),
+        NodeKind::BoundGenericStructure | NodeKind::BoundGenericEnum => {
+            let mut path = path(node)?;
+            let name = path_name(&path);
+            let args = &mut path.components.last_mut()?.generic_args;
+            let sugared = match (name.as_str(), args.len()) {
+                ("Swift.Array", 1) => Type::Array(Box::new(args.remove(0))),
+                ("Swift.Optional", 1) => Type::Optional(Box::new(args.remove(0))),
+                ("Swift.Dictionary", 2) => {
+                    let value = args.remove(1);
+                    Type::Dictionary(Box::new(args.remove(0)), Box::new(value))
+                }
+                _ => return Some(Type::Named(path)),
+            };
+            Some(sugared)
+        }
+        NodeKind::Tuple => node
+            .children()
+            .iter()
+            .map(tuple_element)
+            .collect::<Option<_>>()
+            .map(Type::Tuple),
+        NodeKind::DependentGenericParamType => {
+            let depth = node.child(0)?.index()?;
+            let index = node.child(1)?.index()?;
+            Some(Type::named(&format!("τ_{}_{}", depth, index)))
+        }
+        NodeKind::Metatype | NodeKind::ExistentialMetatype => {
+            // The instance type may be preceded by a representation.
+            Some(Type::Metatype(Box::new(ty(node.children().last()?)?)))
+        }
+        NodeKind::ProtocolList => existential(node.child(0)?, None),
+        NodeKind::ProtocolListWithAnyObject => existential(
+            node.child(0)?.child(0)?,
+            Some(Type::named("Swift.AnyObject")),
+        ),
+        NodeKind::ProtocolListWithClass => {
+            let superclass = ty(node.child(1)?)?;
+            existential(node.child(0)?.child(0)?, Some(superclass))
+        }
+        _ => path(node).map(Type::Named),
+    }
+}
+
+/// Returns the path of the nominal type `node`, without sugar.
+fn path(node: &Node) -> Option<TypePath> {
+    match node.kind() {
+        NodeKind::Type => path(node.child(0)?),
+        NodeKind::Module => Some(TypePath {
+            components: vec![component(node.text()?)],
+        }),
+        NodeKind::Structure | NodeKind::Enum | NodeKind::Class | NodeKind::Protocol => {
+            // Private and local types have no name that bindings can use.
+            let name = node
+                .child(1)
+                .filter(|name| name.kind() == NodeKind::Identifier)?;
+            let mut path = path(node.child(0)?)?;
+            path.components.push(component(name.text()?));
+            Some(path)
+        }
+        NodeKind::Extension => path(node.child(1)?),
+        NodeKind::BoundGenericStructure
+        | NodeKind::BoundGenericEnum
+        | NodeKind::BoundGenericClass => {
+            let mut path = path(node.child(0)?)?;
+            let args = node
+                .child(1)?
+                .children()
+                .iter()
+                .map(ty)
+                .collect::<Option<_>>()?;
+            path.components.last_mut()?.generic_args = args;
+            Some(path)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the dot-separated names of `path`, without generic arguments.
+fn path_name(path: &TypePath) -> String {
+    let names: Vec<&str> = path.components.iter().map(|c| c.name.as_str()).collect();
+    names.join(".")
+}
+
+fn component(name: &str) -> PathComponent {
+    PathComponent {
+        name: name.to_owned(),
+        generic_args: Vec::new(),
+    }
+}
+
+fn tuple_element(node: &Node) -> Option<TupleElement> {
+    let mut label = None;
+    let mut element = None;
+    for child in node.children() {
+        match child.kind() {
+            NodeKind::TupleElementName => label = Some(child.text()?.to_owned()),
+            NodeKind::Type => element = Some(ty(child)?),
+            // Variadic elements only appear in parameter lists.
+            _ => return None,
+        }
+    }
+    Some(TupleElement {
+        label,
+        ty: element?,
+    })
+}
+
+/// Returns the existential of the protocols in `list` and the class or
+/// `AnyObject` constraint `class`.
+fn existential(list: &Node, class: Option<Type>) -> Option<Type> {
+    let mut types: Vec<Type> = list.children().iter().map(ty).collect::<Option<_>>()?;
+    types.extend(class);
+    match types.len() {
+        0 => Some(Type::named("Swift.Any")),
+        1 => types.pop(),
+        _ => Some(Type::Composition(types)),
+    }
+}
@@ -0,0 +1,205 @@
+//! An in-memory image of a library's allocated sections.
+
+use super::Error;
+use object::{
+    elf, Object, ObjectSection, ObjectSymbol, ObjectSymbolTable, RelocationTarget, SectionFlags,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::{TryFrom, TryInto},
+    mem, str,
+};
+
+/// The most address space an image may span.
+const MAX_SPAN: u64 = 1 << 30;
+
+/// The contents of a library's allocated sections, placed at their virtual
+/// addresses as if loaded.
+///
+/// Reflection metadata refers to other metadata by offsets relative to
+/// itself. Laying out sections at their relative addresses allows the
+/// `swift-sys` types to follow those offsets as they would in memory. Every
+/// access is bounds-checked against the image.
+pub(crate) struct Image {
+    /// The image, stored as words so that records are aligned.
+    words: Vec<u64>,
+
+    /// The address of the first byte of the image.
+    base: u64,
+    len: usize,
+
+    /// Allocated sections, as `(name, address, size)`.
+    sections: Vec<(String, u64, u64)>,
+
+    /// Addresses of exported definitions, if there is a dynamic symbol table.
+    exported: Option<HashSet<u64>>,
+
+    /// The symbols that the dynamic linker writes to each address, such as
+    /// for pointers to definitions in other libraries.
+    imports: HashMap<u64, String>,
+}
+
+impl Image {
+    /// Loads the ELF file in `bytes`.
+    pub fn load(bytes: &[u8]) -> Result<Self, Error> {
+        let file = object::File::parse(bytes).map_err(|error| Error::new(error.to_string()))?;
+        if file.format() != object::BinaryFormat::Elf {
+            return Err(Error::new("only ELF binaries are supported"));
+        }
+        if !file.is_little_endian() {
+            return Err(Error::new("only little-endian binaries are supported"));
+        }
+
+        let allocated: Vec<_> = file
+            .sections()
+            .filter(|section| match section.flags() {
+                SectionFlags::Elf { sh_flags } => sh_flags & u64::from(elf::SHF_ALLOC) != 0,
+                _ => false,
+            })
+            .filter(|section| section.size() != 0)
+            .collect();
+
+        let base = allocated.iter().map(|s| s.address()).min().unwrap_or(0);
+        let end = allocated
+            .iter()
+            .map(|s| s.address().saturating_add(s.size()))
+            .max()
+            .unwrap_or(0);
+        let span = end - base;
+        if span > MAX_SPAN {
+            return Err(Error::new("allocated sections span too much address space"));
+        }
+
+        let len = span as usize;
+        let mut image = Self {
+            words: vec![0; len.div_ceil(8)],
+            base,
+            len,
+            sections: Vec::new(),
+            exported: None,
+            imports: HashMap::new(),
+        };
+
+        for section in &allocated {
+            let name = section
+                .name()
+                .map_err(|error| Error::new(error.to_string()))?;
+            image
+                .sections
+                .push((name.to_owned(), section.address(), section.size()));
+
+            // `SHT_NOBITS` sections such as `.bss` have no data and stay zeroed.
+            let data = section
+                .data()
+                .map_err(|error| Error::new(error.to_string()))?;
+            let start = (section.address() - base) as usize;
+            image.bytes_mut()[start..][..data.len()].copy_from_slice(data);
+        }
+
+        let exported: HashSet<u64> = file
+            .dynamic_symbols()
+            .filter(|symbol| symbol.is_definition() && symbol.is_global())
+            .map(|symbol| symbol.address())
+            .collect();
+        if !exported.is_empty() {
+            image.exported = Some(exported);
+        }
+
+        if let (Some(relocations), Some(symbols)) =
+            (file.dynamic_relocations(), file.dynamic_symbol_table())
+        {
+            for (address, relocation) in relocations {
+                let symbol = match relocation.target() {
+                    RelocationTarget::Symbol(index) => symbols.symbol_by_index(index),
+                    _ => continue,
+                };
+                if let Ok(name) = symbol.and_then(|symbol| symbol.name()) {
+                    image.imports.insert(address, name.to_owned());
+                }
+            }
+        }
+
+        Ok(image)
+    }
+
+    fn bytes(&self) -> &[u8] {
+        // SAFETY: The words are initialized and at least `len` bytes long.
+        unsafe { std::slice::from_raw_parts(self.words.as_ptr().cast(), self.len) }
+    }
+
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        // SAFETY: The words are initialized and at least `len` bytes long.
+        unsafe { std::slice::from_raw_parts_mut(self.words.as_mut_ptr().cast(), self.len) }
+    }
+
+    /// Returns the address and size of the allocated section `name`.
+    pub fn section(&self, name: &str) -> Option<(u64, u64)> {
+        self.sections
+            .iter()
+            .find(|(section, _, _)| section == name)
+            .map(|&(_, address, size)| (address, size))
+    }
+
+    /// Returns `true` if a symbol defined at `address` is exported, or if the
+    /// library has no dynamic symbols.
+    pub fn is_exported(&self, address: u64) -> bool {
+        self.exported
+            .as_ref()
+            .is_none_or(|exported| exported.contains(&address))
+    }
+
+    /// Returns the symbol whose address the dynamic linker writes to
+    /// `address`, if any.
+    pub fn import_at(&self, address: u64) -> Option<&str> {
+        self.imports.get(&address).map(String::as_str)
+    }
+
+    /// Returns the bytes from `address` to the end of the image.
+    pub fn bytes_at(&self, address: u64) -> Option<&[u8]> {
+        let start = address.checked_sub(self.base)?;
+        self.bytes().get(usize::try_from(start).ok()?..)
+    }
+
+    /// Returns the record of type `T` at `address`.
+    ///
+    /// # Safety
+    ///
+    /// Any bit pattern must be valid for `T`, except as checked by the caller.
+    pub unsafe fn get<T>(&self, address: u64) -> Option<&T> {
+        let bytes = self.bytes_at(address)?.get(..mem::size_of::<T>())?;
+        let ptr = bytes.as_ptr();
+        if ptr.align_offset(mem::align_of::<T>()) != 0 {
+            return None;
+        }
+        Some(&*ptr.cast())
+    }
+
+    /// Returns the `i32` at `address`.
+    pub fn read_i32(&self, address: u64) -> Option<i32> {
+        let bytes = self.bytes_at(address)?.get(..4)?;
+        Some(i32::from_le_bytes(bytes.try_into().ok()?))
+    }
+
+    /// Returns the address of `value`, which must be within the image.
+    pub fn address_of<T>(&self, value: &T) -> u64 {
+        self.address(value as *const T)
+            .expect("value is outside of the image")
+    }
+
+    /// Returns the address of `ptr`, if it is within the image.
+    pub fn address<T>(&self, ptr: *const T) -> Option<u64> {
+        let start = self.words.as_ptr() as usize;
+        let offset = (ptr as usize).checked_sub(start)?;
+        if offset >= self.len {
+            return None;
+        }
+        Some(self.base + offset as u64)
+    }
+
+    /// Returns the NUL-terminated UTF-8 string at `address`.
+    pub fn str_at(&self, address: u64) -> Option<&str> {
+        let bytes = self.bytes_at(address)?;
+        let len = bytes.iter().position(|&b| b == 0)?;
+        str::from_utf8(&bytes[..len]).ok()
+    }
+}
@@ -0,0 +1,642 @@
+//! Reading of reflection metadata from compiled Swift libraries.
+//!
+//! `swiftc` emits metadata describing the nominal types of a module so that
+//! they can be reflected at runtime:
+//!
+//! - `swift5_types` lists the context descriptors of the types.
+//! - `swift5_fieldmd` holds the field descriptor of each type.
+//! - `swift5_reflstr` holds the names of fields and enum cases.
+//! - `swift5_typeref` holds the mangled names of field types.
+//!
+//! These are read from an ELF library into a [`Module`] of the exported types,
+//! with their stored properties, enum cases and generic signatures. Functions
+//! and computed members are not described by metadata, so only type layouts
+//! can be bound. Metadata also doesn't record whether the library was built
+//! with library evolution, so the layouts of types are assumed to be fixed.
+//!
+//! Generic types whose signatures can't be represented, such as those with
+//! parameter packs, are not read.
+
+use crate::ir::{
+    Decl, EnumCase, EnumElement, GenericParam, Generics, Module, NominalDecl, NominalKind,
+    PathComponent, Requirement, TupleElement, Type, TypePath, Variable,
+};
+use std::{collections::HashMap, error, fmt, mem, os::raw::c_char};
+use swift_sys::{
+    ctx_desc::{
+        ClassDescriptor, ContextDescriptor, ContextDescriptorKind, EnumDescriptor,
+        GenericContextDescriptorHeader, GenericParamDescriptor, GenericRequirementDescriptor,
+        GenericRequirementKind, ModuleContextDescriptor, StructDescriptor, TypeContextDescriptor,
+        TypeGenericContextDescriptorHeader,
+    },
+    mangling::{self, NodeKind, SymbolicReference},
+    ptr::RelativeDirectPointer,
+    reflection::{FieldDescriptor, FieldRecord},
+};
+
+mod demangled;
+mod image;
+
+mod tests;
+
+use image::Image;
+
+/// The deepest nesting of contexts that is followed.
+const MAX_DEPTH: usize = 32;
+
+/// Reads the reflection metadata of the ELF library in `bytes`.
+pub fn parse(bytes: &[u8]) -> Result<Module, Error> {
+    let image = Image::load(bytes)?;
+    let (start, size) = image
+        .section("swift5_types")
+        .ok_or_else(|| Error::new("library has no `swift5_types` section"))?;
+
+    let reader = Reader { image: &image };
+    let mut types = Vec::new();
+    for record in (start..start + size).step_by(4) {
+        let offset = image
+            .read_i32(record)
+            .ok_or_else(|| Error::new("truncated `swift5_types` section"))?;
+
+        // The low bits are the kind of reference, where zero is a direct
+        // reference to a context descriptor.
+        if offset & 0b11 == 0 {
+            types.push(relative(record, offset));
+        }
+    }
+
+    reader.module(&types)
+}
+
+/// Returns the address that `offset` refers to, relative to `address`.
+fn relative(address: u64, offset: i32) -> u64 {
+    address.wrapping_add(offset as i64 as u64)
+}
+
+/// A context descriptor read from an image.
+struct Context {
+    kind: ContextDescriptorKind,
+    is_generic: bool,
+    parent: Option<u64>,
+    name: String,
+    access_function: Option<u64>,
+    fields: Option<u64>,
+}
+
+/// A type to declare in the module.
+struct Node {
+    address: u64,
+
+    /// The type that this type is nested in, if any.
+    parent: Option<u64>,
+    decl: NominalDecl,
+}
+
+struct Reader<'a> {
+    image: &'a Image,
+}
+
+impl Reader<'_> {
+    /// Reads the context descriptor at `address`.
+    ///
+    /// Contexts with an indirect parent, which is in another module, are not
+    /// read.
+    fn context(&self, address: u64) -> Option<Context> {
+        let image = self.image;
+
+        // SAFETY: `ContextDescriptor` is made of plain integers.
+        let base: &ContextDescriptor = unsafe { image.get(address)? };
+        let parent = match base.parent.offset() {
+            0 => None,
+            _ if base.parent.is_indirect() => return None,
+            offset => Some(relative(image.address_of(&base.parent), offset)),
+        };
+
+        let mut context = Context {
+            kind: base.flags.kind(),
+            is_generic: base.flags.is_generic(),
+            parent,
+            name: String::new(),
+            access_function: None,
+            fields: None,
+        };
+
+        let is_named = matches!(
+            context.kind,
+            ContextDescriptorKind::MODULE | ContextDescriptorKind::PROTOCOL
+        );
+        if !is_named && !context.kind.is_type() {
+            return Some(context);
+        }
+
+        // These descriptors have a non-null name pointer after the base.
+        let name_offset = std::mem::size_of::<ContextDescriptor>() as u64;
+        if image.read_i32(address + name_offset)? == 0 {
+            return None;
+        }
+        if is_named {
+            // SAFETY: The name pointer is non-null, and protocol descriptors
+            // start like module descriptors.
+            let module: &ModuleContextDescriptor = unsafe { image.get(address)? };
+            context.name = image
+                .str_at(image.address(module.name.as_ptr())?)?
+                .to_owned();
+        } else {
+            // SAFETY: The name pointer is non-null.
+            let ty: &TypeContextDescriptor = unsafe { image.get(address)? };
+            context.name = image.str_at(image.address(ty.name.as_ptr())?)?.to_owned();
+            context.access_function = self.target(&ty.access_function);
+            context.fields = self.target(&ty.fields);
+        }
+        Some(context)
+    }
+
+    /// Returns the address that `ptr` refers to, if any.
+    fn target<T>(&self, ptr: &RelativeDirectPointer<T>) -> Option<u64> {
+        match ptr.is_null() {
+            true => None,
+            false => self.image.address(ptr.as_ptr()),
+        }
+    }
+
+    /// Returns the module and the names of the types that the type at
+    /// `address` is nested in, outermost first.
+    fn path(&self, address: u64) -> Option<(String, Vec<String>)> {
+        let mut names = Vec::new();
+        let mut address = address;
+        for _ in 0..MAX_DEPTH {
+            let context = self.context(address)?;
+            if context.kind == ContextDescriptorKind::MODULE {
+                names.reverse();
+                return Some((context.name, names));
+            }
+            if !context.kind.is_type() {
+                return None;
+            }
+            names.push(context.name);
+            address = context.parent?;
+        }
+        None
+    }
+
+    /// Returns the demangling node of the context at `address`, which is a
+    /// module or a type in it.
+    fn context_node(&self, address: u64) -> Option<mangling::Node> {
+        let mut address = address;
+        let mut contexts: Vec<Context> = Vec::new();
+        for _ in 0..MAX_DEPTH {
+            let context = self.context(address)?;
+            if context.kind == ContextDescriptorKind::MODULE {
+                let mut node = mangling::Node::with_text(NodeKind::Module, context.name);
+                for context in contexts.into_iter().rev() {
+                    let name = mangling::Node::with_text(NodeKind::Identifier, context.name);
+                    node = mangling::Node::with_children(
+                        nominal_node_kind(context.kind)?,
+                        vec![node, name],
+                    );
+                }
+                return Some(node);
+            }
+            address = context.parent?;
+            contexts.push(context);
+        }
+        None
+    }
+
+    fn module(&self, types: &[u64]) -> Result<Module, Error> {
+        let mut module_name = None;
+        let mut nodes = Vec::new();
+
+        for &address in types {
+            let context = match self.context(address) {
+                Some(context) => context,
+                None => continue,
+            };
+            let kind = match context.kind {
+                ContextDescriptorKind::STRUCT => NominalKind::Struct,
+                ContextDescriptorKind::ENUM => NominalKind::Enum,
+                ContextDescriptorKind::CLASS => NominalKind::Class,
+                _ => continue,
+            };
+
+            // Types without exported metadata accessors are not public.
+            let is_public = context
+                .access_function
+                .is_some_and(|function| self.image.is_exported(function));
+            let module = match self.path(address) {
+                Some((module, _)) if is_public => module,
+                _ => continue,
+            };
+            if *module_name.get_or_insert_with(|| module.clone()) != module {
+                continue;
+            }
+
+            // Types declared at the top level have the module as their parent.
+            let parent = context.parent.filter(|&parent| {
+                self.context(parent)
+                    .is_some_and(|parent| parent.kind.is_type())
+            });
+            let decl = match self.nominal(kind, address, context) {
+                Some(decl) => decl,
+                None => continue,
+            };
+            nodes.push(Node {
+                address,
+                parent,
+                decl,
+            });
+        }
+
+        let name = module_name.ok_or_else(|| Error::new("library has no public Swift types"))?;
+        Ok(Module {
+            name,
+            flags: Vec::new(),
+            imports: Vec::new(),
+            decls: nest(nodes),
+        })
+    }
+
+    /// Reads the type at `address`, or returns `None` if its generic
+    /// signature can't be read.
+    fn nominal(&self, kind: NominalKind, address: u64, context: Context) -> Option<NominalDecl> {
+        let generics = match context.is_generic {
+            true => self.generics(address, &context)?,
+            false => Generics::default(),
+        };
+        let mut decl = NominalDecl {
+            kind,
+            attributes: Vec::new(),
+            modifiers: vec!["public".to_owned()],
+            name: context.name,
+            generics,
+            inherited: Vec::new(),
+            decls: Vec::new(),
+        };
+
+        let fields = context.fields.and_then(|address| {
+            // SAFETY: `FieldDescriptor` is made of plain integers.
+            unsafe { self.image.get::<FieldDescriptor>(address) }
+        });
+        if let Some(fields) = fields {
+            if !fields.superclass.is_null() {
+                decl.inherited.push(self.field_type(&fields.superclass));
+            }
+            // Class fields are not part of the layout of references.
+            if kind != NominalKind::Class {
+                decl.decls = self.fields(kind, fields);
+            }
+        }
+        Some(decl)
+    }
+
+    /// Returns the header of the generic context of the type at `address`.
+    fn generic_header(
+        &self,
+        address: u64,
+        kind: ContextDescriptorKind,
+    ) -> Option<&GenericContextDescriptorHeader> {
+        // The header trails the descriptor of the specific kind of type.
+        let size = match kind {
+            ContextDescriptorKind::STRUCT => mem::size_of::<StructDescriptor>(),
+            ContextDescriptorKind::ENUM => mem::size_of::<EnumDescriptor>(),
+            ContextDescriptorKind::CLASS => mem::size_of::<ClassDescriptor>(),
+            _ => return None,
+        };
+
+        // SAFETY: `TypeGenericContextDescriptorHeader` is made of plain
+        // integers.
+        let header: &TypeGenericContextDescriptorHeader =
+            unsafe { self.image.get(address + size as u64)? };
+        Some(&header.base)
+    }
+
+    /// Reads the generic signature of the type at `address`.
+    ///
+    /// Parameters are named by their canonical depth and index, such as
+    /// `τ_0_0`, which is how mangled names refer to them. Only the parameters
+    /// that the type adds to those of its parents are declared, but the
+    /// requirements include those of its parents.
+    ///
+    /// Returns `None` if the signature has parameters that aren't types with
+    /// metadata arguments, or requirements that can't be represented.
+    fn generics(&self, address: u64, context: &Context) -> Option<Generics> {
+        let image = self.image;
+        let header = self.generic_header(address, context.kind)?;
+
+        // Parameter packs have extra arguments.
+        if header.num_extra_arguments != 0 {
+            return None;
+        }
+
+        let num_params = usize::from(header.num_params);
+        let start = image.address_of(header) + mem::size_of_val(header) as u64;
+        let params = image.bytes_at(start)?.get(..num_params)?;
+        let all_key_types = params.iter().all(|&param| {
+            let param = GenericParamDescriptor::from_bits(param);
+            param.kind() == GenericParamDescriptor::KIND_TYPE && param.has_key_argument()
+        });
+        if !all_key_types {
+            return None;
+        }
+
+        let (depth, num_parent_params) = self.parent_generics(context.parent)?;
+        let mut generics = Generics::default();
+        for index in 0..num_params.checked_sub(num_parent_params)? {
+            generics.params.push(GenericParam {
+                name: format!("τ_{}_{}", depth, index),
+            });
+        }
+
+        // Requirements are aligned to 4 bytes after the parameters.
+        let start = (start + num_params as u64 + 3) & !3;
+        let size = mem::size_of::<GenericRequirementDescriptor>() as u64;
+        let mut num_key_arguments = num_params;
+        for i in 0..u64::from(header.num_requirements) {
+            // SAFETY: `GenericRequirementDescriptor` is made of plain integers.
+            let requirement: &GenericRequirementDescriptor =
+                unsafe { image.get(start + i * size)? };
+            if requirement.flags.has_key_argument() {
+                num_key_arguments += 1;
+            }
+
+            let subject = self.field_type(&requirement.param);
+            let content = image.address_of(&requirement.content);
+            let constraint = match requirement.flags.kind() {
+                GenericRequirementKind::PROTOCOL => self.protocol(content, requirement.content)?,
+                GenericRequirementKind::SAME_TYPE => {
+                    let right = self.mangled_type(relative(content, requirement.content));
+                    generics.requirements.push(Requirement::SameType {
+                        left: subject,
+                        right,
+                    });
+                    continue;
+                }
+                GenericRequirementKind::BASE_CLASS => {
+                    self.mangled_type(relative(content, requirement.content))
+                }
+                // A class layout.
+                GenericRequirementKind::LAYOUT if requirement.content == 0 => {
+                    Type::named("Swift.AnyObject")
+                }
+                // Conformances that follow from same-type requirements take
+                // no arguments.
+                GenericRequirementKind::SAME_CONFORMANCE => continue,
+                _ => return None,
+            };
+            generics.requirements.push(Requirement::Conformance {
+                subject,
+                constraint,
+            });
+        }
+
+        // Each parameter and witness table is an argument of the metadata
+        // accessor, so bindings must agree on their number.
+        match num_key_arguments == usize::from(header.num_key_arguments) {
+            true => Some(generics),
+            false => None,
+        }
+    }
+
+    /// Returns the depth of the parameters of a type nested in `parent`, and
+    /// the number of parameters of the types that enclose it.
+    fn parent_generics(&self, parent: Option<u64>) -> Option<(usize, usize)> {
+        // The number of parameters of each generic parent, innermost first.
+        let mut counts = Vec::new();
+        let mut parent = parent;
+        for _ in 0..MAX_DEPTH {
+            let address = match parent {
+                Some(address) => address,
+                None => break,
+            };
+            let context = self.context(address)?;
+            if context.is_generic {
+                let header = self.generic_header(address, context.kind)?;
+                counts.push(usize::from(header.num_params));
+            }
+            parent = context.parent;
+        }
+
+        // Each parent that adds parameters is a level deeper.
+        let (mut depth, mut num_params) = (0, 0);
+        for count in counts.into_iter().rev() {
+            if count > num_params {
+                depth += 1;
+                num_params = count;
+            }
+        }
+        Some((depth, num_params))
+    }
+
+    /// Returns the protocol of a conformance requirement, whose relative
+    /// pointer at `address` is `offset`.
+    ///
+    /// Protocols of other modules are referred to indirectly, through a
+    /// pointer that the dynamic linker binds to their descriptor's symbol.
+    fn protocol(&self, address: u64, offset: i32) -> Option<Type> {
+        let target = relative(address, offset & !1);
+        let node = match offset & 1 {
+            0 => self.context_node(target)?,
+            _ => {
+                let symbol = self.image.import_at(target)?;
+                let global = mangling::demangle_symbol(symbol)?;
+                let descriptor = global.child_of_kind(NodeKind::ProtocolDescriptor)?;
+                descriptor.child(0)?.clone()
+            }
+        };
+        demangled::ty(&node)
+    }
+
+    /// Reads the stored properties or enum cases in `descriptor`.
+    fn fields(&self, kind: NominalKind, descriptor: &FieldDescriptor) -> Vec<Decl> {
+        let image = self.image;
+        let start = FieldDescriptor::field_record_start(descriptor);
+        let start = match image.address(start) {
+            Some(start) => start,
+            None => return Vec::new(),
+        };
+        let stride =
+            u64::from(descriptor.field_record_size).max(std::mem::size_of::<FieldRecord>() as u64);
+
+        let mut decls = Vec::new();
+        for i in 0..u64::from(descriptor.num_fields) {
+            // SAFETY: `FieldRecord` is made of plain integers.
+            let record: &FieldRecord = match unsafe { image.get(start + i * stride) } {
+                Some(record) => record,
+                None => break,
+            };
+            let name = match self
+                .target(&record.field_name)
+                .and_then(|name| image.str_at(name))
+            {
+                Some(name) => name.to_owned(),
+                None => continue,
+            };
+            let ty = match record.mangled_type_name.is_null() {
+                true => None,
+                false => Some(self.field_type(&record.mangled_type_name)),
+            };
+
+            decls.push(match kind {
+                NominalKind::Enum => Decl::EnumCase(EnumCase {
+                    attributes: Vec::new(),
+                    modifiers: match record.flags.is_indirect() {
+                        true => vec!["indirect".to_owned()],
+                        false => Vec::new(),
+                    },
+                    elements: vec![EnumElement {
+                        name,
+                        payload: ty.map(|ty| vec![TupleElement { label: None, ty }]),
+                    }],
+                }),
+                _ => Decl::Variable(Variable {
+                    attributes: Vec::new(),
+                    modifiers: vec!["public".to_owned()],
+                    is_let: !record.flags.is_var(),
+                    name,
+                    ty: ty.unwrap_or(Type::VOID),
+                    accessors: Vec::new(),
+                }),
+            });
+        }
+        decls
+    }
+
+    /// Decodes the mangled type name that `name` refers to.
+    fn field_type(&self, name: &RelativeDirectPointer<c_char>) -> Type {
+        match self.target(name) {
+            Some(address) => self.mangled_type(address),
+            None => Type::VOID,
+        }
+    }
+
+    /// Decodes the mangled type name at `address`.
+    ///
+    /// Names that can't be decoded are kept as an opaque type named after the
+    /// escaped mangling, which bindings report as unsupported.
+    fn mangled_type(&self, address: u64) -> Type {
+        let mangled = self
+            .image
+            .bytes_at(address)
+            .map(|bytes| &bytes[..mangled_len(bytes).unwrap_or(0)])
+            .unwrap_or_default();
+
+        self.demangle(mangled).unwrap_or_else(|| {
+            Type::Named(TypePath {
+                components: vec![PathComponent {
+                    name: format!("${}", mangled.escape_ascii()),
+                    generic_args: Vec::new(),
+                }],
+            })
+        })
+    }
+
+    /// Demangles the type name `mangled`, which is within the image.
+    ///
+    /// Only direct references to context descriptors are resolved, since
+    /// other references are to entities in other modules or to functions.
+    fn demangle(&self, mangled: &[u8]) -> Option<Type> {
+        let node = mangling::demangle_type_with(mangled, |reference: SymbolicReference| {
+            if reference.control != 0x01 {
+                return None;
+            }
+            let payload = self.image.address(reference.payload)?;
+            let context = relative(payload, self.image.read_i32(payload)?);
+            let node = self.context_node(context)?;
+            match node.kind() {
+                NodeKind::Module => Some(node),
+                _ => Some(mangling::Node::with_children(NodeKind::Type, vec![node])),
+            }
+        })?;
+        demangled::ty(&node)
+    }
+}
+
+/// Returns the length of the mangled name at the start of `bytes`.
+///
+/// The name ends with a NUL byte, which may also appear within the offset of a
+/// symbolic reference.
+fn mangled_len(bytes: &[u8]) -> Option<usize> {
+    let mut i = 0;
+    loop {
+        i += match *bytes.get(i)? {
+            0 => return Some(i),
+            0x01..=0x17 => 1 + 4,
+            0x18..=0x1f => 1 + 8,
+            _ => 1,
+        };
+    }
+}
+
+/// Returns the kind of demangling node for a nominal context of `kind`.
+fn nominal_node_kind(kind: ContextDescriptorKind) -> Option<NodeKind> {
+    Some(match kind {
+        ContextDescriptorKind::STRUCT => NodeKind::Structure,
+        ContextDescriptorKind::ENUM => NodeKind::Enum,
+        ContextDescriptorKind::CLASS => NodeKind::Class,
+        ContextDescriptorKind::PROTOCOL => NodeKind::Protocol,
+        _ => return None,
+    })
+}
+
+/// Nests the declarations of `nodes` within those of their parents.
+///
+/// Types nested within types that aren't declared are dropped.
+fn nest(nodes: Vec<Node>) -> Vec<Decl> {
+    let index: HashMap<u64, usize> = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (node.address, i))
+        .collect();
+
+    let mut roots = Vec::new();
+    let mut children = vec![Vec::new(); nodes.len()];
+    for (i, node) in nodes.iter().enumerate() {
+        match node.parent.map(|parent| index.get(&parent)) {
+            None => roots.push(i),
+            Some(Some(&parent)) => children[parent].push(i),
+            Some(None) => {}
+        }
+    }
+
+    fn build(i: usize, decls: &mut [Option<NominalDecl>], children: &[Vec<usize>]) -> Decl {
+        let mut decl = decls[i].take().expect("types are nested once");
+        for &child in &children[i] {
+            decl.decls.push(build(child, decls, children));
+        }
+        Decl::Nominal(decl)
+    }
+
+    let mut decls: Vec<_> = nodes.into_iter().map(|node| Some(node.decl)).collect();
+    roots
+        .into_iter()
+        .map(|i| build(i, &mut decls, &children))
+        .collect()
+}
+
+/// An error encountered while reading a binary.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    message: String,
+}
+
+impl error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error {
+    pub(crate) fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+
+    /// A description of the error.
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
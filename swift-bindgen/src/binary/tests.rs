@@ -0,0 +1,639 @@
+#![cfg(test)]
+
+use super::*;
+use crate::Bindings;
+
+/// Demangles `mangled` and converts it to an IR type.
+fn decode(mangled: &str) -> Option<Type> {
+    demangled::ty(&mangling::demangle_type(mangled)?)
+}
+
+#[test]
+fn demangled_types() {
+    let int = || Type::named("Swift.Int");
+
+    assert_eq!(decode("Sd"), Some(Type::named("Swift.Double")));
+    assert_eq!(decode("yt"), Some(Type::VOID));
+    assert_eq!(decode("s5Int64V"), Some(Type::named("Swift.Int64")));
+    assert_eq!(
+        decode("6Shapes5PointV4KindO"),
+        Some(Type::named("Shapes.Point.Kind"))
+    );
+    assert_eq!(
+        decode("SaySiGSg"),
+        Some(Type::Optional(Box::new(Type::Array(Box::new(int())))))
+    );
+    assert_eq!(
+        decode("SDySSSdG"),
+        Some(Type::Dictionary(
+            Box::new(Type::named("Swift.String")),
+            Box::new(Type::named("Swift.Double")),
+        ))
+    );
+    assert_eq!(
+        decode("Si1x_SSt"),
+        Some(Type::Tuple(vec![
+            TupleElement {
+                label: Some("x".to_owned()),
+                ty: int(),
+            },
+            TupleElement {
+                label: None,
+                ty: Type::named("Swift.String"),
+            },
+        ]))
+    );
+    assert_eq!(decode("yXl"), Some(Type::named("Swift.AnyObject")));
+
+    // Generic parameters are named by their depth and index.
+    assert_eq!(decode("x"), Some(Type::named("τ_0_0")));
+    assert_eq!(
+        decode("Sayq_G"),
+        Some(Type::Array(Box::new(Type::named("τ_0_1"))))
+    );
+
+    // Words of earlier identifiers are substituted.
+    assert_eq!(
+        decode("6Shapes9ShapeKindO0B4ListV"),
+        Some(Type::named("Shapes.ShapeKind.ShapeList"))
+    );
+
+    // Function types aren't converted.
+    assert_eq!(decode("SiSScn"), None);
+}
+
+#[test]
+fn mangled_names() {
+    assert_eq!(mangled_len(b"Sd\0"), Some(2));
+    assert_eq!(mangled_len(b"\x01\x00\x00\x00\x00Sg\0"), Some(7));
+    assert_eq!(mangled_len(b"Sd"), None);
+}
+
+/// An allocated section of a synthetic library.
+struct Section {
+    name: &'static str,
+    address: u64,
+    data: Vec<u8>,
+}
+
+impl Section {
+    fn new(name: &'static str, address: u64) -> Self {
+        Self {
+            name,
+            address,
+            data: Vec::new(),
+        }
+    }
+
+    /// Returns the address of the next byte written.
+    fn here(&self) -> u64 {
+        self.address + self.data.len() as u64
+    }
+
+    fn align(&mut self, align: usize) {
+        while !self.data.len().is_multiple_of(align) {
+            self.data.push(0);
+        }
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    /// Writes a relative pointer to `target`, or a null pointer.
+    fn rel(&mut self, target: Option<u64>) {
+        let offset = target.map_or(0, |target| target.wrapping_sub(self.here()) as i32);
+        self.data.extend_from_slice(&offset.to_le_bytes());
+    }
+
+    /// Writes `bytes` and returns their address.
+    fn bytes(&mut self, bytes: &[u8]) -> u64 {
+        let address = self.here();
+        self.data.extend_from_slice(bytes);
+        address
+    }
+
+    /// Writes a NUL-terminated string and returns its address.
+    fn str(&mut self, s: &str) -> u64 {
+        let address = self.bytes(s.as_bytes());
+        self.data.push(0);
+        address
+    }
+}
+
+/// Writes an ELF64 shared library for x86-64 with the given allocated
+/// sections and dynamic symbols, as `(name, address)` within `.text`.
+///
+/// `imports` are undefined symbols, as `(name, address)` of the pointer that
+/// the dynamic linker binds to them.
+fn elf(sections: Vec<Section>, symbols: &[(&str, u64)], imports: &[(&str, u64)]) -> Vec<u8> {
+    const SHT_PROGBITS: u32 = 1;
+    const SHT_STRTAB: u32 = 3;
+    const SHT_RELA: u32 = 4;
+    const SHT_DYNSYM: u32 = 11;
+    const SHF_ALLOC: u64 = 2;
+    const R_X86_64_GLOB_DAT: u64 = 6;
+
+    struct Header {
+        name: u32,
+        kind: u32,
+        flags: u64,
+        address: u64,
+        offset: u64,
+        size: u64,
+        link: u32,
+        info: u32,
+        entsize: u64,
+    }
+
+    let mut shstrtab = vec![0];
+    let mut name = |name: &str| {
+        let offset = shstrtab.len() as u32;
+        shstrtab.extend_from_slice(name.as_bytes());
+        shstrtab.push(0);
+        offset
+    };
+
+    let mut file = vec![0; 64];
+    let mut headers = Vec::new();
+    let append = |file: &mut Vec<u8>, data: &[u8]| {
+        while !file.len().is_multiple_of(8) {
+            file.push(0);
+        }
+        let offset = file.len() as u64;
+        file.extend_from_slice(data);
+        offset
+    };
+
+    let text_index = 1 + sections.iter().position(|s| s.name == ".text").unwrap() as u16;
+    for section in &sections {
+        headers.push(Header {
+            name: name(section.name),
+            kind: SHT_PROGBITS,
+            flags: SHF_ALLOC,
+            address: section.address,
+            offset: append(&mut file, &section.data),
+            size: section.data.len() as u64,
+            link: 0,
+            info: 0,
+            entsize: 0,
+        });
+    }
+
+    let mut dynstr = vec![0];
+    let mut dynsym = vec![0; 24];
+    for &(symbol, address) in symbols {
+        dynsym.extend_from_slice(&(dynstr.len() as u32).to_le_bytes());
+        dynstr.extend_from_slice(symbol.as_bytes());
+        dynstr.push(0);
+
+        // A global function defined in `.text`.
+        dynsym.push(0x12);
+        dynsym.push(0);
+        dynsym.extend_from_slice(&text_index.to_le_bytes());
+        dynsym.extend_from_slice(&address.to_le_bytes());
+        dynsym.extend_from_slice(&4u64.to_le_bytes());
+    }
+    let mut rela = Vec::new();
+    for &(symbol, address) in imports {
+        let index = (dynsym.len() / 24) as u64;
+        dynsym.extend_from_slice(&(dynstr.len() as u32).to_le_bytes());
+        dynstr.extend_from_slice(symbol.as_bytes());
+        dynstr.push(0);
+
+        // An undefined global symbol.
+        dynsym.push(0x10);
+        dynsym.push(0);
+        dynsym.extend_from_slice(&[0; 18]);
+
+        rela.extend_from_slice(&address.to_le_bytes());
+        rela.extend_from_slice(&(index << 32 | R_X86_64_GLOB_DAT).to_le_bytes());
+        rela.extend_from_slice(&0u64.to_le_bytes());
+    }
+    let dynsym_index = headers.len() as u32 + 1;
+    headers.push(Header {
+        name: name(".dynsym"),
+        kind: SHT_DYNSYM,
+        flags: 0,
+        address: 0,
+        offset: append(&mut file, &dynsym),
+        size: dynsym.len() as u64,
+        link: dynsym_index + 1,
+        info: 1,
+        entsize: 24,
+    });
+    headers.push(Header {
+        name: name(".dynstr"),
+        kind: SHT_STRTAB,
+        flags: 0,
+        address: 0,
+        offset: append(&mut file, &dynstr),
+        size: dynstr.len() as u64,
+        link: 0,
+        info: 0,
+        entsize: 0,
+    });
+    headers.push(Header {
+        name: name(".rela.dyn"),
+        kind: SHT_RELA,
+        flags: 0,
+        address: 0,
+        offset: append(&mut file, &rela),
+        size: rela.len() as u64,
+        link: dynsym_index,
+        info: 0,
+        entsize: 24,
+    });
+    let shstrtab_name = name(".shstrtab");
+    headers.push(Header {
+        name: shstrtab_name,
+        kind: SHT_STRTAB,
+        flags: 0,
+        address: 0,
+        offset: append(&mut file, &shstrtab),
+        size: shstrtab.len() as u64,
+        link: 0,
+        info: 0,
+        entsize: 0,
+    });
+
+    let shoff = append(&mut file, &[]);
+    file.extend_from_slice(&[0; 64]);
+    for header in &headers {
+        file.extend_from_slice(&header.name.to_le_bytes());
+        file.extend_from_slice(&header.kind.to_le_bytes());
+        file.extend_from_slice(&header.flags.to_le_bytes());
+        file.extend_from_slice(&header.address.to_le_bytes());
+        file.extend_from_slice(&header.offset.to_le_bytes());
+        file.extend_from_slice(&header.size.to_le_bytes());
+        file.extend_from_slice(&header.link.to_le_bytes());
+        file.extend_from_slice(&header.info.to_le_bytes());
+        file.extend_from_slice(&1u64.to_le_bytes());
+        file.extend_from_slice(&header.entsize.to_le_bytes());
+    }
+
+    let mut header = Vec::new();
+    header.extend_from_slice(b"\x7fELF\x02\x01\x01\0\0\0\0\0\0\0\0\0");
+    header.extend_from_slice(&3u16.to_le_bytes()); // ET_DYN
+    header.extend_from_slice(&62u16.to_le_bytes()); // EM_X86_64
+    header.extend_from_slice(&1u32.to_le_bytes());
+    header.extend_from_slice(&0u64.to_le_bytes());
+    header.extend_from_slice(&0u64.to_le_bytes());
+    header.extend_from_slice(&shoff.to_le_bytes());
+    header.extend_from_slice(&0u32.to_le_bytes());
+    header.extend_from_slice(&64u16.to_le_bytes());
+    header.extend_from_slice(&56u16.to_le_bytes());
+    header.extend_from_slice(&0u16.to_le_bytes());
+    header.extend_from_slice(&64u16.to_le_bytes());
+    header.extend_from_slice(&(headers.len() as u16 + 1).to_le_bytes());
+    header.extend_from_slice(&(headers.len() as u16).to_le_bytes());
+    file[..64].copy_from_slice(&header);
+    file
+}
+
+/// A field of a synthetic type, as `(flags, mangled type, name)`.
+type Field<'a> = (u32, &'a [u8], &'a str);
+
+/// The generic signature of a synthetic type, as the number of parameters
+/// and the mangled parameters that conform to `Hashable`.
+type Signature<'a> = (u8, &'a [&'a [u8]]);
+
+/// A synthetic type descriptor, as `(flags, parent, name, accessor, generic
+/// signature)`, where the parent is the index of a descriptor or the module.
+type Context<'a> = (u32, Option<usize>, u64, u64, Option<Signature<'a>>);
+
+/// Returns a library for the module `Shapes`, which would be declared as:
+///
+/// ```swift
+/// public struct Point {
+///     public var x: Double
+///     public var y: Double
+///     public let kind: Kind
+///
+///     public enum Kind {
+///         case circle
+///         case square
+///     }
+/// }
+///
+/// open class Shape {
+///     public var name: String = ""
+/// }
+///
+/// struct Hidden {
+///     var value: Int
+/// }
+///
+/// public struct Box<T> {
+///     public var value: T
+/// }
+///
+/// public struct Pair<A, B> {
+///     public var first: A
+///     public var second: B
+/// }
+///
+/// public struct Keyed<Key: Hashable> {
+///     public var key: Key
+/// }
+/// ```
+fn shapes() -> Vec<u8> {
+    let text = {
+        let mut text = Section::new(".text", 0x1000);
+        text.bytes(&[0xc3; 0x20]);
+        text
+    };
+    let mut rodata = Section::new(".rodata", 0x2000);
+    let mut fieldmd = Section::new("swift5_fieldmd", 0x3000);
+    let mut typeref = Section::new("swift5_typeref", 0x4000);
+    let mut reflstr = Section::new("swift5_reflstr", 0x5000);
+    let mut types = Section::new("swift5_types", 0x6000);
+
+    // The pointer to `Swift.Hashable`, which is bound by the dynamic linker.
+    let hashable = 0x7000;
+    let mut got = Section::new(".got", hashable);
+    got.bytes(&[0; 8]);
+
+    let names: Vec<u64> = [
+        "Shapes", "Point", "Kind", "Shape", "Hidden", "Box", "Pair", "Keyed",
+    ]
+    .iter()
+    .map(|name| rodata.str(name))
+    .collect();
+    rodata.align(4);
+
+    let module = rodata.here();
+    rodata.u32(0);
+    rodata.rel(None);
+    rodata.rel(Some(names[0]));
+
+    let contexts: [Context; 7] = [
+        (0x51, None, names[1], 0x1000, None),
+        (0x52, Some(0), names[2], 0x1004, None),
+        (0x50, None, names[3], 0x1008, None),
+        (0x51, None, names[4], 0x100c, None),
+        (0xd1, None, names[5], 0x1010, Some((1, &[]))),
+        (0xd1, None, names[6], 0x1014, Some((2, &[]))),
+        (0xd1, None, names[7], 0x1018, Some((1, &[b"x"]))),
+    ];
+
+    // Descriptors are written after their field descriptors, whose mangled
+    // names may refer to descriptors by their known addresses.
+    // Class descriptors are longer than those of structs and enums.
+    let base_size = |flags: u32| if flags & 0x1f == 0x10 { 44 } else { 28 };
+    let mut descriptors = vec![rodata.here()];
+    for &(flags, .., signature) in &contexts {
+        let mut size = base_size(flags);
+        if let Some((num_params, requirements)) = signature {
+            size += 16 + u64::from(num_params).div_ceil(4) * 4 + 12 * requirements.len() as u64;
+        }
+        descriptors.push(descriptors.last().unwrap() + size);
+    }
+    let descriptor = |i: usize| descriptors[i];
+
+    let mut field_descriptor = |superclass: Option<&[u8]>, fields: &[Field]| {
+        let superclass = superclass.map(|name| {
+            let address = typeref.bytes(name);
+            typeref.bytes(&[0]);
+            address
+        });
+        let address = fieldmd.here();
+        fieldmd.rel(None);
+        fieldmd.rel(superclass);
+        fieldmd.u32(12 << 16);
+        fieldmd.u32(fields.len() as u32);
+        for &(flags, ty, name) in fields {
+            let ty = match ty {
+                // A symbolic reference to `Point.Kind`.
+                b"Kind" => {
+                    let address = typeref.here();
+                    typeref.bytes(&[1]);
+                    typeref.rel(Some(descriptor(1)));
+                    typeref.bytes(&[0]);
+                    Some(address)
+                }
+                b"" => None,
+                ty => {
+                    let address = typeref.bytes(ty);
+                    typeref.bytes(&[0]);
+                    Some(address)
+                }
+            };
+            fieldmd.u32(flags);
+            fieldmd.rel(ty);
+            fieldmd.rel(Some(reflstr.str(name)));
+        }
+        address
+    };
+
+    let var = 0x2;
+    let fields = [
+        field_descriptor(
+            None,
+            &[(var, b"Sd", "x"), (var, b"Sd", "y"), (0, b"Kind", "kind")],
+        ),
+        field_descriptor(None, &[(0, b"", "circle"), (0, b"", "square")]),
+        field_descriptor(None, &[(var, b"SS", "name")]),
+        field_descriptor(None, &[(var, b"Si", "value")]),
+        field_descriptor(None, &[(var, b"x", "value")]),
+        field_descriptor(None, &[(var, b"x", "first"), (var, b"q_", "second")]),
+        field_descriptor(None, &[(var, b"x", "key")]),
+    ];
+
+    for (i, &(flags, parent, name, accessor, signature)) in contexts.iter().enumerate() {
+        assert_eq!(rodata.here(), descriptor(i));
+        rodata.u32(flags);
+        rodata.rel(Some(parent.map_or(module, descriptor)));
+        rodata.rel(Some(name));
+        rodata.rel(Some(accessor));
+        rodata.rel(Some(fields[i]));
+        while rodata.here() < descriptor(i) + base_size(flags) {
+            rodata.u32(0);
+        }
+
+        if let Some((num_params, requirements)) = signature {
+            // No instantiation cache or pattern.
+            rodata.rel(None);
+            rodata.rel(None);
+
+            let num_requirements = requirements.len() as u32;
+            let num_key_arguments = u32::from(num_params) + num_requirements;
+            rodata.u32(u32::from(num_params) | num_requirements << 16);
+            rodata.u32(num_key_arguments);
+
+            // Type parameters with key arguments.
+            rodata.bytes(&vec![0x80; usize::from(num_params)]);
+            rodata.align(4);
+
+            for &param in requirements {
+                // A protocol requirement with a key argument.
+                rodata.u32(0x80);
+                rodata.rel(Some(typeref.here()));
+                typeref.bytes(param);
+                typeref.bytes(&[0]);
+                // An indirect reference to the protocol.
+                let offset = hashable.wrapping_sub(rodata.here()) as i32 | 1;
+                rodata.bytes(&offset.to_le_bytes());
+            }
+        }
+    }
+    assert_eq!(rodata.here(), descriptor(contexts.len()));
+
+    for i in 0..contexts.len() {
+        types.rel(Some(descriptor(i)));
+    }
+    // Indirect references are skipped.
+    types.u32(0x11);
+
+    elf(
+        vec![text, rodata, fieldmd, typeref, reflstr, types, got],
+        &[
+            ("$s6Shapes5PointVMa", 0x1000),
+            ("$s6Shapes5PointV4KindOMa", 0x1004),
+            ("$s6Shapes5ShapeCMa", 0x1008),
+            ("$s6Shapes3BoxVMa", 0x1010),
+            ("$s6Shapes4PairVMa", 0x1014),
+            ("$s6Shapes5KeyedVMa", 0x1018),
+        ],
+        &[("$sSHMp", hashable)],
+    )
+}
+
+fn variable(is_let: bool, name: &str, ty: Type) -> Decl {
+    Decl::Variable(Variable {
+        attributes: Vec::new(),
+        modifiers: vec!["public".to_owned()],
+        is_let,
+        name: name.to_owned(),
+        ty,
+        accessors: Vec::new(),
+    })
+}
+
+fn case(name: &str) -> Decl {
+    Decl::EnumCase(EnumCase {
+        attributes: Vec::new(),
+        modifiers: Vec::new(),
+        elements: vec![EnumElement {
+            name: name.to_owned(),
+            payload: None,
+        }],
+    })
+}
+
+fn nominal(kind: NominalKind, name: &str, decls: Vec<Decl>) -> NominalDecl {
+    NominalDecl {
+        kind,
+        attributes: Vec::new(),
+        modifiers: vec!["public".to_owned()],
+        name: name.to_owned(),
+        generics: Generics::default(),
+        inherited: Vec::new(),
+        decls,
+    }
+}
+
+#[test]
+fn module() {
+    let module = parse(&shapes()).unwrap();
+    assert_eq!(module.name, "Shapes");
+
+    let kind = nominal(
+        NominalKind::Enum,
+        "Kind",
+        vec![case("circle"), case("square")],
+    );
+    let point = nominal(
+        NominalKind::Struct,
+        "Point",
+        vec![
+            variable(false, "x", Type::named("Swift.Double")),
+            variable(false, "y", Type::named("Swift.Double")),
+            variable(true, "kind", Type::named("Shapes.Point.Kind")),
+            Decl::Nominal(kind),
+        ],
+    );
+    let shape = nominal(NominalKind::Class, "Shape", Vec::new());
+    let param = |name: &str| GenericParam {
+        name: name.to_owned(),
+    };
+    let mut boxed = nominal(
+        NominalKind::Struct,
+        "Box",
+        vec![variable(false, "value", Type::named("τ_0_0"))],
+    );
+    boxed.generics.params.push(param("τ_0_0"));
+    let mut pair = nominal(
+        NominalKind::Struct,
+        "Pair",
+        vec![
+            variable(false, "first", Type::named("τ_0_0")),
+            variable(false, "second", Type::named("τ_0_1")),
+        ],
+    );
+    pair.generics.params = vec![param("τ_0_0"), param("τ_0_1")];
+    let mut keyed = nominal(
+        NominalKind::Struct,
+        "Keyed",
+        vec![variable(false, "key", Type::named("τ_0_0"))],
+    );
+    keyed.generics.params.push(param("τ_0_0"));
+    keyed.generics.requirements.push(Requirement::Conformance {
+        subject: Type::named("τ_0_0"),
+        constraint: Type::named("Swift.Hashable"),
+    });
+
+    assert_eq!(
+        module.decls,
+        [
+            Decl::Nominal(point),
+            Decl::Nominal(shape),
+            Decl::Nominal(boxed),
+            Decl::Nominal(pair),
+            Decl::Nominal(keyed),
+        ]
+    );
+}
+
+#[test]
+fn bindings() {
+    let bindings = Bindings::from_binary(&shapes()).unwrap();
+    let code = bindings.as_str();
+    assert_eq!(bindings.module_names(), ["Shapes"]);
+
+    assert!(code.contains("pub struct Point {"));
+    assert!(code.contains("pub x: ::swift::Double,"));
+    assert!(code.contains("pub struct Shape("));
+
+    // Accessors of generic types take the metadata of each parameter, and the
+    // witness tables of their conformances.
+    let generic_lines = [
+        "pub fn Box_metadata_accessor(request: ::swift::sys::metadata::MetadataRequest, \
+         arg0: *const ::std::ffi::c_void) -> ::swift::rt::metadata::MetadataResponse;",
+        "pub struct Box<τ_0_0: ::swift::rt::metadata::Type>(::swift::rt::value::ValueBox, \
+         ::std::marker::PhantomData<τ_0_0>);",
+        "let response = unsafe { sys::Box_metadata_accessor(request, arguments[0]) };",
+        "pub fn Pair_metadata_accessor(request: ::swift::sys::metadata::MetadataRequest, \
+         arg0: *const ::std::ffi::c_void, arg1: *const ::std::ffi::c_void) \
+         -> ::swift::rt::metadata::MetadataResponse;",
+        "pub struct Pair<τ_0_0: ::swift::rt::metadata::Type, τ_0_1: ::swift::rt::metadata::Type>\
+         (::swift::rt::value::ValueBox, ::std::marker::PhantomData<(τ_0_0, τ_0_1)>);",
+        "let response = unsafe { sys::Pair_metadata_accessor(request, arguments[0], arguments[1]) };",
+        "// Skipped `Shapes.Keyed`: requirement `τ_0_0: Swift.Hashable` is not supported",
+    ];
+    for line in &generic_lines {
+        assert!(code.contains(line), "missing `{}` in:\n{}", line, code);
+    }
+    assert!(!code.contains("Keyed_metadata_accessor"));
+    assert!(!code.contains("Hidden"));
+}
+
+#[test]
+fn errors() {
+    let error = parse(b"not a library").unwrap_err();
+    assert!(!error.message().is_empty());
+
+    let empty = elf(vec![Section::new(".text", 0x1000)], &[], &[]);
+    let error = parse(&empty).unwrap_err();
+    assert_eq!(error.message(), "library has no `swift5_types` section");
+}
@@ -0,0 +1,81 @@
+use crate::{binary, codegen, interface, ir};
+use std::{fmt, fs, io, path::Path};
+
+/// Rust bindings generated for Swift modules.
+///
+/// Bindings for a single module can be generated directly, as shown below.
+/// Use a [`Builder`](crate::Builder) to filter declarations or to generate bindings from a
+/// build script.
+///
+/// # Examples
+///
+/// ```
+/// let interface = "// swift-module-flags: -module-name Shapes
+/// @frozen public struct Point {
+///   public var x: Swift.Double
+///   public var y: Swift.Double
+/// }
+/// ";
+///
+/// let bindings = swift_bindgen::Bindings::from_interface(interface).unwrap();
+/// assert!(bindings.to_string().contains("pub struct Point"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Bindings {
+    module_names: Vec<String>,
+    pub(crate) code: String,
+}
+
+impl fmt::Display for Bindings {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.code)
+    }
+}
+
+impl Bindings {
+    /// Generates bindings for a parsed module.
+    pub fn generate(module: &ir::Module) -> Self {
+        Self::with_options(&[module], &codegen::Options::default())
+    }
+
+    pub(crate) fn with_options(modules: &[&ir::Module], options: &codegen::Options) -> Self {
+        let code: Vec<String> = modules
+            .iter()
+            .map(|module| codegen::generate(module, options))
+            .collect();
+        Self {
+            module_names: modules.iter().map(|module| module.name.clone()).collect(),
+            code: code.join("\n"),
+        }
+    }
+
+    /// Parses the contents of a `.swiftinterface` file and generates bindings
+    /// for it.
+    pub fn from_interface(source: &str) -> Result<Self, interface::Error> {
+        interface::parse(source).map(|module| Self::generate(&module))
+    }
+
+    /// Reads the reflection metadata of a compiled ELF library and generates
+    /// bindings for its types.
+    pub fn from_binary(bytes: &[u8]) -> Result<Self, binary::Error> {
+        binary::parse(bytes).map(|module| Self::generate(&module))
+    }
+
+    /// Returns the names of the bound Swift modules.
+    #[inline]
+    pub fn module_names(&self) -> &[String] {
+        &self.module_names
+    }
+
+    /// Returns the generated Rust source code.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.code
+    }
+
+    /// Writes the generated Rust source code to `path`.
+    pub fn write_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, &self.code)
+    }
+}
@@ -0,0 +1,756 @@
+use crate::{binary, codegen, interface, ir, Bindings};
+use std::{
+    env, error, fmt, fs,
+    io::{self, Write as _},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+};
+
+/// The directory of the Swift runtime libraries, as searched by `swift-sys`.
+const DEFAULT_SWIFT_LIBRARY_PATH: &str = "/usr/lib/swift/";
+
+/// Returns a new [`Builder`] with the default configuration.
+#[inline]
+pub fn builder() -> Builder {
+    Builder::default()
+}
+
+/// Configures and generates Rust bindings for Swift modules.
+///
+/// This is meant to be used from a build script, much like the builder of
+/// C `bindgen`. The generated file is then included by the crate with
+/// [`include!`].
+///
+/// # Examples
+///
+/// In `build.rs`:
+///
+/// ```no_run
+/// use std::{env, path::PathBuf};
+///
+/// let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+///
+/// swift_bindgen::Builder::default()
+///     .binary("swift/.build/release/libShapes.so")
+///     .allowlist_type("Point|Rect")
+///     .allowlist_function("distance")
+///     .output(out_dir.join("shapes.rs"))
+///     .generate()
+///     .expect("failed to generate bindings");
+/// ```
+///
+/// In the crate:
+///
+/// ```ignore
+/// include!(concat!(env!("OUT_DIR"), "/shapes.rs"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Builder {
+    modules: Vec<String>,
+    module_search_paths: Vec<PathBuf>,
+    interfaces: Vec<PathBuf>,
+    binaries: Vec<PathBuf>,
+    link_name: Option<String>,
+    allowlist_types: Vec<String>,
+    allowlist_functions: Vec<String>,
+    blocklist_types: Vec<String>,
+    blocklist_functions: Vec<String>,
+    output: Option<PathBuf>,
+    rustfmt_bindings: bool,
+    rustfmt_path: Option<PathBuf>,
+    rustfmt_configuration_file: Option<PathBuf>,
+    emit_cargo_metadata: bool,
+    swift_library_path: PathBuf,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            modules: Vec::new(),
+            module_search_paths: Vec::new(),
+            interfaces: Vec::new(),
+            binaries: Vec::new(),
+            link_name: None,
+            allowlist_types: Vec::new(),
+            allowlist_functions: Vec::new(),
+            blocklist_types: Vec::new(),
+            blocklist_functions: Vec::new(),
+            output: None,
+            rustfmt_bindings: true,
+            rustfmt_path: None,
+            rustfmt_configuration_file: None,
+            emit_cargo_metadata: true,
+            swift_library_path: PathBuf::from(DEFAULT_SWIFT_LIBRARY_PATH),
+        }
+    }
+}
+
+impl Builder {
+    /// Generates bindings for the module `name`.
+    ///
+    /// The module's interface is found in the
+    /// [module search paths](Self::module_search_path) as either
+    /// `<name>.swiftinterface` or an architecture-specific interface within
+    /// `<name>.swiftmodule`.
+    pub fn module(mut self, name: impl Into<String>) -> Self {
+        self.modules.push(name.into());
+        self
+    }
+
+    /// Adds a directory in which to find [modules](Self::module).
+    pub fn module_search_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.module_search_paths.push(path.into());
+        self
+    }
+
+    /// Generates bindings for the `.swiftinterface` file at `path`.
+    ///
+    /// If the interface doesn't specify `-module-name`, the module is named
+    /// after the file or its enclosing `.swiftmodule` directory.
+    pub fn interface(mut self, path: impl Into<PathBuf>) -> Self {
+        self.interfaces.push(path.into());
+        self
+    }
+
+    /// Links against the Swift library at `path`.
+    ///
+    /// The library's directory is added to the module search paths and to the
+    /// linker search paths. If no modules or interfaces are given, bindings
+    /// are generated for the module that the library is named after, such as
+    /// `Shapes` for `libShapes.so`. If that module's interface isn't found,
+    /// the types of an ELF library are read from its
+    /// [reflection metadata](crate::binary).
+    pub fn binary(mut self, path: impl Into<PathBuf>) -> Self {
+        self.binaries.push(path.into());
+        self
+    }
+
+    /// Sets the name of the library that the generated code links against.
+    ///
+    /// This defaults to the name of the first [binary](Self::binary), or to
+    /// the module name otherwise.
+    pub fn link_name(mut self, name: impl Into<String>) -> Self {
+        self.link_name = Some(name.into());
+        self
+    }
+
+    /// Generates only the types whose names match `pattern`, along with the
+    /// other allowlisted declarations.
+    ///
+    /// Patterns are regular expressions matched against whole names, which
+    /// may be qualified by the module name, such as `Shapes.Point`. Types
+    /// needed for the layout of allowlisted types or for the signature of
+    /// allowlisted functions are generated as well.
+    pub fn allowlist_type(mut self, pattern: impl Into<String>) -> Self {
+        self.allowlist_types.push(pattern.into());
+        self
+    }
+
+    /// Generates only the module-level functions whose names match
+    /// `pattern`, along with the other allowlisted declarations.
+    ///
+    /// See [`allowlist_type`](Self::allowlist_type) for how patterns match.
+    pub fn allowlist_function(mut self, pattern: impl Into<String>) -> Self {
+        self.allowlist_functions.push(pattern.into());
+        self
+    }
+
+    /// Omits the definitions of types whose names match `pattern`.
+    ///
+    /// Blocklisted types are still referred to by name, so the crate including
+    /// the bindings must define them with the same layout.
+    pub fn blocklist_type(mut self, pattern: impl Into<String>) -> Self {
+        self.blocklist_types.push(pattern.into());
+        self
+    }
+
+    /// Omits the module-level functions whose names match `pattern`.
+    pub fn blocklist_function(mut self, pattern: impl Into<String>) -> Self {
+        self.blocklist_functions.push(pattern.into());
+        self
+    }
+
+    /// Writes the generated bindings to `path`.
+    pub fn output(mut self, path: impl Into<PathBuf>) -> Self {
+        self.output = Some(path.into());
+        self
+    }
+
+    /// Sets whether to format the bindings with `rustfmt`, which is enabled by
+    /// default.
+    ///
+    /// If `rustfmt` can't be found, the bindings are left unformatted.
+    pub fn rustfmt_bindings(mut self, doit: bool) -> Self {
+        self.rustfmt_bindings = doit;
+        self
+    }
+
+    /// Sets the `rustfmt` executable to use.
+    ///
+    /// This defaults to the `RUSTFMT` environment variable, or `rustfmt`.
+    pub fn rustfmt_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.rustfmt_path = Some(path.into());
+        self
+    }
+
+    /// Sets the `rustfmt.toml` used to format the bindings.
+    pub fn rustfmt_configuration_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.rustfmt_configuration_file = Some(path.into());
+        self
+    }
+
+    /// Sets whether to print instructions for Cargo, which is enabled by
+    /// default.
+    ///
+    /// These link against `swiftCore` and rerun the build script when an input
+    /// changes. Disable this when not running from a build script.
+    pub fn emit_cargo_metadata(mut self, doit: bool) -> Self {
+        self.emit_cargo_metadata = doit;
+        self
+    }
+
+    /// Sets the directory containing the Swift runtime libraries, which
+    /// defaults to `/usr/lib/swift/`.
+    pub fn swift_library_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.swift_library_path = path.into();
+        self
+    }
+
+    /// Generates the bindings and writes them to the [output](Self::output)
+    /// path, if any.
+    pub fn generate(self) -> Result<Bindings, Error> {
+        let options = codegen::Options {
+            filter: self.filter()?,
+            link_name: self
+                .link_name
+                .clone()
+                .or_else(|| self.binaries.iter().find_map(|path| library_name(path))),
+        };
+
+        let inputs = self.inputs()?;
+        let modules = parse_inputs(&inputs)?;
+
+        let modules: Vec<&ir::Module> = modules.iter().collect();
+        let mut bindings = Bindings::with_options(&modules, &options);
+        if self.rustfmt_bindings {
+            bindings.code = self.rustfmt(bindings.code)?;
+        }
+
+        if let Some(output) = &self.output {
+            bindings.write_to_file(output).map_err(|error| Error::Io {
+                path: output.clone(),
+                error,
+            })?;
+        }
+        if self.emit_cargo_metadata {
+            for line in self.cargo_metadata(&inputs) {
+                println!("{}", line);
+            }
+        }
+        Ok(bindings)
+    }
+
+    /// Parses the inputs without generating bindings.
+    pub fn parse(&self) -> Result<Vec<ir::Module>, Error> {
+        parse_inputs(&self.inputs()?)
+    }
+
+    fn filter(&self) -> Result<codegen::Filter, Error> {
+        let patterns = |patterns: &[String]| {
+            codegen::Patterns::new(patterns)
+                .map_err(|error| Error::InvalidPattern(error.to_string()))
+        };
+        Ok(codegen::Filter {
+            allowlist_types: patterns(&self.allowlist_types)?,
+            allowlist_functions: patterns(&self.allowlist_functions)?,
+            blocklist_types: patterns(&self.blocklist_types)?,
+            blocklist_functions: patterns(&self.blocklist_functions)?,
+        })
+    }
+
+    /// Returns the files to parse.
+    fn inputs(&self) -> Result<Vec<Input>, Error> {
+        let mut inputs: Vec<Input> = self
+            .interfaces
+            .iter()
+            .map(|path| Input::Interface {
+                path: path.clone(),
+                module_name: interface_module_name(path),
+            })
+            .collect();
+
+        if self.modules.is_empty() && inputs.is_empty() {
+            for binary in &self.binaries {
+                let name = library_name(binary);
+                inputs.push(
+                    match name.as_deref().and_then(|name| self.find_module(name)) {
+                        Some(path) => Input::Interface {
+                            path,
+                            module_name: name,
+                        },
+                        None => Input::Binary(binary.clone()),
+                    },
+                );
+            }
+        }
+        for name in &self.modules {
+            let path = self
+                .find_module(name)
+                .ok_or_else(|| Error::ModuleNotFound(name.clone()))?;
+            inputs.push(Input::Interface {
+                path,
+                module_name: Some(name.clone()),
+            });
+        }
+
+        if inputs.is_empty() {
+            return Err(Error::NoInput);
+        }
+        Ok(inputs)
+    }
+
+    /// Returns the directories containing the binaries.
+    fn binary_dirs(&self) -> Vec<&Path> {
+        let mut dirs: Vec<&Path> = Vec::new();
+        for binary in &self.binaries {
+            let dir = match binary.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir,
+                _ => Path::new("."),
+            };
+            if !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+        dirs
+    }
+
+    /// Finds the interface of the module `name` in the search paths.
+    fn find_module(&self, name: &str) -> Option<PathBuf> {
+        let search_paths = self.module_search_paths.iter().map(PathBuf::as_path);
+
+        for dir in search_paths.chain(self.binary_dirs()) {
+            let path = dir.join(format!("{}.swiftinterface", name));
+            if path.is_file() {
+                return Some(path);
+            }
+            let module_dir = dir.join(format!("{}.swiftmodule", name));
+            if let Some(path) = find_arch_interface(&module_dir) {
+                return Some(path);
+            }
+        }
+        None
+    }
+
+    /// Formats `code` with `rustfmt`.
+    fn rustfmt(&self, code: String) -> Result<String, Error> {
+        let rustfmt = self
+            .rustfmt_path
+            .clone()
+            .or_else(|| env::var_os("RUSTFMT").map(PathBuf::from))
+            .unwrap_or_else(|| PathBuf::from("rustfmt"));
+
+        let mut command = Command::new(rustfmt);
+        command.args(["--emit", "stdout", "--edition", "2018"]);
+        if let Some(config) = &self.rustfmt_configuration_file {
+            command.arg("--config-path").arg(config);
+        }
+        command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let mut child = match command.spawn() {
+            Ok(child) => child,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(code),
+            Err(error) => return Err(Error::Rustfmt(error.to_string())),
+        };
+
+        // Write from another thread so that a full stdout pipe can't block
+        // rustfmt while it is still reading.
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let source = code.clone();
+        let writer = thread::spawn(move || stdin.write_all(source.as_bytes()));
+
+        let output = child
+            .wait_with_output()
+            .map_err(|error| Error::Rustfmt(error.to_string()))?;
+        writer
+            .join()
+            .expect("rustfmt writer panicked")
+            .map_err(|error| Error::Rustfmt(error.to_string()))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(Error::Rustfmt(stderr.trim().to_owned()));
+        }
+        String::from_utf8(output.stdout).map_err(|error| Error::Rustfmt(error.to_string()))
+    }
+
+    /// Returns the instructions for Cargo to link the bindings and to rerun
+    /// the build script when `inputs` change.
+    fn cargo_metadata(&self, inputs: &[Input]) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        let mut changed: Vec<&Path> = Vec::new();
+        for path in inputs
+            .iter()
+            .map(Input::path)
+            .chain(self.binaries.iter().map(PathBuf::as_path))
+        {
+            if !changed.contains(&path) {
+                changed.push(path);
+            }
+        }
+        for path in changed {
+            lines.push(format!("cargo:rerun-if-changed={}", path.display()));
+        }
+
+        let search_paths = self.binary_dirs();
+        for dir in search_paths {
+            lines.push(format!("cargo:rustc-link-search=native={}", dir.display()));
+        }
+        lines.push(format!(
+            "cargo:rustc-link-search=native={}",
+            self.swift_library_path.display()
+        ));
+        lines.push("cargo:rustc-link-lib=dylib=swiftCore".to_owned());
+        lines
+    }
+}
+
+/// A file to generate bindings from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Input {
+    /// A `.swiftinterface` file, with the module name to use if it doesn't
+    /// specify one.
+    Interface {
+        path: PathBuf,
+        module_name: Option<String>,
+    },
+
+    /// A library whose reflection metadata is read.
+    Binary(PathBuf),
+}
+
+impl Input {
+    fn path(&self) -> &Path {
+        match self {
+            Self::Interface { path, .. } | Self::Binary(path) => path,
+        }
+    }
+}
+
+/// Parses the files of `inputs`.
+fn parse_inputs(inputs: &[Input]) -> Result<Vec<ir::Module>, Error> {
+    let mut modules = Vec::new();
+    for input in inputs {
+        let path = input.path();
+        let io_error = |error| Error::Io {
+            path: path.to_owned(),
+            error,
+        };
+        let module = match input {
+            Input::Interface { module_name, .. } => {
+                let source = fs::read_to_string(path).map_err(io_error)?;
+                interface::parse_named(&source, module_name.as_deref()).map_err(|error| {
+                    Error::Interface {
+                        path: path.to_owned(),
+                        error,
+                    }
+                })?
+            }
+            Input::Binary(_) => {
+                let bytes = fs::read(path).map_err(io_error)?;
+                binary::parse(&bytes).map_err(|error| Error::Binary {
+                    path: path.to_owned(),
+                    error,
+                })?
+            }
+        };
+        modules.push(module);
+    }
+    Ok(modules)
+}
+
+/// Returns the module name implied by the location of an interface file.
+fn interface_module_name(path: &Path) -> Option<String> {
+    let parent = path.parent().filter(|dir| {
+        dir.extension()
+            .is_some_and(|extension| extension == "swiftmodule")
+    });
+    parent
+        .unwrap_or(path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+}
+
+/// Returns the name of the library at `path`, such as `Shapes` for
+/// `libShapes.so`.
+fn library_name(path: &Path) -> Option<String> {
+    let file_name = path.file_name()?.to_str()?;
+    let name = file_name.strip_prefix("lib").unwrap_or(file_name);
+    let name = name.split('.').next()?;
+    Some(name.to_owned()).filter(|name| !name.is_empty())
+}
+
+/// Returns the interface in a `.swiftmodule` directory that best matches the
+/// target architecture.
+fn find_arch_interface(module_dir: &Path) -> Option<PathBuf> {
+    let mut interfaces: Vec<PathBuf> = fs::read_dir(module_dir)
+        .ok()?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| {
+            let name = path.file_name().and_then(|name| name.to_str());
+            name.is_some_and(|name| {
+                name.ends_with(".swiftinterface") && !name.ends_with(".private.swiftinterface")
+            })
+        })
+        .collect();
+    interfaces.sort();
+
+    // Build scripts are given the target triple, such as
+    // `aarch64-apple-darwin`, whereas Swift names the architecture `arm64`.
+    let target = env::var("TARGET").ok();
+    let target_arch = target
+        .as_deref()
+        .and_then(|target| target.split('-').next())
+        .unwrap_or(env::consts::ARCH);
+    let arch_matches = |path: &PathBuf| {
+        let stem = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("");
+        let arch = match stem.split('-').next() {
+            Some("arm64") => "aarch64",
+            Some(arch) => arch,
+            None => return false,
+        };
+        arch == target_arch
+    };
+
+    let index = interfaces.iter().position(arch_matches).unwrap_or(0);
+    (index < interfaces.len()).then(|| interfaces.swap_remove(index))
+}
+
+/// An error encountered by [`Builder::generate`].
+#[derive(Debug)]
+pub enum Error {
+    /// No module, interface, or binary was given.
+    NoInput,
+
+    /// A module's interface was not found in the search paths.
+    ModuleNotFound(String),
+
+    /// A file could not be read or written.
+    Io {
+        /// The path of the file.
+        path: PathBuf,
+
+        /// The underlying error.
+        error: io::Error,
+    },
+
+    /// An interface could not be parsed.
+    Interface {
+        /// The path of the interface.
+        path: PathBuf,
+
+        /// The underlying error.
+        error: interface::Error,
+    },
+
+    /// The reflection metadata of a binary could not be read.
+    Binary {
+        /// The path of the binary.
+        path: PathBuf,
+
+        /// The underlying error.
+        error: binary::Error,
+    },
+
+    /// An allowlist or blocklist pattern is not a valid regular expression.
+    InvalidPattern(String),
+
+    /// `rustfmt` failed to format the bindings.
+    Rustfmt(String),
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io { error, .. } => Some(error),
+            Self::Interface { error, .. } => Some(error),
+            Self::Binary { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NoInput => f.write_str("no module, interface, or binary was given"),
+            Self::ModuleNotFound(name) => {
+                write!(f, "module `{}` was not found in the search paths", name)
+            }
+            Self::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            Self::Interface { path, error } => write!(f, "{}:{}", path.display(), error),
+            Self::Binary { path, error } => write!(f, "{}: {}", path.display(), error),
+            Self::InvalidPattern(message) => write!(f, "invalid pattern: {}", message),
+            Self::Rustfmt(message) => write!(f, "rustfmt failed: {}", message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHAPES: &str = include_str!("../fixtures/Shapes.swiftinterface");
+
+    /// A temporary directory that is removed when dropped.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path =
+                env::temp_dir().join(format!("swift-bindgen-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn builder() -> Builder {
+        super::builder()
+            .rustfmt_bindings(false)
+            .emit_cargo_metadata(false)
+    }
+
+    #[test]
+    fn library_names() {
+        assert_eq!(
+            library_name(Path::new("a/libShapes.so")).as_deref(),
+            Some("Shapes")
+        );
+        assert_eq!(
+            library_name(Path::new("libShapes.so.5")).as_deref(),
+            Some("Shapes")
+        );
+        assert_eq!(
+            library_name(Path::new("Shapes.dll")).as_deref(),
+            Some("Shapes")
+        );
+        assert_eq!(library_name(Path::new("lib.so")), None);
+    }
+
+    #[test]
+    fn interface_module_names() {
+        let name = |path: &str| interface_module_name(Path::new(path));
+        assert_eq!(name("a/Shapes.swiftinterface").as_deref(), Some("Shapes"));
+        assert_eq!(
+            name("Shapes.swiftmodule/x86_64.swiftinterface").as_deref(),
+            Some("Shapes")
+        );
+    }
+
+    #[test]
+    fn module_search() {
+        let dir = TempDir::new("module-search");
+        let module_dir = dir.0.join("Shapes.swiftmodule");
+        fs::create_dir(&module_dir).unwrap();
+        for arch in ["arm64-apple-macos", env::consts::ARCH].iter() {
+            let path = module_dir.join(format!("{}.swiftinterface", arch));
+            fs::write(path, SHAPES).unwrap();
+        }
+
+        let output = dir.0.join("shapes.rs");
+        let bindings = builder()
+            .module("Shapes")
+            .module_search_path(&dir.0)
+            .output(&output)
+            .generate()
+            .unwrap();
+
+        assert_eq!(bindings.module_names(), ["Shapes"]);
+        assert_eq!(fs::read_to_string(&output).unwrap(), bindings.as_str());
+
+        let error = builder()
+            .module("Circles")
+            .module_search_path(&dir.0)
+            .generate();
+        assert!(matches!(error, Err(Error::ModuleNotFound(name)) if name == "Circles"));
+    }
+
+    #[test]
+    fn binary() {
+        let dir = TempDir::new("binary");
+        let binary = dir.0.join("libShapes.so");
+        fs::write(&binary, "").unwrap();
+        fs::write(dir.0.join("Shapes.swiftinterface"), SHAPES).unwrap();
+
+        let builder = builder().binary(&binary).allowlist_type("Point");
+        let inputs = builder.inputs().unwrap();
+        assert_eq!(
+            inputs,
+            [Input::Interface {
+                path: dir.0.join("Shapes.swiftinterface"),
+                module_name: Some("Shapes".to_owned()),
+            }]
+        );
+
+        let metadata = builder.cargo_metadata(&inputs);
+        assert_eq!(
+            metadata[2..],
+            [
+                format!("cargo:rustc-link-search=native={}", dir.0.display()),
+                "cargo:rustc-link-search=native=/usr/lib/swift/".to_owned(),
+                "cargo:rustc-link-lib=dylib=swiftCore".to_owned(),
+            ]
+        );
+
+        let bindings = builder.link_name("ShapesKit").generate().unwrap();
+        assert!(bindings.as_str().contains("pub struct Point"));
+        assert!(!bindings.as_str().contains("pub struct Rect"));
+        assert!(bindings.as_str().contains("#[link(name = \"ShapesKit\""));
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(builder().generate(), Err(Error::NoInput)));
+
+        let error = builder()
+            .interface("Shapes.swiftinterface")
+            .allowlist_type("(")
+            .generate();
+        assert!(matches!(error, Err(Error::InvalidPattern(_))));
+
+        let error = builder()
+            .interface("/nonexistent/Shapes.swiftinterface")
+            .generate();
+        assert!(matches!(error, Err(Error::Io { .. })));
+
+        // Without an interface, the binary itself is read.
+        let dir = TempDir::new("errors");
+        let binary = dir.0.join("libShapes.so");
+        fs::write(&binary, "").unwrap();
+        let error = builder().binary(&binary).generate();
+        assert!(matches!(error, Err(Error::Binary { path, .. }) if path == binary));
+    }
+
+    #[test]
+    fn missing_rustfmt() {
+        let code = builder()
+            .rustfmt_path("/nonexistent/rustfmt")
+            .rustfmt("fn  f() {}".to_owned())
+            .unwrap();
+        assert_eq!(code, "fn  f() {}");
+    }
+}
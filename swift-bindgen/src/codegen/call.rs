@@ -0,0 +1,280 @@
+//! Lowering of calls that C can't express to `swift::rt::call`.
+//!
+//! `swiftcc` passes `self` in the context register when it's a reference or
+//! passed indirectly, which is the case for protocol witnesses. There is no C
+//! parameter for that register, so such calls are made through a
+//! `SwiftCall`, which loads the registers in a trampoline. The other
+//! arguments are placed in the same registers that C would use, so they're
+//! lowered as for `extern "C"` declarations first.
+//!
+//! Results of resilient types are written to a buffer passed in the indirect
+//! result register, which C has no parameter for either. Loadable results are
+//! returned in up to four registers of each class.
+
+use super::{
+    function::Lowered,
+    types::{Resolved, Scalar},
+};
+
+/// The architectures that `swift::rt::call` has trampolines for.
+pub(crate) const ARCHES: &[&str] = &["x86_64", "aarch64"];
+
+/// The number of integer registers that arguments are passed in on every
+/// architecture in `ARCHES`.
+const INT_ARGS: usize = 6;
+
+/// The number of floating-point registers that arguments are passed in.
+const FLOAT_ARGS: usize = 8;
+
+/// The number of registers of each class that results are returned in.
+const RESULTS: usize = 4;
+
+/// How a wrapper receives `self`.
+#[derive(Clone, Copy)]
+pub(crate) enum Receiver {
+    Ref,
+    Mut,
+    Static,
+}
+
+impl Receiver {
+    /// Returns the parameter of the wrapper for `self`, if any.
+    pub fn param(self) -> Option<&'static str> {
+        match self {
+            Self::Ref => Some("&self"),
+            Self::Mut => Some("&mut self"),
+            Self::Static => None,
+        }
+    }
+}
+
+/// A call that passes a value in the context register.
+pub(crate) struct ContextCall<'a> {
+    /// The arguments, which are passed first.
+    pub lowered: &'a Lowered,
+
+    /// Statements run after the arguments are evaluated, which define
+    /// `function` as the address to call and `context` if any.
+    pub setup: Vec<String>,
+
+    /// Whether `setup` defines `context`, which is passed in the context
+    /// register.
+    pub has_context: bool,
+
+    /// Pointer-sized values passed after the arguments.
+    pub trailing: Vec<&'a str>,
+}
+
+/// Returns the statements of a wrapper that makes `call`, ending with an
+/// expression of type `result`.
+pub(crate) fn lower(call: &ContextCall, result: &Resolved) -> Result<Vec<String>, String> {
+    let mut statements = call.lowered.prelude.clone();
+
+    // Arguments are converted to register values in one statement, so that
+    // their names don't shadow parameters that are yet to be read.
+    let mut patterns = Vec::new();
+    let mut values = Vec::new();
+    let mut args = Vec::new();
+    let mut is_unsafe = false;
+    for (i, ((arg, ty), scalars)) in call
+        .lowered
+        .args
+        .iter()
+        .zip(&call.lowered.arg_types)
+        .zip(&call.lowered.arg_scalars)
+        .enumerate()
+    {
+        let name = format!("arg{}", i);
+        let value = match scalars.as_slice() {
+            // Zero-sized values aren't passed, but may still be read out of
+            // their owner to be consumed.
+            [] => {
+                is_unsafe |= arg.contains("::std::ptr::read(");
+                patterns.push("_".to_owned());
+                values.push(arg.clone());
+                continue;
+            }
+            // Primitives are cast, and integers aren't sign-extended.
+            [scalar] => {
+                let register = register_type(*scalar);
+                let value = match primitive(ty) {
+                    Some(primitive) if primitive == register => arg.clone(),
+                    Some(_) => format!("{} as {}", arg, register),
+                    None => format!("::std::mem::transmute::<_, {}>({})", register, arg),
+                };
+                match scalar {
+                    Scalar::Int(size) if *size < 8 => format!("{} as u64", value),
+                    _ => value,
+                }
+            }
+            scalars if scalars.iter().all(|s| *s == Scalar::Int(8)) => format!(
+                "::std::mem::transmute::<_, [u64; {}]>({})",
+                scalars.len(),
+                arg
+            ),
+            _ => return Err(format!("`{}` mixes integer and float registers", arg)),
+        };
+        is_unsafe |= value.contains("::std::mem::transmute");
+        match scalars.as_slice() {
+            [scalar] => args.push((name.clone(), *scalar)),
+            scalars => {
+                for (j, scalar) in scalars.iter().enumerate() {
+                    args.push((format!("{}[{}]", name, j), *scalar));
+                }
+            }
+        }
+        patterns.push(name);
+        values.push(value);
+    }
+    if !values.is_empty() {
+        let values = format!("({},)", values.join(", "));
+        statements.push(format!(
+            "let ({},) = {};",
+            patterns.join(", "),
+            match is_unsafe {
+                true => format!("unsafe {{ {} }}", values),
+                false => values,
+            }
+        ));
+    }
+    statements.extend(call.setup.iter().cloned());
+    for value in &call.trailing {
+        args.push((format!("{} as u64", value), Scalar::Int(8)));
+    }
+
+    let indirect = call.lowered.indirect_result;
+    if indirect {
+        statements.push(format!(
+            "let mut result = unsafe {{ ::swift::rt::value::ValueBox::new_uninit(\
+             ::std::convert::AsRef::<::swift::rt::metadata::Metadata>::as_ref(\
+             <{} as ::swift::rt::metadata::Type>::get_metadata())) }};",
+            result.rust
+        ));
+        statements.push("let result_ptr = result.as_mut_ptr();".to_owned());
+    }
+
+    let result_scalars = match indirect {
+        true => &[][..],
+        false => &result.layout.scalars[..],
+    };
+    let ints = args
+        .iter()
+        .filter(|(_, s)| matches!(s, Scalar::Int(_)))
+        .count();
+    if ints > INT_ARGS || args.len() - ints > FLOAT_ARGS {
+        return Err("arguments are passed on the stack".to_owned());
+    }
+    let not_in_registers = || format!("result `{}` is not returned in registers", result.rust);
+    let int_results = result_scalars
+        .iter()
+        .filter(|s| matches!(s, Scalar::Int(_)))
+        .count();
+    if int_results > RESULTS || result_scalars.len() - int_results > RESULTS {
+        return Err(not_in_registers());
+    }
+
+    let binding = match result_scalars {
+        [] => "",
+        _ => "let ret = ",
+    };
+    let mut call_expr = vec![
+        format!("{}unsafe {{", binding),
+        "    ::swift::rt::call::SwiftCall::new(function)".to_owned(),
+    ];
+    for (value, scalar) in &args {
+        let method = match scalar {
+            Scalar::Int(_) => "int",
+            Scalar::Float(4) => "float32",
+            Scalar::Float(_) => "float",
+        };
+        call_expr.push(format!("        .{}({})", method, value));
+    }
+    if call.has_context {
+        call_expr.push("        .context(context)".to_owned());
+    }
+    if indirect {
+        call_expr.push("        .indirect_result(result_ptr)".to_owned());
+    }
+    call_expr.push("        .call()".to_owned());
+    call_expr.push("};".to_owned());
+    statements.extend(call_expr);
+
+    // Each class of result is returned in its own sequence of registers.
+    let (mut ints, mut floats) = (0, 0);
+    let results: Vec<String> = result_scalars
+        .iter()
+        .map(|scalar| match scalar {
+            Scalar::Int(size) => {
+                ints += 1;
+                match size {
+                    8 => format!("ret.int({})", ints - 1),
+                    size => format!("ret.int({}) as u{}", ints - 1, size * 8),
+                }
+            }
+            Scalar::Float(size) => {
+                floats += 1;
+                match size {
+                    4 => format!("ret.float32({})", floats - 1),
+                    _ => format!("ret.float({})", floats - 1),
+                }
+            }
+        })
+        .collect();
+
+    statements.extend(call.lowered.cleanup.iter().cloned());
+    match results.as_slice() {
+        [] if indirect => statements.push(format!("{}(result)", result.rust)),
+        [] => {}
+        [result0] => {
+            let register = register_type(result_scalars[0]);
+            statements.push(match primitive(&result.rust) {
+                Some(primitive) if primitive == register => result0.clone(),
+                // Integers can't be cast to `bool`.
+                Some(primitive) if primitive != "bool" => format!("{} as {}", result0, primitive),
+                _ => format!("unsafe {{ ::std::mem::transmute({}) }}", result0),
+            });
+        }
+        results => {
+            // Arrays can't mix the types of scalars.
+            if result_scalars.windows(2).any(|pair| pair[0] != pair[1]) {
+                return Err(not_in_registers());
+            }
+            statements.push(format!(
+                "unsafe {{ ::std::mem::transmute([{}]) }}",
+                results.join(", ")
+            ));
+        }
+    }
+    Ok(statements)
+}
+
+/// Returns the type of the values of `scalar` that `SwiftCall` takes and
+/// returns, before integers are widened to `u64`.
+fn register_type(scalar: Scalar) -> String {
+    match scalar {
+        Scalar::Int(size) => format!("u{}", size * 8),
+        Scalar::Float(size) => format!("f{}", size * 8),
+    }
+}
+
+/// Returns the primitive type that `rust` is, or that `swift` aliases it to,
+/// if it's a number or `bool`.
+fn primitive(rust: &str) -> Option<&'static str> {
+    let primitive = match rust.strip_prefix("::swift::").unwrap_or(rust) {
+        "Bool" | "bool" => "bool",
+        "Int" | "isize" => "isize",
+        "Int8" | "i8" => "i8",
+        "Int16" | "i16" => "i16",
+        "Int32" | "i32" => "i32",
+        "Int64" | "i64" => "i64",
+        "UInt" | "usize" => "usize",
+        "UInt8" | "u8" => "u8",
+        "UInt16" | "u16" => "u16",
+        "UInt32" | "u32" => "u32",
+        "UInt64" | "u64" => "u64",
+        "Float" | "Float32" | "f32" => "f32",
+        "Double" | "Float64" | "f64" => "f64",
+        _ => return None,
+    };
+    Some(primitive)
+}
@@ -0,0 +1,267 @@
+//! Generation of methods of classes.
+//!
+//! Class members take `self` in the context register, so they're called
+//! through `swift::rt::call`. Members that subclasses can override are found in the
+//! vtable of the object's dynamic class with `swift_lookUpClassMethod`, so
+//! that overrides are honored. Final members are called directly.
+
+use super::{
+    call::{ContextCall, Receiver},
+    function::Lowered,
+    labels,
+    types::{Repr, Resolved},
+    Callee, Generator, Scope,
+};
+use crate::{
+    ir::{has_modifier, Decl},
+    mangle,
+};
+
+impl Generator<'_> {
+    /// Returns a wrapper that calls the class member `decl`, whose symbol is
+    /// for `entity`.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn class_member(
+        &mut self,
+        scope: &Scope,
+        decl: &Decl,
+        rust_name: &str,
+        callee: Callee,
+        entity: mangle::Entity,
+        is_static: bool,
+        lowered: &Lowered,
+        result: &Resolved,
+    ) -> Result<String, String> {
+        let setup = self.class_setup(scope, decl, rust_name, entity, is_static)?;
+        let receiver = match is_static {
+            true => Receiver::Static,
+            false => Receiver::Ref,
+        };
+        let call = ContextCall {
+            lowered,
+            setup,
+            has_context: true,
+            trailing: Vec::new(),
+        };
+        self.context_wrapper(scope, "pub fn", rust_name, callee, receiver, &call, result)
+    }
+
+    /// Returns statements that define `function` as the implementation of
+    /// `decl` for `self`, or for the class itself if `is_static`, and
+    /// `context` as the value passed in the context register.
+    pub(super) fn class_setup(
+        &mut self,
+        scope: &Scope,
+        decl: &Decl,
+        rust_name: &str,
+        entity: mangle::Entity,
+        is_static: bool,
+    ) -> Result<Vec<String>, String> {
+        let index = scope.index.expect("members are declared in types");
+        let type_name = scope.rust_name.as_deref().expect("classes are named");
+
+        let mut setup = Vec::new();
+        if is_static {
+            setup.push(
+                "let metadata = <Self as ::swift::rt::metadata::Type>::get_metadata() \
+                 as *const ::swift::rt::metadata::ClassMetadata;"
+                    .to_owned(),
+            );
+        }
+        if self.is_overridable(index, decl) {
+            let (class, entity) = self.introduced(index, decl, entity)?;
+            if !is_static {
+                setup.push(
+                    "let metadata = unsafe { \
+                     ::swift::sys::casting::swift_getObjectType(self.0.as_ptr() as *const _) };"
+                        .to_owned(),
+                );
+            }
+            let method = self.declare_extern_static(
+                &format!("{}_{}_method", type_name, rust_name),
+                &mangle::method_descriptor(&entity),
+                "::swift::sys::ctx_desc::MethodDescriptor",
+            );
+            let descriptor = self.class_descriptor(class);
+            setup.push(format!(
+                "let function = unsafe {{ \
+                 ::swift::sys::metadata::fns::swift_lookUpClassMethod(metadata.cast(), &{}, &{}) \
+                 }};",
+                method, descriptor
+            ));
+        } else {
+            let path = self.declare_extern(
+                &format!("{}_{}", type_name, rust_name),
+                &mangle::entity(&entity),
+                &[],
+                None,
+            );
+            setup.push(format!(
+                "let function = {} as *const ::std::ffi::c_void;",
+                path
+            ));
+        }
+        setup.push(match is_static {
+            true => "let context = metadata;".to_owned(),
+            false => "let context = self.0.as_ptr();".to_owned(),
+        });
+        Ok(setup)
+    }
+
+    /// Returns `true` if subclasses may override `decl` of the class at
+    /// `index`, so it has a vtable entry.
+    fn is_overridable(&self, index: usize, decl: &Decl) -> bool {
+        let class = self.table.entries[index].decl;
+        let modifiers = modifiers(decl);
+
+        // Members of extensions are never in the vtable.
+        let in_class = class.decls.iter().any(|member| std::ptr::eq(member, decl));
+        // Constants can't be overridden.
+        let is_let = matches!(decl, Decl::Variable(var) if var.is_let);
+        // Initializers are called on the static type of the class.
+        let is_init = matches!(decl, Decl::Initializer(_));
+        in_class
+            && !is_let
+            && !is_init
+            && !has_modifier(&class.modifiers, "final")
+            && !has_modifier(modifiers, "final")
+            && !has_modifier(modifiers, "static")
+    }
+
+    /// Returns the class that introduced the vtable entry of `decl` in the
+    /// class at `index`, and `entity` within that class.
+    fn introduced(
+        &self,
+        index: usize,
+        decl: &Decl,
+        entity: mangle::Entity,
+    ) -> Result<(usize, mangle::Entity), String> {
+        if !has_modifier(modifiers(decl), "override") {
+            return Ok((index, entity));
+        }
+
+        let mut class = index;
+        while let Some(superclass) = self.superclass(class) {
+            let overridden = self.table.entries[superclass]
+                .decl
+                .decls
+                .iter()
+                .find(|member| is_same_member(member, decl));
+            if let Some(overridden) = overridden {
+                if !has_modifier(modifiers(overridden), "override") {
+                    let nominal = self.table.entries[superclass].nominal.clone();
+                    let context = mangle::Context::Nominal(Box::new(nominal));
+                    return Ok((superclass, with_context(entity, context)));
+                }
+            }
+            class = superclass;
+        }
+        Err("overrides of members from other modules are not supported".to_owned())
+    }
+
+    /// Returns the superclass of the class at `index` if it's declared in the
+    /// module.
+    fn superclass(&self, index: usize) -> Option<usize> {
+        let entry = &self.table.entries[index];
+        entry
+            .inherited
+            .iter()
+            .filter_map(|ty| ty.as_path())
+            .filter_map(|path| self.table.find(path, entry.parent))
+            .find(|&found| matches!(*self.table.repr(found), Repr::Class))
+    }
+
+    /// Declares the descriptor of the class at `index` and returns its path.
+    fn class_descriptor(&mut self, index: usize) -> String {
+        if let Some(path) = self.class_descriptors.get(&index) {
+            return path.clone();
+        }
+        let entry = &self.table.entries[index];
+        let symbol = mangle::type_symbol(
+            &mangle::Type::Nominal(entry.nominal.clone()),
+            mangle::TypeSymbol::NominalTypeDescriptor,
+        );
+        let path = self.declare_extern_static(
+            &format!("{}_descriptor", entry.rust_name),
+            &symbol,
+            "::swift::sys::ctx_desc::ClassDescriptor",
+        );
+        self.class_descriptors.insert(index, path.clone());
+        path
+    }
+}
+
+fn modifiers(decl: &Decl) -> &[String] {
+    match decl {
+        Decl::Function(f) => &f.modifiers,
+        Decl::Initializer(init) => &init.modifiers,
+        Decl::Variable(var) => &var.modifiers,
+        Decl::Subscript(sub) => &sub.modifiers,
+        _ => &[],
+    }
+}
+
+/// Returns `true` if `a` and `b` have the same name, so one overrides the
+/// other.
+fn is_same_member(a: &Decl, b: &Decl) -> bool {
+    match (a, b) {
+        (Decl::Function(a), Decl::Function(b)) => {
+            a.name == b.name && labels(&a.params) == labels(&b.params)
+        }
+        (Decl::Variable(a), Decl::Variable(b)) => a.name == b.name,
+        (Decl::Subscript(a), Decl::Subscript(b)) => labels(&a.params) == labels(&b.params),
+        _ => false,
+    }
+}
+
+/// Returns `entity` declared in `context` instead.
+fn with_context(entity: mangle::Entity, context: mangle::Context) -> mangle::Entity {
+    use mangle::Entity::*;
+
+    match entity {
+        Function {
+            name,
+            signature,
+            is_static,
+            ..
+        } => Function {
+            context,
+            name,
+            signature,
+            is_static,
+        },
+        Constructor {
+            signature,
+            allocating,
+            ..
+        } => Constructor {
+            context,
+            signature,
+            allocating,
+        },
+        Variable {
+            name,
+            ty,
+            accessor,
+            is_static,
+            ..
+        } => Variable {
+            context,
+            name,
+            ty,
+            accessor,
+            is_static,
+        },
+        Subscript {
+            signature,
+            accessor,
+            is_static,
+            ..
+        } => Subscript {
+            context,
+            signature,
+            accessor,
+            is_static,
+        },
+    }
+}
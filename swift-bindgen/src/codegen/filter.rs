@@ -0,0 +1,58 @@
+//! Selection of the declarations to bind.
+
+use regex::RegexSet;
+
+/// Regular expressions matched against entire declaration names.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Patterns(Option<RegexSet>);
+
+impl Patterns {
+    pub fn new(patterns: &[String]) -> Result<Self, regex::Error> {
+        if patterns.is_empty() {
+            return Ok(Self(None));
+        }
+        let anchored = patterns.iter().map(|pattern| format!("^(?:{})$", pattern));
+        RegexSet::new(anchored).map(|set| Self(Some(set)))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Returns `true` if a pattern matches `name` within `module`, with or
+    /// without the module name as a prefix.
+    pub fn matches(&self, module: &str, name: &str) -> bool {
+        match &self.0 {
+            Some(set) => set.is_match(name) || set.is_match(&format!("{}.{}", module, name)),
+            None => false,
+        }
+    }
+}
+
+/// Allowlists and blocklists of Swift types and functions.
+///
+/// Without an allowlist, every public declaration is bound. Otherwise, only
+/// allowlisted declarations are bound, along with the types they need for
+/// their layout or signature.
+///
+/// Blocklisted types are not defined, but remain usable in signatures. As in
+/// C `bindgen`, the crate including the bindings must then define them.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Filter {
+    pub allowlist_types: Patterns,
+    pub allowlist_functions: Patterns,
+    pub blocklist_types: Patterns,
+    pub blocklist_functions: Patterns,
+}
+
+impl Filter {
+    pub fn has_allowlist(&self) -> bool {
+        !self.allowlist_types.is_empty() || !self.allowlist_functions.is_empty()
+    }
+
+    /// Returns `true` if the module-level function `name` should be bound.
+    pub fn allows_function(&self, module: &str, name: &str) -> bool {
+        (!self.has_allowlist() || self.allowlist_functions.matches(module, name))
+            && !self.blocklist_functions.matches(module, name)
+    }
+}
@@ -0,0 +1,232 @@
+//! Lowering of Swift functions to `extern "C"` declarations.
+//!
+//! Under `swiftcc`, a loadable value is expanded into its scalar fields when
+//! passed directly. Declaring each of those scalars as a separate C parameter
+//! places them in the same registers. Results that don't fit in the two
+//! registers that C and `swiftcc` agree on are returned in up to four.
+//!
+//! Values of resilient types are always passed by address. Their results are
+//! written to a buffer passed in the indirect result register. C can't express
+//! either of these, so those calls are made through `swift::rt::call`.
+
+use super::{
+    names,
+    types::{Resolved, Scalar},
+};
+use crate::ir::Ownership;
+
+/// The most scalars a value may have before `swiftcc` passes it indirectly.
+const MAX_DIRECT_SCALARS: usize = 4;
+
+/// The number of integer argument registers on x86-64.
+const MAX_INT_REGISTERS: usize = 6;
+
+/// The number of floating-point argument registers on x86-64 and AArch64.
+const MAX_FLOAT_REGISTERS: usize = 8;
+
+/// A parameter to lower.
+pub(crate) struct Param {
+    pub name: String,
+    pub ty: Resolved,
+    pub ownership: Ownership,
+
+    /// Whether the callee takes ownership of the argument.
+    pub owned: bool,
+}
+
+/// A function lowered to an `extern "C"` declaration and its Rust wrapper.
+#[derive(Default)]
+pub(crate) struct Lowered {
+    pub extern_params: Vec<String>,
+    pub extern_result: Option<String>,
+
+    /// `self` or `&self`.
+    pub receiver: Option<&'static str>,
+    pub params: Vec<String>,
+
+    /// Statements to run before the call.
+    pub prelude: Vec<String>,
+    pub args: Vec<String>,
+
+    /// The Rust types of `args`.
+    pub arg_types: Vec<String>,
+
+    /// The registers used by each of `args`.
+    pub arg_scalars: Vec<Vec<Scalar>>,
+    pub result: Option<String>,
+
+    /// Whether the result is written to a buffer passed in the indirect
+    /// result register.
+    pub indirect_result: bool,
+
+    /// Whether the result is returned in registers that C doesn't return
+    /// values in.
+    pub wide_result: bool,
+
+    /// Statements to run after the call.
+    pub cleanup: Vec<String>,
+}
+
+impl Lowered {
+    /// Lowers a call with `params`, followed by `self_ty` if it's a method.
+    pub fn new(
+        params: &[Param],
+        self_ty: Option<&Resolved>,
+        result: &Resolved,
+    ) -> Result<Self, String> {
+        let mut lowered = Self::default();
+        let mut scalars = Vec::new();
+
+        for param in params {
+            if param.ty.layout.indirect {
+                scalars.push(Scalar::Int(8));
+                lowered.push_indirect(param);
+                continue;
+            }
+            if param.ownership == Ownership::InOut {
+                lowered
+                    .params
+                    .push(format!("{}: &mut {}", param.name, param.ty.rust));
+                lowered
+                    .extern_params
+                    .push(format!("{}: *mut {}", param.name, param.ty.rust));
+                lowered.args.push(param.name.clone());
+                lowered.arg_types.push(format!("&mut {}", param.ty.rust));
+                lowered.arg_scalars.push(vec![Scalar::Int(8)]);
+                scalars.push(Scalar::Int(8));
+                continue;
+            }
+            if param.ty.layout.scalars.len() > MAX_DIRECT_SCALARS {
+                return Err(format!("`{}` is passed indirectly", param.name));
+            }
+            scalars.extend_from_slice(&param.ty.layout.scalars);
+            lowered.push_param(&param.name, &param.ty, param.owned);
+        }
+
+        if let Some(self_ty) = self_ty {
+            if self_ty.layout.indirect || self_ty.layout.scalars.len() > MAX_DIRECT_SCALARS {
+                return Err("`self` is passed indirectly".to_owned());
+            }
+            scalars.extend_from_slice(&self_ty.layout.scalars);
+            lowered.push_param("self", self_ty, false);
+        }
+
+        let ints = scalars
+            .iter()
+            .filter(|s| matches!(s, Scalar::Int(_)))
+            .count();
+        if ints > MAX_INT_REGISTERS || scalars.len() - ints > MAX_FLOAT_REGISTERS {
+            return Err("arguments are passed on the stack".to_owned());
+        }
+
+        if result.layout.indirect {
+            lowered.indirect_result = true;
+            lowered.result = Some(result.rust.clone());
+        } else if !result.is_void() {
+            let direct = matches!(
+                result.layout.scalars.as_slice(),
+                [_] | [Scalar::Int(8), Scalar::Int(8)] | [Scalar::Float(8), Scalar::Float(8)]
+            );
+            match direct {
+                true => lowered.extern_result = Some(result.rust.clone()),
+                false => lowered.wide_result = true,
+            }
+            lowered.result = Some(result.rust.clone());
+        }
+
+        Ok(lowered)
+    }
+
+    /// Adds a parameter of a resilient type, which is passed by address.
+    fn push_indirect(&mut self, param: &Param) {
+        let (name, rust) = (&param.name, &param.ty.rust);
+        let (ty, extern_ty, arg) = match param.ownership {
+            Ownership::InOut => (format!("&mut {}", rust), "*mut u8", "as_mut_ptr()"),
+            _ if param.owned => (rust.clone(), "*mut u8", "as_ptr() as *mut u8"),
+            _ => (format!("&{}", rust), "*const u8", "as_ptr()"),
+        };
+        self.params.push(format!("{}: {}", name, ty));
+        self.extern_params.push(format!("{}: {}", name, extern_ty));
+        self.args.push(format!("{}.0.{}", name, arg));
+        self.arg_types.push(extern_ty.to_owned());
+        self.arg_scalars.push(vec![Scalar::Int(8)]);
+
+        // The callee takes the value, leaving only its storage.
+        if param.owned {
+            self.cleanup
+                .push(format!("unsafe {{ {}.0.forget_value() }};", name));
+        }
+    }
+
+    /// Adds a parameter passed as its leaves.
+    fn push_param(&mut self, name: &str, ty: &Resolved, owned: bool) {
+        let is_self = name == "self";
+
+        if ty.layout.pod || (owned && is_single_leaf(ty)) {
+            match is_self {
+                true => self.receiver = Some("self"),
+                false => self.params.push(format!("{}: {}", name, ty.rust)),
+            }
+            for leaf in &ty.leaves {
+                let extern_name = extern_name(name, &leaf.path);
+                self.extern_params
+                    .push(format!("{}: {}", extern_name, leaf.rust));
+                self.args.push(format!("{}{}", name, leaf.path));
+                self.arg_types.push(leaf.rust.clone());
+                self.arg_scalars.push(leaf.scalars.clone());
+            }
+            return;
+        }
+
+        match (is_self, owned) {
+            (true, _) => self.receiver = Some("&self"),
+            (false, true) => {
+                self.params.push(format!("{}: {}", name, ty.rust));
+                self.prelude.push(format!(
+                    "let {0} = ::std::mem::ManuallyDrop::new({0});",
+                    name
+                ));
+            }
+            (false, false) => self.params.push(format!("{}: &{}", name, ty.rust)),
+        }
+
+        // The callee borrows or consumes the leaves without the caller
+        // dropping them.
+        for leaf in &ty.leaves {
+            let extern_name = extern_name(name, &leaf.path);
+            if leaf.pod {
+                self.extern_params
+                    .push(format!("{}: {}", extern_name, leaf.rust));
+                self.args.push(format!("{}{}", name, leaf.path));
+                self.arg_types.push(leaf.rust.clone());
+            } else {
+                self.extern_params.push(format!(
+                    "{}: ::std::mem::ManuallyDrop<{}>",
+                    extern_name, leaf.rust
+                ));
+                let place = match leaf.path.as_str() {
+                    "" => name.to_owned(),
+                    path => format!("&{}{}", name, path),
+                };
+                self.args.push(format!(
+                    "::std::mem::ManuallyDrop::new(::std::ptr::read({}))",
+                    place
+                ));
+                self.arg_types
+                    .push(format!("::std::mem::ManuallyDrop<{}>", leaf.rust));
+            }
+            self.arg_scalars.push(leaf.scalars.clone());
+        }
+    }
+}
+
+fn is_single_leaf(ty: &Resolved) -> bool {
+    matches!(ty.leaves.as_slice(), [leaf] if leaf.path.is_empty())
+}
+
+/// Returns the `extern "C"` parameter name for the leaf at `path` of `name`.
+fn extern_name(name: &str, path: &str) -> String {
+    let name = name.trim_start_matches("r#");
+    let path = path.replace("r#", "").replace('.', "_");
+    names::escape(&format!("{}{}", name, path))
+}
@@ -0,0 +1,209 @@
+//! Generation of accessors for global variables and static properties.
+//!
+//! Outside of library evolution, stored variables are reached through their
+//! unsafe mutable addressor, which lazily initializes the value and returns
+//! its address. Constants are then borrowed for `'static`, while variables
+//! are copied out and assigned in place, since Swift may change them at any
+//! time. Computed variables, and all of those of resilient modules, are
+//! called through their getter and setter instead.
+
+use super::{
+    function::{Lowered, Param},
+    names::{self, NameSet},
+    types::{Repr, Resolved},
+    Call, Callee, Generator, Scope, Writer,
+};
+use crate::{
+    ir::{self, Decl, Ownership},
+    mangle,
+};
+
+impl Generator<'_> {
+    /// Returns the accessors of the global or static variable `var`.
+    pub(super) fn global(
+        &mut self,
+        scope: &Scope,
+        decl: &Decl,
+        var: &ir::Variable,
+        is_static: bool,
+        names: &mut NameSet,
+    ) -> Result<String, String> {
+        let ty = self.table.resolve(&var.ty, scope.index)?;
+        let rust_name = names.unique(&names::snake_case(&var.name), &[]);
+        let entity = |accessor| mangle::Entity::Variable {
+            context: scope.context.clone(),
+            name: var.name.clone(),
+            ty: ty.mangled.clone(),
+            accessor,
+            is_static,
+        };
+
+        if var.is_stored() && !self.table.module.is_resilient() {
+            let entity = entity(mangle::Accessor::UnsafeMutableAddressor);
+            return Ok(self.addressed(scope, var, &ty, &rust_name, &entity, names));
+        }
+
+        let lowered = Lowered::new(&[], None, &ty)?;
+        let getter = entity(mangle::Accessor::Getter);
+        let mut wrappers = vec![self.accessor(
+            scope,
+            decl,
+            &rust_name,
+            Callee::Function(&var.name),
+            getter,
+            &lowered,
+            &ty,
+        )?];
+
+        if var.is_settable() {
+            let param = Param {
+                name: "value".to_owned(),
+                ty: ty.clone(),
+                ownership: Ownership::Default,
+                owned: true,
+            };
+            let void = Resolved::void();
+            let lowered = Lowered::new(&[param], None, &void)?;
+            let setter_name = names.unique(&format!("set_{}", rust_name), &[]);
+            let setter = entity(mangle::Accessor::Setter);
+            wrappers.push(self.accessor(
+                scope,
+                decl,
+                &setter_name,
+                Callee::Setter(&var.name),
+                setter,
+                &lowered,
+                &void,
+            )?);
+        }
+        Ok(wrappers.join("\n"))
+    }
+
+    /// Returns a wrapper that calls the accessor `entity`.
+    #[allow(clippy::too_many_arguments)]
+    fn accessor(
+        &mut self,
+        scope: &Scope,
+        decl: &Decl,
+        rust_name: &str,
+        callee: Callee,
+        entity: mangle::Entity,
+        lowered: &Lowered,
+        result: &Resolved,
+    ) -> Result<String, String> {
+        // Static accessors of classes take the metadata in the context
+        // register, and may be overridden.
+        if let Some(Repr::Class) = scope.repr.as_deref() {
+            return self.class_member(
+                scope, decl, rust_name, callee, entity, true, lowered, result,
+            );
+        }
+        let symbol = mangle::entity(&entity);
+        let call = Call {
+            symbol: &symbol,
+            lowered,
+            result,
+            indirect_self: false,
+            mutating: false,
+        };
+        self.wrapper(scope, rust_name, callee, &call)
+    }
+
+    /// Returns functions that access the stored variable `var` through the
+    /// addressor `entity`.
+    fn addressed(
+        &mut self,
+        scope: &Scope,
+        var: &ir::Variable,
+        ty: &Resolved,
+        rust_name: &str,
+        entity: &mangle::Entity,
+        names: &mut NameSet,
+    ) -> String {
+        let extern_base = match &scope.rust_name {
+            Some(type_name) => format!("{}_{}", type_name, rust_name),
+            None => rust_name.to_owned(),
+        };
+        let addressor = self.declare_extern(
+            &format!("{}_addressor", extern_base),
+            &mangle::entity(entity),
+            &[],
+            Some("*mut u8"),
+        );
+        let swift_name = format!("{}.{}", scope.swift_name, var.name);
+        let address = format!("{}().cast::<{}>()", addressor, ty.rust);
+        let metadata = format!(
+            "let metadata = ::std::convert::AsRef::<::swift::rt::metadata::Metadata>::as_ref(\
+             <{} as ::swift::rt::metadata::Type>::get_metadata());",
+            ty.rust
+        );
+
+        let mut w = Writer::new();
+        let alias = |w: &mut Writer, rust_name: &str| {
+            if var.name != rust_name {
+                w.line(format!("#[doc(alias = \"{}\")]", var.name));
+            }
+        };
+        if var.is_let && !ty.layout.indirect {
+            w.doc(format!("Returns a reference to `{}`.", swift_name));
+            w.line("#[inline]");
+            alias(&mut w, rust_name);
+            w.open(format!(
+                "pub fn {}() -> &'static {}",
+                names::escape(rust_name),
+                ty.rust
+            ));
+            w.line(format!("unsafe {{ &*{} }}", address));
+            w.close();
+        } else {
+            w.doc(format!("Returns a copy of `{}`.", swift_name));
+            w.line("#[inline]");
+            alias(&mut w, rust_name);
+            w.open(format!(
+                "pub fn {}() -> {}",
+                names::escape(rust_name),
+                ty.rust
+            ));
+            if ty.layout.indirect {
+                w.line(&metadata);
+                w.line("unsafe {");
+                w.line("    let mut value = ::swift::rt::value::ValueBox::new_uninit(metadata);");
+                w.line(format!(
+                    "    metadata.vw_initialize_with_copy(value.as_mut_ptr(), {}());",
+                    addressor
+                ));
+                w.line(format!("    {}(value)", ty.rust));
+                w.line("}");
+            } else {
+                w.line(format!("unsafe {{ (*{}).clone() }}", address));
+            }
+            w.close();
+        }
+
+        if var.is_settable() {
+            let setter_name = names.unique(&format!("set_{}", rust_name), &[]);
+            w.blank();
+            w.doc(format!("Assigns `value` to `{}`.", swift_name));
+            w.line("#[inline]");
+            w.open(format!(
+                "pub fn {}(value: {})",
+                names::escape(&setter_name),
+                ty.rust
+            ));
+            if ty.layout.indirect {
+                w.line(&metadata);
+                w.line("unsafe {");
+                w.line(format!(
+                    "    metadata.vw_assign_with_take({}(), value.0.as_ptr() as *mut u8);",
+                    addressor
+                ));
+                w.line("    value.0.forget_value();");
+                w.line("}");
+            } else {
+                w.line(format!("unsafe {{ *{} = value }};", address));
+            }
+            w.close();
+        }
+        w.finish()
+    }
+}
@@ -0,0 +1,1174 @@
+//! Generation of Rust source from a parsed module.
+//!
+//! Each Swift module becomes a Rust module containing its types and
+//! functions. Symbols are declared in a private `sys` module and called
+//! through safe wrappers. Declarations that can't be bound soundly are
+//! replaced by a `// Skipped` comment stating why.
+
+use crate::{
+    ir::{self, has_modifier, Decl, Ownership},
+    mangle,
+};
+use std::{collections::HashMap, fmt::Write as _, rc::Rc};
+
+mod call;
+mod class;
+mod filter;
+mod function;
+mod global;
+mod names;
+mod payload;
+mod property;
+mod protocol;
+mod tests;
+mod types;
+mod writer;
+
+pub(crate) use filter::{Filter, Patterns};
+pub(crate) use writer::Writer;
+
+use call::{ContextCall, Receiver};
+use function::Lowered;
+use names::NameSet;
+use types::{is_public, is_unavailable, Repr, Resolved, Signature, TypeTable};
+
+/// Options for generating bindings.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Options {
+    pub filter: Filter,
+
+    /// The library that defines the module's symbols, which defaults to the
+    /// module name.
+    pub link_name: Option<String>,
+}
+
+/// Generates Rust bindings for `module`.
+pub(crate) fn generate(module: &ir::Module, options: &Options) -> String {
+    Generator::new(module, options).generate()
+}
+
+/// The declaration being bound within a type or module.
+struct Scope {
+    /// The type's index in the [`TypeTable`], if any.
+    index: Option<usize>,
+    context: mangle::Context,
+
+    /// The qualified Swift name of the scope, such as `Shapes.Point`.
+    swift_name: String,
+
+    /// The Rust name of the type, used to prefix symbol names.
+    rust_name: Option<String>,
+    repr: Option<Rc<Repr>>,
+}
+
+/// A call to a Swift function from its wrapper.
+#[derive(Clone, Copy)]
+struct Call<'a> {
+    symbol: &'a str,
+    lowered: &'a Lowered,
+    result: &'a Resolved,
+
+    /// Whether `self` is passed by address in the context register.
+    indirect_self: bool,
+
+    /// Whether `self` is `inout`, so its address is passed in the context
+    /// register for the callee to mutate.
+    mutating: bool,
+}
+
+/// The Swift declaration that a wrapper calls, which documents it.
+#[derive(Clone, Copy)]
+enum Callee<'a> {
+    /// A function, initializer or getter, by its Swift name.
+    Function(&'a str),
+
+    /// The setter of a property or subscript, by its Swift name.
+    Setter(&'a str),
+}
+
+struct Generator<'a> {
+    table: TypeTable<'a>,
+    options: &'a Options,
+
+    /// Whether each entry of the table is defined in the bindings.
+    defined: Vec<bool>,
+
+    /// Declarations in the `extern` block of the `sys` module.
+    externs: Vec<String>,
+    extern_names: NameSet,
+
+    /// Paths to the declared descriptors of classes, by index.
+    class_descriptors: HashMap<usize, String>,
+
+    /// Whether any wrapper calls Swift through `swift::rt::call`.
+    uses_swift_call: bool,
+}
+
+impl<'a> Generator<'a> {
+    fn new(module: &'a ir::Module, options: &'a Options) -> Self {
+        let mut generator = Self {
+            table: TypeTable::new(module),
+            options,
+            defined: Vec::new(),
+            externs: Vec::new(),
+            extern_names: NameSet::new(),
+            class_descriptors: HashMap::new(),
+            uses_swift_call: false,
+        };
+        generator.select();
+        generator
+    }
+
+    /// Determines which types are defined according to the filter.
+    fn select(&mut self) {
+        let filter = &self.options.filter;
+        let table = &mut self.table;
+        let module = table.module;
+        let count = table.entries.len();
+
+        let mut selected = vec![!filter.has_allowlist(); count];
+        if filter.has_allowlist() {
+            let mut pending: Vec<usize> = (0..count)
+                .filter(|&index| {
+                    let name = &table.entries[index].swift_name;
+                    filter.allowlist_types.matches(&module.name, name)
+                })
+                .collect();
+            for decl in &module.decls {
+                if let Decl::Function(f) = decl {
+                    if filter.allows_function(&module.name, &f.name) {
+                        for param in &f.params {
+                            table.references(&param.ty, None, &mut pending);
+                        }
+                        table.references(&f.result, None, &mut pending);
+                    }
+                }
+            }
+
+            // Types of stored properties and payloads are needed for the
+            // layout, and protocols of generic requirements for bounds.
+            while let Some(index) = pending.pop() {
+                if std::mem::replace(&mut selected[index], true) {
+                    continue;
+                }
+                for requirement in &table.entries[index].decl.generics.requirements {
+                    if let ir::Requirement::Conformance { constraint, .. } = requirement {
+                        table.references(constraint, Some(index), &mut pending);
+                    }
+                }
+                for member in &table.entries[index].members {
+                    match member.decl {
+                        Decl::Variable(var) if var.is_stored() => {
+                            table.references(&var.ty, Some(index), &mut pending);
+                        }
+                        Decl::EnumCase(case) => {
+                            let payloads = case.elements.iter().flat_map(|e| e.payload.iter());
+                            for value in payloads.flatten() {
+                                table.references(&value.ty, Some(index), &mut pending);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        table.excluded = selected.iter().map(|&selected| !selected).collect();
+        self.defined = table
+            .entries
+            .iter()
+            .zip(selected)
+            .map(|(entry, selected)| {
+                selected
+                    && !filter
+                        .blocklist_types
+                        .matches(&module.name, &entry.swift_name)
+            })
+            .collect();
+    }
+
+    fn module_name(&self) -> &'a str {
+        &self.table.module.name
+    }
+
+    fn generate(mut self) -> String {
+        let mut body = Writer::new();
+
+        for index in 0..self.table.entries.len() {
+            if self.defined[index] {
+                self.nominal(&mut body, index);
+            }
+        }
+
+        let module = self.table.module;
+        let scope = Scope {
+            index: None,
+            context: mangle::Context::Module(module.name.clone()),
+            swift_name: module.name.clone(),
+            rust_name: None,
+            repr: None,
+        };
+        let mut names = NameSet::new();
+        let members: Vec<&Decl> = module.decls.iter().collect();
+        self.members(&mut body, &scope, &members, &mut names, false);
+
+        let skipped_extensions = match self.options.filter.has_allowlist() {
+            true => &[][..],
+            false => &self.table.skipped_extensions[..],
+        };
+        for (ext, reason) in skipped_extensions {
+            body.blank();
+            body.line(format!(
+                "// Skipped `extension {}`: {}",
+                ext.extended, reason
+            ));
+        }
+
+        let mut w = Writer::new();
+        w.line(format!(
+            "/// Bindings to the `{}` Swift module.",
+            module.name
+        ));
+        w.line("#[allow(dead_code, non_camel_case_types, non_snake_case, clippy::all)]");
+        w.open(format!(
+            "pub mod {}",
+            names::escape(&names::snake_case(&module.name))
+        ));
+
+        if self.uses_swift_call {
+            let arches: Vec<String> = call::ARCHES
+                .iter()
+                .map(|arch| format!("target_arch = \"{}\"", arch))
+                .collect();
+            w.line(format!("#[cfg(not(any({})))]", arches.join(", ")));
+            w.line(format!(
+                "compile_error!(\"bindings to `{}` call Swift through `swift::rt::call`, \
+                 which is only implemented for {}\");",
+                module.name,
+                call::ARCHES.join(" and ")
+            ));
+            w.blank();
+        }
+
+        w.open("mod sys");
+        w.line("#[allow(unused_imports)]");
+        w.line("use super::*;");
+        if !self.externs.is_empty() {
+            w.blank();
+            let link_name = self.options.link_name.as_ref().unwrap_or(&module.name);
+            w.line(format!(
+                "#[link(name = \"{}\", kind = \"dylib\")]",
+                link_name
+            ));
+            w.open("extern \"C\"");
+            for (i, decl) in self.externs.iter().enumerate() {
+                if i > 0 {
+                    w.blank();
+                }
+                for line in decl.lines() {
+                    w.line(line);
+                }
+            }
+            w.close();
+        }
+        w.close();
+        w.blank();
+
+        for line in body.finish().lines() {
+            if line.is_empty() {
+                w.blank();
+            } else {
+                w.line(line);
+            }
+        }
+        w.close();
+        w.finish()
+    }
+
+    /// Declares an `extern "C"` function and returns its path.
+    fn declare_extern(
+        &mut self,
+        base: &str,
+        symbol: &str,
+        params: &[String],
+        result: Option<&str>,
+    ) -> String {
+        let name = self.extern_names.unique(base, &[]);
+        let mut decl = format!(
+            "#[link_name = \"{}\"]\npub fn {}({})",
+            symbol,
+            name,
+            params.join(", ")
+        );
+        if let Some(result) = result {
+            write!(decl, " -> {}", result).unwrap();
+        }
+        decl.push(';');
+        self.externs.push(decl);
+        format!("sys::{}", name)
+    }
+
+    /// Declares an `extern` static and returns its path.
+    fn declare_extern_static(&mut self, base: &str, symbol: &str, ty: &str) -> String {
+        let name = self.extern_names.unique(base, &[]);
+        self.externs.push(format!(
+            "#[link_name = \"{}\"]\npub static {}: {};",
+            symbol, name, ty
+        ));
+        format!("sys::{}", name)
+    }
+
+    fn nominal(&mut self, w: &mut Writer, index: usize) {
+        let table = &self.table;
+        let entry = &table.entries[index];
+        let repr = table.repr(index);
+        let swift_name = format!("{}.{}", self.module_name(), entry.swift_name);
+        let rust_name = entry.rust_name.as_str();
+        let signature = table.signature(index).unwrap_or_default();
+        let generics = signature.declare();
+        let ty = format!("{}{}", rust_name, signature.apply());
+
+        w.blank();
+        let (metadata, pod) = match &*repr {
+            Repr::Unsupported(reason) => {
+                w.line(format!("// Skipped `{}`: {}", swift_name, reason));
+                return;
+            }
+            Repr::Protocol => {
+                self.protocol(w, index);
+                return;
+            }
+            Repr::Struct(fields) => {
+                let pod = fields.iter().all(|field| field.ty.layout.pod);
+                w.doc(format!("The `{}` struct.", swift_name));
+                w.line("#[repr(C)]");
+                w.line(if pod {
+                    "#[derive(Clone, Copy)]"
+                } else {
+                    "#[derive(Clone)]"
+                });
+                if fields.is_empty() {
+                    w.line(format!("pub struct {} {{}}", rust_name));
+                } else {
+                    w.open(format!("pub struct {}", rust_name));
+                    for field in fields {
+                        let vis = if field.is_public { "pub " } else { "" };
+                        w.line(format!("{}{}: {},", vis, field.name, field.ty.rust));
+                    }
+                    w.close();
+                }
+                ("StructMetadata", Some(pod))
+            }
+            Repr::Enum { tag, cases } => {
+                w.doc(format!("The `{}` enum.", swift_name));
+                if let Some(tag) = tag {
+                    w.line(format!("#[repr({})]", tag));
+                }
+                w.line("#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]");
+                if cases.is_empty() {
+                    w.line(format!("pub enum {} {{}}", rust_name));
+                } else {
+                    w.open(format!("pub enum {}", rust_name));
+                    let mut variants = NameSet::new();
+                    for case in cases {
+                        let variant = variants.unique(&names::upper_camel_case(case), &[]);
+                        w.line(format!("#[doc(alias = \"{}\")]", case));
+                        w.line(format!("{},", names::escape(&variant)));
+                    }
+                    w.close();
+                }
+                ("EnumMetadata", Some(true))
+            }
+            Repr::PayloadEnum(cases) => {
+                payload::declare(w, &swift_name, rust_name, cases);
+                ("EnumMetadata", None)
+            }
+            Repr::Resilient => {
+                let (kind, metadata) = match entry.decl.kind {
+                    ir::NominalKind::Enum => ("enum", "EnumMetadata"),
+                    _ => ("struct", "StructMetadata"),
+                };
+                w.doc(format!("The `{}` {}.", swift_name, kind));
+                w.doc("");
+                w.doc("Its layout is only known at runtime, so it's stored with the size from");
+                w.doc("its value witnesses, which also copy and destroy it.");
+                match signature.phantom() {
+                    None => {
+                        w.line("#[derive(Clone)]");
+                        w.line(format!(
+                            "pub struct {}(::swift::rt::value::ValueBox);",
+                            rust_name
+                        ));
+                    }
+                    // Deriving `Clone` would require it of the parameters.
+                    Some(phantom) => {
+                        w.line(format!(
+                            "pub struct {}{}(::swift::rt::value::ValueBox, {});",
+                            rust_name, generics, phantom
+                        ));
+                        w.blank();
+                        w.open(format!("impl{} Clone for {}", generics, ty));
+                        w.line("#[inline]");
+                        w.open("fn clone(&self) -> Self");
+                        w.line("Self(self.0.clone(), ::std::marker::PhantomData)");
+                        w.close();
+                        w.close();
+                    }
+                }
+                (metadata, None)
+            }
+            Repr::Class => {
+                w.doc(format!(
+                    "A reference to an instance of the `{}` class.",
+                    swift_name
+                ));
+                let pointer = "::std::ptr::NonNull<::std::ffi::c_void>";
+                w.line("#[repr(transparent)]");
+                match signature.phantom() {
+                    None => w.line(format!("pub struct {}({});", rust_name, pointer)),
+                    Some(phantom) => w.line(format!(
+                        "pub struct {}{}({}, {});",
+                        rust_name, generics, pointer, phantom
+                    )),
+                }
+                w.blank();
+                w.open(format!("impl{} Drop for {}", generics, ty));
+                w.line("#[inline]");
+                w.open("fn drop(&mut self)");
+                w.line("unsafe { ::swift::sys::heap::fns::swift_release(self.0.as_ptr()) };");
+                w.close();
+                w.close();
+                w.blank();
+                w.open(format!("impl{} Clone for {}", generics, ty));
+                w.line("#[inline]");
+                w.open("fn clone(&self) -> Self");
+                w.line(
+                    "let ptr = unsafe { ::swift::sys::heap::fns::swift_retain(self.0.as_ptr()) };",
+                );
+                match signature.is_empty() {
+                    true => w.line("Self(unsafe { ::std::ptr::NonNull::new_unchecked(ptr) })"),
+                    false => w.line(
+                        "Self(unsafe { ::std::ptr::NonNull::new_unchecked(ptr) }, ::std::marker::PhantomData)",
+                    ),
+                }
+                w.close();
+                w.close();
+                ("ClassMetadata", None)
+            }
+        };
+
+        let scope = Scope {
+            index: Some(index),
+            context: mangle::Context::Nominal(Box::new(entry.nominal.clone())),
+            swift_name,
+            rust_name: Some(rust_name.to_owned()),
+            repr: Some(repr.clone()),
+        };
+        self.type_impl(w, index, &scope.swift_name, &signature, metadata, pod);
+
+        let entry = &self.table.entries[index];
+        let members: Vec<(&Decl, bool)> = entry
+            .members
+            .iter()
+            .map(|member| (member.decl, member.implicitly_public))
+            .collect();
+
+        let mut impl_body = Writer::new();
+        let mut names = NameSet::new();
+        if let Repr::PayloadEnum(cases) = &*repr {
+            for name in payload::RESERVED_METHODS {
+                names.reserve(name);
+            }
+            let rust_name = &self.table.entries[index].rust_name;
+            payload::methods(
+                &mut impl_body,
+                &scope.swift_name,
+                rust_name,
+                cases,
+                &mut names,
+            );
+        }
+        for (decl, implicitly_public) in members {
+            self.members(
+                &mut impl_body,
+                &scope,
+                &[decl],
+                &mut names,
+                implicitly_public,
+            );
+        }
+        let impl_body = impl_body.finish();
+        if !impl_body.is_empty() {
+            w.blank();
+            w.open(format!("impl{} {}", generics, ty));
+            for line in impl_body.lines() {
+                if line.is_empty() {
+                    w.blank();
+                } else {
+                    w.line(line);
+                }
+            }
+            w.close();
+        }
+        self.conformances(w, index);
+    }
+
+    /// Implements `Type` using the metadata accessor.
+    fn type_impl(
+        &mut self,
+        w: &mut Writer,
+        index: usize,
+        swift_name: &str,
+        signature: &Signature,
+        metadata: &str,
+        pod: Option<bool>,
+    ) {
+        let entry = &self.table.entries[index];
+        let rust_name = entry.rust_name.clone();
+        let symbol = mangle::type_symbol(
+            &mangle::Type::Nominal(entry.nominal.clone()),
+            mangle::TypeSymbol::MetadataAccessor,
+        );
+
+        // The accessor takes the metadata of each generic parameter followed
+        // by the witness tables of their conformances, or a pointer to them
+        // if there are more than three.
+        let mut arguments = Vec::new();
+        for (param, _) in &signature.params {
+            arguments.push(format!(
+                "::std::convert::AsRef::<::swift::rt::metadata::Metadata>::as_ref(\
+                 <{} as ::swift::rt::metadata::Type>::get_metadata_blocking(blocking)?) \
+                 as *const ::swift::rt::metadata::Metadata as *const ::std::ffi::c_void,",
+                param
+            ));
+        }
+        for (param, protocol) in &signature.witness_tables {
+            arguments.push(format!(
+                "<{} as {}>::witness_table() \
+                 as *const ::swift::rt::metadata::WitnessTable as *const ::std::ffi::c_void,",
+                param, protocol
+            ));
+        }
+        let mut params = vec!["request: ::swift::sys::metadata::MetadataRequest".to_owned()];
+        let mut args = vec!["request".to_owned()];
+        match arguments.len() {
+            0 => {}
+            1..=3 => {
+                for i in 0..arguments.len() {
+                    params.push(format!("arg{}: *const ::std::ffi::c_void", i));
+                    args.push(format!("arguments[{}]", i));
+                }
+            }
+            _ => {
+                params.push("arguments: *const *const ::std::ffi::c_void".to_owned());
+                args.push("arguments.as_ptr()".to_owned());
+            }
+        }
+        let accessor = self.declare_extern(
+            &format!("{}_metadata_accessor", rust_name),
+            &symbol,
+            &params,
+            Some("::swift::rt::metadata::MetadataResponse"),
+        );
+
+        w.blank();
+        w.open(format!(
+            "impl{} ::swift::rt::metadata::Type for {}{}",
+            signature.declare(),
+            rust_name,
+            signature.apply()
+        ));
+        w.line(format!(
+            "type Metadata = ::swift::rt::metadata::{};",
+            metadata
+        ));
+        if let Some(pod) = pod {
+            w.blank();
+            w.line("#[inline]");
+            w.open("fn is_pod() -> bool");
+            w.line(pod.to_string());
+            w.close();
+        }
+        w.blank();
+        w.line("#[inline]");
+        w.open("fn is_bitwise_takable() -> bool");
+        w.line("true");
+        w.close();
+        w.blank();
+        w.line("#[inline]");
+        w.line(format!("#[doc(alias = \"{}\")]", symbol));
+        w.open("fn get_metadata() -> &'static Self::Metadata");
+        w.line("Self::get_metadata_blocking(true)");
+        w.line(format!(
+            "    .expect(\"incomplete metadata for '{}'\")",
+            swift_name
+        ));
+        w.close();
+        w.blank();
+        w.line(format!("#[doc(alias = \"{}\")]", symbol));
+        w.open("fn get_metadata_blocking(blocking: bool) -> Option<&'static Self::Metadata>");
+        if !arguments.is_empty() {
+            w.line(format!(
+                "let arguments: [*const ::std::ffi::c_void; {}] = [",
+                arguments.len()
+            ));
+            for argument in &arguments {
+                w.line(format!("    {}", argument));
+            }
+            w.line("];");
+        }
+        w.line("let request = ::swift::sys::metadata::MetadataRequest::new(");
+        w.line("    ::swift::rt::metadata::MetadataState::COMPLETE,");
+        w.line("    !blocking,");
+        w.line(");");
+        w.line(format!(
+            "let response = unsafe {{ {}({}) }};",
+            accessor,
+            args.join(", ")
+        ));
+        if metadata == "Metadata" {
+            w.line("response.completed_value()");
+        } else {
+            w.line("let metadata = response.completed_value()?;");
+            w.blank();
+            w.line(format!(
+                "// SAFETY: The accessor returns `{}` for this type.",
+                metadata
+            ));
+            w.line("let metadata = metadata as *const ::swift::rt::metadata::Metadata;");
+            w.line("Some(unsafe { &*metadata.cast() })");
+        }
+        w.close();
+        w.close();
+    }
+
+    /// Binds `decls` declared within `scope`.
+    fn members(
+        &mut self,
+        w: &mut Writer,
+        scope: &Scope,
+        decls: &[&Decl],
+        names: &mut NameSet,
+        implicitly_public: bool,
+    ) {
+        for decl in decls {
+            let (attributes, modifiers) = match decl {
+                Decl::Function(f) => (&f.attributes, &f.modifiers),
+                Decl::Initializer(init) => (&init.attributes, &init.modifiers),
+                Decl::Variable(var) => (&var.attributes, &var.modifiers),
+                Decl::Subscript(sub) => (&sub.attributes, &sub.modifiers),
+                _ => continue,
+            };
+            if !(implicitly_public || is_public(modifiers)) || is_unavailable(attributes) {
+                continue;
+            }
+            if scope.index.is_none() && !self.is_selected(decl) {
+                continue;
+            }
+
+            let result = match decl {
+                Decl::Function(f) => self.function(scope, decl, f, names),
+                Decl::Initializer(init) => self.initializer(scope, decl, init, names),
+                Decl::Variable(var) => self.variable(scope, decl, var, names),
+                Decl::Subscript(sub) => self.subscript_member(scope, decl, sub, names),
+                _ => continue,
+            };
+
+            w.blank();
+            match result {
+                Ok(wrapper) => {
+                    for line in wrapper.lines() {
+                        w.line(line);
+                    }
+                }
+                // Stored properties are bound as fields.
+                Err((_, reason)) if reason.is_empty() => {}
+                Err((name, reason)) => {
+                    w.line(format!(
+                        "// Skipped `{}.{}`: {}",
+                        scope.swift_name, name, reason
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if the filter selects the module-level `decl`.
+    fn is_selected(&self, decl: &Decl) -> bool {
+        let filter = &self.options.filter;
+        match decl {
+            Decl::Function(f) => filter.allows_function(self.module_name(), &f.name),
+            _ => !filter.has_allowlist(),
+        }
+    }
+
+    /// Resolves the parameters of a function or initializer.
+    fn params(
+        &self,
+        scope: &Scope,
+        params: &[ir::Param],
+        is_init: bool,
+    ) -> Result<(Vec<function::Param>, Vec<mangle::Param>), String> {
+        let mut names = NameSet::new();
+        names.reserve("self");
+
+        let mut lowered = Vec::new();
+        let mut mangled = Vec::new();
+        for (i, param) in params.iter().enumerate() {
+            if param.is_variadic {
+                return Err("variadic parameters are not supported".to_owned());
+            }
+            let ty = self.table.resolve(&param.ty, scope.index)?;
+            mangled.push(mangle::Param {
+                label: param.label.clone(),
+                ty: ty.mangled.clone(),
+                ownership: param.ownership.into(),
+                is_variadic: false,
+            });
+
+            let name = match param.name.as_str() {
+                "_" => format!("arg{}", i),
+                name => names::snake_case(name),
+            };
+            let owned = match param.ownership {
+                Ownership::Owned => true,
+                Ownership::Default => is_init,
+                Ownership::InOut | Ownership::Shared => false,
+            };
+            lowered.push(function::Param {
+                name: names::escape(&names.unique(&name, &[])),
+                ty,
+                ownership: param.ownership,
+                owned,
+            });
+        }
+        Ok((lowered, mangled))
+    }
+
+    /// Returns the type of `self` in `scope`, if it can be passed directly.
+    fn self_type(&self, scope: &Scope) -> Result<Resolved, String> {
+        let index = scope.index.expect("members are declared in types");
+        let path = ir::Type::named(&self.table.entries[index].swift_name);
+        self.table.resolve(&path, None)
+    }
+
+    /// Returns a reason that a member of `scope` with `modifiers` can't be
+    /// bound, if any.
+    fn unsupported_member(&self, scope: &Scope, modifiers: &[String]) -> Option<&'static str> {
+        // These take the metadata of the generic arguments too.
+        if let Some(index) = scope.index {
+            if !self.table.entries[index].decl.generics.is_empty() {
+                return Some("members of generic types are not supported");
+            }
+        }
+        if has_modifier(modifiers, "mutating") {
+            return Some("mutating members are not supported");
+        }
+        if has_modifier(modifiers, "__consuming") || has_modifier(modifiers, "consuming") {
+            return Some("consuming members are not supported");
+        }
+        None
+    }
+
+    fn function(
+        &mut self,
+        scope: &Scope,
+        decl: &Decl,
+        f: &ir::Function,
+        names: &mut NameSet,
+    ) -> Result<String, (String, String)> {
+        // Operator parameters never have argument labels.
+        let mut params = f.params.clone();
+        if f.is_operator() {
+            params.iter_mut().for_each(|param| param.label = None);
+        }
+
+        let swift_name = format!("{}({})", f.name, labels(&params));
+        let err = |reason: &str| (swift_name.clone(), reason.to_owned());
+
+        if let Some(reason) = self.unsupported_member(scope, &f.modifiers) {
+            return Err(err(reason));
+        }
+        if !f.generics.is_empty() {
+            return Err(err("generic functions are not supported"));
+        }
+        if f.is_async {
+            return Err(err("async functions are not supported"));
+        }
+        if f.throws {
+            return Err(err("throwing functions are not supported"));
+        }
+
+        let is_static = scope.index.is_some()
+            && (has_modifier(&f.modifiers, "static") || has_modifier(&f.modifiers, "class"));
+
+        let (base, name) = if f.is_operator() {
+            let fixity = if has_modifier(&f.modifiers, "prefix") {
+                mangle::Fixity::Prefix
+            } else if has_modifier(&f.modifiers, "postfix") {
+                mangle::Fixity::Postfix
+            } else {
+                mangle::Fixity::Infix
+            };
+            let base =
+                operator_name(&f.name, fixity).ok_or_else(|| err("operator has no Rust name"))?;
+            (
+                base.to_owned(),
+                mangle::DeclName::Operator(f.name.clone(), fixity),
+            )
+        } else {
+            (
+                names::snake_case(&f.name),
+                mangle::DeclName::Identifier(f.name.clone()),
+            )
+        };
+
+        let labels: Vec<Option<String>> = params.iter().map(|p| p.label.clone()).collect();
+        let (params, mangled_params) = self.params(scope, &params, false).map_err(|r| err(&r))?;
+        let result = self
+            .table
+            .resolve(&f.result, scope.index)
+            .map_err(|r| err(&r))?;
+        // Classes pass `self` in the context register instead.
+        let is_class = matches!(scope.repr.as_deref(), Some(Repr::Class));
+        let self_ty = match scope.index {
+            Some(_) if !is_static && !is_class => Some(self.self_type(scope).map_err(|r| err(&r))?),
+            _ => None,
+        };
+        // Resilient values are passed by address in the context register.
+        let indirect_self = self_ty.as_ref().is_some_and(|ty| ty.layout.indirect);
+        let self_ty = self_ty.filter(|_| !indirect_self);
+
+        let lowered = Lowered::new(&params, self_ty.as_ref(), &result).map_err(|r| err(&r))?;
+        let entity = mangle::Entity::Function {
+            context: scope.context.clone(),
+            name,
+            signature: mangle::FunctionType::new(mangled_params, result.mangled.clone()),
+            is_static,
+        };
+
+        let rust_name = names.unique(&base, &labels);
+        if is_class {
+            return self
+                .class_member(
+                    scope,
+                    decl,
+                    &rust_name,
+                    Callee::Function(&swift_name),
+                    entity,
+                    is_static,
+                    &lowered,
+                    &result,
+                )
+                .map_err(|r| err(&r));
+        }
+        let symbol = mangle::entity(&entity);
+        let call = Call {
+            symbol: &symbol,
+            lowered: &lowered,
+            result: &result,
+            indirect_self,
+            mutating: false,
+        };
+        self.wrapper(scope, &rust_name, Callee::Function(&swift_name), &call)
+            .map_err(|r| err(&r))
+    }
+
+    fn initializer(
+        &mut self,
+        scope: &Scope,
+        decl: &Decl,
+        init: &ir::Initializer,
+        names: &mut NameSet,
+    ) -> Result<String, (String, String)> {
+        let swift_name = format!("init({})", labels(&init.params));
+        let err = |reason: &str| (swift_name.clone(), reason.to_owned());
+
+        if let Some(reason) = self.unsupported_member(scope, &init.modifiers) {
+            return Err(err(reason));
+        }
+        if !init.generics.is_empty() {
+            return Err(err("generic initializers are not supported"));
+        }
+        if init.is_async {
+            return Err(err("async initializers are not supported"));
+        }
+        if init.throws {
+            return Err(err("throwing initializers are not supported"));
+        }
+
+        let (params, mangled_params) = self
+            .params(scope, &init.params, true)
+            .map_err(|r| err(&r))?;
+        let entry = &self.table.entries[scope.index.expect("initializers are declared in types")];
+        let result = match init.failability {
+            ir::Failability::None => ir::Type::named(&entry.swift_name),
+            _ => ir::Type::Optional(Box::new(ir::Type::named(&entry.swift_name))),
+        };
+        let result = self.table.resolve(&result, None).map_err(|r| err(&r))?;
+
+        let lowered = Lowered::new(&params, None, &result).map_err(|r| err(&r))?;
+        let entity = mangle::Entity::Constructor {
+            context: scope.context.clone(),
+            signature: mangle::FunctionType::new(mangled_params, result.mangled.clone()),
+            allocating: true,
+        };
+
+        let labels: Vec<Option<String>> = init.params.iter().map(|p| p.label.clone()).collect();
+        let rust_name = names.unique("new", &labels);
+        // Allocating initializers of classes take the class metadata in the
+        // context register.
+        if let Some(Repr::Class) = scope.repr.as_deref() {
+            return self
+                .class_member(
+                    scope,
+                    decl,
+                    &rust_name,
+                    Callee::Function(&swift_name),
+                    entity,
+                    true,
+                    &lowered,
+                    &result,
+                )
+                .map_err(|r| err(&r));
+        }
+        let symbol = mangle::entity(&entity);
+        let call = Call {
+            symbol: &symbol,
+            lowered: &lowered,
+            result: &result,
+            indirect_self: false,
+            mutating: false,
+        };
+        self.wrapper(scope, &rust_name, Callee::Function(&swift_name), &call)
+            .map_err(|r| err(&r))
+    }
+
+    fn variable(
+        &mut self,
+        scope: &Scope,
+        decl: &Decl,
+        var: &ir::Variable,
+        names: &mut NameSet,
+    ) -> Result<String, (String, String)> {
+        let err = |reason: &str| (var.name.clone(), reason.to_owned());
+
+        let is_static =
+            has_modifier(&var.modifiers, "static") || has_modifier(&var.modifiers, "class");
+        if let Some(reason) = self.unsupported_member(scope, &var.modifiers) {
+            return Err(err(reason));
+        }
+        if scope.index.is_none() || is_static {
+            return self
+                .global(scope, decl, var, is_static, names)
+                .map_err(|r| err(&r));
+        }
+        // Stored properties of classes and resilient types are only
+        // accessible through their accessors.
+        let is_class = matches!(scope.repr.as_deref(), Some(Repr::Class));
+        let is_resilient = matches!(scope.repr.as_deref(), Some(Repr::Resilient));
+        if var.is_stored() && !is_class && !is_resilient {
+            return Err(err(""));
+        }
+        self.property(scope, decl, var, names).map_err(|r| err(&r))
+    }
+
+    fn subscript_member(
+        &mut self,
+        scope: &Scope,
+        decl: &Decl,
+        sub: &ir::Subscript,
+        names: &mut NameSet,
+    ) -> Result<String, (String, String)> {
+        let swift_name = format!("subscript({})", labels(&sub.params));
+        let err = |reason: &str| (swift_name.clone(), reason.to_owned());
+
+        if let Some(reason) = self.unsupported_member(scope, &sub.modifiers) {
+            return Err(err(reason));
+        }
+        self.subscript(scope, decl, sub, names).map_err(|r| err(&r))
+    }
+
+    /// Declares the symbol of `call` and returns its Rust wrapper.
+    fn wrapper(
+        &mut self,
+        scope: &Scope,
+        rust_name: &str,
+        callee: Callee,
+        call: &Call,
+    ) -> Result<String, String> {
+        let Call {
+            symbol,
+            lowered,
+            result,
+            indirect_self,
+            mutating,
+        } = *call;
+        let extern_base = match &scope.rust_name {
+            Some(type_name) => format!("{}_{}", type_name, rust_name),
+            None => rust_name.to_owned(),
+        };
+
+        // C has no parameters for the registers of these.
+        if indirect_self || mutating || lowered.indirect_result || lowered.wide_result {
+            let path = self.declare_extern(&extern_base, symbol, &[], None);
+            let mut setup = vec![format!(
+                "let function = {} as *const ::std::ffi::c_void;",
+                path
+            )];
+            let receiver = match (indirect_self, mutating) {
+                (true, true) => {
+                    setup.push("let context = self.0.as_mut_ptr();".to_owned());
+                    Receiver::Mut
+                }
+                (false, true) => {
+                    setup.push("let context = (self as *mut Self).cast::<u8>();".to_owned());
+                    Receiver::Mut
+                }
+                (true, false) => {
+                    setup.push("let context = self.0.as_ptr();".to_owned());
+                    Receiver::Ref
+                }
+                (false, false) => Receiver::Static,
+            };
+            let call = ContextCall {
+                lowered,
+                setup,
+                has_context: indirect_self || mutating,
+                trailing: Vec::new(),
+            };
+            return self
+                .context_wrapper(scope, "pub fn", rust_name, callee, receiver, &call, result);
+        }
+
+        let path = self.declare_extern(
+            &extern_base,
+            symbol,
+            &lowered.extern_params,
+            lowered.extern_result.as_deref(),
+        );
+
+        let rust_name = names::escape(rust_name);
+        let mut params: Vec<&str> = lowered.receiver.into_iter().collect();
+        params.extend(lowered.params.iter().map(String::as_str));
+
+        let mut w = Writer::new();
+        wrapper_attributes(&mut w, scope, &rust_name, callee);
+        let mut header = format!("pub fn {}({})", rust_name, params.join(", "));
+        if let Some(result) = &lowered.result {
+            write!(header, " -> {}", result).unwrap();
+        }
+        w.open(header);
+        for statement in &lowered.prelude {
+            w.line(statement);
+        }
+        let call = format!("unsafe {{ {}({}) }}", path, lowered.args.join(", "));
+        if lowered.cleanup.is_empty() {
+            w.line(call);
+        } else {
+            match lowered.result {
+                Some(_) => w.line(format!("let result = {};", call)),
+                None => w.line(format!("{};", call)),
+            }
+            for statement in &lowered.cleanup {
+                w.line(statement);
+            }
+            if lowered.result.is_some() {
+                w.line("result");
+            }
+        }
+        w.close();
+        Ok(w.finish())
+    }
+
+    /// Returns a wrapper that makes `call` through `swift::rt::call`, declared
+    /// with `keyword` such as `pub fn`.
+    #[allow(clippy::too_many_arguments)]
+    fn context_wrapper(
+        &mut self,
+        scope: &Scope,
+        keyword: &str,
+        rust_name: &str,
+        callee: Callee,
+        receiver: Receiver,
+        call: &ContextCall,
+        result: &Resolved,
+    ) -> Result<String, String> {
+        let statements = call::lower(call, result)?;
+        self.uses_swift_call = true;
+
+        let rust_name = names::escape(rust_name);
+        let mut params: Vec<&str> = receiver.param().into_iter().collect();
+        params.extend(call.lowered.params.iter().map(String::as_str));
+
+        let mut w = Writer::new();
+        wrapper_attributes(&mut w, scope, &rust_name, callee);
+        let mut header = format!("{} {}({})", keyword, rust_name, params.join(", "));
+        if let Some(result) = &call.lowered.result {
+            write!(header, " -> {}", result).unwrap();
+        }
+        w.open(header);
+        for statement in statements {
+            w.line(statement);
+        }
+        w.close();
+        Ok(w.finish())
+    }
+}
+
+/// Writes the docs and attributes of a wrapper named `rust_name` that calls
+/// `callee`.
+///
+/// Wrappers are aliased to the Swift names of the functions they call, but
+/// not setters, so that searching for a property finds its getter.
+fn wrapper_attributes(w: &mut Writer, scope: &Scope, rust_name: &str, callee: Callee) {
+    match callee {
+        Callee::Function(swift_name) => {
+            w.doc(format!("Calls `{}.{}`.", scope.swift_name, swift_name));
+            w.line("#[inline]");
+            if swift_name != rust_name {
+                w.line(format!("#[doc(alias = \"{}\")]", swift_name));
+            }
+        }
+        Callee::Setter(swift_name) => {
+            w.doc(format!("Sets `{}.{}`.", scope.swift_name, swift_name));
+            w.line("#[inline]");
+        }
+    }
+}
+
+/// Returns the argument labels of a declaration name, such as `to:` or
+/// `_:_:`.
+fn labels(params: &[ir::Param]) -> String {
+    params
+        .iter()
+        .map(|p| format!("{}:", p.label.as_deref().unwrap_or("_")))
+        .collect()
+}
+
+/// Returns the Rust name for an operator function.
+fn operator_name(op: &str, fixity: mangle::Fixity) -> Option<&'static str> {
+    use mangle::Fixity::*;
+
+    Some(match (op, fixity) {
+        ("==", Infix) => "eq",
+        ("!=", Infix) => "ne",
+        ("<", Infix) => "lt",
+        ("<=", Infix) => "le",
+        (">", Infix) => "gt",
+        (">=", Infix) => "ge",
+        ("+", Infix) => "add",
+        ("-", Infix) => "sub",
+        ("*", Infix) => "mul",
+        ("/", Infix) => "div",
+        ("%", Infix) => "rem",
+        ("&", Infix) => "bitand",
+        ("|", Infix) => "bitor",
+        ("^", Infix) => "bitxor",
+        ("<<", Infix) => "shl",
+        (">>", Infix) => "shr",
+        ("-", Prefix) => "neg",
+        ("!", Prefix) | ("~", Prefix) => "not",
+        _ => return None,
+    })
+}
@@ -0,0 +1,134 @@
+//! Conversion of Swift identifiers to Rust identifiers.
+
+use std::collections::HashMap;
+
+/// Rust keywords that can be used as raw identifiers.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+    "where", "while", "yield",
+];
+
+/// Keywords that can't be raw identifiers.
+const RESERVED: &[&str] = &["crate", "self", "Self", "super", "_"];
+
+/// Returns `ident` as a valid Rust identifier.
+pub(crate) fn escape(ident: &str) -> String {
+    if RESERVED.contains(&ident) {
+        format!("{}_", ident)
+    } else if KEYWORDS.contains(&ident) {
+        format!("r#{}", ident)
+    } else {
+        ident.to_owned()
+    }
+}
+
+/// Converts a `lowerCamelCase` Swift name to `snake_case`.
+///
+/// Runs of capitals are treated as a single word, so `makeURLRequest` becomes
+/// `make_url_request`.
+pub(crate) fn snake_case(ident: &str) -> String {
+    let chars: Vec<char> = ident.chars().collect();
+    let mut result = String::with_capacity(ident.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev = i.checked_sub(1).map(|i| chars[i]);
+            let next = chars.get(i + 1).copied();
+            let starts_word = match prev {
+                None | Some('_') => false,
+                Some(prev) => {
+                    prev.is_lowercase()
+                        || prev.is_ascii_digit()
+                        || (prev.is_uppercase() && next.is_some_and(char::is_lowercase))
+                }
+            };
+            if starts_word {
+                result.push('_');
+            }
+            result.extend(c.to_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Converts a `lowerCamelCase` Swift name to `UpperCamelCase`.
+pub(crate) fn upper_camel_case(ident: &str) -> String {
+    let mut chars = ident.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Assigns unique Rust names to overloaded Swift declarations.
+///
+/// The first declaration of a name keeps it. Later ones are suffixed with
+/// their argument labels, then with a number if still ambiguous.
+#[derive(Default)]
+pub(crate) struct NameSet {
+    used: HashMap<String, usize>,
+}
+
+impl NameSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks `name` as used, such as for fields.
+    pub fn reserve(&mut self, name: &str) {
+        *self.used.entry(name.to_owned()).or_default() += 1;
+    }
+
+    /// Returns a unique name based on `base`, using `labels` for overloads.
+    pub fn unique(&mut self, base: &str, labels: &[Option<String>]) -> String {
+        let mut name = base.to_owned();
+        if self.used.contains_key(&name) {
+            for label in labels.iter().flatten() {
+                name.push('_');
+                name.push_str(&snake_case(label));
+            }
+        }
+        if self.used.contains_key(&name) {
+            let stem = name.clone();
+            let mut n = 2;
+            while self.used.contains_key(&name) {
+                name = format!("{}{}", stem, n);
+                n += 1;
+            }
+        }
+        self.reserve(&name);
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snake_case_names() {
+        assert_eq!(snake_case("distance"), "distance");
+        assert_eq!(snake_case("isEmpty"), "is_empty");
+        assert_eq!(snake_case("makeURLRequest"), "make_url_request");
+        assert_eq!(snake_case("URL"), "url");
+        assert_eq!(snake_case("utf8CString"), "utf8_c_string");
+        assert_eq!(snake_case("_combine"), "_combine");
+    }
+
+    #[test]
+    fn overloads() {
+        let mut names = NameSet::new();
+        let to = [Some("to".to_owned())];
+        assert_eq!(names.unique("distance", &to), "distance");
+        assert_eq!(
+            names.unique("distance", &[Some("from".to_owned())]),
+            "distance_from"
+        );
+        assert_eq!(names.unique("distance", &to), "distance_to");
+        assert_eq!(names.unique("distance", &to), "distance_to2");
+    }
+}
@@ -0,0 +1,213 @@
+//! Generation of enums with associated values.
+//!
+//! The layout of such enums depends on spare bits of their payloads, so they
+//! are stored in a `ValueBox` sized at runtime. Values are inspected through
+//! a Rust `enum` view made with the enum value witnesses, and built by
+//! writing a payload and injecting its tag. Tags are mapped to cases by name
+//! through the enum's field descriptor rather than by declaration order, and
+//! cases that the bindings don't know of, such as those added to resilient
+//! enums by later versions of their library, are viewed as `Unknown`.
+
+use super::{
+    names::{self, NameSet},
+    types::{Case, CaseField},
+    Writer,
+};
+
+/// Names of methods of payload enums that aren't cases.
+pub(super) const RESERVED_METHODS: &[&str] = &["case", "case_name", "case_tag"];
+
+/// The variant of views for cases that the bindings don't know of.
+const UNKNOWN_VARIANT: &str = "Unknown";
+
+/// Writes the wrapper struct and the view enum of a payload enum.
+pub(super) fn declare(w: &mut Writer, swift_name: &str, rust_name: &str, cases: &[Case]) {
+    w.doc(format!("The `{}` enum.", swift_name));
+    w.doc("");
+    w.doc(format!(
+        "Use [`case`]({}::case) to match on its value.",
+        rust_name
+    ));
+    w.line("#[derive(Clone)]");
+    w.line(format!(
+        "pub struct {}(::swift::rt::value::ValueBox);",
+        rust_name
+    ));
+
+    w.blank();
+    w.doc(format!(
+        "A case of [`{}`] with its associated values.",
+        rust_name
+    ));
+    w.line("#[derive(Clone)]");
+    w.open(format!("pub enum {}Case", rust_name));
+    let mut variants = NameSet::new();
+    variants.reserve(UNKNOWN_VARIANT);
+    for case in cases {
+        let variant = names::escape(&variants.unique(&names::upper_camel_case(&case.name), &[]));
+        w.line(format!("#[doc(alias = \"{}\")]", case.name));
+        if case.payload.is_empty() {
+            w.line(format!("{},", variant));
+        } else if case.payload.iter().all(|field| field.label.is_none()) {
+            let types: Vec<&str> = case.payload.iter().map(|f| f.ty.rust.as_str()).collect();
+            w.line(format!("{}({}),", variant, types.join(", ")));
+        } else {
+            w.open(variant);
+            for (i, field) in case.payload.iter().enumerate() {
+                w.line(format!("{}: {},", field_name(field, i), field.ty.rust));
+            }
+            w.close_with(",");
+        }
+    }
+    w.doc("A case that these bindings don't know of, with its tag.");
+    w.line(format!("{}(u32),", UNKNOWN_VARIANT));
+    w.close();
+}
+
+/// Writes the methods that view and construct cases of a payload enum.
+pub(super) fn methods(
+    w: &mut Writer,
+    swift_name: &str,
+    rust_name: &str,
+    cases: &[Case],
+    names: &mut NameSet,
+) {
+    let view = format!("{}Case", rust_name);
+    let metadata = "let metadata = <Self as ::swift::rt::metadata::Type>::get_metadata();";
+
+    w.doc("Returns a copy of the case of this value and its associated values.");
+    w.open(format!("pub fn case(&self) -> {}", view));
+    w.line(metadata);
+    w.line("let mut copy = self.0.clone();");
+    w.line("let data = copy.as_mut_ptr();");
+    w.line("let tag = unsafe { metadata.vw_get_enum_tag(data) };");
+    w.line("unsafe { metadata.vw_destructive_project_enum_data(data) };");
+    w.open("let case = match Self::case_name(tag)");
+    let mut variants = NameSet::new();
+    variants.reserve(UNKNOWN_VARIANT);
+    for case in cases {
+        let variant = names::escape(&variants.unique(&names::upper_camel_case(&case.name), &[]));
+        let read = |field: &CaseField| {
+            format!(
+                "unsafe {{ ::std::ptr::read(data.add({}).cast()) }}",
+                field.offset
+            )
+        };
+        if case.payload.is_empty() {
+            w.line(format!("Some(\"{}\") => {}::{},", case.name, view, variant));
+        } else if case.payload.iter().all(|field| field.label.is_none()) {
+            w.line(format!("Some(\"{}\") => {}::{}(", case.name, view, variant));
+            for field in &case.payload {
+                w.line(format!("    {},", read(field)));
+            }
+            w.line("),");
+        } else {
+            w.open(format!("Some(\"{}\") => {}::{}", case.name, view, variant));
+            for (i, field) in case.payload.iter().enumerate() {
+                w.line(format!("{}: {},", field_name(field, i), read(field)));
+            }
+            w.close_with(",");
+        }
+    }
+    w.open("_ =>");
+    w.line("// The payload stays in the copy, which destroys it once its tag is");
+    w.line("// injected back.");
+    w.line("unsafe { metadata.vw_destructive_inject_enum_tag(data, tag) };");
+    w.line(format!("return {}::{}(tag);", view, UNKNOWN_VARIANT));
+    w.close();
+    w.close_with(";");
+    w.blank();
+    w.line("// SAFETY: The payload was moved out of the copy.");
+    w.line("unsafe { copy.forget_value() };");
+    w.line("case");
+    w.close();
+
+    for case in cases {
+        let labels: Vec<Option<String>> = case.payload.iter().map(|f| f.label.clone()).collect();
+        let rust_name = names::escape(&names.unique(&names::snake_case(&case.name), &labels));
+        let mut params = NameSet::new();
+        let params: Vec<(String, &CaseField)> = case
+            .payload
+            .iter()
+            .enumerate()
+            .map(|(i, field)| (params.unique(&field_name(field, i), &[]), field))
+            .collect();
+        let swift_name = match case.payload.is_empty() {
+            true => case.name.clone(),
+            false => {
+                let labels: String = case
+                    .payload
+                    .iter()
+                    .map(|f| format!("{}:", f.label.as_deref().unwrap_or("_")))
+                    .collect();
+                format!("{}({})", case.name, labels)
+            }
+        };
+
+        w.blank();
+        w.doc(format!("Returns the `{}` case.", swift_name));
+        if swift_name != rust_name {
+            w.line(format!("#[doc(alias = \"{}\")]", swift_name));
+        }
+        let decls: Vec<String> = params
+            .iter()
+            .map(|(name, field)| format!("{}: {}", name, field.ty.rust))
+            .collect();
+        w.open(format!(
+            "pub fn {}({}) -> Self",
+            rust_name,
+            decls.join(", ")
+        ));
+        w.line(metadata);
+        w.line("unsafe {");
+        w.line(
+            "    let mut value = ::swift::rt::value::ValueBox::new_uninit(metadata.as_metadata());",
+        );
+        w.line("    let data = value.as_mut_ptr();");
+        for (name, field) in &params {
+            w.line(format!(
+                "    ::std::ptr::write(data.add({}).cast(), {});",
+                field.offset, name
+            ));
+        }
+        w.line(format!(
+            "    metadata.vw_destructive_inject_enum_tag(data, Self::case_tag(\"{}\"));",
+            case.name
+        ));
+        w.line("    Self(value)");
+        w.line("}");
+        w.close();
+    }
+
+    let fields = format!(
+        "let fields = metadata.type_descriptor().fields().expect(\"missing field descriptor for '{}'\");",
+        swift_name
+    );
+    w.blank();
+    w.doc("Returns the name of the case with `tag` from the field descriptor.");
+    w.open("fn case_name(tag: u32) -> Option<&'static str>");
+    w.line(metadata);
+    w.line(&fields);
+    w.line("fields.field_records().get(tag as usize)?.field_name()");
+    w.close();
+    w.blank();
+    w.doc("Returns the tag of the case named `name` from the field descriptor.");
+    w.open("fn case_tag(name: &str) -> u32");
+    w.line(metadata);
+    w.line(&fields);
+    w.line("let records = fields.field_records();");
+    w.line("let tag = records.iter().position(|record| record.field_name() == Some(name));");
+    w.line(format!(
+        "tag.expect(\"missing case of '{}'\") as u32",
+        swift_name
+    ));
+    w.close();
+}
+
+/// Returns the name of an associated value in views and constructors.
+fn field_name(field: &CaseField, index: usize) -> String {
+    match &field.label {
+        Some(label) => names::escape(&names::snake_case(label)),
+        None => format!("arg{}", index),
+    }
+}
@@ -0,0 +1,368 @@
+//! Generation of accessors of properties and subscripts.
+//!
+//! Getters are called like methods, and setters take the new value before
+//! the indices of subscripts. Setters of value types are `mutating`, so they
+//! take the address of `self` in the context register.
+//!
+//! `_modify` is a yield-once coroutine. It's called with a buffer for its
+//! frame and returns a continuation along with the address of the value. The
+//! value is then modified in place, and the continuation is called to end
+//! the access.
+
+use super::{
+    call::{self, ContextCall, Receiver},
+    function::{Lowered, Param},
+    names::{self, NameSet},
+    types::{Repr, Resolved},
+    Call, Callee, Generator, Scope, Writer,
+};
+use crate::{
+    ir::{self, has_modifier, AccessorKind, Decl, Ownership},
+    mangle,
+};
+
+/// The number of words in the buffer of a yield-once coroutine.
+const COROUTINE_BUFFER_WORDS: usize = 4;
+
+/// A property or subscript of a type.
+struct Storage<'a> {
+    decl: &'a Decl,
+
+    /// The name used in docs, such as `count` or `subscript(_:)`.
+    swift_name: String,
+
+    /// The name of the getter, which the other accessors are named after.
+    rust_name: String,
+
+    /// The labels of `indices`, used to name overloads.
+    labels: Vec<Option<String>>,
+    indices: Vec<Param>,
+    ty: Resolved,
+    accessors: &'a [ir::Accessor],
+    settable: bool,
+
+    /// Returns the entity of the accessor.
+    entity: Box<dyn Fn(mangle::Accessor) -> mangle::Entity + 'a>,
+}
+
+impl Storage<'_> {
+    fn accessor(&self, kind: AccessorKind) -> Option<&ir::Accessor> {
+        self.accessors.iter().find(|accessor| accessor.kind == kind)
+    }
+
+    /// Returns `true` if assigning through `self` mutates it, which is the
+    /// case for setters of value types unless declared `nonmutating`.
+    fn is_mutating(&self, scope: &Scope) -> bool {
+        if let Some(Repr::Class) = scope.repr.as_deref() {
+            return false;
+        }
+        let nonmutating = [AccessorKind::Set, AccessorKind::Modify]
+            .iter()
+            .filter_map(|&kind| self.accessor(kind))
+            .any(|accessor| has_modifier(&accessor.modifiers, "nonmutating"));
+        !nonmutating
+    }
+}
+
+impl Generator<'_> {
+    /// Returns the accessors of the instance property `var`.
+    pub(super) fn property(
+        &mut self,
+        scope: &Scope,
+        decl: &Decl,
+        var: &ir::Variable,
+        names: &mut NameSet,
+    ) -> Result<String, String> {
+        let ty = self.table.resolve(&var.ty, scope.index)?;
+        let mangled = ty.mangled.clone();
+        let storage = Storage {
+            decl,
+            swift_name: var.name.clone(),
+            rust_name: names.unique(&names::snake_case(&var.name), &[]),
+            labels: Vec::new(),
+            indices: Vec::new(),
+            ty,
+            accessors: &var.accessors,
+            settable: var.is_settable(),
+            entity: Box::new(move |accessor| mangle::Entity::Variable {
+                context: scope.context.clone(),
+                name: var.name.clone(),
+                ty: mangled.clone(),
+                accessor,
+                is_static: false,
+            }),
+        };
+        self.storage(scope, &storage, names)
+    }
+
+    /// Returns the accessors of the subscript `sub`.
+    pub(super) fn subscript(
+        &mut self,
+        scope: &Scope,
+        decl: &Decl,
+        sub: &ir::Subscript,
+        names: &mut NameSet,
+    ) -> Result<String, String> {
+        if has_modifier(&sub.modifiers, "static") || has_modifier(&sub.modifiers, "class") {
+            return Err("static subscripts are not supported".to_owned());
+        }
+        if !sub.generics.is_empty() {
+            return Err("generic subscripts are not supported".to_owned());
+        }
+
+        let (indices, mangled_params) = self.params(scope, &sub.params, false)?;
+        let ty = self.table.resolve(&sub.result, scope.index)?;
+        let labels: Vec<Option<String>> = sub.params.iter().map(|p| p.label.clone()).collect();
+        let sig = mangle::FunctionType::new(mangled_params, ty.mangled.clone());
+        let storage = Storage {
+            decl,
+            swift_name: format!("subscript({})", super::labels(&sub.params)),
+            rust_name: names.unique("index", &labels),
+            labels,
+            indices,
+            ty,
+            accessors: &sub.accessors,
+            settable: sub.accessors.iter().any(|accessor| {
+                matches!(
+                    accessor.kind,
+                    AccessorKind::Set | AccessorKind::Modify | AccessorKind::MutableAddress
+                )
+            }),
+            entity: Box::new(move |accessor| mangle::Entity::Subscript {
+                context: scope.context.clone(),
+                signature: sig.clone(),
+                accessor,
+                is_static: false,
+            }),
+        };
+        self.storage(scope, &storage, names)
+    }
+
+    /// Returns the getter, setter and `_modify` wrappers of `storage`.
+    fn storage(
+        &mut self,
+        scope: &Scope,
+        storage: &Storage,
+        names: &mut NameSet,
+    ) -> Result<String, String> {
+        if let Some(getter) = storage.accessor(AccessorKind::Get) {
+            if let Some(reason) = self.unsupported_member(scope, &getter.modifiers) {
+                return Err(reason.to_owned());
+            }
+        }
+        let is_class = matches!(scope.repr.as_deref(), Some(Repr::Class));
+        let self_ty = match is_class {
+            true => None,
+            false => Some(self.self_type(scope)?),
+        };
+        let indirect_self = self_ty.as_ref().is_some_and(|ty| ty.layout.indirect);
+        let self_ty = self_ty.filter(|_| !indirect_self);
+
+        let lowered = Lowered::new(&storage.indices, self_ty.as_ref(), &storage.ty)?;
+        let getter = (storage.entity)(mangle::Accessor::Getter);
+        let mut wrappers = vec![self.accessor_call(
+            scope,
+            storage,
+            &storage.rust_name,
+            Callee::Function(&storage.swift_name),
+            getter,
+            &lowered,
+            &storage.ty,
+            indirect_self,
+            false,
+        )?];
+        if !storage.settable {
+            return Ok(wrappers.join("\n"));
+        }
+
+        let mutating = storage.is_mutating(scope);
+        let value = match storage.indices.iter().any(|index| index.name == "value") {
+            true => "new_value",
+            false => "value",
+        };
+        let mut params = vec![Param {
+            name: value.to_owned(),
+            ty: storage.ty.clone(),
+            ownership: Ownership::Default,
+            owned: true,
+        }];
+        params.extend(storage.indices.iter().map(|index| Param {
+            name: index.name.clone(),
+            ty: index.ty.clone(),
+            ownership: index.ownership,
+            owned: index.owned,
+        }));
+        let void = Resolved::void();
+        let self_ty = self_ty.filter(|_| !mutating);
+        let lowered = Lowered::new(&params, self_ty.as_ref(), &void)?;
+        let setter_name = names.unique(&format!("set_{}", storage.rust_name), &storage.labels);
+        let setter = (storage.entity)(mangle::Accessor::Setter);
+        wrappers.push(self.accessor_call(
+            scope,
+            storage,
+            &setter_name,
+            Callee::Setter(&storage.swift_name),
+            setter,
+            &lowered,
+            &void,
+            indirect_self,
+            mutating,
+        )?);
+
+        if storage.accessor(AccessorKind::Modify).is_some() {
+            let modify_name =
+                names.unique(&format!("modify_{}", storage.rust_name), &storage.labels);
+            match self.modify(scope, storage, &modify_name, indirect_self, mutating) {
+                Ok(wrapper) => wrappers.push(wrapper),
+                Err(reason) => wrappers.push(format!(
+                    "// Skipped `{}.{}` `_modify`: {}",
+                    scope.swift_name, storage.swift_name, reason
+                )),
+            }
+        }
+        Ok(wrappers.join("\n"))
+    }
+
+    /// Returns a wrapper that calls `callee`, the accessor `entity` of
+    /// `storage`.
+    #[allow(clippy::too_many_arguments)]
+    fn accessor_call(
+        &mut self,
+        scope: &Scope,
+        storage: &Storage,
+        rust_name: &str,
+        callee: Callee,
+        entity: mangle::Entity,
+        lowered: &Lowered,
+        result: &Resolved,
+        indirect_self: bool,
+        mutating: bool,
+    ) -> Result<String, String> {
+        if let Some(Repr::Class) = scope.repr.as_deref() {
+            return self.class_member(
+                scope,
+                storage.decl,
+                rust_name,
+                callee,
+                entity,
+                false,
+                lowered,
+                result,
+            );
+        }
+        let symbol = mangle::entity(&entity);
+        let call = Call {
+            symbol: &symbol,
+            lowered,
+            result,
+            indirect_self,
+            mutating,
+        };
+        self.wrapper(scope, rust_name, callee, &call)
+    }
+
+    /// Returns a wrapper that modifies `storage` in place through its
+    /// `_modify` coroutine.
+    fn modify(
+        &mut self,
+        scope: &Scope,
+        storage: &Storage,
+        rust_name: &str,
+        indirect_self: bool,
+        mutating: bool,
+    ) -> Result<String, String> {
+        let ty = &storage.ty;
+        if ty.layout.indirect {
+            return Err(format!("`{}` can't be borrowed in place", ty.rust));
+        }
+
+        // The coroutine returns its continuation and the address of the value.
+        let words = Resolved::words(2);
+        let mut lowered = Lowered::new(&storage.indices, None, &words)?;
+        lowered.args.insert(0, "buffer.as_mut_ptr()".to_owned());
+        lowered.arg_types.insert(0, "*mut usize".to_owned());
+        lowered
+            .arg_scalars
+            .insert(0, words.layout.scalars[..1].to_vec());
+
+        let entity = (storage.entity)(mangle::Accessor::Modify);
+        let (setup, receiver) = match scope.repr.as_deref() {
+            Some(Repr::Class) => {
+                let setup = self.class_setup(scope, storage.decl, rust_name, entity, false)?;
+                (setup, Receiver::Ref)
+            }
+            _ => {
+                let type_name = scope.rust_name.as_deref().expect("types are named");
+                let path = self.declare_extern(
+                    &format!("{}_{}", type_name, rust_name),
+                    &mangle::entity(&entity),
+                    &[],
+                    None,
+                );
+                let (context, receiver) = match (indirect_self, mutating) {
+                    (true, true) => ("self.0.as_mut_ptr()", Receiver::Mut),
+                    (false, true) => ("(self as *mut Self).cast::<u8>()", Receiver::Mut),
+                    (true, false) => ("self.0.as_ptr()", Receiver::Ref),
+                    (false, false) => return Err("`self` is passed directly".to_owned()),
+                };
+                let setup = vec![
+                    format!("let function = {} as *const ::std::ffi::c_void;", path),
+                    format!("let context = {};", context),
+                ];
+                (setup, receiver)
+            }
+        };
+        let call = ContextCall {
+            lowered: &lowered,
+            setup,
+            has_context: true,
+            trailing: Vec::new(),
+        };
+        let statements = call::lower(&call, &words)?;
+        self.uses_swift_call = true;
+
+        let mut params: Vec<String> = receiver.param().into_iter().map(str::to_owned).collect();
+        params.extend(lowered.params.iter().cloned());
+        params.push(format!("f: impl FnOnce(&mut {}) -> R", ty.rust));
+
+        let mut w = Writer::new();
+        w.doc(format!(
+            "Calls `f` with `{}.{}` to modify it in place.",
+            scope.swift_name, storage.swift_name
+        ));
+        w.line("#[inline]");
+        w.open(format!(
+            "pub fn {}<R>({}) -> R",
+            names::escape(rust_name),
+            params.join(", ")
+        ));
+        w.line(format!(
+            "let mut buffer = [0usize; {}];",
+            COROUTINE_BUFFER_WORDS
+        ));
+        w.open("let [continuation, address]: [usize; 2] =");
+        for statement in statements {
+            w.line(statement);
+        }
+        w.close_with(";");
+        w.blank();
+        w.line("// Ends the access, and unwinds it if `f` panics.");
+        w.line("struct Resume(usize, *mut usize);");
+        w.open("impl Drop for Resume");
+        w.open("fn drop(&mut self)");
+        w.line(
+            "let resume: extern \"C\" fn(*mut usize, bool) = \
+             unsafe { ::std::mem::transmute(self.0) };",
+        );
+        w.line("resume(self.1, ::std::thread::panicking());");
+        w.close();
+        w.close();
+        w.line("let _resume = Resume(continuation, buffer.as_mut_ptr());");
+        w.line(format!(
+            "f(unsafe {{ &mut *(address as *mut {}) }})",
+            ty.rust
+        ));
+        w.close();
+        Ok(w.finish())
+    }
+}
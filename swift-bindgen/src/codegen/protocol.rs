@@ -0,0 +1,492 @@
+//! Generation of traits for protocols.
+//!
+//! A protocol becomes an `unsafe` trait that conforming types implement, and
+//! its requirements become provided methods. These call the witness of the
+//! conformance, which is found at runtime with `swift_conformsToProtocol`.
+//! Witnesses take `self` in the context register, so they're called through
+//! `swift::rt::call`.
+//!
+//! The layout of witness tables of resilient protocols is private to their
+//! module. Requirements of those are called through the dispatch thunks that
+//! the module exports instead, which take the same arguments.
+
+use super::{
+    call::{ContextCall, Receiver},
+    function::Lowered,
+    names::{self, NameSet},
+    operator_name,
+    types::{constraint_paths, Repr, MARKER_PROTOCOLS},
+    Callee, Generator, Scope, Writer,
+};
+use crate::{
+    ir::{self, has_modifier, Decl},
+    mangle,
+};
+
+/// Names of methods that conforming types already have.
+const RESERVED_METHODS: &[&str] = &[
+    "witness_table",
+    "is_pod",
+    "is_bitwise_takable",
+    "get_metadata",
+    "get_metadata_blocking",
+];
+
+impl Generator<'_> {
+    /// Binds the protocol at `index` as a trait.
+    pub(super) fn protocol(&mut self, w: &mut Writer, index: usize) {
+        let entry = &self.table.entries[index];
+        let swift_name = format!("{}.{}", self.module_name(), entry.swift_name);
+        let rust_name = entry.rust_name.clone();
+        let nominal = entry.nominal.clone();
+        let decls = &entry.decl.decls;
+
+        let symbol = mangle::protocol_descriptor(&nominal);
+        let descriptor = self.declare_extern_static(
+            &format!("{}_descriptor", rust_name),
+            &symbol,
+            "::swift::rt::ctx_desc::ProtocolContextDescriptor",
+        );
+        w.doc(format!(
+            "Returns the descriptor of the `{}` protocol.",
+            swift_name
+        ));
+        w.line("#[inline]");
+        w.line(format!("#[doc(alias = \"{}\")]", symbol));
+        w.open(format!(
+            "pub fn {}_descriptor() -> &'static ::swift::rt::ctx_desc::ProtocolContextDescriptor",
+            rust_name
+        ));
+        w.line(format!("unsafe {{ &{} }}", descriptor));
+        w.close();
+
+        let mut bounds = vec!["::swift::rt::metadata::Type".to_owned(), "Sized".to_owned()];
+        for base in self.base_protocols(index) {
+            bounds.push(self.table.entries[base].rust_name.clone());
+        }
+
+        w.blank();
+        w.doc(format!("The `{}` protocol.", swift_name));
+        w.doc("");
+        w.doc("# Safety");
+        w.doc("");
+        w.doc(format!(
+            "Implementing this trait asserts that the type conforms to `{}`.",
+            swift_name
+        ));
+        w.open(format!(
+            "pub unsafe trait {}: {}",
+            rust_name,
+            bounds.join(" + ")
+        ));
+        w.doc(format!(
+            "Returns the witness table of the conformance to `{}`.",
+            swift_name
+        ));
+        w.line("#[inline]");
+        w.open("fn witness_table() -> &'static ::swift::rt::metadata::WitnessTable");
+        w.line("let metadata: &::swift::rt::metadata::Metadata =");
+        w.line("    ::std::convert::AsRef::as_ref(Self::get_metadata());");
+        w.line("metadata");
+        w.line(format!("    .conformance({}_descriptor())", rust_name));
+        w.line(format!(
+            "    .expect(\"missing conformance to '{}'\")",
+            swift_name
+        ));
+        w.close();
+
+        let scope = Scope {
+            index: Some(index),
+            context: mangle::Context::Nominal(Box::new(nominal)),
+            swift_name,
+            rust_name: Some(rust_name),
+            repr: Some(self.table.repr(index)),
+        };
+        let mut names = NameSet::new();
+        for name in RESERVED_METHODS {
+            names.reserve(name);
+        }
+
+        // Witnesses of members follow those of the requirement signature and
+        // associated types, in declaration order.
+        let mut slot = self.base_witnesses(index);
+        for decl in decls {
+            let (result, witnesses) = match decl {
+                Decl::Function(f) => (self.requirement(&scope, f, slot, &mut names), 1),
+                Decl::Variable(var) => (
+                    self.property_requirement(&scope, var, slot, &mut names),
+                    if var.is_settable() { 3 } else { 1 },
+                ),
+                Decl::Initializer(init) => {
+                    let name = format!("init({})", super::labels(&init.params));
+                    let reason = "initializer requirements are not supported";
+                    (Err((name, reason.to_owned())), 1)
+                }
+                Decl::Subscript(sub) => {
+                    let name = format!("subscript({})", super::labels(&sub.params));
+                    let reason = "subscript requirements are not supported";
+                    let settable = sub
+                        .accessors
+                        .iter()
+                        .any(|a| a.kind == ir::AccessorKind::Set);
+                    (Err((name, reason.to_owned())), if settable { 3 } else { 1 })
+                }
+                _ => continue,
+            };
+            slot += witnesses;
+
+            w.blank();
+            match result {
+                Ok(method) => {
+                    for line in method.lines() {
+                        w.line(line);
+                    }
+                }
+                Err((name, reason)) => {
+                    w.line(format!(
+                        "// Skipped `{}.{}`: {}",
+                        scope.swift_name, name, reason
+                    ));
+                }
+            }
+        }
+        w.close();
+    }
+
+    /// Implements the traits of the protocols that the type at `index`
+    /// conforms to.
+    pub(super) fn conformances(&self, w: &mut Writer, index: usize) {
+        let entry = &self.table.entries[index];
+        let mut protocols = Vec::new();
+        for ty in &entry.inherited {
+            for path in constraint_paths(ty) {
+                if let Some(protocol) = self.table.find(path, entry.parent) {
+                    self.add_protocol(protocol, &mut protocols);
+                }
+            }
+        }
+
+        let swift_name = format!("{}.{}", self.module_name(), entry.swift_name);
+        let signature = self.table.signature(index).unwrap_or_default();
+        // Witnesses take the address of the value, which a `ValueBox` holds
+        // rather than `self`.
+        let boxed = matches!(
+            *self.table.repr(index),
+            Repr::PayloadEnum(_) | Repr::Resilient
+        );
+        for protocol in protocols {
+            let protocol = &self.table.entries[protocol];
+            w.blank();
+            if boxed {
+                w.line(format!(
+                    "// Skipped conformance of `{}` to `{}.{}`: values in a `ValueBox` are not supported",
+                    swift_name,
+                    self.module_name(),
+                    protocol.swift_name
+                ));
+                continue;
+            }
+            w.line(format!(
+                "// SAFETY: `{}` conforms to `{}.{}`.",
+                swift_name,
+                self.module_name(),
+                protocol.swift_name
+            ));
+            w.line(format!(
+                "unsafe impl{} {} for {}{} {{}}",
+                signature.declare(),
+                protocol.rust_name,
+                entry.rust_name,
+                signature.apply()
+            ));
+        }
+    }
+
+    /// Adds `index` and its base protocols to `protocols` if it's a bound
+    /// protocol.
+    fn add_protocol(&self, index: usize, protocols: &mut Vec<usize>) {
+        let is_protocol = matches!(*self.table.repr(index), Repr::Protocol);
+        if !is_protocol || !self.defined[index] || protocols.contains(&index) {
+            return;
+        }
+        protocols.push(index);
+        for base in self.base_protocols(index) {
+            self.add_protocol(base, protocols);
+        }
+    }
+
+    /// Returns the bound protocols that the protocol at `index` inherits.
+    fn base_protocols(&self, index: usize) -> Vec<usize> {
+        let entry = &self.table.entries[index];
+        entry
+            .inherited
+            .iter()
+            .flat_map(|ty| constraint_paths(ty))
+            .filter_map(|path| self.table.find(path, None))
+            .filter(|&base| self.defined[base] && matches!(*self.table.repr(base), Repr::Protocol))
+            .collect()
+    }
+
+    /// Returns `true` if conforming types of the protocol at `index` must be
+    /// classes.
+    fn is_class_bound(&self, index: usize) -> bool {
+        let decl = self.table.entries[index].decl;
+        let self_requirements = decl
+            .generics
+            .requirements
+            .iter()
+            .filter_map(|req| match req {
+                ir::Requirement::Conformance {
+                    subject,
+                    constraint,
+                } if subject
+                    .as_path()
+                    .is_some_and(|path| path.is_plain(&["Self"])) =>
+                {
+                    Some(constraint)
+                }
+                _ => None,
+            });
+        decl.inherited
+            .iter()
+            .chain(self_requirements)
+            .flat_map(|ty| constraint_paths(ty))
+            .any(|path| {
+                if path.is_plain(&["AnyObject"]) || path.is_plain(&["Swift", "AnyObject"]) {
+                    return true;
+                }
+                match self.table.find(path, None) {
+                    Some(found) => match *self.table.repr(found) {
+                        Repr::Class => true,
+                        Repr::Protocol => found != index && self.is_class_bound(found),
+                        _ => false,
+                    },
+                    None => false,
+                }
+            })
+    }
+
+    /// Returns the number of witnesses before those of the members of the
+    /// protocol at `index`, which are for base protocols, associated types
+    /// and their conformances.
+    fn base_witnesses(&self, index: usize) -> usize {
+        let decl = self.table.entries[index].decl;
+        let has_witness_table = |ty: &&ir::Type| {
+            constraint_paths(ty).into_iter().any(|path| {
+                let name = path.last().name.as_str();
+                let is_class = self
+                    .table
+                    .find(path, None)
+                    .is_some_and(|found| matches!(*self.table.repr(found), Repr::Class));
+                !MARKER_PROTOCOLS.contains(&name) && !is_class
+            })
+        };
+        let conformances = |requirements: &[ir::Requirement]| {
+            requirements
+                .iter()
+                .filter(|req| match req {
+                    ir::Requirement::Conformance { constraint, .. } => {
+                        has_witness_table(&constraint)
+                    }
+                    ir::Requirement::SameType { .. } => false,
+                })
+                .count()
+        };
+
+        let mut count = decl.inherited.iter().filter(has_witness_table).count();
+        count += conformances(&decl.generics.requirements);
+        for member in &decl.decls {
+            if let Decl::AssociatedType(assoc) = member {
+                count += 1 + assoc.inherited.iter().filter(has_witness_table).count();
+                count += conformances(&assoc.requirements);
+            }
+        }
+        count
+    }
+
+    fn requirement(
+        &mut self,
+        scope: &Scope,
+        f: &ir::Function,
+        slot: usize,
+        names: &mut NameSet,
+    ) -> Result<String, (String, String)> {
+        // Operator parameters never have argument labels.
+        let mut params = f.params.clone();
+        if f.is_operator() {
+            params.iter_mut().for_each(|param| param.label = None);
+        }
+
+        let swift_name = format!("{}({})", f.name, super::labels(&params));
+        let err = |reason: &str| (swift_name.clone(), reason.to_owned());
+
+        if !f.generics.is_empty() {
+            return Err(err("generic functions are not supported"));
+        }
+        if f.is_async {
+            return Err(err("async functions are not supported"));
+        }
+        if f.throws {
+            return Err(err("throwing functions are not supported"));
+        }
+
+        let receiver = if has_modifier(&f.modifiers, "static") {
+            Receiver::Static
+        } else if has_modifier(&f.modifiers, "mutating") {
+            Receiver::Mut
+        } else {
+            Receiver::Ref
+        };
+
+        let (base, name) = if f.is_operator() {
+            let fixity = if has_modifier(&f.modifiers, "prefix") {
+                mangle::Fixity::Prefix
+            } else if has_modifier(&f.modifiers, "postfix") {
+                mangle::Fixity::Postfix
+            } else {
+                mangle::Fixity::Infix
+            };
+            let base =
+                operator_name(&f.name, fixity).ok_or_else(|| err("operator has no Rust name"))?;
+            (
+                base.to_owned(),
+                mangle::DeclName::Operator(f.name.clone(), fixity),
+            )
+        } else {
+            (
+                names::snake_case(&f.name),
+                mangle::DeclName::Identifier(f.name.clone()),
+            )
+        };
+
+        let labels: Vec<Option<String>> = params.iter().map(|p| p.label.clone()).collect();
+        let (params, mangled_params) = self.params(scope, &params, false).map_err(|r| err(&r))?;
+        let result = self
+            .table
+            .resolve(&f.result, scope.index)
+            .map_err(|r| err(&r))?;
+        let lowered = Lowered::new(&params, None, &result).map_err(|r| err(&r))?;
+
+        let entity = mangle::Entity::Function {
+            context: scope.context.clone(),
+            name,
+            signature: mangle::FunctionType::new(mangled_params, result.mangled.clone()),
+            is_static: matches!(receiver, Receiver::Static),
+        };
+        let rust_name = names.unique(&base, &labels);
+        self.witness_method(
+            scope,
+            &rust_name,
+            &swift_name,
+            &entity,
+            slot,
+            receiver,
+            &lowered,
+            &result,
+        )
+        .map_err(|r| err(&r))
+    }
+
+    fn property_requirement(
+        &mut self,
+        scope: &Scope,
+        var: &ir::Variable,
+        slot: usize,
+        names: &mut NameSet,
+    ) -> Result<String, (String, String)> {
+        let err = |reason: &str| (var.name.clone(), reason.to_owned());
+
+        let is_static = has_modifier(&var.modifiers, "static");
+        let getter = var
+            .accessors
+            .iter()
+            .find(|a| a.kind == ir::AccessorKind::Get);
+        let receiver = if is_static {
+            Receiver::Static
+        } else if getter.is_some_and(|g| has_modifier(&g.modifiers, "mutating")) {
+            Receiver::Mut
+        } else {
+            Receiver::Ref
+        };
+
+        let ty = self
+            .table
+            .resolve(&var.ty, scope.index)
+            .map_err(|r| err(&r))?;
+        let lowered = Lowered::new(&[], None, &ty).map_err(|r| err(&r))?;
+        let entity = mangle::Entity::Variable {
+            context: scope.context.clone(),
+            name: var.name.clone(),
+            ty: ty.mangled.clone(),
+            accessor: mangle::Accessor::Getter,
+            is_static,
+        };
+
+        let rust_name = names.unique(&names::snake_case(&var.name), &[]);
+        self.witness_method(
+            scope, &rust_name, &var.name, &entity, slot, receiver, &lowered, &ty,
+        )
+        .map_err(|r| err(&r))
+    }
+
+    /// Returns a provided method that calls the witness for `entity`, which
+    /// is at `slot` in the witness table.
+    #[allow(clippy::too_many_arguments)]
+    fn witness_method(
+        &mut self,
+        scope: &Scope,
+        rust_name: &str,
+        swift_name: &str,
+        entity: &mangle::Entity,
+        slot: usize,
+        receiver: Receiver,
+        lowered: &Lowered,
+        result: &crate::codegen::types::Resolved,
+    ) -> Result<String, String> {
+        let index = scope.index.expect("requirements are declared in protocols");
+        let trait_name = scope.rust_name.as_deref().expect("protocols are named");
+        let class_bound = self.is_class_bound(index);
+
+        let function = if self.table.module.is_resilient() {
+            let symbol = mangle::dispatch_thunk(entity);
+            let path =
+                self.declare_extern(&format!("{}_{}", trait_name, rust_name), &symbol, &[], None);
+            format!("let function = {} as *const ::std::ffi::c_void;", path)
+        } else {
+            format!(
+                "let function = unsafe {{ \
+                 *::swift::rt::metadata::WitnessTable::witnesses_ptr(witness_table).add({}) \
+                 }};",
+                slot
+            )
+        };
+        let context = match (&receiver, class_bound) {
+            (Receiver::Static, _) => "let context = metadata;",
+            (_, true) => {
+                "let context = unsafe { *(self as *const Self).cast::<*const ::std::ffi::c_void>() };"
+            }
+            (Receiver::Ref, false) => "let context = self as *const Self;",
+            (Receiver::Mut, false) => "let context = self as *mut Self;",
+        };
+        let setup = vec![
+            "let metadata = ::std::convert::AsRef::<::swift::rt::metadata::Metadata>::as_ref(\
+             Self::get_metadata()) as *const ::swift::rt::metadata::Metadata;"
+                .to_owned(),
+            format!(
+                "let witness_table = <Self as {}>::witness_table() \
+                 as *const ::swift::rt::metadata::WitnessTable;",
+                trait_name
+            ),
+            function,
+            context.to_owned(),
+        ];
+        let call = ContextCall {
+            lowered,
+            setup,
+            has_context: true,
+            trailing: vec!["metadata", "witness_table"],
+        };
+        let callee = Callee::Function(swift_name);
+        self.context_wrapper(scope, "fn", rust_name, callee, receiver, &call, result)
+    }
+}
@@ -0,0 +1,903 @@
+#![cfg(test)]
+
+use super::*;
+use crate::interface;
+use std::{fs, path::Path, process::Command};
+
+const SHAPES: &str = include_str!("../../fixtures/Shapes.swiftinterface");
+
+fn generate_named(source: &str) -> String {
+    let module = interface::parse_named(source, Some("Test")).unwrap();
+    generate(&module, &Options::default())
+}
+
+fn shapes() -> String {
+    shapes_with(&Options::default())
+}
+
+fn shapes_with(options: &Options) -> String {
+    generate(&interface::parse(SHAPES).unwrap(), options)
+}
+
+fn patterns(patterns: &[&str]) -> Patterns {
+    let patterns: Vec<String> = patterns.iter().map(|&p| p.to_owned()).collect();
+    Patterns::new(&patterns).unwrap()
+}
+
+/// Runs `cargo check` on `code` within a crate named `name` that depends on
+/// `swift`, denying warnings.
+///
+/// Crates are kept in the workspace's target directory and share their own
+/// target directory, so that dependencies are only built once. They use the
+/// workspace's lock file so that they can be checked offline.
+#[track_caller]
+fn check(name: &str, code: &str) {
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let root = workspace.join("target").join("check-bindings");
+    let dir = root.join(name);
+    fs::create_dir_all(dir.join("src")).unwrap();
+
+    let manifest = format!(
+        "[package]\n\
+         name = {:?}\n\
+         version = \"0.0.0\"\n\
+         edition = \"2018\"\n\
+         \n\
+         [dependencies]\n\
+         swift = {{ path = {:?} }}\n\
+         \n\
+         [workspace]\n",
+        name,
+        workspace.join("swift"),
+    );
+    fs::write(dir.join("Cargo.toml"), manifest).unwrap();
+    fs::copy(workspace.join("Cargo.lock"), dir.join("Cargo.lock")).unwrap();
+    let lib = format!("#![deny(warnings)]\n\n{}", code);
+    fs::write(dir.join("src").join("lib.rs"), lib).unwrap();
+
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned());
+    let output = Command::new(cargo)
+        .args(["check", "--offline", "--quiet", "--message-format=short"])
+        .arg("--target-dir")
+        .arg(root.join("target"))
+        .current_dir(&dir)
+        .env_remove("RUSTFLAGS")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "generated code doesn't compile:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[track_caller]
+fn assert_contains(code: &str, expected: &str) {
+    assert!(
+        code.contains(expected),
+        "missing `{}` in:\n{}",
+        expected,
+        code
+    );
+}
+
+#[test]
+fn fixture_compiles() {
+    check("shapes", &shapes());
+}
+
+#[test]
+fn frozen_struct() {
+    let code = shapes();
+
+    assert_contains(&code, "pub mod shapes {");
+    assert_contains(&code, "#[link(name = \"Shapes\", kind = \"dylib\")]");
+    assert_contains(
+        &code,
+        "#[repr(C)]\n    #[derive(Clone, Copy)]\n    pub struct Point {\n        pub x: ::swift::Double,\n        pub y: ::swift::Double,\n    }",
+    );
+    assert_contains(&code, "impl ::swift::rt::metadata::Type for Point {");
+    assert_contains(&code, "#[link_name = \"$s6Shapes5PointVMa\"]");
+    assert_contains(
+        &code,
+        "type Metadata = ::swift::rt::metadata::StructMetadata;",
+    );
+}
+
+#[test]
+fn methods() {
+    let code = shapes();
+
+    assert_contains(&code, "#[link_name = \"$s6Shapes5PointV1x1yACSd_SdtcfC\"]");
+    assert_contains(
+        &code,
+        "pub fn new(x: ::swift::Double, y: ::swift::Double) -> Point {",
+    );
+
+    // `self` is passed after the arguments, expanded into its fields.
+    assert_contains(
+        &code,
+        "#[link_name = \"$s6Shapes5PointV8distance2toSdAC_tF\"]",
+    );
+    assert_contains(
+        &code,
+        "pub fn distance(self, other: Point) -> ::swift::Double {",
+    );
+    assert_contains(
+        &code,
+        "sys::Point_distance(other.x, other.y, self.x, self.y)",
+    );
+    assert_contains(
+        &code,
+        "sys::Rect_area(self.origin.x, self.origin.y, self.size.width, self.size.height)",
+    );
+
+    // Operators have no argument labels.
+    assert_contains(&code, "#[link_name = \"$s6Shapes5PointV2eeoiySbAC_ACtFZ\"]");
+    assert_contains(
+        &code,
+        "pub fn eq(lhs: Point, rhs: Point) -> ::swift::Bool {",
+    );
+
+    assert_contains(
+        &code,
+        "#[link_name = \"$s6Shapes9ShapeKindO9isPolygonSbvg\"]",
+    );
+    assert_contains(&code, "pub fn is_polygon(self) -> ::swift::Bool {");
+}
+
+#[test]
+fn functions() {
+    let code = shapes();
+
+    assert_contains(
+        &code,
+        "#[link_name = \"$s6Shapes10makeCircle6radiusAA0C0CSd_tF\"]",
+    );
+    assert_contains(
+        &code,
+        "pub fn make_circle(radius: ::swift::Double) -> Circle {",
+    );
+    assert_contains(
+        &code,
+        "#[link_name = \"$s6Shapes8distanceySdAA5PointV_ADtF\"]",
+    );
+    assert_contains(
+        &code,
+        "pub fn total_area(shapes: &::swift::Array<Shape>) -> ::swift::Double {",
+    );
+    assert_contains(
+        &code,
+        "sys::total_area(::std::mem::ManuallyDrop::new(::std::ptr::read(shapes)))",
+    );
+}
+
+#[test]
+fn enums_and_classes() {
+    let code = shapes();
+
+    assert_contains(&code, "#[repr(u8)]\n    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]\n    pub enum ShapeKind {");
+    assert_contains(
+        &code,
+        "type Metadata = ::swift::rt::metadata::EnumMetadata;",
+    );
+
+    assert_contains(
+        &code,
+        "pub struct Shape(::std::ptr::NonNull<::std::ffi::c_void>);",
+    );
+    assert_contains(
+        &code,
+        "::swift::sys::heap::fns::swift_release(self.0.as_ptr())",
+    );
+    assert_contains(&code, "#[link_name = \"$s6Shapes6CircleCMa\"]");
+}
+
+#[test]
+fn skipped() {
+    let code = shapes();
+
+    for expected in [
+        "// Skipped `Shapes.Point.scale(by:)`: mutating members are not supported",
+        "// Skipped `Shapes.loadShape(named:)`: throwing functions are not supported",
+        "// Skipped `Shapes.largest(_:)`: generic functions are not supported",
+        "// Skipped `extension Swift.Double`",
+    ]
+    .iter()
+    {
+        assert_contains(&code, expected);
+    }
+}
+
+#[test]
+fn class_methods() {
+    let code = shapes();
+
+    assert_contains(
+        &code,
+        "type Metadata = ::swift::rt::metadata::ClassMetadata;",
+    );
+
+    // Open members are looked up in the vtable of the object's class.
+    assert_contains(&code, "#[link_name = \"$s6Shapes5ShapeC4areaSdyFTq\"]");
+    assert_contains(&code, "#[link_name = \"$s6Shapes5ShapeCMn\"]");
+    assert_contains(&code, "pub fn area(&self) -> ::swift::Double {");
+    assert_contains(
+        &code,
+        "::swift::sys::metadata::fns::swift_lookUpClassMethod(metadata.cast(), &sys::Shape_area_method, &sys::Shape_descriptor)",
+    );
+    assert_contains(&code, "#[link_name = \"$s6Shapes5ShapeC4nameSSvgTq\"]");
+    assert_contains(&code, "pub fn name(&self) -> ::swift::String {");
+
+    // Members of final classes are called directly.
+    assert_contains(
+        &code,
+        "#[link_name = \"$s6Shapes6CircleC6radiusSdvg\"]\n            pub fn Circle_radius();",
+    );
+    assert_contains(
+        &code,
+        "let function = sys::Circle_radius as *const ::std::ffi::c_void;",
+    );
+    assert_contains(&code, "let context = self.0.as_ptr();");
+
+    // Allocating initializers take the class metadata as `self`.
+    assert_contains(
+        &code,
+        "#[link_name = \"$s6Shapes6CircleC6radiusACSd_tcfC\"]",
+    );
+    assert_contains(&code, "pub fn new(radius: ::swift::Double) -> Circle {");
+    assert_contains(
+        &code,
+        "let function = sys::Circle_new as *const ::std::ffi::c_void;",
+    );
+    assert_contains(&code, "#[link_name = \"$s6Shapes5ShapeC4nameSSvsTq\"]");
+    assert_contains(&code, "pub fn set_name(&self, value: ::swift::String) {");
+}
+
+#[test]
+fn multiple_results() {
+    let code = generate_named(
+        "@frozen public struct Rect {
+           public var x: Swift.Int
+           public var y: Swift.Int
+           public var width: Swift.Int
+           public var height: Swift.Int
+         }
+         @frozen public struct Color {
+           public var red: Swift.Float
+           public var green: Swift.Float
+           public var blue: Swift.Float
+         }
+         final public class View {
+           public func frame() -> Test.Rect
+           public func color() -> Test.Color
+         }",
+    );
+
+    // Results are returned in up to four registers of each class.
+    assert_contains(&code, "pub fn frame(&self) -> Rect {");
+    assert_contains(
+        &code,
+        "::std::mem::transmute([ret.int(0), ret.int(1), ret.int(2), ret.int(3)])",
+    );
+    assert_contains(
+        &code,
+        "::std::mem::transmute([ret.float32(0), ret.float32(1), ret.float32(2)])",
+    );
+}
+
+#[test]
+fn class_overrides() {
+    let code = generate_named(
+        "open class Base {
+           open func run(_ count: Swift.Int)
+           public final func stop()
+           open class func make() -> Swift.Int
+         }
+         open class Derived : Test.Base {
+           override open func run(_ count: Swift.Int)
+         }",
+    );
+
+    // Overrides are dispatched through the entry of the overridden method.
+    assert_contains(
+        &code,
+        "#[link_name = \"$s4Test4BaseC3runyySiFTq\"]\n            pub static Derived_run_method: ::swift::sys::ctx_desc::MethodDescriptor;",
+    );
+    assert_contains(
+        &code,
+        "swift_lookUpClassMethod(metadata.cast(), &sys::Derived_run_method, &sys::Base_descriptor)",
+    );
+    assert_contains(
+        &code,
+        "let function = sys::Base_stop as *const ::std::ffi::c_void;",
+    );
+    assert_contains(
+        &code,
+        "let metadata = <Self as ::swift::rt::metadata::Type>::get_metadata() as *const ::swift::rt::metadata::ClassMetadata;",
+    );
+    assert_contains(&code, "pub fn make() -> ::swift::Int {");
+    assert_contains(&code, "let context = metadata;");
+}
+
+#[test]
+fn protocol() {
+    let code = shapes();
+
+    assert_contains(&code, "#[link_name = \"$s6Shapes8DrawableMp\"]");
+    assert_contains(
+        &code,
+        "pub static Drawable_descriptor: ::swift::rt::ctx_desc::ProtocolContextDescriptor;",
+    );
+    assert_contains(
+        &code,
+        "pub unsafe trait Drawable: ::swift::rt::metadata::Type + Sized {",
+    );
+    assert_contains(&code, ".conformance(Drawable_descriptor())");
+
+    // Requirements of resilient protocols are called through dispatch thunks.
+    assert_contains(
+        &code,
+        "#[link_name = \"$s6Shapes8DrawableP4drawyyFTj\"]\n            pub fn Drawable_draw();",
+    );
+    assert_contains(&code, "fn draw(&self) {");
+    assert_contains(
+        &code,
+        "let function = sys::Drawable_draw as *const ::std::ffi::c_void;",
+    );
+    assert_contains(&code, "let context = self as *const Self;");
+    assert_contains(
+        &code,
+        ".int(metadata as u64)\n                    .int(witness_table as u64)\n                    .context(context)\n                    .call()",
+    );
+    assert_contains(
+        &code,
+        "compile_error!(\"bindings to `Shapes` call Swift through `swift::rt::call`",
+    );
+}
+
+#[test]
+fn protocol_witnesses() {
+    let code = generate_named(
+        "public protocol Base {
+           func base() -> Swift.Int
+         }
+         public protocol Counter : Test.Base {
+           associatedtype Element
+           var count: Swift.Int { get set }
+           mutating func add(_ value: Swift.Int, scale: Swift.Double) -> Swift.Double
+           static func make() -> Swift.Int
+         }
+         @frozen public struct Tally : Test.Counter {
+           public var value: Swift.Int
+         }",
+    );
+
+    assert_contains(
+        &code,
+        "pub unsafe trait Counter: ::swift::rt::metadata::Type + Sized + Base {",
+    );
+    assert_contains(&code, "fn count(&self) -> ::swift::Int {");
+    assert_contains(
+        &code,
+        "fn add(&mut self, value: ::swift::Int, scale: ::swift::Double) -> ::swift::Double {",
+    );
+    assert_contains(&code, "fn make() -> ::swift::Int {");
+
+    // The base protocol and associated type come before the members, and
+    // settable properties have three witnesses.
+    assert_contains(&code, "witnesses_ptr(witness_table).add(2) };");
+    assert_contains(&code, "witnesses_ptr(witness_table).add(5) };");
+    assert_contains(&code, "witnesses_ptr(witness_table).add(6) };");
+    assert_contains(&code, "let context = self as *mut Self;");
+    assert_contains(&code, "let context = metadata;");
+    assert_contains(
+        &code,
+        ".int(arg0)\n                    .float(arg1)\n                    .int(metadata as u64)",
+    );
+
+    // Arguments and results of primitive types are cast rather than
+    // transmuted, so that bindings compile without warnings.
+    assert_contains(&code, "let (arg0, arg1,) = (value as u64, scale,);");
+    assert_contains(&code, "};\n            ret.float(0)\n        }");
+    assert_contains(&code, "};\n            ret.int(0) as isize\n        }");
+
+    assert_contains(
+        &code,
+        "// SAFETY: `Test.Tally` conforms to `Test.Counter`.\n    unsafe impl Counter for Tally {}",
+    );
+    assert_contains(&code, "unsafe impl Base for Tally {}");
+    check("protocol-witnesses", &code);
+}
+
+#[test]
+fn tail_padding() {
+    let code = generate_named(
+        "public struct Padded {
+           public var a: Swift.Int64
+           public var b: Swift.Int8
+         }
+         public struct Outer {
+           public var padded: Test.Padded
+           public var c: Swift.Int8
+         }
+         public struct Last {
+           public var c: Swift.Int8
+           public var padded: Test.Padded
+         }",
+    );
+
+    // Without library evolution, every type has a fixed layout.
+    assert_contains(&code, "pub struct Padded {");
+    assert_contains(
+        &code,
+        "// Skipped `Test.Outer`: field `padded` has tail padding",
+    );
+    assert_contains(&code, "pub struct Last {");
+}
+
+#[test]
+fn ownership() {
+    let code = generate_named(
+        "public struct Tag {
+           public var name: Swift.String
+           public init(name: Swift.String)
+           public func renamed(_ name: Swift.String, id: Swift.Int) -> Swift.String
+         }
+         public func consume(_ tag: __owned Test.Tag)",
+    );
+
+    // Initializer arguments are owned.
+    assert_contains(&code, "pub fn new(name: ::swift::String) -> Tag {");
+    assert_contains(&code, "sys::Tag_new(name)");
+
+    // Other arguments are borrowed.
+    assert_contains(
+        &code,
+        "pub fn renamed(&self, name: &::swift::String, id: ::swift::Int) -> ::swift::String {",
+    );
+    assert_contains(
+        &code,
+        "sys::Tag_renamed(::std::mem::ManuallyDrop::new(::std::ptr::read(name)), id, \
+         ::std::mem::ManuallyDrop::new(::std::ptr::read(&self.name)))",
+    );
+
+    assert_contains(&code, "let tag = ::std::mem::ManuallyDrop::new(tag);");
+    assert_contains(&code, "#[link_name = \"$s4Test7consumeyyAA3TagVnF\"]");
+}
+
+#[test]
+fn nested_types() {
+    let code = generate_named(
+        "public struct Outer {
+           public enum Inner {
+             case a, b
+           }
+           public func inner() -> Test.Outer.Inner
+         }
+         extension Test.Outer.Inner {
+           public var flipped: Inner { get }
+         }",
+    );
+
+    assert_contains(&code, "pub enum Outer_Inner {");
+    assert_contains(&code, "#[link_name = \"$s4Test5OuterV5InnerOMa\"]");
+    assert_contains(&code, "pub fn inner(self) -> Outer_Inner {");
+    assert_contains(
+        &code,
+        "#[link_name = \"$s4Test5OuterV5InnerO7flippedAEvg\"]",
+    );
+}
+
+#[test]
+fn allowlist() {
+    let options = Options {
+        filter: Filter {
+            allowlist_types: patterns(&["Rect"]),
+            allowlist_functions: patterns(&["Shapes.dist.*"]),
+            ..Filter::default()
+        },
+        ..Options::default()
+    };
+    let code = shapes_with(&options);
+
+    // `Point` and `Size` are needed by `Rect` and `distance(_:_:)`.
+    for expected in [
+        "pub struct Rect {",
+        "pub struct Point {",
+        "pub struct Size {",
+        "pub fn distance(a: Point, b: Point) -> ::swift::Double {",
+        "pub fn new(origin: Point, size: Size) -> Rect {",
+    ]
+    .iter()
+    {
+        assert_contains(&code, expected);
+    }
+    for unexpected in ["Circle", "ShapeKind", "describe", "extension"].iter() {
+        assert!(!code.contains(unexpected), "found `{}`", unexpected);
+    }
+}
+
+#[test]
+fn blocklist() {
+    let options = Options {
+        filter: Filter {
+            blocklist_types: patterns(&["Point"]),
+            blocklist_functions: patterns(&["make.*"]),
+            ..Filter::default()
+        },
+        link_name: Some("ShapesKit".to_owned()),
+    };
+    let code = shapes_with(&options);
+
+    assert!(!code.contains("pub struct Point"));
+    assert!(!code.contains("make_circle"));
+    assert_contains(
+        &code,
+        "pub fn distance(a: Point, b: Point) -> ::swift::Double {",
+    );
+    assert_contains(&code, "#[link(name = \"ShapesKit\", kind = \"dylib\")]");
+}
+
+#[test]
+fn payload_enum() {
+    let code = generate_named(
+        "public enum Fill {
+           case none
+           case solid(red: Swift.Double, green: Swift.Double, blue: Swift.Double)
+           case gray(Swift.Double)
+           public func blended(with other: Test.Fill) -> Test.Fill
+           public static func white() -> Test.Fill
+         }",
+    );
+
+    assert_contains(
+        &code,
+        "#[derive(Clone)]\n    pub struct Fill(::swift::rt::value::ValueBox);",
+    );
+    assert_contains(
+        &code,
+        "type Metadata = ::swift::rt::metadata::EnumMetadata;",
+    );
+    assert_contains(
+        &code,
+        "pub enum FillCase {\n        #[doc(alias = \"none\")]\n        None,",
+    );
+    assert_contains(&code, "Gray(::swift::Double),");
+
+    // Payloads are laid out as tuples.
+    assert_contains(&code, "Some(\"solid\") => FillCase::Solid {");
+    assert_contains(
+        &code,
+        "blue: unsafe { ::std::ptr::read(data.add(16).cast()) },",
+    );
+    assert_contains(
+        &code,
+        "pub fn solid(red: ::swift::Double, green: ::swift::Double, blue: ::swift::Double) -> Self {",
+    );
+    assert_contains(&code, "::std::ptr::write(data.add(8).cast(), green);");
+    assert_contains(
+        &code,
+        "metadata.vw_destructive_inject_enum_tag(data, Self::case_tag(\"solid\"));",
+    );
+    assert_contains(&code, "pub fn gray(arg0: ::swift::Double) -> Self {");
+
+    // Tags are mapped to cases through the field descriptor, and cases that
+    // the bindings don't know of are viewed by their tag.
+    assert_contains(&code, "let case = match Self::case_name(tag) {");
+    assert_contains(
+        &code,
+        "fields.field_records().get(tag as usize)?.field_name()",
+    );
+    assert_contains(
+        &code,
+        "/// A case that these bindings don't know of, with its tag.\n        Unknown(u32),\n    }",
+    );
+    assert_contains(
+        &code,
+        "unsafe { metadata.vw_destructive_inject_enum_tag(data, tag) };\n                    return FillCase::Unknown(tag);",
+    );
+
+    assert_contains(&code, "// Skipped `Test.Fill.blended(with:)`: `Test.Fill` has payloads, so it can't be passed directly");
+}
+
+#[test]
+fn resilient_payload_enum() {
+    // `Shapes` is built with library evolution, and `Fill` isn't frozen.
+    let code = shapes();
+    assert_contains(
+        &code,
+        "#[derive(Clone)]\n    pub struct Fill(::swift::rt::value::ValueBox);",
+    );
+    assert_contains(
+        &code,
+        "pub enum FillCase {\n        #[doc(alias = \"none\")]\n        None,",
+    );
+    assert_contains(&code, "Some(\"solid\") => FillCase::Solid {");
+    assert_contains(
+        &code,
+        "pub fn solid(red: ::swift::Double, green: ::swift::Double, blue: ::swift::Double) -> Self {",
+    );
+    assert_contains(&code, "return FillCase::Unknown(tag);");
+
+    // Values are passed indirectly, and payloads without a fixed layout can't
+    // be viewed.
+    let code = generate_named(
+        "// swift-module-flags: -enable-library-evolution -module-name Test
+         public struct Polygon {
+           public var sides: Swift.Int
+         }
+         public enum Fill {
+           case none
+           case gray(Swift.Double)
+           public func blended(with other: Test.Fill) -> Test.Fill
+         }
+         public enum Outline {
+           case none
+           case polygon(Test.Polygon)
+         }",
+    );
+    assert_contains(&code, "pub enum FillCase {");
+    assert_contains(&code, "pub fn blended(&self, other: &Fill) -> Fill {");
+    assert_contains(&code, "pub struct Outline(::swift::rt::value::ValueBox);");
+    assert!(!code.contains("OutlineCase"));
+}
+
+#[test]
+fn resilient_types() {
+    let code = generate_named(
+        "// swift-module-flags: -enable-library-evolution -module-name Test
+         public struct Polygon {
+           public var sides: Swift.Int
+           public init(sides: Swift.Int)
+           public func scaled(by factor: Swift.Double) -> Test.Polygon
+         }
+         public enum Fill {
+           case none
+         }
+         public func merge(_ a: Test.Polygon, into b: inout Test.Polygon)
+         public func store(_ polygon: __owned Test.Polygon)",
+    );
+
+    assert_contains(
+        &code,
+        "#[derive(Clone)]\n    pub struct Polygon(::swift::rt::value::ValueBox);",
+    );
+    assert_contains(
+        &code,
+        "type Metadata = ::swift::rt::metadata::StructMetadata;",
+    );
+    assert_contains(&code, "pub struct Fill(::swift::rt::value::ValueBox);");
+
+    // Stored properties are read through their getters, passing `self` by
+    // address in the context register.
+    assert_contains(&code, "#[link_name = \"$s4Test7PolygonV5sidesSivg\"]");
+    assert_contains(&code, "pub fn sides(&self) -> ::swift::Int {");
+    assert_contains(&code, "let context = self.0.as_ptr();");
+
+    // Results are written to a buffer sized by the value witnesses.
+    assert_contains(
+        &code,
+        "pub fn scaled(&self, factor: ::swift::Double) -> Polygon {",
+    );
+    assert_contains(
+        &code,
+        "<Polygon as ::swift::rt::metadata::Type>::get_metadata())) };",
+    );
+    assert_contains(&code, ".indirect_result(result_ptr)");
+    assert_contains(&code, "Polygon(result)");
+
+    // Arguments are passed by address.
+    assert_contains(&code, "pub fn merge(a: &Polygon, b: &mut Polygon) {");
+    assert_contains(
+        &code,
+        "unsafe { sys::merge(a.0.as_ptr(), b.0.as_mut_ptr()) }",
+    );
+    assert_contains(
+        &code,
+        "unsafe { sys::store(polygon.0.as_ptr() as *mut u8) };\n        unsafe { polygon.0.forget_value() };",
+    );
+}
+
+#[test]
+fn generic_types() {
+    let code = generate_named(
+        "public protocol Shape {
+           func area() -> Swift.Double
+         }
+         public protocol Polygon : Test.Shape {}
+         public struct Pair<First, Second> where First : Test.Polygon, First : Test.Shape {
+           public var first: First
+           public var second: Second
+         }
+         public class Node<Element> {
+           public func value() -> Element
+         }
+         public struct Hashed<Key> where Key : Swift.Hashable {}
+         public func root() -> Test.Node<Swift.Int>",
+    );
+
+    // Conformance requirements become bounds, and witness tables of base
+    // protocols aren't passed.
+    assert_contains(
+        &code,
+        "pub struct Pair<First: ::swift::rt::metadata::Type + Polygon, Second: ::swift::rt::metadata::Type>(::swift::rt::value::ValueBox, ::std::marker::PhantomData<(First, Second)>);",
+    );
+    assert_contains(
+        &code,
+        "pub fn Pair_metadata_accessor(request: ::swift::sys::metadata::MetadataRequest, arg0: *const ::std::ffi::c_void, arg1: *const ::std::ffi::c_void, arg2: *const ::std::ffi::c_void)",
+    );
+    assert_contains(
+        &code,
+        "<Second as ::swift::rt::metadata::Type>::get_metadata_blocking(blocking)?)",
+    );
+    assert_contains(
+        &code,
+        "<First as Polygon>::witness_table() as *const ::swift::rt::metadata::WitnessTable as *const ::std::ffi::c_void,\n            ];",
+    );
+    assert_contains(
+        &code,
+        "// Skipped `Test.Pair.first`: members of generic types are not supported",
+    );
+
+    assert_contains(
+        &code,
+        "#[repr(transparent)]\n    pub struct Node<Element: ::swift::rt::metadata::Type>(::std::ptr::NonNull<::std::ffi::c_void>, ::std::marker::PhantomData<Element>);",
+    );
+    assert_contains(
+        &code,
+        "impl<Element: ::swift::rt::metadata::Type> Drop for Node<Element> {",
+    );
+    assert_contains(&code, "#[link_name = \"$s4Test4rootAA4NodeCySiGyF\"]");
+    assert_contains(&code, "pub fn root() -> Node<::swift::Int> {");
+
+    assert_contains(
+        &code,
+        "// Skipped `Test.Hashed`: requirement `Key: Swift.Hashable` is not supported",
+    );
+}
+
+#[test]
+fn globals() {
+    let code = generate_named(
+        "public let version: Swift.Int
+         public var counter: Swift.Int
+         public var name: Swift.String {
+           get
+           set
+         }
+         public struct Config {
+           public static let shared: Test.Config
+           public static var limit: Swift.Int {
+             get
+           }
+         }
+         public class Registry {
+           public static var current: Test.Registry
+           public class var count: Swift.Int {
+             get
+           }
+         }",
+    );
+
+    // Stored variables are reached through their addressors.
+    assert_contains(&code, "#[link_name = \"$s4Test7versionSivau\"]");
+    assert_contains(
+        &code,
+        "pub fn version() -> &'static ::swift::Int {\n        unsafe { &*sys::version_addressor().cast::<::swift::Int>() }",
+    );
+    assert_contains(
+        &code,
+        "unsafe { (*sys::counter_addressor().cast::<::swift::Int>()).clone() }",
+    );
+    assert_contains(
+        &code,
+        "/// Assigns `value` to `Test.counter`.\n    #[inline]\n    pub fn set_counter(value: ::swift::Int) {\n        unsafe { *sys::counter_addressor().cast::<::swift::Int>() = value };",
+    );
+    assert_contains(&code, "#[link_name = \"$s4Test6ConfigV6sharedACvauZ\"]");
+    assert_contains(&code, "pub fn shared() -> &'static Config {");
+    assert_contains(&code, "pub fn set_current(value: Registry) {");
+
+    // Computed variables are called through their accessors.
+    assert_contains(&code, "#[link_name = \"$s4Test4nameSSvs\"]");
+    assert_contains(
+        &code,
+        "/// Sets `Test.name`.\n    #[inline]\n    pub fn set_name(value: ::swift::String) {",
+    );
+    assert_contains(&code, "#[link_name = \"$s4Test6ConfigV5limitSivgZ\"]");
+    assert_contains(&code, "#[link_name = \"$s4Test8RegistryC5countSivgZTq\"]");
+    assert_contains(&code, "let context = metadata;");
+}
+
+#[test]
+fn resilient_globals() {
+    let code = generate_named(
+        "// swift-module-flags: -enable-library-evolution -module-name Test
+         public let version: Swift.Int",
+    );
+
+    // Stored variables of resilient modules may become computed.
+    assert_contains(&code, "#[link_name = \"$s4Test7versionSivg\"]");
+    assert_contains(&code, "pub fn version() -> ::swift::Int {");
+}
+
+#[test]
+fn accessors() {
+    let code = generate_named(
+        "public struct Grid {
+           public var width: Swift.Int { get set }
+           public var height: Swift.Int { get nonmutating set }
+           public var depth: Swift.Int { get _modify }
+           public subscript(row: Swift.Int, column: Swift.Int) -> Swift.Double { get set }
+           public subscript(_ index: Swift.Int) -> Swift.Double { get }
+           public static subscript(_ index: Swift.Int) -> Swift.Int { get }
+         }
+         open class Node {
+           public init?(name: Swift.String)
+           public subscript(key: Swift.String) -> Swift.Int { get set _modify }
+         }",
+    );
+
+    // Setters of value types take the address of `self`. Only getters are
+    // aliased to the names of properties.
+    assert_contains(&code, "#[link_name = \"$s4Test4GridV5widthSivs\"]");
+    assert_contains(
+        &code,
+        "/// Sets `Test.Grid.width`.\n        #[inline]\n        pub fn set_width(&mut self, value: ::swift::Int) {",
+    );
+    assert_contains(
+        &code,
+        "/// Calls `Test.Grid.width`.\n        #[inline]\n        pub fn width(self) -> ::swift::Int {",
+    );
+    assert!(!code.contains("#[doc(alias = \"width\")]"));
+    assert_contains(&code, "let context = (self as *mut Self).cast::<u8>();");
+    assert_contains(
+        &code,
+        "pub fn set_height(self, value: ::swift::Int) {\n            unsafe { sys::Grid_set_height(value) }",
+    );
+
+    // `_modify` yields the address of the value to a closure.
+    assert_contains(&code, "#[link_name = \"$s4Test4GridV5depthSivM\"]");
+    assert_contains(
+        &code,
+        "pub fn modify_depth<R>(&mut self, f: impl FnOnce(&mut ::swift::Int) -> R) -> R {",
+    );
+    assert_contains(&code, "let mut buffer = [0usize; 4];");
+    assert_contains(&code, "resume(self.1, ::std::thread::panicking());");
+    assert_contains(&code, "f(unsafe { &mut *(address as *mut ::swift::Int) })");
+
+    // Subscripts are named after `Index`, and setters take the value first.
+    assert_contains(
+        &code,
+        "#[link_name = \"$s4Test4GridV3row6columnSdSi_Sitcig\"]",
+    );
+    assert_contains(
+        &code,
+        "pub fn index(self, row: ::swift::Int, column: ::swift::Int) -> ::swift::Double {",
+    );
+    assert_contains(
+        &code,
+        "pub fn set_index(&mut self, value: ::swift::Double, row: ::swift::Int, column: ::swift::Int) {",
+    );
+    assert_contains(&code, "#[link_name = \"$s4Test4GridVySdSicig\"]");
+    assert_contains(
+        &code,
+        "pub fn index2(self, index: ::swift::Int) -> ::swift::Double {",
+    );
+    assert_contains(
+        &code,
+        "// Skipped `Test.Grid.subscript(_:)`: static subscripts are not supported",
+    );
+
+    // Class accessors are looked up in the vtable.
+    assert_contains(&code, "#[link_name = \"$s4Test4NodeC4nameACSgSS_tcfC\"]");
+    assert_contains(
+        &code,
+        "pub fn new(name: ::swift::String) -> ::std::option::Option<Node> {",
+    );
+    assert_contains(&code, "#[link_name = \"$s4Test4NodeC3keySiSS_tciMTq\"]");
+    assert_contains(
+        &code,
+        "pub fn modify_index<R>(&self, key: &::swift::String, f: impl FnOnce(&mut ::swift::Int) -> R) -> R {",
+    );
+}
@@ -0,0 +1,1083 @@
+//! Resolution of Swift types to Rust types with known layouts.
+
+use super::names;
+use crate::{
+    ir::{self, has_attribute, has_modifier, Decl, NominalDecl, NominalKind, TypePath},
+    mangle,
+};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+/// A value passed in a single register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Scalar {
+    /// An integer or pointer of the given size.
+    Int(usize),
+    /// A floating-point number of the given size.
+    Float(usize),
+}
+
+impl Scalar {
+    pub fn size(self) -> usize {
+        match self {
+            Self::Int(size) | Self::Float(size) => size,
+        }
+    }
+}
+
+/// The layout of a Swift type on a 64-bit target.
+#[derive(Clone, Debug)]
+pub(crate) struct Layout {
+    pub size: usize,
+    pub align: usize,
+    pub pod: bool,
+
+    /// The registers used when the type is passed directly under `swiftcc`.
+    pub scalars: Vec<Scalar>,
+
+    /// Whether the size is only known at runtime, so values are always
+    /// passed by address.
+    pub indirect: bool,
+}
+
+impl Layout {
+    fn scalar(scalar: Scalar) -> Self {
+        let size = scalar.size();
+        Self {
+            size,
+            align: size,
+            pod: true,
+            scalars: vec![scalar],
+            indirect: false,
+        }
+    }
+
+    fn pointer(pod: bool) -> Self {
+        Self {
+            pod,
+            ..Self::scalar(Scalar::Int(8))
+        }
+    }
+
+    /// Returns the layout of a resilient type, whose address is passed in a
+    /// pointer register.
+    fn indirect() -> Self {
+        Self {
+            size: 0,
+            align: 1,
+            pod: false,
+            scalars: vec![Scalar::Int(8)],
+            indirect: true,
+        }
+    }
+
+    /// Returns `true` if Swift places the next field at the same offset as C.
+    ///
+    /// Swift packs fields into the tail padding of preceding fields, whereas C
+    /// always advances by the stride.
+    fn has_tail_padding(&self) -> bool {
+        !self.size.is_multiple_of(self.align)
+    }
+}
+
+/// A Swift type resolved to a Rust type.
+#[derive(Clone, Debug)]
+pub(crate) struct Resolved {
+    /// The canonical type used for mangling.
+    pub mangled: mangle::Type,
+
+    /// The path to the Rust type.
+    pub rust: String,
+
+    pub layout: Layout,
+
+    /// The values that the type is expanded to when passed directly, which
+    /// are its fields for frozen structs and the type itself otherwise.
+    pub leaves: Vec<Leaf>,
+
+    /// Whether `Optional` of this type uses a null pointer for `nil`, which is
+    /// the case for Rust's `Option` of the same type.
+    nullable: bool,
+}
+
+/// A value within a [`Resolved`] type that is passed as-is.
+#[derive(Clone, Debug)]
+pub(crate) struct Leaf {
+    /// A field access path such as `.origin.x`, or empty for the value itself.
+    pub path: String,
+    pub rust: String,
+    pub pod: bool,
+
+    /// The registers used by this value, in order.
+    pub scalars: Vec<Scalar>,
+}
+
+impl Resolved {
+    fn leaf(mangled: mangle::Type, rust: String, layout: Layout, nullable: bool) -> Self {
+        let leaves = vec![Leaf {
+            path: String::new(),
+            rust: rust.clone(),
+            pod: layout.pod,
+            scalars: layout.scalars.clone(),
+        }];
+        Self {
+            mangled,
+            rust,
+            layout,
+            leaves,
+            nullable,
+        }
+    }
+
+    /// Returns `count` pointer-sized integers, such as the results of a
+    /// coroutine, which have no Swift type.
+    pub fn words(count: usize) -> Self {
+        let layout = Layout {
+            size: count * 8,
+            align: 8,
+            pod: true,
+            scalars: vec![Scalar::Int(8); count],
+            indirect: false,
+        };
+        let rust = format!("[usize; {}]", count);
+        Self::leaf(mangle::Type::VOID, rust, layout, false)
+    }
+
+    pub fn void() -> Self {
+        Self {
+            mangled: mangle::Type::VOID,
+            rust: "()".to_owned(),
+            layout: Layout {
+                size: 0,
+                align: 1,
+                pod: true,
+                scalars: Vec::new(),
+                indirect: false,
+            },
+            leaves: Vec::new(),
+            nullable: false,
+        }
+    }
+
+    pub fn is_void(&self) -> bool {
+        self.mangled == mangle::Type::VOID
+    }
+}
+
+/// How a nominal type is represented in Rust.
+#[derive(Debug)]
+pub(crate) enum Repr {
+    /// A struct with a fixed layout that matches `#[repr(C)]`.
+    Struct(Vec<Field>),
+
+    /// An enum without payloads, with the integer type of its tag if any.
+    Enum {
+        tag: Option<&'static str>,
+        cases: Vec<String>,
+    },
+
+    /// An enum with associated values, stored in a `ValueBox`.
+    PayloadEnum(Vec<Case>),
+
+    /// A struct or enum whose layout is only known at runtime because it's
+    /// resilient or generic, stored in a `ValueBox`.
+    Resilient,
+
+    /// A reference to a class instance.
+    Class,
+
+    /// A protocol, which is bound as a trait rather than a type.
+    Protocol,
+
+    /// The type can't be represented, for the given reason.
+    Unsupported(String),
+}
+
+/// A stored property of a [`Repr::Struct`].
+#[derive(Debug)]
+pub(crate) struct Field {
+    pub name: String,
+    pub ty: Resolved,
+    pub is_public: bool,
+}
+
+/// A case of a [`Repr::PayloadEnum`].
+#[derive(Debug)]
+pub(crate) struct Case {
+    pub name: String,
+    pub payload: Vec<CaseField>,
+}
+
+/// An associated value of a [`Case`].
+#[derive(Debug)]
+pub(crate) struct CaseField {
+    pub label: Option<String>,
+    pub ty: Resolved,
+
+    /// The offset within the payload, which is laid out as a tuple.
+    pub offset: usize,
+}
+
+/// The generic parameters of a type as Rust type parameters.
+#[derive(Debug, Default)]
+pub(crate) struct Signature {
+    /// The names of the parameters with their trait bounds.
+    pub params: Vec<(String, Vec<String>)>,
+
+    /// The parameter and trait of each witness table that the metadata
+    /// accessor takes after the metadata of the parameters.
+    pub witness_tables: Vec<(String, String)>,
+}
+
+impl Signature {
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty()
+    }
+
+    /// Returns the parameters with their bounds, such as `<T: Type>`.
+    pub fn declare(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+        let params: Vec<String> = self
+            .params
+            .iter()
+            .map(|(name, bounds)| {
+                let mut bounds = bounds.clone();
+                bounds.insert(0, "::swift::rt::metadata::Type".to_owned());
+                format!("{}: {}", name, bounds.join(" + "))
+            })
+            .collect();
+        format!("<{}>", params.join(", "))
+    }
+
+    /// Returns the parameters as arguments, such as `<T>`.
+    pub fn apply(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+        format!("<{}>", self.names().join(", "))
+    }
+
+    /// Returns the marker that uses the parameters in a wrapper.
+    pub fn phantom(&self) -> Option<String> {
+        match self.names().as_slice() {
+            [] => None,
+            [name] => Some(format!("::std::marker::PhantomData<{}>", name)),
+            names => Some(format!(
+                "::std::marker::PhantomData<({})>",
+                names.join(", ")
+            )),
+        }
+    }
+
+    fn names(&self) -> Vec<&str> {
+        self.params.iter().map(|(name, _)| name.as_str()).collect()
+    }
+}
+
+/// A declaration within a nominal type or one of its extensions.
+pub(crate) struct Member<'a> {
+    pub decl: &'a Decl,
+
+    /// Whether the member is public without an explicit modifier, such as
+    /// within a `public extension`.
+    pub implicitly_public: bool,
+}
+
+impl Member<'_> {
+    pub fn is_public(&self, modifiers: &[String]) -> bool {
+        self.implicitly_public || is_public(modifiers)
+    }
+}
+
+/// A nominal type declared in the module being bound.
+pub(crate) struct Entry<'a> {
+    pub decl: &'a NominalDecl,
+    pub parent: Option<usize>,
+    pub nominal: mangle::Nominal,
+
+    /// The qualified Swift name within its module, such as `Shape.Kind`.
+    pub swift_name: String,
+    pub rust_name: String,
+    pub members: Vec<Member<'a>>,
+
+    /// The superclass and protocols in the inheritance clauses of the
+    /// declaration and its extensions.
+    pub inherited: Vec<&'a ir::Type>,
+}
+
+enum ReprState {
+    Pending,
+    Computing,
+    Done(Rc<Repr>),
+}
+
+/// The nominal types and type aliases of a module.
+pub(crate) struct TypeTable<'a> {
+    pub module: &'a ir::Module,
+    pub entries: Vec<Entry<'a>>,
+    by_name: HashMap<String, usize>,
+    aliases: HashMap<&'a str, &'a ir::Type>,
+    reprs: RefCell<Vec<ReprState>>,
+
+    /// Entries that are filtered out of the bindings, so can't be referred
+    /// to.
+    pub excluded: Vec<bool>,
+
+    /// Extensions of types declared outside of the module, with the reason
+    /// they are skipped.
+    pub skipped_extensions: Vec<(&'a ir::Extension, String)>,
+}
+
+/// Protocols that have no witness tables.
+pub(crate) const MARKER_PROTOCOLS: &[&str] = &[
+    "AnyObject",
+    "BitwiseCopyable",
+    "Copyable",
+    "Escapable",
+    "Sendable",
+];
+
+/// Returns `true` if `modifiers` make a declaration visible to clients.
+pub(crate) fn is_public(modifiers: &[String]) -> bool {
+    has_modifier(modifiers, "public") || has_modifier(modifiers, "open")
+}
+
+/// Returns `true` if the declaration is marked unavailable.
+pub(crate) fn is_unavailable(attributes: &[ir::Attribute]) -> bool {
+    attributes.iter().any(|attr| {
+        attr.name == "available"
+            && attr
+                .arguments
+                .as_deref()
+                .is_some_and(|args| args.starts_with('*') && args.contains("unavailable"))
+    })
+}
+
+impl<'a> TypeTable<'a> {
+    pub fn new(module: &'a ir::Module) -> Self {
+        let mut table = Self {
+            module,
+            entries: Vec::new(),
+            by_name: HashMap::new(),
+            aliases: HashMap::new(),
+            reprs: RefCell::new(Vec::new()),
+            excluded: Vec::new(),
+            skipped_extensions: Vec::new(),
+        };
+
+        table.collect(&module.decls, None, false);
+
+        for decl in &module.decls {
+            match decl {
+                Decl::Extension(ext) => table.collect_extension(ext),
+                Decl::TypeAlias(alias) if alias.generics.is_empty() => {
+                    table.aliases.insert(&alias.name, &alias.ty);
+                }
+                _ => {}
+            }
+        }
+
+        let count = table.entries.len();
+        table.reprs = RefCell::new((0..count).map(|_| ReprState::Pending).collect());
+        table.excluded = vec![false; count];
+        table
+    }
+
+    fn collect(&mut self, decls: &'a [Decl], parent: Option<usize>, implicitly_public: bool) {
+        for decl in decls {
+            let decl = match decl {
+                Decl::Nominal(decl) => decl,
+                _ => continue,
+            };
+            if !implicitly_public && !is_public(&decl.modifiers) {
+                continue;
+            }
+
+            let (nominal, swift_name, rust_name) = match parent {
+                Some(parent) => {
+                    let parent = &self.entries[parent];
+                    (
+                        parent.nominal.nested(&decl.name, decl.kind.into()),
+                        format!("{}.{}", parent.swift_name, decl.name),
+                        format!("{}_{}", parent.rust_name, decl.name),
+                    )
+                }
+                None => (
+                    mangle::Nominal::new(&self.module.name, &decl.name, decl.kind.into()),
+                    decl.name.clone(),
+                    names::escape(&decl.name),
+                ),
+            };
+
+            let index = self.entries.len();
+            self.by_name.insert(swift_name.clone(), index);
+            self.entries.push(Entry {
+                decl,
+                parent,
+                nominal,
+                swift_name,
+                rust_name,
+                members: decl
+                    .decls
+                    .iter()
+                    .map(|decl| Member {
+                        decl,
+                        implicitly_public: false,
+                    })
+                    .collect(),
+                inherited: decl.inherited.iter().collect(),
+            });
+            self.collect(&decl.decls, Some(index), false);
+        }
+    }
+
+    fn collect_extension(&mut self, ext: &'a ir::Extension) {
+        let index = match ext
+            .extended
+            .as_path()
+            .and_then(|path| self.find(path, None))
+        {
+            Some(index) => index,
+            None => {
+                let reason = "extensions of types from other modules are not supported";
+                self.skipped_extensions.push((ext, reason.to_owned()));
+                return;
+            }
+        };
+        if !ext.requirements.is_empty() {
+            let reason = "constrained extensions are not supported";
+            self.skipped_extensions.push((ext, reason.to_owned()));
+            return;
+        }
+
+        let implicitly_public = is_public(&ext.modifiers);
+        let members = ext.decls.iter().map(|decl| Member {
+            decl,
+            implicitly_public,
+        });
+        self.entries[index].members.extend(members);
+        self.entries[index].inherited.extend(&ext.inherited);
+        self.collect(&ext.decls, Some(index), implicitly_public);
+    }
+
+    /// Finds the entry that `path` refers to from within `scope`.
+    pub fn find(&self, path: &TypePath, scope: Option<usize>) -> Option<usize> {
+        let mut names: Vec<&str> = path.components.iter().map(|c| c.name.as_str()).collect();
+        if names.len() > 1 && names[0] == self.module.name {
+            names.remove(0);
+        }
+        let name = names.join(".");
+
+        let mut scope = scope;
+        while let Some(index) = scope {
+            let entry = &self.entries[index];
+            let qualified = format!("{}.{}", entry.swift_name, name);
+            if let Some(&found) = self.by_name.get(&qualified) {
+                return Some(found);
+            }
+            scope = entry.parent;
+        }
+        self.by_name.get(&name).copied()
+    }
+
+    /// Adds the entries that `ty` refers to from within `scope` to `found`.
+    pub fn references(&self, ty: &ir::Type, scope: Option<usize>, found: &mut Vec<usize>) {
+        match ty {
+            ir::Type::Named(path) => {
+                if let Some(index) = self.find(path, scope) {
+                    found.push(index);
+                } else if let [component] = path.components.as_slice() {
+                    if let Some(ty) = self.aliases.get(component.name.as_str()) {
+                        self.references(ty, None, found);
+                    }
+                }
+                for component in &path.components {
+                    for arg in &component.generic_args {
+                        self.references(arg, scope, found);
+                    }
+                }
+            }
+            ir::Type::Optional(wrapped)
+            | ir::Type::ImplicitlyUnwrappedOptional(wrapped)
+            | ir::Type::Array(wrapped) => self.references(wrapped, scope, found),
+            _ => {}
+        }
+    }
+
+    /// Returns the Rust representation of the entry at `index`.
+    pub fn repr(&self, index: usize) -> Rc<Repr> {
+        match &self.reprs.borrow()[index] {
+            ReprState::Done(repr) => return repr.clone(),
+            ReprState::Computing => {
+                let reason = "recursive types are not supported".to_owned();
+                return Rc::new(Repr::Unsupported(reason));
+            }
+            ReprState::Pending => {}
+        }
+
+        self.reprs.borrow_mut()[index] = ReprState::Computing;
+        let repr = Rc::new(self.compute_repr(index));
+        self.reprs.borrow_mut()[index] = ReprState::Done(repr.clone());
+        repr
+    }
+
+    /// Returns `true` if the layout of `decl` is known to clients.
+    fn is_fixed_layout(&self, decl: &NominalDecl) -> bool {
+        !self.module.is_resilient()
+            || has_attribute(&decl.attributes, "frozen")
+            || has_attribute(&decl.attributes, "_fixed_layout")
+    }
+
+    fn compute_repr(&self, index: usize) -> Repr {
+        let entry = &self.entries[index];
+        let decl = entry.decl;
+
+        match decl.kind {
+            // The `where` clause of a protocol constrains `Self` rather than
+            // generic parameters.
+            NominalKind::Protocol => Repr::Protocol,
+            _ if self.in_generic_context(index) => {
+                Repr::Unsupported("types nested in generic types are not supported".to_owned())
+            }
+            _ if !decl.generics.is_empty() => match (self.signature(index), decl.kind) {
+                (Err(reason), _) => Repr::Unsupported(reason),
+                (Ok(_), NominalKind::Class | NominalKind::Actor) => Repr::Class,
+                (Ok(_), _) => Repr::Resilient,
+            },
+            NominalKind::Class | NominalKind::Actor => Repr::Class,
+            // Cases are viewed through the enum's value witnesses, which don't
+            // need a fixed layout, but their payloads must have one.
+            NominalKind::Enum if !self.is_fixed_layout(decl) => match self.enum_repr(index) {
+                repr @ Repr::PayloadEnum(_) => repr,
+                _ => Repr::Resilient,
+            },
+            _ if !self.is_fixed_layout(decl) => Repr::Resilient,
+            NominalKind::Struct => self.struct_repr(index),
+            NominalKind::Enum => self.enum_repr(index),
+        }
+    }
+
+    /// Returns `true` if a type that encloses the entry at `index` is
+    /// generic.
+    fn in_generic_context(&self, index: usize) -> bool {
+        let mut parent = self.entries[index].parent;
+        while let Some(index) = parent {
+            if !self.entries[index].decl.generics.is_empty() {
+                return true;
+            }
+            parent = self.entries[index].parent;
+        }
+        false
+    }
+
+    /// Returns the generic signature of the entry at `index`.
+    ///
+    /// Witness tables are passed for the protocols that aren't implied by
+    /// others, ordered by parameter and then by protocol name as in Swift's
+    /// canonical signatures.
+    pub fn signature(&self, index: usize) -> Result<Signature, String> {
+        let generics = &self.entries[index].decl.generics;
+        let params: Vec<&str> = generics.params.iter().map(|p| p.name.as_str()).collect();
+
+        let mut conformances = Vec::new();
+        for requirement in &generics.requirements {
+            let (subject, constraint) = match requirement {
+                ir::Requirement::Conformance {
+                    subject,
+                    constraint,
+                } => (subject, constraint),
+                ir::Requirement::SameType { .. } => {
+                    return Err("same-type requirements are not supported".to_owned());
+                }
+            };
+            let param = subject
+                .as_path()
+                .filter(|path| path.components.len() == 1)
+                .and_then(|path| params.iter().position(|&p| path.is_plain(&[p])))
+                .ok_or_else(|| format!("requirements on `{}` are not supported", subject))?;
+            for path in constraint_paths(constraint) {
+                if MARKER_PROTOCOLS.contains(&path.last().name.as_str()) {
+                    continue;
+                }
+                match self.find(path, Some(index)) {
+                    Some(found)
+                        if !self.excluded[found] && matches!(*self.repr(found), Repr::Protocol) =>
+                    {
+                        conformances.push((param, found));
+                    }
+                    _ => {
+                        return Err(format!(
+                            "requirement `{}: {}` is not supported",
+                            subject, path
+                        ));
+                    }
+                }
+            }
+        }
+
+        // Witness tables of base protocols are reached through those of the
+        // protocols that refine them.
+        let implied: Vec<(usize, usize)> = conformances
+            .iter()
+            .flat_map(|&(param, protocol)| {
+                let mut bases = Vec::new();
+                self.base_protocols(protocol, &mut bases);
+                bases.into_iter().map(move |base| (param, base))
+            })
+            .collect();
+        conformances.retain(|conformance| !implied.contains(conformance));
+        conformances.sort_by(|&(a_param, a), &(b_param, b)| {
+            let name = |index: usize| &self.entries[index].swift_name;
+            (a_param, name(a)).cmp(&(b_param, name(b)))
+        });
+        conformances.dedup();
+
+        let names: Vec<String> = params.iter().map(|&p| names::escape(p)).collect();
+        let mut signature = Signature::default();
+        for (param, name) in names.iter().enumerate() {
+            let bounds = conformances
+                .iter()
+                .filter(|&&(p, _)| p == param)
+                .map(|&(_, protocol)| self.entries[protocol].rust_name.clone())
+                .collect();
+            signature.params.push((name.clone(), bounds));
+        }
+        signature.witness_tables = conformances
+            .iter()
+            .map(|&(param, protocol)| {
+                (
+                    names[param].clone(),
+                    self.entries[protocol].rust_name.clone(),
+                )
+            })
+            .collect();
+        Ok(signature)
+    }
+
+    /// Adds the protocols that the protocol at `index` inherits from,
+    /// transitively, to `bases`.
+    fn base_protocols(&self, index: usize, bases: &mut Vec<usize>) {
+        let entry = &self.entries[index];
+        for ty in &entry.inherited {
+            for path in constraint_paths(ty) {
+                if let Some(base) = self.find(path, entry.parent) {
+                    if matches!(*self.repr(base), Repr::Protocol) && !bases.contains(&base) {
+                        bases.push(base);
+                        self.base_protocols(base, bases);
+                    }
+                }
+            }
+        }
+    }
+
+    fn struct_repr(&self, index: usize) -> Repr {
+        let mut fields = Vec::new();
+        for member in &self.entries[index].members {
+            let var = match member.decl {
+                Decl::Variable(var) if var.is_stored() => var,
+                _ => continue,
+            };
+            if has_modifier(&var.modifiers, "static") || has_modifier(&var.modifiers, "class") {
+                continue;
+            }
+
+            let ty = match self.resolve_stored(&var.ty, index) {
+                Ok(ty) => ty,
+                Err(reason) => {
+                    return Repr::Unsupported(format!(
+                        "stored property `{}`: {}",
+                        var.name, reason
+                    ));
+                }
+            };
+            fields.push(Field {
+                name: names::escape(&names::snake_case(&var.name)),
+                ty,
+                is_public: member.is_public(&var.modifiers),
+            });
+        }
+
+        let padded = fields
+            .iter()
+            .rev()
+            .skip(1)
+            .find(|f| f.ty.layout.has_tail_padding());
+        if let Some(field) = padded {
+            return Repr::Unsupported(format!(
+                "field `{}` has tail padding, so the layout differs from `#[repr(C)]`",
+                field.name
+            ));
+        }
+
+        Repr::Struct(fields)
+    }
+
+    fn enum_repr(&self, index: usize) -> Repr {
+        let entry = &self.entries[index];
+        if has_modifier(&entry.decl.modifiers, "indirect") {
+            return Repr::Unsupported("indirect enums are not supported".to_owned());
+        }
+
+        let elements: Vec<(&ir::EnumCase, &ir::EnumElement)> = entry
+            .members
+            .iter()
+            .filter_map(|member| match member.decl {
+                Decl::EnumCase(case) => Some(case),
+                _ => None,
+            })
+            .flat_map(|case| case.elements.iter().map(move |element| (case, element)))
+            .collect();
+        if elements
+            .iter()
+            .any(|(_, element)| element.payload.is_some())
+        {
+            return self.payload_enum_repr(index, &elements);
+        }
+
+        let cases: Vec<String> = elements
+            .iter()
+            .map(|(_, element)| element.name.clone())
+            .collect();
+
+        let tag = match cases.len() {
+            0 | 1 => None,
+            2..=0x100 => Some("u8"),
+            0x101..=0x10000 => Some("u16"),
+            _ => Some("u32"),
+        };
+        Repr::Enum { tag, cases }
+    }
+
+    fn payload_enum_repr(
+        &self,
+        index: usize,
+        elements: &[(&ir::EnumCase, &ir::EnumElement)],
+    ) -> Repr {
+        let mut cases = Vec::new();
+        for (case, element) in elements {
+            if has_modifier(&case.modifiers, "indirect") {
+                let reason = format!("indirect case `{}` is not supported", element.name);
+                return Repr::Unsupported(reason);
+            }
+
+            let mut payload = Vec::new();
+            let mut end = 0;
+            for value in element.payload.iter().flatten() {
+                let ty = match self.resolve_stored(&value.ty, index) {
+                    Ok(ty) => ty,
+                    Err(reason) => {
+                        return Repr::Unsupported(format!(
+                            "payload of case `{}`: {}",
+                            element.name, reason
+                        ));
+                    }
+                };
+                let offset = round_up(end, ty.layout.align);
+                end = offset + ty.layout.size;
+                payload.push(CaseField {
+                    label: value.label.clone(),
+                    ty,
+                    offset,
+                });
+            }
+            cases.push(Case {
+                name: element.name.clone(),
+                payload,
+            });
+        }
+        Repr::PayloadEnum(cases)
+    }
+
+    /// Resolves the type of a value stored inline in the type at `index`,
+    /// which must have a fixed layout.
+    fn resolve_stored(&self, ty: &ir::Type, index: usize) -> Result<Resolved, String> {
+        let resolved = self.resolve(ty, Some(index))?;
+        if resolved.layout.indirect {
+            return Err(format!("`{}` has a resilient layout", ty));
+        }
+        Ok(resolved)
+    }
+
+    /// Resolves `ty` as written within the type at `scope`.
+    pub fn resolve(&self, ty: &ir::Type, scope: Option<usize>) -> Result<Resolved, String> {
+        let unsupported = || Err(format!("unsupported type `{}`", ty));
+
+        match ty {
+            _ if ty.is_void() => Ok(Resolved::void()),
+            ir::Type::Named(path) => self.resolve_path(path, scope),
+            ir::Type::Optional(wrapped) | ir::Type::ImplicitlyUnwrappedOptional(wrapped) => {
+                let wrapped = self.resolve(wrapped, scope)?;
+                self.optional(wrapped).map_or_else(unsupported, Ok)
+            }
+            ir::Type::Array(element) => {
+                let element = self.resolve(element, scope)?;
+                Ok(Self::array(element))
+            }
+            _ => unsupported(),
+        }
+    }
+
+    fn resolve_path(&self, path: &TypePath, scope: Option<usize>) -> Result<Resolved, String> {
+        let unsupported = || Err(format!("unsupported type `{}`", path));
+
+        if path.is_plain(&["Self"]) {
+            return match scope {
+                Some(index) => self.resolve_entry(index, path, scope),
+                None => unsupported(),
+            };
+        }
+
+        if let Some(index) = self.find(path, scope) {
+            return self.resolve_entry(index, path, scope);
+        }
+
+        if let [component] = path.components.as_slice() {
+            if let Some(ty) = self.aliases.get(component.name.as_str()) {
+                if component.generic_args.is_empty() {
+                    return self.resolve(ty, None);
+                }
+            }
+        }
+
+        let components = match path.components.as_slice() {
+            [swift, rest @ ..] if swift.name == "Swift" && !rest.is_empty() => rest,
+            components => components,
+        };
+        match components {
+            [component] => self.resolve_stdlib(&component.name, &component.generic_args, scope),
+            _ => unsupported(),
+        }
+    }
+
+    fn resolve_entry(
+        &self,
+        index: usize,
+        path: &TypePath,
+        scope: Option<usize>,
+    ) -> Result<Resolved, String> {
+        let entry = &self.entries[index];
+        if self.excluded[index] {
+            return Err(format!("`{}` is not allowlisted", entry.swift_name));
+        }
+        let (outer, last) = path.components.split_at(path.components.len() - 1);
+        if outer.iter().any(|c| !c.generic_args.is_empty()) {
+            return Err(format!("generic type `{}` is not supported", path));
+        }
+
+        let params = &entry.decl.generics.params;
+        let generic_args = &last[0].generic_args;
+        let (mangled, rust) = if params.is_empty() && generic_args.is_empty() {
+            let mangled = mangle::Type::Nominal(entry.nominal.clone());
+            (mangled, entry.rust_name.clone())
+        } else if generic_args.len() == params.len() {
+            let mut mangled = Vec::new();
+            let mut rust = Vec::new();
+            for arg in generic_args {
+                let arg = self.resolve(arg, scope)?;
+                mangled.push(arg.mangled);
+                rust.push(arg.rust);
+            }
+            (
+                mangle::Type::BoundGeneric(entry.nominal.clone(), mangled),
+                format!("{}<{}>", entry.rust_name, rust.join(", ")),
+            )
+        } else {
+            return Err(format!(
+                "`{}` takes {} generic arguments",
+                entry.swift_name,
+                params.len()
+            ));
+        };
+
+        match &*self.repr(index) {
+            Repr::Struct(fields) => {
+                let mut layout = Layout {
+                    size: 0,
+                    align: 1,
+                    pod: true,
+                    scalars: Vec::new(),
+                    indirect: false,
+                };
+                let mut leaves = Vec::new();
+                for field in fields {
+                    let field_layout = &field.ty.layout;
+                    layout.size = round_up(layout.size, field_layout.align) + field_layout.size;
+                    layout.align = layout.align.max(field_layout.align);
+                    layout.pod &= field_layout.pod;
+                    layout.scalars.extend_from_slice(&field_layout.scalars);
+                    leaves.extend(field.ty.leaves.iter().map(|leaf| Leaf {
+                        path: format!(".{}{}", field.name, leaf.path),
+                        ..leaf.clone()
+                    }));
+                }
+                Ok(Resolved {
+                    mangled,
+                    rust,
+                    layout,
+                    leaves,
+                    nullable: false,
+                })
+            }
+            Repr::Enum { tag, .. } => {
+                let layout = match tag {
+                    Some(tag) => Layout::scalar(Scalar::Int(int_size(tag))),
+                    None => Layout {
+                        size: 0,
+                        align: 1,
+                        pod: true,
+                        scalars: Vec::new(),
+                        indirect: false,
+                    },
+                };
+                Ok(Resolved::leaf(mangled, rust, layout, false))
+            }
+            // Resilient values are passed indirectly, whatever their cases.
+            Repr::PayloadEnum(_) if !self.is_fixed_layout(entry.decl) => {
+                Ok(Resolved::leaf(mangled, rust, Layout::indirect(), false))
+            }
+            Repr::PayloadEnum(_) => Err(format!(
+                "`{}` has payloads, so it can't be passed directly",
+                path
+            )),
+            // Values of fixed-layout generic types are passed directly, in
+            // registers that depend on the arguments.
+            Repr::Resilient if self.is_fixed_layout(entry.decl) => Err(format!(
+                "generic type `{}` has a layout that's only known at runtime",
+                path
+            )),
+            Repr::Resilient => Ok(Resolved::leaf(mangled, rust, Layout::indirect(), false)),
+            Repr::Class => Ok(Resolved::leaf(mangled, rust, Layout::pointer(false), true)),
+            Repr::Protocol if path.is_plain(&["Self"]) => {
+                Err("`Self` of protocols is not supported".to_owned())
+            }
+            Repr::Protocol => Err(format!("protocol `{}` can't be used as a type", path)),
+            Repr::Unsupported(reason) => Err(format!("`{}` is not bound: {}", path, reason)),
+        }
+    }
+
+    fn resolve_stdlib(
+        &self,
+        name: &str,
+        generic_args: &[ir::Type],
+        scope: Option<usize>,
+    ) -> Result<Resolved, String> {
+        let scalar = |rust: &str, scalar: Scalar| {
+            let mangled = mangle::Type::stdlib(name);
+            Ok(Resolved::leaf(
+                mangled,
+                format!("::swift::{}", rust),
+                Layout::scalar(scalar),
+                false,
+            ))
+        };
+
+        match (name, generic_args) {
+            ("Void", []) => Ok(Resolved::void()),
+            ("Bool", []) => scalar("Bool", Scalar::Int(1)),
+            ("Int", []) | ("UInt", []) | ("Int64", []) | ("UInt64", []) => {
+                scalar(name, Scalar::Int(8))
+            }
+            ("Int32", []) | ("UInt32", []) => scalar(name, Scalar::Int(4)),
+            ("Int16", []) | ("UInt16", []) => scalar(name, Scalar::Int(2)),
+            ("Int8", []) | ("UInt8", []) => scalar(name, Scalar::Int(1)),
+            ("Double", []) | ("Float64", []) => scalar("Double", Scalar::Float(8)),
+            ("Float", []) | ("Float32", []) => scalar("Float", Scalar::Float(4)),
+            ("String", []) => {
+                let layout = Layout {
+                    size: 16,
+                    align: 8,
+                    pod: false,
+                    scalars: vec![Scalar::Int(8); 2],
+                    indirect: false,
+                };
+                let rust = "::swift::String".to_owned();
+                Ok(Resolved::leaf(
+                    mangle::Type::stdlib(name),
+                    rust,
+                    layout,
+                    false,
+                ))
+            }
+            ("AnyObject", []) => {
+                let mangled = mangle::Type::Existential {
+                    protocols: Vec::new(),
+                    class_bound: true,
+                };
+                let rust = "::swift::AnyObject".to_owned();
+                Ok(Resolved::leaf(mangled, rust, Layout::pointer(false), true))
+            }
+            ("UnsafeRawPointer", []) | ("UnsafeMutableRawPointer", []) => {
+                let rust = format!("::swift::{}", name);
+                let layout = Layout::pointer(true);
+                Ok(Resolved::leaf(
+                    mangle::Type::stdlib(name),
+                    rust,
+                    layout,
+                    true,
+                ))
+            }
+            ("UnsafePointer", [pointee]) | ("UnsafeMutablePointer", [pointee]) => {
+                let pointee = self.resolve(pointee, scope)?;
+                let nominal = mangle::Nominal::new("Swift", name, mangle::NominalKind::Struct);
+                let mangled = mangle::Type::BoundGeneric(nominal, vec![pointee.mangled]);
+                let rust = format!("::swift::{}<{}>", name, pointee.rust);
+                Ok(Resolved::leaf(mangled, rust, Layout::pointer(true), true))
+            }
+            ("Array", [element]) => {
+                let element = self.resolve(element, scope)?;
+                Ok(Self::array(element))
+            }
+            ("Optional", [wrapped]) | ("ImplicitlyUnwrappedOptional", [wrapped]) => {
+                let wrapped = self.resolve(wrapped, scope)?;
+                let display = format!("Optional<{}>", wrapped.rust);
+                self.optional(wrapped)
+                    .ok_or_else(|| format!("unsupported type `{}`", display))
+            }
+            _ => Err(format!("unsupported type `Swift.{}`", name)),
+        }
+    }
+
+    fn array(element: Resolved) -> Resolved {
+        let nominal = mangle::Nominal::new("Swift", "Array", mangle::NominalKind::Struct);
+        let mangled = mangle::Type::BoundGeneric(nominal, vec![element.mangled]);
+        let rust = format!("::swift::Array<{}>", element.rust);
+        Resolved::leaf(mangled, rust, Layout::pointer(false), true)
+    }
+
+    /// Returns `Optional<wrapped>` if it has the same layout as Rust's
+    /// `Option`.
+    fn optional(&self, wrapped: Resolved) -> Option<Resolved> {
+        if !wrapped.nullable {
+            return None;
+        }
+        let rust = format!("::std::option::Option<{}>", wrapped.rust);
+        Some(Resolved::leaf(
+            wrapped.mangled.optional(),
+            rust,
+            wrapped.layout,
+            false,
+        ))
+    }
+}
+
+/// Returns the named types in a constraint, flattening compositions.
+pub(crate) fn constraint_paths(ty: &ir::Type) -> Vec<&TypePath> {
+    match ty {
+        ir::Type::Named(path) => vec![path],
+        ir::Type::Composition(types) => types.iter().flat_map(constraint_paths).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn round_up(value: usize, align: usize) -> usize {
+    value.div_ceil(align) * align
+}
+
+fn int_size(rust: &str) -> usize {
+    match rust {
+        "u8" => 1,
+        "u16" => 2,
+        _ => 4,
+    }
+}
@@ -0,0 +1,65 @@
+//! Indentation-aware source output.
+
+/// Writes lines of source code with brace-delimited blocks.
+#[derive(Default)]
+pub(crate) struct Writer {
+    buf: String,
+    indent: usize,
+}
+
+impl Writer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn finish(self) -> String {
+        self.buf
+    }
+
+    /// Writes `line` at the current indentation.
+    pub fn line(&mut self, line: impl AsRef<str>) {
+        let line = line.as_ref();
+        if !line.is_empty() {
+            for _ in 0..self.indent {
+                self.buf.push_str("    ");
+            }
+            self.buf.push_str(line);
+        }
+        self.buf.push('\n');
+    }
+
+    /// Writes an empty line unless at the start of a block.
+    pub fn blank(&mut self) {
+        if !self.buf.is_empty() && !self.buf.ends_with("{\n") && !self.buf.ends_with("\n\n") {
+            self.buf.push('\n');
+        }
+    }
+
+    /// Writes `header {` and indents subsequent lines.
+    pub fn open(&mut self, header: impl AsRef<str>) {
+        self.line(format!("{} {{", header.as_ref()));
+        self.indent += 1;
+    }
+
+    /// Writes the `}` matching the last [`open`](Self::open).
+    pub fn close(&mut self) {
+        self.close_with("");
+    }
+
+    /// Writes the `}` matching the last [`open`](Self::open), followed by
+    /// `suffix`.
+    pub fn close_with(&mut self, suffix: impl AsRef<str>) {
+        self.indent -= 1;
+        self.line(format!("}}{}", suffix.as_ref()));
+    }
+
+    /// Writes a `///` doc comment line.
+    pub fn doc(&mut self, doc: impl AsRef<str>) {
+        let doc = doc.as_ref();
+        if doc.is_empty() {
+            self.line("///");
+        } else {
+            self.line(format!("/// {}", doc));
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//! Generation of the C header that Swift imports.
+
+use super::items::{Enum, Function, IntKind, Item, Primitive, Struct, Ty};
+use crate::codegen::Writer;
+use std::collections::HashMap;
+
+/// C keywords that can't be used as names.
+const KEYWORDS: &[&str] = &[
+    "auto", "bool", "break", "case", "char", "const", "continue", "default", "do", "double",
+    "else", "enum", "extern", "float", "for", "goto", "if", "inline", "int", "long", "register",
+    "restrict", "return", "short", "signed", "sizeof", "static", "struct", "switch", "typedef",
+    "union", "unsigned", "void", "volatile", "while",
+];
+
+/// The C type that `swift::String` is passed as.
+pub(crate) const STRING: &str = "SwiftBindgenString";
+
+/// The C type that `swift::Array<T>` is passed as.
+pub(crate) const ARRAY: &str = "SwiftBindgenArray";
+
+/// Generates the header of the C module `module_name`.
+pub(crate) fn generate(items: &[Item], module_name: &str) -> String {
+    let mut w = Writer::new();
+    let guard = format!("{}_H", module_name.to_uppercase());
+
+    w.line("// Generated by swift-bindgen. Do not edit.");
+    w.blank();
+    w.line(format!("#ifndef {}", guard));
+    w.line(format!("#define {}", guard));
+    w.blank();
+    w.line("#include <stdbool.h>");
+    w.line("#include <stdint.h>");
+
+    let functions: Vec<&Function> = items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Function(function) => Some(function),
+            _ => None,
+        })
+        .collect();
+    let uses = |f: fn(&Ty) -> bool| {
+        functions.iter().any(|function| {
+            function
+                .params
+                .iter()
+                .map(|(_, ty)| ty)
+                .chain(Some(&function.ret))
+                .any(|ty| contains(ty, f))
+        })
+    };
+
+    if uses(|ty| *ty == Ty::String) {
+        w.blank();
+        w.line("/// The representation of a Swift `String`, which owns its contents.");
+        w.open(format!("typedef struct {}", STRING));
+        w.line("uint64_t raw_bits[2];");
+        w.close_with(format!(" {};", STRING));
+    }
+    if uses(|ty| matches!(ty, Ty::Array(_))) {
+        w.blank();
+        w.line("/// The representation of a Swift `Array`, which owns its buffer.");
+        w.open(format!("typedef struct {}", ARRAY));
+        w.line("void *buffer;");
+        w.close_with(format!(" {};", ARRAY));
+    }
+
+    for item in items {
+        match item {
+            Item::Enum(item) => enumeration(&mut w, item),
+            Item::Struct(item) => {
+                w.blank();
+                w.line(format!("typedef struct {0} {0};", item.name));
+            }
+            _ => {}
+        }
+    }
+    for item in struct_order(items) {
+        structure(&mut w, item);
+    }
+    for function in functions {
+        declare_function(&mut w, function);
+    }
+
+    w.blank();
+    w.line(format!("#endif // {}", guard));
+    w.finish()
+}
+
+/// Returns `true` if `ty` or a type it contains satisfies `f`.
+fn contains(ty: &Ty, f: fn(&Ty) -> bool) -> bool {
+    f(ty)
+        || match ty {
+            Ty::Array(element) => contains(element, f),
+            Ty::Pointer {
+                pointee: Some(pointee),
+                ..
+            } => contains(pointee, f),
+            _ => false,
+        }
+}
+
+/// Returns the structs of `items`, ordered so that each is defined after the
+/// types of its fields.
+fn struct_order(items: &[Item]) -> Vec<&Struct> {
+    let structs: HashMap<&str, &Struct> = items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Struct(item) => Some((item.name.as_str(), item)),
+            _ => None,
+        })
+        .collect();
+
+    fn visit<'a>(
+        item: &'a Struct,
+        structs: &HashMap<&str, &'a Struct>,
+        order: &mut Vec<&'a Struct>,
+    ) {
+        if order.iter().any(|other| other.name == item.name) {
+            return;
+        }
+        for (_, ty) in &item.fields {
+            // Pointers only need the forward declaration.
+            if let Ty::Named(name) = ty {
+                if let Some(field) = structs.get(name.as_str()) {
+                    visit(field, structs, order);
+                }
+            }
+        }
+        order.push(item);
+    }
+
+    let mut order = Vec::new();
+    for item in items {
+        if let Item::Struct(item) = item {
+            visit(item, &structs, &mut order);
+        }
+    }
+    order
+}
+
+fn docs(w: &mut Writer, docs: &[String]) {
+    for line in docs {
+        w.doc(line);
+    }
+}
+
+fn enumeration(w: &mut Writer, item: &Enum) {
+    w.blank();
+    docs(w, &item.docs);
+
+    // Closed enums are imported as Swift enums, with the common prefix of the
+    // constants removed from the case names.
+    let repr = match item.repr {
+        Some(repr) => primitive(repr),
+        None => "int",
+    };
+    w.open(format!(
+        "typedef enum __attribute__((enum_extensibility(closed))) {} : {}",
+        item.name, repr
+    ));
+    for (variant, discriminant) in &item.variants {
+        match discriminant {
+            Some(value) => w.line(format!("{}{} = {},", item.name, variant, value)),
+            None => w.line(format!("{}{},", item.name, variant)),
+        }
+    }
+    w.close_with(format!(" {};", item.name));
+}
+
+fn structure(w: &mut Writer, item: &Struct) {
+    w.blank();
+    docs(w, &item.docs);
+    w.open(format!("struct {}", item.name));
+    for (name, ty) in &item.fields {
+        w.line(format!("{};", declaration(ty, name)));
+    }
+    w.close_with(";");
+}
+
+fn declare_function(w: &mut Writer, function: &Function) {
+    let params: Vec<String> = function
+        .params
+        .iter()
+        .map(|(name, ty)| declaration(ty, name.as_deref().unwrap_or("")))
+        .collect();
+    let params = match params.is_empty() {
+        true => "void".to_owned(),
+        false => params.join(", "),
+    };
+
+    // Swift calls the functions through the wrappers, so they're hidden.
+    w.blank();
+    docs(w, &function.docs);
+    w.line(format!(
+        "{}({}) __attribute__((swift_private));",
+        declaration(&function.ret, &function.symbol),
+        params
+    ));
+}
+
+/// Returns the C declaration of `name` with type `ty`.
+fn declaration(ty: &Ty, name: &str) -> String {
+    let ty = c_type(ty);
+    let name = match KEYWORDS.contains(&name) {
+        true => format!("{}_", name),
+        false => name.to_owned(),
+    };
+    if name.is_empty() {
+        ty
+    } else if ty.ends_with('*') {
+        format!("{}{}", ty, name)
+    } else {
+        format!("{} {}", ty, name)
+    }
+}
+
+/// Returns the C spelling of `ty`.
+pub(crate) fn c_type(ty: &Ty) -> String {
+    match ty {
+        Ty::Void => "void".to_owned(),
+        Ty::Primitive(ty) => primitive(*ty).to_owned(),
+        Ty::String => STRING.to_owned(),
+        Ty::Array(_) => ARRAY.to_owned(),
+        Ty::Named(name) => name.clone(),
+        Ty::Pointer { mutable, pointee } => {
+            let pointee = pointee.as_deref().map_or_else(|| "void".to_owned(), c_type);
+            match (mutable, pointee.ends_with('*')) {
+                (true, _) => format!("{} *", pointee),
+                (false, false) => format!("const {} *", pointee),
+                (false, true) => format!("{}const *", pointee),
+            }
+        }
+    }
+}
+
+fn primitive(ty: Primitive) -> &'static str {
+    match ty {
+        Primitive::Bool => "bool",
+        Primitive::Float => "float",
+        Primitive::Double => "double",
+        Primitive::Int(IntKind { signed, bits }) => match (signed, bits) {
+            (true, Some(8)) => "int8_t",
+            (true, Some(16)) => "int16_t",
+            (true, Some(32)) => "int32_t",
+            (true, Some(64)) => "int64_t",
+            (true, _) => "intptr_t",
+            (false, Some(8)) => "uint8_t",
+            (false, Some(16)) => "uint16_t",
+            (false, Some(32)) => "uint32_t",
+            (false, Some(64)) => "uint64_t",
+            (false, _) => "uintptr_t",
+        },
+    }
+}
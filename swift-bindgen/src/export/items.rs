@@ -0,0 +1,527 @@
+//! Collection of exported items from Rust source.
+
+use super::Error;
+use syn::{
+    spanned::Spanned, Attribute, Expr, Fields, FnArg, Lit, Pat, ReturnType, UnOp, Visibility,
+};
+
+/// An item that can be called or used from Swift.
+pub(crate) enum Item {
+    Function(Function),
+    Struct(Struct),
+    Enum(Enum),
+
+    /// An item marked for export that can't be represented.
+    Skipped {
+        name: String,
+        reason: String,
+    },
+}
+
+/// A `#[no_mangle] pub extern "C"` or `#[swift::export]` function.
+pub(crate) struct Function {
+    pub docs: Vec<String>,
+    pub name: String,
+
+    /// The name given with `#[swift::export(name = "...")]`.
+    pub swift_name: Option<String>,
+
+    /// The symbol that the function is exported as.
+    pub symbol: String,
+    pub params: Vec<(Option<String>, Ty)>,
+    pub ret: Ty,
+}
+
+/// A `#[repr(C)]` struct with named fields.
+pub(crate) struct Struct {
+    pub docs: Vec<String>,
+    pub name: String,
+    pub fields: Vec<(String, Ty)>,
+}
+
+/// A `#[repr(C)]` or `#[repr(<integer>)]` enum without fields.
+pub(crate) struct Enum {
+    pub docs: Vec<String>,
+    pub name: String,
+
+    /// The integer type that the enum is represented as, or `None` for the
+    /// C `int`-sized `#[repr(C)]`.
+    pub repr: Option<Primitive>,
+    pub variants: Vec<(String, Option<i128>)>,
+}
+
+/// A Rust type that has a Swift counterpart.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Ty {
+    Void,
+    Primitive(Primitive),
+
+    /// `swift::String`.
+    String,
+
+    /// `swift::Array<T>`.
+    Array(Box<Ty>),
+
+    /// An exported struct or enum.
+    Named(String),
+
+    /// `*const T` or `*mut T`, where `None` is `c_void`.
+    Pointer {
+        mutable: bool,
+        pointee: Option<Box<Ty>>,
+    },
+}
+
+/// A primitive type, or its `swift::primitive` alias.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Primitive {
+    Bool,
+    Int(IntKind),
+    Float,
+    Double,
+}
+
+/// The width and signedness of an integer type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct IntKind {
+    pub signed: bool,
+
+    /// The width in bits, or `None` for pointer-sized integers.
+    pub bits: Option<u8>,
+}
+
+impl Primitive {
+    /// Returns the primitive named `name` in Rust or in `swift`.
+    fn from_name(name: &str) -> Option<Self> {
+        let int = |signed, bits| Some(Self::Int(IntKind { signed, bits }));
+        match name {
+            "bool" | "Bool" => Some(Self::Bool),
+            "i8" | "Int8" => int(true, Some(8)),
+            "i16" | "Int16" => int(true, Some(16)),
+            "i32" | "Int32" => int(true, Some(32)),
+            "i64" | "Int64" => int(true, Some(64)),
+            "isize" | "Int" => int(true, None),
+            "u8" | "UInt8" => int(false, Some(8)),
+            "u16" | "UInt16" => int(false, Some(16)),
+            "u32" | "UInt32" => int(false, Some(32)),
+            "u64" | "UInt64" => int(false, Some(64)),
+            "usize" | "UInt" => int(false, None),
+            "f32" | "Float" | "Float32" => Some(Self::Float),
+            "f64" | "Double" | "Float64" => Some(Self::Double),
+            _ => None,
+        }
+    }
+}
+
+impl Ty {
+    /// Returns `true` if the type can be declared in C as is.
+    pub fn is_c_representable(&self) -> bool {
+        match self {
+            Self::String | Self::Array(_) => false,
+            Self::Pointer { pointee, .. } => pointee.as_deref().is_none_or(Ty::is_c_representable),
+            Self::Void | Self::Primitive(_) | Self::Named(_) => true,
+        }
+    }
+
+    /// Calls `f` with the names of the exported types that this refers to.
+    pub fn visit_names(&self, f: &mut impl FnMut(&str)) {
+        match self {
+            Self::Named(name) => f(name),
+            Self::Array(element) => element.visit_names(f),
+            Self::Pointer {
+                pointee: Some(pointee),
+                ..
+            } => pointee.visit_names(f),
+            _ => {}
+        }
+    }
+}
+
+/// Collects the exported items of the Rust source file `source`.
+pub(crate) fn collect(source: &str) -> Result<Vec<Item>, Error> {
+    let file = syn::parse_file(source).map_err(|error| {
+        let start = error.span().start();
+        Error::new(start.line, start.column + 1, error.to_string())
+    })?;
+
+    let mut items = Vec::new();
+    collect_items(&file.items, &mut items);
+    Ok(items)
+}
+
+fn collect_items(syn_items: &[syn::Item], items: &mut Vec<Item>) {
+    for item in syn_items {
+        match item {
+            syn::Item::Fn(item) => {
+                let export = match macro_export(item) {
+                    Some(swift_name) => Some((macro_symbol(&item.sig.ident), swift_name)),
+                    None => export_symbol(item).map(|symbol| (symbol, None)),
+                };
+                if let Some((symbol, swift_name)) = export {
+                    items.push(function(item, symbol, swift_name).unwrap_or_else(|reason| {
+                        Item::Skipped {
+                            name: item.sig.ident.to_string(),
+                            reason,
+                        }
+                    }));
+                }
+            }
+            syn::Item::Struct(item) if is_public(&item.vis) && repr(&item.attrs).is_some() => {
+                items.push(structure(item).unwrap_or_else(|reason| Item::Skipped {
+                    name: item.ident.to_string(),
+                    reason,
+                }));
+            }
+            syn::Item::Enum(item) if is_public(&item.vis) => {
+                if let Some(repr) = repr(&item.attrs) {
+                    items.push(
+                        enumeration(item, repr).unwrap_or_else(|reason| Item::Skipped {
+                            name: item.ident.to_string(),
+                            reason,
+                        }),
+                    );
+                }
+            }
+            // Symbols are global, so items in inline modules are exported too.
+            syn::Item::Mod(syn::ItemMod {
+                content: Some((_, content)),
+                ..
+            }) => collect_items(content, items),
+            _ => {}
+        }
+    }
+}
+
+/// Returns the name of `ident` without the `r#` of raw identifiers.
+fn ident(ident: &syn::Ident) -> String {
+    let ident = ident.to_string();
+    match ident.strip_prefix("r#") {
+        Some(ident) => ident.to_owned(),
+        None => ident,
+    }
+}
+
+fn is_public(vis: &Visibility) -> bool {
+    matches!(vis, Visibility::Public(_))
+}
+
+/// Returns the symbol of a function exported with the C ABI.
+fn export_symbol(item: &syn::ItemFn) -> Option<String> {
+    let abi = item.sig.abi.as_ref()?;
+    if abi.name.as_ref().is_some_and(|name| name.value() != "C") || !is_public(&item.vis) {
+        return None;
+    }
+
+    // Attributes may be wrapped in `unsafe(...)` since Rust 2024.
+    let mut symbol = None;
+    for attr in &item.attrs {
+        let meta = match &attr.meta {
+            syn::Meta::List(list) if list.path.is_ident("unsafe") => {
+                match list.parse_args::<syn::Meta>() {
+                    Ok(meta) => meta,
+                    Err(_) => continue,
+                }
+            }
+            meta => meta.clone(),
+        };
+        if meta.path().is_ident("no_mangle") {
+            symbol = Some(item.sig.ident.to_string());
+        } else if meta.path().is_ident("export_name") {
+            if let syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    Expr::Lit(syn::ExprLit {
+                        lit: Lit::Str(name),
+                        ..
+                    }),
+                ..
+            }) = meta
+            {
+                return Some(name.value());
+            }
+        }
+    }
+    symbol
+}
+
+/// Returns the Swift name given to a `#[swift::export]` function, or
+/// `Some(None)` if it has none.
+fn macro_export(item: &syn::ItemFn) -> Option<Option<String>> {
+    let attr = item.attrs.iter().find(|attr| {
+        let path = attr.path();
+        path.is_ident("export")
+            || (path.segments.len() == 2
+                && path.segments[0].ident == "swift"
+                && path.segments[1].ident == "export")
+    })?;
+
+    // Invalid options are reported by the macro itself.
+    let mut name = None;
+    if let syn::Meta::List(_) = attr.meta {
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+            }
+            Ok(())
+        });
+    }
+    Some(name)
+}
+
+/// Returns the symbol of the shim generated by `#[swift::export]`, which must
+/// match the one in `swift-macros`.
+fn macro_symbol(name: &syn::Ident) -> String {
+    format!("__swift_export_{}", ident(name))
+}
+
+/// Returns the `#[repr]` of an item: `Some(None)` for `repr(C)` and
+/// `Some(Some(int))` for an integer repr.
+fn repr(attrs: &[Attribute]) -> Option<Option<Primitive>> {
+    let mut result = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            let name = meta.path.get_ident().map(|ident| ident.to_string());
+            match name.as_deref() {
+                Some("C") => {
+                    result.get_or_insert(None);
+                }
+                Some(name) => {
+                    if let Some(int @ Primitive::Int(_)) = Primitive::from_name(name) {
+                        result = Some(Some(int));
+                    }
+                }
+                None => {}
+            }
+            Ok(())
+        });
+    }
+    result
+}
+
+/// Returns the contents of the `///` comments in `attrs`.
+fn docs(attrs: &[Attribute]) -> Vec<String> {
+    let mut docs: Vec<String> = attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(meta) if meta.path.is_ident("doc") => match &meta.value {
+                Expr::Lit(syn::ExprLit {
+                    lit: Lit::Str(doc), ..
+                }) => Some(doc.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .flat_map(|doc| doc.split('\n').map(str::to_owned).collect::<Vec<_>>())
+        .collect();
+
+    // Remove the space after `///` shared by all lines.
+    if docs
+        .iter()
+        .all(|line| line.is_empty() || line.starts_with(' '))
+    {
+        for line in &mut docs {
+            if !line.is_empty() {
+                line.remove(0);
+            }
+        }
+    }
+    docs
+}
+
+fn function(
+    item: &syn::ItemFn,
+    symbol: String,
+    swift_name: Option<String>,
+) -> Result<Item, String> {
+    let sig = &item.sig;
+    if !sig.generics.params.is_empty() {
+        return Err("generic functions are not supported".to_owned());
+    }
+    if sig.asyncness.is_some() {
+        return Err("async functions are not supported".to_owned());
+    }
+    if sig.variadic.is_some() {
+        return Err("variadic functions are not supported".to_owned());
+    }
+
+    let mut params = Vec::new();
+    for input in &sig.inputs {
+        let input = match input {
+            FnArg::Typed(input) => input,
+            FnArg::Receiver(_) => return Err("methods are not supported".to_owned()),
+        };
+        let name = match &*input.pat {
+            Pat::Ident(pat) => Some(ident(&pat.ident)),
+            Pat::Wild(_) => None,
+            _ => return Err("parameter patterns are not supported".to_owned()),
+        };
+        params.push((name, ty(&input.ty)?));
+    }
+
+    let ret = match &sig.output {
+        ReturnType::Default => Ty::Void,
+        ReturnType::Type(_, ret) => ty(ret)?,
+    };
+
+    Ok(Item::Function(Function {
+        docs: docs(&item.attrs),
+        name: sig.ident.to_string(),
+        swift_name,
+        symbol,
+        params,
+        ret,
+    }))
+}
+
+fn structure(item: &syn::ItemStruct) -> Result<Item, String> {
+    if !item.generics.params.is_empty() {
+        return Err("generic structs are not supported".to_owned());
+    }
+    let fields = match &item.fields {
+        Fields::Named(fields) => &fields.named,
+        _ => return Err("only structs with named fields are supported".to_owned()),
+    };
+
+    let mut result = Vec::new();
+    for field in fields {
+        let name = ident(field.ident.as_ref().expect("named field"));
+        if !is_public(&field.vis) {
+            return Err(format!("field `{}` is private", name));
+        }
+        let ty = ty(&field.ty)?;
+        if !ty.is_c_representable() || ty == Ty::Void {
+            return Err(format!("field `{}` has no C representation", name));
+        }
+        result.push((name, ty));
+    }
+
+    Ok(Item::Struct(Struct {
+        docs: docs(&item.attrs),
+        name: item.ident.to_string(),
+        fields: result,
+    }))
+}
+
+fn enumeration(item: &syn::ItemEnum, repr: Option<Primitive>) -> Result<Item, String> {
+    if !item.generics.params.is_empty() {
+        return Err("generic enums are not supported".to_owned());
+    }
+
+    let mut variants = Vec::new();
+    for variant in &item.variants {
+        if !variant.fields.is_empty() {
+            return Err("enums with fields are not supported".to_owned());
+        }
+        let discriminant = match &variant.discriminant {
+            Some((_, expr)) => Some(int_literal(expr).ok_or_else(|| {
+                format!("the discriminant of `{}` is not a literal", variant.ident)
+            })?),
+            None => None,
+        };
+        variants.push((variant.ident.to_string(), discriminant));
+    }
+
+    Ok(Item::Enum(Enum {
+        docs: docs(&item.attrs),
+        name: item.ident.to_string(),
+        repr,
+        variants,
+    }))
+}
+
+/// Returns the value of an integer literal, which may be negated.
+fn int_literal(expr: &Expr) -> Option<i128> {
+    match expr {
+        Expr::Lit(syn::ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse().ok(),
+        Expr::Unary(syn::ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => int_literal(expr).map(|value| -value),
+        Expr::Group(group) => int_literal(&group.expr),
+        Expr::Paren(paren) => int_literal(&paren.expr),
+        _ => None,
+    }
+}
+
+/// Converts a Rust type to its Swift counterpart.
+fn ty(ty: &syn::Type) -> Result<Ty, String> {
+    let unsupported = || {
+        let span = ty.span();
+        match span.source_text() {
+            Some(text) => format!("`{}` is not supported", text),
+            None => "type is not supported".to_owned(),
+        }
+    };
+
+    match ty {
+        syn::Type::Tuple(tuple) if tuple.elems.is_empty() => Ok(Ty::Void),
+        syn::Type::Paren(paren) => self::ty(&paren.elem),
+        syn::Type::Group(group) => self::ty(&group.elem),
+        syn::Type::Ptr(ptr) => {
+            let pointee = match &*ptr.elem {
+                syn::Type::Path(path) if last_ident(path).as_deref() == Some("c_void") => None,
+                elem => Some(Box::new(self::ty(elem)?)),
+            };
+            if pointee
+                .as_deref()
+                .is_some_and(|pointee| !pointee.is_c_representable() || *pointee == Ty::Void)
+            {
+                return Err(unsupported());
+            }
+            Ok(Ty::Pointer {
+                mutable: ptr.mutability.is_some(),
+                pointee,
+            })
+        }
+        syn::Type::Path(path) if path.qself.is_none() => {
+            let segments: Vec<_> = path.path.segments.iter().collect();
+            let last = segments.last().ok_or_else(unsupported)?;
+            let name = last.ident.to_string();
+            let in_swift = segments.len() >= 2 && segments[segments.len() - 2].ident == "swift";
+
+            let args: Vec<&syn::Type> = match &last.arguments {
+                syn::PathArguments::None => Vec::new(),
+                syn::PathArguments::AngleBracketed(args) => args
+                    .args
+                    .iter()
+                    .map(|arg| match arg {
+                        syn::GenericArgument::Type(ty) => Ok(ty),
+                        _ => Err(unsupported()),
+                    })
+                    .collect::<Result<_, _>>()?,
+                syn::PathArguments::Parenthesized(_) => return Err(unsupported()),
+            };
+
+            match (name.as_str(), args.as_slice()) {
+                // A bare `String` is `std::string::String` unless imported.
+                ("String", []) if in_swift => Ok(Ty::String),
+                ("String", []) => Err("`String` is not FFI-safe; use `swift::String`".to_owned()),
+                ("Array", [element]) => {
+                    let element = self::ty(element)?;
+                    match element {
+                        Ty::Primitive(_) | Ty::String | Ty::Array(_) => {
+                            Ok(Ty::Array(Box::new(element)))
+                        }
+                        _ => Err(unsupported()),
+                    }
+                }
+                (name, []) => match Primitive::from_name(name) {
+                    Some(primitive) => Ok(Ty::Primitive(primitive)),
+                    None if segments.len() == 1 => Ok(Ty::Named(name.to_owned())),
+                    None => Err(unsupported()),
+                },
+                _ => Err(unsupported()),
+            }
+        }
+        _ => Err(unsupported()),
+    }
+}
+
+fn last_ident(path: &syn::TypePath) -> Option<String> {
+    path.path
+        .segments
+        .last()
+        .map(|segment| segment.ident.to_string())
+}
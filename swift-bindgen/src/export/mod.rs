@@ -0,0 +1,215 @@
+//! Generation of Swift source for Rust items.
+//!
+//! This is the Rust-to-Swift direction of bridging. Public items of a Rust
+//! source file are exported to Swift when they have a C representation:
+//!
+//! - `#[no_mangle] pub extern "C"` functions, which become Swift functions.
+//! - Functions marked `#[swift::export]`, which are called through the shim
+//!   that the macro generates. These need not be public or `extern "C"`.
+//! - `#[repr(C)]` structs with named fields.
+//! - `#[repr(C)]` and `#[repr(<integer>)]` enums without fields, which become
+//!   closed Swift enums.
+//!
+//! The items are declared in a C header that Swift imports through a module
+//! map, and a Swift file wraps the functions with idiomatic names and types.
+//! The primitive aliases of `swift::primitive` map to their Swift types, and
+//! `swift::String` and `swift::Array<T>` map to `String` and `[T]`. Those are
+//! passed by handing over ownership of their representation, so their
+//! contents are never copied.
+
+use std::{collections::HashSet, error, fmt, fs, io, path::Path};
+
+mod header;
+mod items;
+mod swift;
+
+mod tests;
+
+use items::{Item, Ty};
+
+/// Swift source and a C module generated for the exported items of Rust
+/// source.
+///
+/// # Examples
+///
+/// ```
+/// let source = r#"
+///     /// Returns the number of characters in `string`.
+///     #[no_mangle]
+///     pub extern "C" fn char_count(string: swift::String) -> swift::Int {
+///         string.count()
+///     }
+/// "#;
+///
+/// let exports = swift_bindgen::export::Exports::from_source(source, "Text").unwrap();
+/// assert!(exports.swift().contains("public func charCount(string: String) -> Int {"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Exports {
+    module_name: String,
+    swift: String,
+    header: String,
+    module_map: String,
+}
+
+impl Exports {
+    /// Generates Swift declarations for the module `module_name` from the
+    /// contents of a Rust source file.
+    pub fn from_source(source: &str, module_name: &str) -> Result<Self, Error> {
+        let items = check_names(items::collect(source)?);
+        let ffi_module_name = format!("{}FFI", module_name);
+        let header_name = format!("{}.h", ffi_module_name);
+
+        Ok(Self {
+            module_name: module_name.to_owned(),
+            swift: swift::generate(&items, &ffi_module_name),
+            header: header::generate(&items, &ffi_module_name),
+            module_map: format!(
+                "module {} {{\n    header \"{}\"\n    export *\n}}\n",
+                ffi_module_name, header_name
+            ),
+        })
+    }
+
+    /// Returns the name of the Swift module.
+    #[inline]
+    pub fn module_name(&self) -> &str {
+        &self.module_name
+    }
+
+    /// Returns the name of the C module that declares the exported items,
+    /// which is the Swift module name suffixed with `FFI`.
+    #[inline]
+    pub fn ffi_module_name(&self) -> String {
+        format!("{}FFI", self.module_name)
+    }
+
+    /// Returns the Swift source that wraps the exported functions.
+    #[inline]
+    pub fn swift(&self) -> &str {
+        &self.swift
+    }
+
+    /// Returns the C header that declares the exported items.
+    #[inline]
+    pub fn header(&self) -> &str {
+        &self.header
+    }
+
+    /// Returns the `module.modulemap` of the C module.
+    #[inline]
+    pub fn module_map(&self) -> &str {
+        &self.module_map
+    }
+
+    /// Writes `<Module>.swift`, `<Module>FFI.h`, and `module.modulemap` to the
+    /// directory `dir`.
+    ///
+    /// The directory must be passed to `swiftc` with `-I` so that the C module
+    /// can be imported.
+    pub fn write_to_dir(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join(format!("{}.swift", self.module_name)), &self.swift)?;
+        fs::write(
+            dir.join(format!("{}.h", self.ffi_module_name())),
+            &self.header,
+        )?;
+        fs::write(dir.join("module.modulemap"), &self.module_map)
+    }
+}
+
+/// Skips items that refer to types that are not exported.
+fn check_names(mut items: Vec<Item>) -> Vec<Item> {
+    // Skipping a struct may invalidate the items that refer to it.
+    loop {
+        let exported: HashSet<String> = items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Struct(item) => Some(item.name.clone()),
+                Item::Enum(item) => Some(item.name.clone()),
+                _ => None,
+            })
+            .collect();
+
+        let mut changed = false;
+        for item in &mut items {
+            let (name, types): (&str, Vec<&Ty>) = match item {
+                Item::Function(item) => (
+                    &item.name,
+                    item.params
+                        .iter()
+                        .map(|(_, ty)| ty)
+                        .chain(Some(&item.ret))
+                        .collect(),
+                ),
+                Item::Struct(item) => (&item.name, item.fields.iter().map(|(_, ty)| ty).collect()),
+                _ => continue,
+            };
+
+            let mut missing = None;
+            for ty in types {
+                ty.visit_names(&mut |name| {
+                    if !exported.contains(name) {
+                        missing.get_or_insert_with(|| name.to_owned());
+                    }
+                });
+            }
+            if let Some(missing) = missing {
+                *item = Item::Skipped {
+                    name: name.to_owned(),
+                    reason: format!("`{}` is not exported", missing),
+                };
+                changed = true;
+            }
+        }
+
+        if !changed {
+            return items;
+        }
+    }
+}
+
+/// An error encountered while parsing Rust source.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    line: usize,
+    column: usize,
+    message: String,
+}
+
+impl error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error {
+    pub(crate) fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// The 1-based line number where the error occurred.
+    #[inline]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The 1-based column where the error occurred.
+    #[inline]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// A description of the error.
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
@@ -0,0 +1,264 @@
+//! Generation of the Swift wrappers of exported functions.
+
+use super::{
+    header,
+    items::{Function, IntKind, Item, Primitive, Ty},
+};
+use crate::codegen::Writer;
+
+/// Swift keywords that must be escaped with backticks.
+const KEYWORDS: &[&str] = &[
+    "Any",
+    "Protocol",
+    "Self",
+    "Type",
+    "as",
+    "associatedtype",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "continue",
+    "default",
+    "defer",
+    "deinit",
+    "do",
+    "else",
+    "enum",
+    "extension",
+    "fallthrough",
+    "false",
+    "fileprivate",
+    "for",
+    "func",
+    "guard",
+    "if",
+    "import",
+    "in",
+    "init",
+    "inout",
+    "internal",
+    "is",
+    "let",
+    "nil",
+    "open",
+    "operator",
+    "private",
+    "protocol",
+    "public",
+    "repeat",
+    "rethrows",
+    "return",
+    "self",
+    "static",
+    "struct",
+    "subscript",
+    "super",
+    "switch",
+    "throw",
+    "throws",
+    "true",
+    "try",
+    "typealias",
+    "var",
+    "where",
+    "while",
+];
+
+/// The helper that passes Swift values to Rust.
+const GIVE: &str = "\
+/// Moves a retained copy of `value` into its C representation, passing
+/// ownership to the callee.
+@inlinable
+internal func __swiftBindgenGive<T, Raw>(_ value: T, as _: Raw.Type) -> Raw {
+    let pointer = UnsafeMutablePointer<T>.allocate(capacity: 1)
+    defer { pointer.deallocate() }
+    pointer.initialize(to: value)
+    return UnsafeMutableRawPointer(pointer).bindMemory(to: Raw.self, capacity: 1).move()
+}
+";
+
+/// The helper that takes Swift values returned by Rust.
+const TAKE: &str = "\
+/// Moves a value out of its C representation, taking ownership from the
+/// callee.
+@inlinable
+internal func __swiftBindgenTake<Raw, T>(_ raw: Raw, as _: T.Type) -> T {
+    let pointer = UnsafeMutablePointer<Raw>.allocate(capacity: 1)
+    defer { pointer.deallocate() }
+    pointer.initialize(to: raw)
+    return UnsafeMutableRawPointer(pointer).bindMemory(to: T.self, capacity: 1).move()
+}
+";
+
+/// Generates Swift wrappers for the functions in `items`, which are declared
+/// in the C module `ffi_module_name`.
+pub(crate) fn generate(items: &[Item], ffi_module_name: &str) -> String {
+    let mut w = Writer::new();
+    w.line("// Generated by swift-bindgen. Do not edit.");
+    w.blank();
+    w.line(format!("@_exported import {}", ffi_module_name));
+
+    let mut needs_give = false;
+    let mut needs_take = false;
+    for item in items {
+        match item {
+            Item::Function(function) => {
+                needs_give |= function.params.iter().any(|(_, ty)| is_owned(ty));
+                needs_take |= is_owned(&function.ret);
+                wrapper(&mut w, function);
+            }
+            Item::Skipped { name, reason } => {
+                w.blank();
+                w.line(format!("// Skipped `{}`: {}", name, reason));
+            }
+            _ => {}
+        }
+    }
+
+    for (needed, helper) in [(needs_give, GIVE), (needs_take, TAKE)] {
+        if needed {
+            w.blank();
+            for line in helper.lines() {
+                w.line(line);
+            }
+        }
+    }
+    w.finish()
+}
+
+fn wrapper(w: &mut Writer, function: &Function) {
+    let mut params = Vec::new();
+    let mut args = Vec::new();
+    for (i, (name, ty)) in function.params.iter().enumerate() {
+        let (label, name) = match name {
+            Some(name) => {
+                let name = escape(&lower_camel_case(name));
+                (name.clone(), name)
+            }
+            None => ("_".to_owned(), format!("arg{}", i)),
+        };
+        if label == name {
+            params.push(format!("{}: {}", name, swift_type(ty)));
+        } else {
+            params.push(format!("{} {}: {}", label, name, swift_type(ty)));
+        }
+        args.push(match is_owned(ty) {
+            true => format!(
+                "__swiftBindgenGive({}, as: {}.self)",
+                name,
+                header::c_type(ty)
+            ),
+            false => name,
+        });
+    }
+
+    let call = format!("__{}({})", function.symbol, args.join(", "));
+    let ret = match function.ret {
+        Ty::Void => String::new(),
+        ref ret => format!(" -> {}", swift_type(ret)),
+    };
+
+    w.blank();
+    for line in &function.docs {
+        w.doc(line);
+    }
+    w.line("@inlinable");
+    w.open(format!(
+        "public func {}({}){}",
+        escape(
+            &function
+                .swift_name
+                .clone()
+                .unwrap_or_else(|| lower_camel_case(&function.name))
+        ),
+        params.join(", "),
+        ret
+    ));
+    match &function.ret {
+        Ty::Void => w.line(call),
+        ret if is_owned(ret) => w.line(format!(
+            "return __swiftBindgenTake({}, as: {}.self)",
+            call,
+            swift_type(ret)
+        )),
+        _ => w.line(format!("return {}", call)),
+    }
+    w.close();
+}
+
+/// Returns `true` if values of `ty` own a reference that is passed along with
+/// them.
+fn is_owned(ty: &Ty) -> bool {
+    matches!(ty, Ty::String | Ty::Array(_))
+}
+
+/// Returns the Swift spelling of `ty`.
+fn swift_type(ty: &Ty) -> String {
+    match ty {
+        Ty::Void => "Void".to_owned(),
+        Ty::Primitive(ty) => primitive(*ty).to_owned(),
+        Ty::String => "String".to_owned(),
+        Ty::Array(element) => format!("[{}]", swift_type(element)),
+        Ty::Named(name) => name.clone(),
+        Ty::Pointer { mutable, pointee } => match (mutable, pointee) {
+            (false, None) => "UnsafeRawPointer?".to_owned(),
+            (true, None) => "UnsafeMutableRawPointer?".to_owned(),
+            (false, Some(pointee)) => format!("UnsafePointer<{}>?", swift_type(pointee)),
+            (true, Some(pointee)) => format!("UnsafeMutablePointer<{}>?", swift_type(pointee)),
+        },
+    }
+}
+
+fn primitive(ty: Primitive) -> &'static str {
+    match ty {
+        Primitive::Bool => "Bool",
+        Primitive::Float => "Float",
+        Primitive::Double => "Double",
+        Primitive::Int(IntKind { signed, bits }) => match (signed, bits) {
+            (true, Some(8)) => "Int8",
+            (true, Some(16)) => "Int16",
+            (true, Some(32)) => "Int32",
+            (true, Some(64)) => "Int64",
+            (true, _) => "Int",
+            (false, Some(8)) => "UInt8",
+            (false, Some(16)) => "UInt16",
+            (false, Some(32)) => "UInt32",
+            (false, Some(64)) => "UInt64",
+            (false, _) => "UInt",
+        },
+    }
+}
+
+/// Converts a `snake_case` Rust name to `lowerCamelCase`.
+///
+/// Leading underscores are kept, so `_private_value` becomes `_privateValue`.
+fn lower_camel_case(ident: &str) -> String {
+    let trimmed = ident.trim_start_matches('_');
+    let mut result = ident[..ident.len() - trimmed.len()].to_owned();
+    for (i, word) in trimmed
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .enumerate()
+    {
+        if i == 0 {
+            result.push_str(word);
+        } else {
+            let mut chars = word.chars();
+            if let Some(first) = chars.next() {
+                result.extend(first.to_uppercase());
+                result.push_str(chars.as_str());
+            }
+        }
+    }
+    result
+}
+
+/// Returns `ident` as a valid Swift identifier.
+fn escape(ident: &str) -> String {
+    if KEYWORDS.contains(&ident) {
+        format!("`{}`", ident)
+    } else {
+        ident.to_owned()
+    }
+}
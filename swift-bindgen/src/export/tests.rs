@@ -0,0 +1,303 @@
+#![cfg(test)]
+
+use super::*;
+
+const GEOMETRY: &str = r#"
+use swift::{Array, Double, Int};
+
+/// A point in 2D space.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct Point {
+    pub x: Double,
+    pub y: f64,
+}
+
+#[repr(C)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+    pub kind: Kind,
+}
+
+#[repr(u8)]
+pub enum Kind {
+    Solid,
+    Dashed = 4,
+}
+
+/// Returns the distance between two points.
+///
+/// The order of the points doesn't matter.
+#[no_mangle]
+pub extern "C" fn geometry_distance(a: Point, b: Point) -> Double {
+    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()
+}
+
+#[export_name = "geometry_describe"]
+pub extern "C" fn describe(segment: *const Segment, _: bool) -> swift::String {
+    unimplemented!()
+}
+
+#[no_mangle]
+pub extern "C" fn sum_all(values: Array<Int>) -> Int {
+    unimplemented!()
+}
+
+#[no_mangle]
+pub extern "C" fn reset(_buffer: *mut std::ffi::c_void) {}
+
+#[no_mangle]
+pub extern "C" fn rust_name(name: String) {}
+
+#[no_mangle]
+pub extern "C" fn circle_area(circle: Circle) -> f64 {
+    unimplemented!()
+}
+
+// Not exported.
+pub extern "C" fn unmangled() {}
+pub fn plain() {}
+struct Private;
+#[repr(C)]
+struct Hidden {
+    pub x: f64,
+}
+"#;
+
+fn geometry() -> Exports {
+    Exports::from_source(GEOMETRY, "Geometry").unwrap()
+}
+
+#[test]
+fn header() {
+    let exports = geometry();
+    let header = exports.header();
+
+    assert!(header.starts_with("// Generated by swift-bindgen. Do not edit.\n"));
+    assert!(header.contains("#ifndef GEOMETRYFFI_H\n"));
+    assert!(header.contains(
+        "\
+/// The representation of a Swift `String`, which owns its contents.
+typedef struct SwiftBindgenString {
+    uint64_t raw_bits[2];
+} SwiftBindgenString;
+"
+    ));
+    assert!(header.contains("typedef struct SwiftBindgenArray {\n    void *buffer;\n}"));
+    assert!(header.contains(
+        "\
+typedef enum __attribute__((enum_extensibility(closed))) Kind : uint8_t {
+    KindSolid,
+    KindDashed = 4,
+} Kind;
+"
+    ));
+    assert!(header.contains(
+        "\
+/// A point in 2D space.
+struct Point {
+    double x;
+    double y;
+};
+"
+    ));
+
+    // `Segment` is defined after the types of its fields.
+    let point = header.find("struct Point {").unwrap();
+    let segment = header.find("struct Segment {").unwrap();
+    assert!(point < segment);
+    assert!(header.contains("typedef struct Segment Segment;\n"));
+
+    assert!(header.contains(
+        "\
+/// Returns the distance between two points.
+///
+/// The order of the points doesn't matter.
+double geometry_distance(Point a, Point b) __attribute__((swift_private));
+"
+    ));
+    assert!(header.contains(
+        "SwiftBindgenString geometry_describe(const Segment *segment, bool) \
+         __attribute__((swift_private));"
+    ));
+    assert!(header
+        .contains("intptr_t sum_all(SwiftBindgenArray values) __attribute__((swift_private));"));
+    assert!(header.contains("void reset(void *_buffer) __attribute__((swift_private));"));
+    assert!(header.ends_with("#endif // GEOMETRYFFI_H\n"));
+
+    for name in [
+        "rust_name",
+        "circle_area",
+        "unmangled",
+        "plain",
+        "Private",
+        "Hidden",
+    ] {
+        assert!(!header.contains(name), "{} is declared", name);
+    }
+}
+
+#[test]
+fn swift() {
+    let exports = geometry();
+    let swift = exports.swift();
+
+    assert!(swift.contains("@_exported import GeometryFFI\n"));
+    assert!(swift.contains(
+        "\
+/// Returns the distance between two points.
+///
+/// The order of the points doesn't matter.
+@inlinable
+public func geometryDistance(a: Point, b: Point) -> Double {
+    return __geometry_distance(a, b)
+}
+"
+    ));
+    assert!(swift.contains(
+        "\
+public func describe(segment: UnsafePointer<Segment>?, _ arg1: Bool) -> String {
+    return __swiftBindgenTake(__geometry_describe(segment, arg1), as: String.self)
+}
+"
+    ));
+    assert!(swift.contains(
+        "\
+public func sumAll(values: [Int]) -> Int {
+    return __sum_all(__swiftBindgenGive(values, as: SwiftBindgenArray.self))
+}
+"
+    ));
+    assert!(swift.contains(
+        "\
+public func reset(_buffer: UnsafeMutableRawPointer?) {
+    __reset(_buffer)
+}
+"
+    ));
+    assert!(swift.contains("internal func __swiftBindgenGive<T, Raw>("));
+    assert!(swift.contains("internal func __swiftBindgenTake<Raw, T>("));
+
+    assert!(
+        swift.contains("// Skipped `rust_name`: `String` is not FFI-safe; use `swift::String`\n")
+    );
+    assert!(swift.contains("// Skipped `circle_area`: `Circle` is not exported\n"));
+}
+
+#[test]
+fn module_map() {
+    assert_eq!(
+        geometry().module_map(),
+        "module GeometryFFI {\n    header \"GeometryFFI.h\"\n    export *\n}\n"
+    );
+}
+
+#[test]
+fn skipped() {
+    let source = r#"
+        #[repr(C)]
+        pub struct Label {
+            pub text: swift::String,
+        }
+
+        #[repr(C)]
+        pub struct Tagged {
+            pub label: Label,
+        }
+
+        #[repr(C)]
+        pub enum Shape {
+            Circle(f64),
+        }
+
+        #[no_mangle]
+        pub extern "C" fn tag(tagged: Tagged) {}
+
+        #[no_mangle]
+        pub extern "C" fn first<T>(values: swift::Array<T>) {}
+
+        #[no_mangle]
+        pub extern "C" fn points(values: swift::Array<Label>) {}
+    "#;
+    let exports = Exports::from_source(source, "Tags").unwrap();
+    let swift = exports.swift();
+
+    assert!(swift.contains("// Skipped `Label`: field `text` has no C representation\n"));
+    assert!(swift.contains("// Skipped `Tagged`: `Label` is not exported\n"));
+    assert!(swift.contains("// Skipped `Shape`: enums with fields are not supported\n"));
+    assert!(swift.contains("// Skipped `tag`: `Tagged` is not exported\n"));
+    assert!(swift.contains("// Skipped `first`: generic functions are not supported\n"));
+    assert!(swift.contains("// Skipped `points`: `swift::Array<Label>` is not supported\n"));
+    assert!(!exports.header().contains("Label"));
+    assert!(!swift.contains("__swiftBindgen"));
+}
+
+#[test]
+fn names() {
+    let source = r#"
+        #[no_mangle]
+        pub extern "C" fn _private_value(r#in: u8, default: i16) -> u32 { 0 }
+    "#;
+    let exports = Exports::from_source(source, "Names").unwrap();
+    assert!(exports
+        .swift()
+        .contains("public func _privateValue(`in`: UInt8, `default`: Int16) -> UInt32 {"));
+    assert!(exports
+        .header()
+        .contains("uint32_t _private_value(uint8_t in, int16_t default_) "));
+}
+
+#[test]
+fn macro_export() {
+    let source = r#"
+        use swift::{Array, Int};
+
+        /// Greets `name`.
+        #[swift::export]
+        fn greeting(name: swift::String) -> swift::String {
+            unimplemented!()
+        }
+
+        #[export(name = "total")]
+        fn sum_all(values: Array<Int>) -> Int {
+            unimplemented!()
+        }
+    "#;
+    let exports = Exports::from_source(source, "Greeter").unwrap();
+    let swift = exports.swift();
+    let header = exports.header();
+
+    assert!(header.contains(
+        "SwiftBindgenString __swift_export_greeting(SwiftBindgenString name) \
+         __attribute__((swift_private));"
+    ));
+    assert!(swift.contains(
+        "\
+/// Greets `name`.
+@inlinable
+public func greeting(name: String) -> String {
+    return __swiftBindgenTake(____swift_export_greeting(__swiftBindgenGive(name, as: SwiftBindgenString.self)), as: String.self)
+}
+"
+    ));
+    assert!(swift.contains("public func total(values: [Int]) -> Int {"));
+    assert!(swift.contains("return ____swift_export_sum_all("));
+}
+
+#[test]
+fn errors() {
+    let error = Exports::from_source("fn main() {\n    let x = ;\n}", "Main").unwrap_err();
+    assert_eq!((error.line(), error.column()), (2, 13));
+}
+
+#[test]
+fn write_to_dir() {
+    let dir = std::env::temp_dir().join(format!("swift-bindgen-export-{}", std::process::id()));
+    geometry().write_to_dir(&dir).unwrap();
+
+    for file in ["Geometry.swift", "GeometryFFI.h", "module.modulemap"] {
+        assert!(dir.join(file).is_file(), "{} was not written", file);
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,254 @@
+use super::Error;
+
+/// A lexical token of a Swift interface.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Token {
+    pub kind: TokenKind,
+
+    /// Byte range of the token in the source.
+    pub start: usize,
+    pub end: usize,
+
+    /// Whether whitespace or a comment precedes this token.
+    pub spaced: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum TokenKind {
+    /// An identifier or keyword. Backticks are removed.
+    Ident(String),
+
+    /// A single punctuation or operator character.
+    Punct(char),
+
+    /// `->`
+    Arrow,
+
+    /// `...`
+    Ellipsis,
+
+    /// A numeric literal.
+    Number,
+
+    /// A string literal.
+    String,
+
+    /// A compilation condition line: `#if`, `#elseif`, `#else` or `#endif`.
+    Directive(String),
+
+    /// The end of input.
+    Eof,
+}
+
+/// Characters that may form Swift operators.
+pub(crate) fn is_operator_char(c: char) -> bool {
+    matches!(
+        c,
+        '/' | '=' | '-' | '+' | '!' | '*' | '%' | '<' | '>' | '&' | '|' | '^' | '~' | '?' | '.'
+    )
+}
+
+fn is_ident_start(c: char) -> bool {
+    c == '_' || c == '$' || c.is_alphabetic()
+}
+
+fn is_ident_continue(c: char) -> bool {
+    c == '_' || c == '$' || c.is_alphanumeric()
+}
+
+/// Splits `source` into tokens, skipping whitespace and comments.
+pub(crate) fn tokenize(source: &str) -> Result<Vec<Token>, Error> {
+    let mut lexer = Lexer {
+        source,
+        pos: 0,
+        tokens: Vec::new(),
+    };
+    lexer.run()?;
+    Ok(lexer.tokens)
+}
+
+struct Lexer<'a> {
+    source: &'a str,
+    pos: usize,
+    tokens: Vec<Token>,
+}
+
+impl Lexer<'_> {
+    fn peek(&self) -> Option<char> {
+        self.source[self.pos..].chars().next()
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.source[self.pos..].chars().nth(n)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat_while(&mut self, f: impl Fn(char) -> bool) {
+        while self.peek().is_some_and(&f) {
+            self.bump();
+        }
+    }
+
+    fn error(&self, pos: usize, message: impl Into<String>) -> Error {
+        Error::at(self.source, pos, message)
+    }
+
+    fn push(&mut self, kind: TokenKind, start: usize, spaced: bool) {
+        self.tokens.push(Token {
+            kind,
+            start,
+            end: self.pos,
+            spaced,
+        });
+    }
+
+    /// Skips whitespace and comments, returning whether anything was skipped.
+    fn skip_trivia(&mut self) -> Result<bool, Error> {
+        let start = self.pos;
+        loop {
+            match (self.peek(), self.peek_nth(1)) {
+                (Some(c), _) if c.is_whitespace() => {
+                    self.bump();
+                }
+                (Some('/'), Some('/')) => self.eat_while(|c| c != '\n'),
+                (Some('/'), Some('*')) => {
+                    let comment_start = self.pos;
+                    self.pos += 2;
+                    let mut depth = 1;
+                    while depth > 0 {
+                        match (self.peek(), self.peek_nth(1)) {
+                            (Some('/'), Some('*')) => {
+                                self.pos += 2;
+                                depth += 1;
+                            }
+                            (Some('*'), Some('/')) => {
+                                self.pos += 2;
+                                depth -= 1;
+                            }
+                            (Some(_), _) => {
+                                self.bump();
+                            }
+                            (None, _) => {
+                                return Err(self.error(comment_start, "unterminated comment"))
+                            }
+                        }
+                    }
+                }
+                _ => return Ok(self.pos != start),
+            }
+        }
+    }
+
+    fn run(&mut self) -> Result<(), Error> {
+        loop {
+            let spaced = self.skip_trivia()? || self.pos == 0;
+            let start = self.pos;
+
+            let c = match self.peek() {
+                Some(c) => c,
+                None => {
+                    self.push(TokenKind::Eof, start, spaced);
+                    return Ok(());
+                }
+            };
+
+            if c == '`' {
+                self.bump();
+                let ident_start = self.pos;
+                self.eat_while(|c| c != '`' && c != '\n');
+                if self.peek() != Some('`') {
+                    return Err(self.error(start, "unterminated escaped identifier"));
+                }
+                let ident = self.source[ident_start..self.pos].to_owned();
+                self.bump();
+                self.push(TokenKind::Ident(ident), start, spaced);
+            } else if is_ident_start(c) {
+                self.eat_while(is_ident_continue);
+                let ident = self.source[start..self.pos].to_owned();
+                self.push(TokenKind::Ident(ident), start, spaced);
+            } else if c.is_ascii_digit() {
+                self.lex_number();
+                self.push(TokenKind::Number, start, spaced);
+            } else if c == '"' {
+                self.lex_string()?;
+                self.push(TokenKind::String, start, spaced);
+            } else if c == '#' && self.lex_directive() {
+                let line = self.source[start..self.pos].trim().to_owned();
+                self.push(TokenKind::Directive(line), start, spaced);
+            } else if c == '-' && self.peek_nth(1) == Some('>') {
+                self.pos += 2;
+                self.push(TokenKind::Arrow, start, spaced);
+            } else if self.source[self.pos..].starts_with("...") {
+                self.pos += 3;
+                self.push(TokenKind::Ellipsis, start, spaced);
+            } else {
+                self.bump();
+                self.push(TokenKind::Punct(c), start, spaced);
+            }
+        }
+    }
+
+    fn lex_number(&mut self) {
+        if self.source[self.pos..].starts_with("0x") {
+            self.pos += 2;
+            self.eat_while(|c| c.is_ascii_hexdigit() || c == '_');
+            return;
+        }
+        self.eat_while(|c| c.is_ascii_digit() || c == '_');
+        if self.peek() == Some('.') && self.peek_nth(1).is_some_and(|c| c.is_ascii_digit()) {
+            self.bump();
+            self.eat_while(|c| c.is_ascii_digit() || c == '_');
+        }
+        if let Some('e') | Some('E') = self.peek() {
+            self.bump();
+            if let Some('+') | Some('-') = self.peek() {
+                self.bump();
+            }
+            self.eat_while(|c| c.is_ascii_digit());
+        }
+    }
+
+    fn lex_string(&mut self) -> Result<(), Error> {
+        let start = self.pos;
+        if self.source[self.pos..].starts_with("\"\"\"") {
+            self.pos += 3;
+            match self.source[self.pos..].find("\"\"\"") {
+                Some(len) => self.pos += len + 3,
+                None => return Err(self.error(start, "unterminated string literal")),
+            }
+            return Ok(());
+        }
+        self.bump();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(()),
+                Some('\\') => {
+                    self.bump();
+                }
+                Some('\n') | None => return Err(self.error(start, "unterminated string literal")),
+                Some(_) => {}
+            }
+        }
+    }
+
+    /// Lexes a `#if`-style directive through the end of its line, returning
+    /// `false` for other `#` uses.
+    fn lex_directive(&mut self) -> bool {
+        let rest = &self.source[self.pos + 1..];
+        let keyword_len = rest
+            .find(|c: char| !is_ident_continue(c))
+            .unwrap_or(rest.len());
+        match &rest[..keyword_len] {
+            "if" | "elseif" | "else" | "endif" => {
+                self.eat_while(|c| c != '\n');
+                true
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,136 @@
+//! Parsing of textual `.swiftinterface` files.
+//!
+//! Module interfaces are emitted by `swiftc -emit-module-interface` and
+//! describe the public declarations of a module using a restricted subset of
+//! Swift. Only declarations are modeled; inlinable bodies and default argument
+//! expressions are skipped.
+
+use crate::ir::Module;
+use std::{error, fmt};
+
+mod lexer;
+mod parser;
+
+mod tests;
+
+/// Parses the contents of a `.swiftinterface` file.
+///
+/// The module name is taken from `-module-name` in the `swift-module-flags`
+/// header comment. Use [`parse_named`] for interfaces without one.
+pub fn parse(source: &str) -> Result<Module, Error> {
+    parse_named(source, None)
+}
+
+/// Parses the contents of a `.swiftinterface` file, using `module_name` if the
+/// interface header doesn't specify one.
+pub fn parse_named(source: &str, module_name: Option<&str>) -> Result<Module, Error> {
+    let flags = header_flags(source);
+    let name = flags
+        .iter()
+        .position(|flag| flag == "-module-name")
+        .and_then(|i| flags.get(i + 1))
+        .map(String::as_str)
+        .or(module_name)
+        .ok_or_else(|| Error::new(1, 1, "interface does not specify `-module-name`"))?
+        .to_owned();
+
+    let tokens = lexer::tokenize(source)?;
+    let tokens = strip_inactive_branches(tokens);
+
+    let mut module = parser::Parser::new(source, tokens).parse_module()?;
+    module.name = name;
+    module.flags = flags;
+    Ok(module)
+}
+
+/// Returns the whitespace-separated flags from the `swift-module-flags` line.
+fn header_flags(source: &str) -> Vec<String> {
+    source
+        .lines()
+        .take_while(|line| line.starts_with("//"))
+        .find_map(|line| line.strip_prefix("// swift-module-flags:"))
+        .map(|flags| flags.split_whitespace().map(str::to_owned).collect())
+        .unwrap_or_default()
+}
+
+/// Removes `#elseif`/`#else` branches and all directive tokens.
+///
+/// Interfaces guard newer syntax with `#if compiler(...)` or feature checks
+/// and place fallbacks for older compilers in later branches, so the first
+/// branch is always the most complete.
+fn strip_inactive_branches(tokens: Vec<lexer::Token>) -> Vec<lexer::Token> {
+    use lexer::TokenKind;
+
+    // Whether each enclosing `#if` is currently in its first branch.
+    let mut stack: Vec<bool> = Vec::new();
+
+    tokens
+        .into_iter()
+        .filter(|token| {
+            if let TokenKind::Directive(line) = &token.kind {
+                if line.starts_with("#if") {
+                    stack.push(true);
+                } else if line.starts_with("#endif") {
+                    stack.pop();
+                } else if let Some(active) = stack.last_mut() {
+                    *active = false;
+                }
+                false
+            } else {
+                stack.iter().all(|&active| active)
+            }
+        })
+        .collect()
+}
+
+/// An error encountered while parsing an interface.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    line: usize,
+    column: usize,
+    message: String,
+}
+
+impl error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error {
+    pub(crate) fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Creates an error for the byte offset `pos` in `source`.
+    pub(crate) fn at(source: &str, pos: usize, message: impl Into<String>) -> Self {
+        let before = &source[..pos.min(source.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+        Self::new(line, column, message)
+    }
+
+    /// The 1-based line number where the error occurred.
+    #[inline]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The 1-based column where the error occurred.
+    #[inline]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// A description of the error.
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
@@ -0,0 +1,1074 @@
+use super::{
+    lexer::{is_operator_char, Token, TokenKind},
+    Error,
+};
+use crate::ir::*;
+
+/// Declaration modifiers, excluding `class` which is context-dependent.
+const MODIFIERS: &[&str] = &[
+    "public",
+    "open",
+    "package",
+    "internal",
+    "fileprivate",
+    "private",
+    "final",
+    "static",
+    "mutating",
+    "nonmutating",
+    "override",
+    "convenience",
+    "required",
+    "indirect",
+    "dynamic",
+    "lazy",
+    "weak",
+    "unowned",
+    "optional",
+    "prefix",
+    "postfix",
+    "infix",
+    "__consuming",
+    "consuming",
+    "borrowing",
+    "nonisolated",
+    "distributed",
+];
+
+/// Keywords that introduce a declaration.
+const DECL_KEYWORDS: &[&str] = &[
+    "import",
+    "struct",
+    "enum",
+    "class",
+    "actor",
+    "protocol",
+    "extension",
+    "func",
+    "init",
+    "deinit",
+    "var",
+    "let",
+    "subscript",
+    "case",
+    "typealias",
+    "associatedtype",
+    "operator",
+    "precedencegroup",
+    "macro",
+];
+
+type Result<T> = std::result::Result<T, Error>;
+
+pub(crate) struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(source: &'a str, tokens: Vec<Token>) -> Self {
+        Self {
+            source,
+            tokens,
+            pos: 0,
+        }
+    }
+
+    pub fn parse_module(mut self) -> Result<Module> {
+        let mut module = Module::default();
+        while !self.at_eof() {
+            if self.eat_punct(';') {
+                continue;
+            }
+            self.parse_decl(&mut module.decls, Some(&mut module.imports))?;
+        }
+        Ok(module)
+    }
+
+    // Token helpers -----------------------------------------------------------
+
+    fn token(&self) -> &Token {
+        &self.tokens[self.pos]
+    }
+
+    fn peek(&self) -> &TokenKind {
+        &self.token().kind
+    }
+
+    fn peek_nth(&self, n: usize) -> &TokenKind {
+        let last = self.tokens.len() - 1;
+        &self.tokens[(self.pos + n).min(last)].kind
+    }
+
+    fn at_eof(&self) -> bool {
+        *self.peek() == TokenKind::Eof
+    }
+
+    fn bump(&mut self) -> Token {
+        let token = self.token().clone();
+        if !self.at_eof() {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn is_ident(&self, ident: &str) -> bool {
+        matches!(self.peek(), TokenKind::Ident(i) if i == ident)
+    }
+
+    fn is_punct(&self, c: char) -> bool {
+        *self.peek() == TokenKind::Punct(c)
+    }
+
+    /// Returns `true` if the current token is `c` and directly follows the
+    /// previous token.
+    fn is_attached_punct(&self, c: char) -> bool {
+        self.is_punct(c) && !self.token().spaced
+    }
+
+    fn eat_ident(&mut self, ident: &str) -> bool {
+        let found = self.is_ident(ident);
+        if found {
+            self.bump();
+        }
+        found
+    }
+
+    fn eat_punct(&mut self, c: char) -> bool {
+        let found = self.is_punct(c);
+        if found {
+            self.bump();
+        }
+        found
+    }
+
+    fn expect_punct(&mut self, c: char) -> Result<()> {
+        if self.eat_punct(c) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("`{}`", c)))
+        }
+    }
+
+    fn expect_ident(&mut self) -> Result<String> {
+        match self.peek() {
+            TokenKind::Ident(ident) => {
+                let ident = ident.clone();
+                self.bump();
+                Ok(ident)
+            }
+            _ => Err(self.unexpected("an identifier")),
+        }
+    }
+
+    fn error(&self, message: impl Into<String>) -> Error {
+        Error::at(self.source, self.token().start, message)
+    }
+
+    fn unexpected(&self, expected: &str) -> Error {
+        let found = match self.peek() {
+            TokenKind::Eof => "end of input".to_owned(),
+            _ => format!("`{}`", &self.source[self.token().start..self.token().end]),
+        };
+        self.error(format!("expected {}, found {}", expected, found))
+    }
+
+    /// Skips a balanced `()`, `[]` or `{}` group starting at the current
+    /// token, returning the source text between the delimiters.
+    fn skip_group(&mut self) -> Result<&'a str> {
+        let open = self.bump();
+        let inner_start = open.end;
+        let mut depth = 1;
+        loop {
+            let token = self.bump();
+            match token.kind {
+                TokenKind::Punct('(') | TokenKind::Punct('[') | TokenKind::Punct('{') => depth += 1,
+                TokenKind::Punct(')') | TokenKind::Punct(']') | TokenKind::Punct('}') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(self.source[inner_start..token.start].trim());
+                    }
+                }
+                TokenKind::Eof => {
+                    return Err(Error::at(self.source, open.start, "unbalanced delimiter"))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Skips an expression, such as a default argument, up to a `,` or closing
+    /// delimiter at the current nesting level or the start of a new line.
+    fn skip_expression(&mut self) -> Result<()> {
+        let start = self.pos;
+        loop {
+            match self.peek() {
+                TokenKind::Punct('(') | TokenKind::Punct('[') | TokenKind::Punct('{') => {
+                    self.skip_group()?;
+                }
+                TokenKind::Punct(',')
+                | TokenKind::Punct(')')
+                | TokenKind::Punct(']')
+                | TokenKind::Punct('}')
+                | TokenKind::Punct(';')
+                | TokenKind::Eof => return Ok(()),
+                _ if self.pos != start && self.starts_line() => return Ok(()),
+                _ => {
+                    self.bump();
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if the current token is the first on its line.
+    fn starts_line(&self) -> bool {
+        let start = self.token().start;
+        let prev_end = match self.pos.checked_sub(1) {
+            Some(prev) => self.tokens[prev].end,
+            None => return true,
+        };
+        self.source[prev_end..start].contains('\n')
+    }
+
+    // Declarations ------------------------------------------------------------
+
+    /// Parses the remainder of an `import` declaration.
+    fn parse_import(&mut self, attributes: Vec<Attribute>) -> Result<Import> {
+        // Scoped imports, such as `import struct Foundation.Date`.
+        if let TokenKind::Ident(kind) = self.peek() {
+            if matches!(
+                kind.as_str(),
+                "struct" | "class" | "enum" | "protocol" | "typealias" | "func" | "var" | "let"
+            ) {
+                self.bump();
+            }
+        }
+
+        let mut path = vec![self.expect_ident()?];
+        while self.is_attached_punct('.') {
+            self.bump();
+            path.push(self.expect_ident()?);
+        }
+        Ok(Import { attributes, path })
+    }
+
+    fn parse_attributes(&mut self) -> Result<Vec<Attribute>> {
+        let mut attributes = Vec::new();
+        while self.eat_punct('@') {
+            let name = self.expect_ident()?;
+            let arguments = if self.is_attached_punct('(') {
+                Some(self.skip_group()?.to_owned())
+            } else {
+                None
+            };
+            attributes.push(Attribute { name, arguments });
+        }
+        Ok(attributes)
+    }
+
+    fn parse_modifiers(&mut self) -> Result<Vec<String>> {
+        let mut modifiers = Vec::new();
+        loop {
+            let is_modifier = match self.peek() {
+                TokenKind::Ident(ident) if MODIFIERS.contains(&ident.as_str()) => {
+                    // Guard against modifiers used as names, such as
+                    // `var open: Bool`.
+                    !matches!(
+                        self.peek_nth(1),
+                        TokenKind::Punct(':') | TokenKind::Punct('=')
+                    )
+                }
+                // `class` is a modifier when followed by another keyword, as in
+                // `class func` or `public class var`.
+                TokenKind::Ident(ident) if ident == "class" => match self.peek_nth(1) {
+                    TokenKind::Ident(next) => {
+                        DECL_KEYWORDS.contains(&next.as_str()) || MODIFIERS.contains(&next.as_str())
+                    }
+                    _ => false,
+                },
+                _ => false,
+            };
+            if !is_modifier {
+                return Ok(modifiers);
+            }
+
+            let mut modifier = self.expect_ident()?;
+            // `private(set)`, `unowned(unsafe)`, etc.
+            if self.is_attached_punct('(') {
+                let argument = self.skip_group()?;
+                modifier = format!("{}({})", modifier, argument);
+            }
+            modifiers.push(modifier);
+        }
+    }
+
+    /// Parses a declaration into `decls`, or into `imports` at the top level.
+    fn parse_decl(
+        &mut self,
+        decls: &mut Vec<Decl>,
+        imports: Option<&mut Vec<Import>>,
+    ) -> Result<()> {
+        let attributes = self.parse_attributes()?;
+        let modifiers = self.parse_modifiers()?;
+
+        let keyword = match self.peek() {
+            TokenKind::Ident(keyword) if DECL_KEYWORDS.contains(&keyword.as_str()) => {
+                keyword.clone()
+            }
+            _ => return Err(self.unexpected("a declaration")),
+        };
+        self.bump();
+
+        let decl = match keyword.as_str() {
+            "struct" => self.parse_nominal(NominalKind::Struct, attributes, modifiers)?,
+            "enum" => self.parse_nominal(NominalKind::Enum, attributes, modifiers)?,
+            "class" => self.parse_nominal(NominalKind::Class, attributes, modifiers)?,
+            "actor" => self.parse_nominal(NominalKind::Actor, attributes, modifiers)?,
+            "protocol" => self.parse_nominal(NominalKind::Protocol, attributes, modifiers)?,
+            "extension" => self.parse_extension(attributes, modifiers)?,
+            "func" => Decl::Function(self.parse_function(attributes, modifiers)?),
+            "init" => Decl::Initializer(self.parse_initializer(attributes, modifiers)?),
+            "deinit" => {
+                if self.is_punct('{') {
+                    self.skip_group()?;
+                }
+                Decl::Deinitializer
+            }
+            "var" | "let" => {
+                return self.parse_variables(keyword == "let", attributes, modifiers, decls)
+            }
+            "subscript" => Decl::Subscript(self.parse_subscript(attributes, modifiers)?),
+            "case" => Decl::EnumCase(self.parse_enum_case(attributes, modifiers)?),
+            "typealias" => Decl::TypeAlias(self.parse_type_alias(attributes, modifiers)?),
+            "associatedtype" => Decl::AssociatedType(self.parse_associated_type()?),
+            "import" => {
+                return match imports {
+                    Some(imports) => {
+                        imports.push(self.parse_import(attributes)?);
+                        Ok(())
+                    }
+                    None => Err(self.error("`import` is only allowed at the top level")),
+                };
+            }
+            _ => {
+                // Operator, precedence group and macro declarations don't
+                // produce bindings.
+                self.skip_declaration()?;
+                return Ok(());
+            }
+        };
+        decls.push(decl);
+        Ok(())
+    }
+
+    /// Skips the remainder of an unsupported declaration.
+    fn skip_declaration(&mut self) -> Result<()> {
+        loop {
+            match self.peek() {
+                TokenKind::Punct('{') => {
+                    self.skip_group()?;
+                    return Ok(());
+                }
+                TokenKind::Punct('(') | TokenKind::Punct('[') => {
+                    self.skip_group()?;
+                }
+                TokenKind::Punct('}') | TokenKind::Eof => return Ok(()),
+                _ if self.starts_line() => return Ok(()),
+                _ => {
+                    self.bump();
+                }
+            }
+        }
+    }
+
+    fn parse_member_block(&mut self) -> Result<Vec<Decl>> {
+        self.expect_punct('{')?;
+        let mut decls = Vec::new();
+        loop {
+            if self.eat_punct('}') {
+                return Ok(decls);
+            }
+            if self.eat_punct(';') {
+                continue;
+            }
+            if self.at_eof() {
+                return Err(self.unexpected("`}`"));
+            }
+            self.parse_decl(&mut decls, None)?;
+        }
+    }
+
+    fn parse_nominal(
+        &mut self,
+        kind: NominalKind,
+        attributes: Vec<Attribute>,
+        modifiers: Vec<String>,
+    ) -> Result<Decl> {
+        let name = self.expect_ident()?;
+        let mut generics = self.parse_generic_params()?;
+        let inherited = self.parse_inheritance()?;
+        generics.requirements.extend(self.parse_where_clause()?);
+        let decls = self.parse_member_block()?;
+
+        Ok(Decl::Nominal(NominalDecl {
+            kind,
+            attributes,
+            modifiers,
+            name,
+            generics,
+            inherited,
+            decls,
+        }))
+    }
+
+    fn parse_extension(
+        &mut self,
+        attributes: Vec<Attribute>,
+        modifiers: Vec<String>,
+    ) -> Result<Decl> {
+        let extended = self.parse_type()?;
+        let inherited = self.parse_inheritance()?;
+        let requirements = self.parse_where_clause()?;
+        let decls = self.parse_member_block()?;
+
+        Ok(Decl::Extension(Extension {
+            attributes,
+            modifiers,
+            extended,
+            inherited,
+            requirements,
+            decls,
+        }))
+    }
+
+    fn parse_inheritance(&mut self) -> Result<Vec<Type>> {
+        let mut inherited = Vec::new();
+        if self.eat_punct(':') {
+            loop {
+                inherited.push(self.parse_type()?);
+                if !self.eat_punct(',') {
+                    break;
+                }
+            }
+        }
+        Ok(inherited)
+    }
+
+    fn parse_generic_params(&mut self) -> Result<Generics> {
+        let mut generics = Generics::default();
+        if !self.is_attached_punct('<') {
+            return Ok(generics);
+        }
+        self.bump();
+        loop {
+            // Parameter packs are spelled `each T`.
+            self.eat_ident("each");
+            let name = self.expect_ident()?;
+            if self.eat_punct(':') {
+                let constraint = self.parse_type()?;
+                generics.requirements.push(Requirement::Conformance {
+                    subject: Type::named(&name),
+                    constraint,
+                });
+            }
+            generics.params.push(GenericParam { name });
+            if !self.eat_punct(',') {
+                break;
+            }
+        }
+        self.expect_punct('>')?;
+        Ok(generics)
+    }
+
+    fn parse_where_clause(&mut self) -> Result<Vec<Requirement>> {
+        let mut requirements = Vec::new();
+        if !self.eat_ident("where") {
+            return Ok(requirements);
+        }
+        loop {
+            let subject = self.parse_type()?;
+            if self.eat_punct(':') {
+                let constraint = self.parse_type()?;
+                requirements.push(Requirement::Conformance {
+                    subject,
+                    constraint,
+                });
+            } else if self.is_punct('=') && *self.peek_nth(1) == TokenKind::Punct('=') {
+                self.bump();
+                self.bump();
+                let right = self.parse_type()?;
+                requirements.push(Requirement::SameType {
+                    left: subject,
+                    right,
+                });
+            } else {
+                return Err(self.unexpected("`:` or `==`"));
+            }
+            if !self.eat_punct(',') {
+                return Ok(requirements);
+            }
+        }
+    }
+
+    fn parse_function_name(&mut self) -> Result<String> {
+        if let TokenKind::Ident(_) = self.peek() {
+            return self.expect_ident();
+        }
+
+        // Operators are lexed as individual characters.
+        let mut name = String::new();
+        loop {
+            match self.peek() {
+                TokenKind::Punct(c) if is_operator_char(*c) => name.push(*c),
+                TokenKind::Ellipsis => name.push_str("..."),
+                TokenKind::Arrow => name.push_str("->"),
+                _ => break,
+            }
+            self.bump();
+            if self.token().spaced || self.is_punct('(') {
+                break;
+            }
+        }
+        if name.is_empty() {
+            Err(self.unexpected("a function name"))
+        } else {
+            Ok(name)
+        }
+    }
+
+    fn parse_function(
+        &mut self,
+        attributes: Vec<Attribute>,
+        modifiers: Vec<String>,
+    ) -> Result<Function> {
+        let name = self.parse_function_name()?;
+        let mut generics = self.parse_generic_params()?;
+        let params = self.parse_params()?;
+        let (is_async, throws) = self.parse_effects()?;
+        let result = if self.peek() == &TokenKind::Arrow {
+            self.bump();
+            self.parse_type()?
+        } else {
+            Type::VOID
+        };
+        generics.requirements.extend(self.parse_where_clause()?);
+        self.skip_body()?;
+
+        Ok(Function {
+            attributes,
+            modifiers,
+            name,
+            generics,
+            params,
+            is_async,
+            throws,
+            result,
+        })
+    }
+
+    fn parse_initializer(
+        &mut self,
+        attributes: Vec<Attribute>,
+        modifiers: Vec<String>,
+    ) -> Result<Initializer> {
+        let failability = if self.is_attached_punct('?') {
+            self.bump();
+            Failability::Optional
+        } else if self.is_attached_punct('!') {
+            self.bump();
+            Failability::ImplicitlyUnwrapped
+        } else {
+            Failability::None
+        };
+        let mut generics = self.parse_generic_params()?;
+        let params = self.parse_params()?;
+        let (is_async, throws) = self.parse_effects()?;
+        generics.requirements.extend(self.parse_where_clause()?);
+        self.skip_body()?;
+
+        Ok(Initializer {
+            attributes,
+            modifiers,
+            failability,
+            generics,
+            params,
+            is_async,
+            throws,
+        })
+    }
+
+    fn skip_body(&mut self) -> Result<()> {
+        if self.is_punct('{') {
+            self.skip_group()?;
+        }
+        Ok(())
+    }
+
+    /// Parses `async`, `throws`, `rethrows` and typed `throws(E)`.
+    fn parse_effects(&mut self) -> Result<(bool, bool)> {
+        let mut is_async = false;
+        let mut throws = false;
+        loop {
+            if self.eat_ident("async") {
+                is_async = true;
+            } else if self.eat_ident("throws") || self.eat_ident("rethrows") {
+                throws = true;
+                if self.is_attached_punct('(') {
+                    self.skip_group()?;
+                }
+            } else {
+                return Ok((is_async, throws));
+            }
+        }
+    }
+
+    fn parse_params(&mut self) -> Result<Vec<Param>> {
+        self.expect_punct('(')?;
+        let mut params = Vec::new();
+        while !self.eat_punct(')') {
+            params.push(self.parse_param()?);
+            if !self.eat_punct(',') {
+                self.expect_punct(')')?;
+                break;
+            }
+        }
+        Ok(params)
+    }
+
+    fn parse_param(&mut self) -> Result<Param> {
+        self.parse_attributes()?;
+
+        let first = self.expect_ident()?;
+        let (label, name) = match self.peek() {
+            TokenKind::Ident(_) => (Some(first), self.expect_ident()?),
+            _ => (Some(first.clone()), first),
+        };
+        let label = label.filter(|label| label != "_");
+        self.expect_punct(':')?;
+
+        let ownership = self.parse_ownership();
+        let ty = self.parse_type()?;
+        let is_variadic = self.peek() == &TokenKind::Ellipsis;
+        if is_variadic {
+            self.bump();
+        }
+        let has_default = self.eat_punct('=');
+        if has_default {
+            self.skip_expression()?;
+        }
+
+        Ok(Param {
+            label,
+            name,
+            ty,
+            ownership,
+            is_variadic,
+            has_default,
+        })
+    }
+
+    fn parse_ownership(&mut self) -> Ownership {
+        let ownership = match self.peek() {
+            TokenKind::Ident(ident) => match ident.as_str() {
+                "inout" => Ownership::InOut,
+                "__owned" | "consuming" => Ownership::Owned,
+                "__shared" | "borrowing" => Ownership::Shared,
+                _ => return Ownership::Default,
+            },
+            _ => return Ownership::Default,
+        };
+        self.bump();
+        ownership
+    }
+
+    fn parse_variables(
+        &mut self,
+        is_let: bool,
+        attributes: Vec<Attribute>,
+        modifiers: Vec<String>,
+        decls: &mut Vec<Decl>,
+    ) -> Result<()> {
+        loop {
+            let name = self.expect_ident()?;
+            self.expect_punct(':')?;
+            let ty = self.parse_type()?;
+            if self.eat_punct('=') {
+                self.skip_expression()?;
+            }
+            let accessors = if self.is_punct('{') {
+                self.parse_accessors()?
+            } else {
+                Vec::new()
+            };
+            decls.push(Decl::Variable(Variable {
+                attributes: attributes.clone(),
+                modifiers: modifiers.clone(),
+                is_let,
+                name,
+                ty,
+                accessors,
+            }));
+            if !self.eat_punct(',') {
+                return Ok(());
+            }
+        }
+    }
+
+    fn parse_accessors(&mut self) -> Result<Vec<Accessor>> {
+        // A body without accessor keywords is an implicit getter.
+        let is_accessor_list = match self.peek_nth(1) {
+            TokenKind::Ident(ident) => {
+                AccessorKind::from_keyword(ident).is_some()
+                    || matches!(ident.as_str(), "mutating" | "nonmutating" | "__consuming")
+            }
+            TokenKind::Punct('@') | TokenKind::Punct('}') => true,
+            _ => false,
+        };
+        if !is_accessor_list {
+            self.skip_group()?;
+            return Ok(vec![Accessor {
+                modifiers: Vec::new(),
+                kind: AccessorKind::Get,
+            }]);
+        }
+
+        self.expect_punct('{')?;
+        let mut accessors = Vec::new();
+        while !self.eat_punct('}') {
+            self.parse_attributes()?;
+            let mut modifiers = Vec::new();
+            while let TokenKind::Ident(ident) = self.peek() {
+                if !matches!(ident.as_str(), "mutating" | "nonmutating" | "__consuming") {
+                    break;
+                }
+                modifiers.push(self.expect_ident()?);
+            }
+            let keyword = self.expect_ident()?;
+            let kind = AccessorKind::from_keyword(&keyword)
+                .ok_or_else(|| self.error(format!("unknown accessor `{}`", keyword)))?;
+            // Setter parameter names, such as `set(newValue)`.
+            if self.is_punct('(') {
+                self.skip_group()?;
+            }
+            self.parse_effects()?;
+            self.skip_body()?;
+            accessors.push(Accessor { modifiers, kind });
+        }
+        Ok(accessors)
+    }
+
+    fn parse_subscript(
+        &mut self,
+        attributes: Vec<Attribute>,
+        modifiers: Vec<String>,
+    ) -> Result<Subscript> {
+        let mut generics = self.parse_generic_params()?;
+        let params = self.parse_params()?;
+        if self.peek() != &TokenKind::Arrow {
+            return Err(self.unexpected("`->`"));
+        }
+        self.bump();
+        let result = self.parse_type()?;
+        generics.requirements.extend(self.parse_where_clause()?);
+        let accessors = if self.is_punct('{') {
+            self.parse_accessors()?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Subscript {
+            attributes,
+            modifiers,
+            generics,
+            params,
+            result,
+            accessors,
+        })
+    }
+
+    fn parse_enum_case(
+        &mut self,
+        attributes: Vec<Attribute>,
+        modifiers: Vec<String>,
+    ) -> Result<EnumCase> {
+        let mut elements = Vec::new();
+        loop {
+            let name = self.expect_ident()?;
+            let payload = if self.is_punct('(') {
+                Some(self.parse_payload()?)
+            } else {
+                None
+            };
+            // Raw values, such as `case one = 1`.
+            if self.eat_punct('=') {
+                self.skip_expression()?;
+            }
+            elements.push(EnumElement { name, payload });
+            if !self.eat_punct(',') {
+                break;
+            }
+        }
+        Ok(EnumCase {
+            attributes,
+            modifiers,
+            elements,
+        })
+    }
+
+    fn parse_payload(&mut self) -> Result<Vec<TupleElement>> {
+        self.expect_punct('(')?;
+        let mut elements = Vec::new();
+        while !self.eat_punct(')') {
+            let label = match (self.peek(), self.peek_nth(1), self.peek_nth(2)) {
+                (TokenKind::Ident(label), TokenKind::Punct(':'), _) => {
+                    let label = label.clone();
+                    self.bump();
+                    self.bump();
+                    Some(label)
+                }
+                // `_ name: T`
+                (TokenKind::Ident(label), TokenKind::Ident(_), TokenKind::Punct(':')) => {
+                    let label = label.clone();
+                    self.bump();
+                    self.bump();
+                    self.bump();
+                    Some(label).filter(|label| label != "_")
+                }
+                _ => None,
+            };
+            let ty = self.parse_type()?;
+            if self.eat_punct('=') {
+                self.skip_expression()?;
+            }
+            elements.push(TupleElement { label, ty });
+            if !self.eat_punct(',') {
+                self.expect_punct(')')?;
+                break;
+            }
+        }
+        Ok(elements)
+    }
+
+    fn parse_type_alias(
+        &mut self,
+        attributes: Vec<Attribute>,
+        modifiers: Vec<String>,
+    ) -> Result<TypeAlias> {
+        let name = self.expect_ident()?;
+        let mut generics = self.parse_generic_params()?;
+        self.expect_punct('=')?;
+        let ty = self.parse_type()?;
+        generics.requirements.extend(self.parse_where_clause()?);
+
+        Ok(TypeAlias {
+            attributes,
+            modifiers,
+            name,
+            generics,
+            ty,
+        })
+    }
+
+    fn parse_associated_type(&mut self) -> Result<AssociatedType> {
+        let name = self.expect_ident()?;
+        let inherited = self.parse_inheritance()?;
+        let default = if self.eat_punct('=') {
+            Some(self.parse_type()?)
+        } else {
+            None
+        };
+        let requirements = self.parse_where_clause()?;
+
+        Ok(AssociatedType {
+            name,
+            inherited,
+            default,
+            requirements,
+        })
+    }
+
+    // Types -------------------------------------------------------------------
+
+    pub fn parse_type(&mut self) -> Result<Type> {
+        let mut attributes = self.parse_attributes()?;
+
+        let mut ty = if self.eat_ident("some") {
+            Type::Opaque(Box::new(self.parse_type()?))
+        } else if self.eat_ident("any") {
+            Type::Existential(Box::new(self.parse_type()?))
+        } else {
+            self.parse_composition()?
+        };
+
+        if let Type::Function(function) = &mut ty {
+            if let Some(i) = attributes.iter().position(|a| a.name == "escaping") {
+                attributes.remove(i);
+                function.is_escaping = true;
+            }
+            function.attributes = attributes;
+        }
+        Ok(ty)
+    }
+
+    fn parse_composition(&mut self) -> Result<Type> {
+        let first = self.parse_postfix_type()?;
+        if !self.is_punct('&') {
+            return Ok(first);
+        }
+        let mut types = vec![first];
+        while self.eat_punct('&') {
+            types.push(self.parse_postfix_type()?);
+        }
+        Ok(Type::Composition(types))
+    }
+
+    fn parse_postfix_type(&mut self) -> Result<Type> {
+        let mut ty = self.parse_primary_type()?;
+        loop {
+            if self.is_attached_punct('?') {
+                self.bump();
+                ty = Type::Optional(Box::new(ty));
+            } else if self.is_attached_punct('!') {
+                self.bump();
+                ty = Type::ImplicitlyUnwrappedOptional(Box::new(ty));
+            } else if self.is_punct('.')
+                && matches!(self.peek_nth(1), TokenKind::Ident(i) if i == "Type")
+            {
+                self.bump();
+                self.bump();
+                ty = Type::Metatype(Box::new(ty));
+            } else if self.is_punct('.')
+                && matches!(self.peek_nth(1), TokenKind::Ident(i) if i == "Protocol")
+            {
+                self.bump();
+                self.bump();
+                ty = Type::ProtocolMetatype(Box::new(ty));
+            } else {
+                return Ok(ty);
+            }
+        }
+    }
+
+    fn parse_primary_type(&mut self) -> Result<Type> {
+        match self.peek() {
+            TokenKind::Punct('(') => self.parse_paren_type(),
+            TokenKind::Punct('[') => {
+                self.bump();
+                let element = self.parse_type()?;
+                let ty = if self.eat_punct(':') {
+                    let value = self.parse_type()?;
+                    Type::Dictionary(Box::new(element), Box::new(value))
+                } else {
+                    Type::Array(Box::new(element))
+                };
+                self.expect_punct(']')?;
+                Ok(ty)
+            }
+            TokenKind::Ident(_) => self.parse_type_path().map(Type::Named),
+            _ => Err(self.unexpected("a type")),
+        }
+    }
+
+    fn parse_type_path(&mut self) -> Result<TypePath> {
+        let mut components = Vec::new();
+        loop {
+            let name = self.expect_ident()?;
+            let mut generic_args = Vec::new();
+            if self.is_attached_punct('<') {
+                self.bump();
+                loop {
+                    generic_args.push(self.parse_type()?);
+                    if !self.eat_punct(',') {
+                        break;
+                    }
+                }
+                self.expect_punct('>')?;
+            }
+            components.push(PathComponent { name, generic_args });
+
+            let continues = self.is_punct('.')
+                && matches!(self.peek_nth(1), TokenKind::Ident(i) if i != "Type" && i != "Protocol");
+            if !continues {
+                return Ok(TypePath { components });
+            }
+            self.bump();
+        }
+    }
+
+    /// Parses a parenthesized type, tuple type or function type.
+    fn parse_paren_type(&mut self) -> Result<Type> {
+        self.expect_punct('(')?;
+
+        let mut labels = Vec::new();
+        let mut params = Vec::new();
+        while !self.eat_punct(')') {
+            let label = match (self.peek(), self.peek_nth(1), self.peek_nth(2)) {
+                (TokenKind::Ident(label), TokenKind::Punct(':'), _) => {
+                    let label = label.clone();
+                    self.bump();
+                    self.bump();
+                    Some(label)
+                }
+                // Function type parameters may name their arguments,
+                // as in `(_ x: Int) -> Void`.
+                (TokenKind::Ident(label), TokenKind::Ident(_), TokenKind::Punct(':')) => {
+                    let label = label.clone();
+                    self.bump();
+                    self.bump();
+                    self.bump();
+                    Some(label).filter(|label| label != "_")
+                }
+                _ => None,
+            };
+            self.parse_attributes()?;
+            let ownership = self.parse_ownership();
+            let ty = self.parse_type()?;
+            let is_variadic = self.peek() == &TokenKind::Ellipsis;
+            if is_variadic {
+                self.bump();
+            }
+            labels.push(label);
+            params.push(FunctionTypeParam {
+                ty,
+                ownership,
+                is_variadic,
+            });
+            if !self.eat_punct(',') {
+                self.expect_punct(')')?;
+                break;
+            }
+        }
+
+        let (is_async, throws) = self.parse_effects()?;
+        if is_async || throws || self.peek() == &TokenKind::Arrow {
+            if self.peek() != &TokenKind::Arrow {
+                return Err(self.unexpected("`->`"));
+            }
+            self.bump();
+            let result = self.parse_type()?;
+            return Ok(Type::Function(Box::new(FunctionType {
+                params,
+                result,
+                is_async,
+                throws,
+                is_escaping: false,
+                attributes: Vec::new(),
+            })));
+        }
+
+        if params.len() == 1 && labels[0].is_none() && !params[0].is_variadic {
+            return Ok(params.pop().unwrap().ty);
+        }
+        Ok(Type::Tuple(
+            labels
+                .into_iter()
+                .zip(params)
+                .map(|(label, param)| TupleElement {
+                    label,
+                    ty: param.ty,
+                })
+                .collect(),
+        ))
+    }
+}
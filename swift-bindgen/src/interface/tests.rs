@@ -0,0 +1,251 @@
+#![cfg(test)]
+
+use super::*;
+use crate::ir::*;
+
+const SHAPES: &str = include_str!("../../fixtures/Shapes.swiftinterface");
+
+fn parse_decls(source: &str) -> Vec<Decl> {
+    parse_named(source, Some("Test")).unwrap().decls
+}
+
+fn nominal(decl: &Decl) -> &NominalDecl {
+    match decl {
+        Decl::Nominal(decl) => decl,
+        decl => panic!("expected nominal, found {:?}", decl),
+    }
+}
+
+fn function(decl: &Decl) -> &Function {
+    match decl {
+        Decl::Function(decl) => decl,
+        decl => panic!("expected function, found {:?}", decl),
+    }
+}
+
+#[test]
+fn module_header() {
+    let module = parse(SHAPES).unwrap();
+
+    assert_eq!(module.name, "Shapes");
+    assert!(module.is_resilient());
+    assert_eq!(module.imports.len(), 2);
+    assert_eq!(module.imports[0].path, ["Swift"]);
+}
+
+#[test]
+fn missing_module_name() {
+    let error = parse("public func f()").unwrap_err();
+    assert_eq!((error.line(), error.column()), (1, 1));
+
+    let module = parse_named("public func f()", Some("Fallback")).unwrap();
+    assert_eq!(module.name, "Fallback");
+}
+
+#[test]
+fn shapes_declarations() {
+    let module = parse(SHAPES).unwrap();
+
+    let point = nominal(&module.decls[0]);
+    assert_eq!(point.kind, NominalKind::Struct);
+    assert_eq!(point.name, "Point");
+    assert!(has_attribute(&point.attributes, "frozen"));
+
+    let stored: Vec<&str> = point
+        .decls
+        .iter()
+        .filter_map(|decl| match decl {
+            Decl::Variable(var) if var.is_stored() => Some(var.name.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(stored, ["x", "y"]);
+
+    let kinds: Vec<(NominalKind, &str)> = module
+        .decls
+        .iter()
+        .filter_map(|decl| match decl {
+            Decl::Nominal(decl) => Some((decl.kind, decl.name.as_str())),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            (NominalKind::Struct, "Point"),
+            (NominalKind::Struct, "Rect"),
+            (NominalKind::Struct, "Size"),
+            (NominalKind::Enum, "ShapeKind"),
+            (NominalKind::Struct, "Polygon"),
+            (NominalKind::Struct, "Label"),
+            (NominalKind::Class, "Shape"),
+            (NominalKind::Class, "Circle"),
+            (NominalKind::Enum, "Fill"),
+            (NominalKind::Protocol, "Drawable"),
+        ]
+    );
+}
+
+#[test]
+fn functions() {
+    let decls = parse_decls(
+        "public func distance(from a: Swift.Int, to b: inout Swift.Int, _ c: Swift.Int...) async throws -> Swift.Int
+         @discardableResult public static func == (lhs: Self, rhs: Self) -> Swift.Bool { lhs.x == rhs.x }
+         public func largest<T>(_ values: [T] = []) -> T? where T : Swift.Comparable",
+    );
+
+    let distance = function(&decls[0]);
+    assert_eq!(distance.name, "distance");
+    assert!(distance.is_async && distance.throws);
+    assert_eq!(distance.result, Type::named("Swift.Int"));
+
+    let labels: Vec<Option<&str>> = distance.params.iter().map(|p| p.label.as_deref()).collect();
+    assert_eq!(labels, [Some("from"), Some("to"), None]);
+    assert_eq!(distance.params[1].ownership, Ownership::InOut);
+    assert!(distance.params[2].is_variadic);
+
+    let eq = function(&decls[1]);
+    assert!(eq.is_operator());
+    assert!(has_attribute(&eq.attributes, "discardableResult"));
+    assert!(has_modifier(&eq.modifiers, "static"));
+
+    let largest = function(&decls[2]);
+    assert_eq!(largest.generics.params[0].name, "T");
+    assert!(largest.params[0].has_default);
+    assert_eq!(largest.result, Type::Optional(Box::new(Type::named("T"))));
+    assert_eq!(
+        largest.generics.requirements,
+        [Requirement::Conformance {
+            subject: Type::named("T"),
+            constraint: Type::named("Swift.Comparable"),
+        }]
+    );
+}
+
+#[test]
+fn properties() {
+    let decls = parse_decls(
+        "public struct S {
+           public var a: Swift.Int
+           public let b: Swift.Int
+           public var c: Swift.Int { get }
+           public var d: Swift.Int {
+             @inlinable get { 0 }
+             set
+           }
+           @_hasStorage public var e: Swift.Int { get set }
+         }",
+    );
+
+    let vars: Vec<&Variable> = nominal(&decls[0])
+        .decls
+        .iter()
+        .map(|decl| match decl {
+            Decl::Variable(var) => var,
+            decl => panic!("expected variable, found {:?}", decl),
+        })
+        .collect();
+
+    let stored: Vec<bool> = vars.iter().map(|var| var.is_stored()).collect();
+    assert_eq!(stored, [true, true, false, false, true]);
+
+    let settable: Vec<bool> = vars.iter().map(|var| var.is_settable()).collect();
+    assert_eq!(settable, [true, false, false, true, true]);
+}
+
+#[test]
+fn enum_cases() {
+    let decls = parse_decls(
+        "public enum E {
+           case a, b
+           case c(Swift.Int, label: Swift.String)
+           indirect case d(Test.E)
+         }",
+    );
+
+    let elements: Vec<&EnumElement> = nominal(&decls[0])
+        .decls
+        .iter()
+        .flat_map(|decl| match decl {
+            Decl::EnumCase(case) => case.elements.iter(),
+            decl => panic!("expected case, found {:?}", decl),
+        })
+        .collect();
+
+    let names: Vec<&str> = elements.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, ["a", "b", "c", "d"]);
+    assert!(elements[0].payload.is_none());
+
+    let payload = elements[2].payload.as_ref().unwrap();
+    assert_eq!(payload[0].label, None);
+    assert_eq!(payload[1].label.as_deref(), Some("label"));
+}
+
+#[test]
+fn types() {
+    let decls = parse_decls(
+        "public func f(
+           a: [Swift.String : [Swift.Int]],
+           b: @escaping (Swift.Int, inout Swift.Double) throws -> (),
+           c: Swift.AnyObject.Type,
+           d: any Swift.Equatable & Swift.Hashable,
+           e: (x: Swift.Int, y: Swift.Int)?
+         ) -> some Swift.Collection",
+    );
+    let f = function(&decls[0]);
+
+    let rendered: Vec<String> = f.params.iter().map(|p| p.ty.to_string()).collect();
+    assert_eq!(
+        rendered,
+        [
+            "[Swift.String: [Swift.Int]]",
+            "@escaping (Swift.Int, inout Swift.Double) throws -> ()",
+            "Swift.AnyObject.Type",
+            "any Swift.Equatable & Swift.Hashable",
+            "(x: Swift.Int, y: Swift.Int)?",
+        ]
+    );
+    assert_eq!(f.result.to_string(), "some Swift.Collection");
+}
+
+#[test]
+fn conditional_compilation() {
+    let decls = parse_decls(
+        "#if compiler(>=5.3) && $AsyncAwait
+         public func new() async
+         #else
+         public func old()
+         #endif
+         public func after()",
+    );
+
+    let names: Vec<&str> = decls
+        .iter()
+        .map(|decl| function(decl).name.as_str())
+        .collect();
+    assert_eq!(names, ["new", "after"]);
+}
+
+#[test]
+fn extensions() {
+    let decls = parse_decls(
+        "extension Swift.Array : Test.P where Element : Swift.Equatable {
+           public func f()
+         }",
+    );
+
+    let ext = match &decls[0] {
+        Decl::Extension(ext) => ext,
+        decl => panic!("expected extension, found {:?}", decl),
+    };
+    assert_eq!(ext.extended, Type::named("Swift.Array"));
+    assert_eq!(ext.inherited, [Type::named("Test.P")]);
+    assert_eq!(ext.requirements.len(), 1);
+    assert_eq!(ext.decls.len(), 1);
+}
+
+#[test]
+fn error_position() {
+    let error = parse_named("public struct S {\n  public var x: \n}", Some("Test")).unwrap_err();
+    assert_eq!(error.line(), 3);
+}
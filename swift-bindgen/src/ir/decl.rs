@@ -0,0 +1,484 @@
+use super::{Attribute, Ownership, TupleElement, Type};
+
+/// A parsed Swift module interface.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Module {
+    /// The module's name, as given by `-module-name`.
+    pub name: String,
+
+    /// The flags from the `swift-module-flags` header comment.
+    pub flags: Vec<String>,
+
+    /// Modules imported by the interface.
+    pub imports: Vec<Import>,
+
+    /// Top-level declarations.
+    pub decls: Vec<Decl>,
+}
+
+impl Module {
+    /// Returns `true` if the module was built with library evolution enabled.
+    ///
+    /// Non-`@frozen` types in such modules have resilient layouts.
+    pub fn is_resilient(&self) -> bool {
+        self.flags
+            .iter()
+            .any(|flag| flag == "-enable-library-evolution")
+    }
+}
+
+/// An `import` declaration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Import {
+    /// Attributes such as `@_exported`.
+    pub attributes: Vec<Attribute>,
+
+    /// The imported module path, such as `["Foundation"]`.
+    pub path: Vec<String>,
+}
+
+/// A declaration in a module or in the body of a type.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Decl {
+    /// A `struct`, `enum`, `class`, `actor` or `protocol`.
+    Nominal(NominalDecl),
+
+    /// An `extension`.
+    Extension(Extension),
+
+    /// A `func`.
+    Function(Function),
+
+    /// An `init`.
+    Initializer(Initializer),
+
+    /// A `deinit`.
+    Deinitializer,
+
+    /// A `var` or `let`.
+    Variable(Variable),
+
+    /// A `subscript`.
+    Subscript(Subscript),
+
+    /// A `case` within an enum.
+    EnumCase(EnumCase),
+
+    /// A `typealias`.
+    TypeAlias(TypeAlias),
+
+    /// An `associatedtype` within a protocol.
+    AssociatedType(AssociatedType),
+}
+
+/// The kind of a [`NominalDecl`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NominalKind {
+    /// `struct`
+    Struct,
+
+    /// `enum`
+    Enum,
+
+    /// `class`
+    Class,
+
+    /// `actor`
+    Actor,
+
+    /// `protocol`
+    Protocol,
+}
+
+impl NominalKind {
+    /// Returns the keyword that introduces this kind of declaration.
+    pub fn keyword(self) -> &'static str {
+        match self {
+            Self::Struct => "struct",
+            Self::Enum => "enum",
+            Self::Class => "class",
+            Self::Actor => "actor",
+            Self::Protocol => "protocol",
+        }
+    }
+}
+
+/// A nominal type declaration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NominalDecl {
+    /// The kind of type.
+    pub kind: NominalKind,
+
+    /// Attributes such as `@frozen`.
+    pub attributes: Vec<Attribute>,
+
+    /// Modifiers such as `public` or `final`.
+    pub modifiers: Vec<String>,
+
+    /// The unqualified name of the type.
+    pub name: String,
+
+    /// Generic parameters and requirements.
+    pub generics: Generics,
+
+    /// The superclass and/or conformances in the inheritance clause.
+    pub inherited: Vec<Type>,
+
+    /// Member declarations.
+    pub decls: Vec<Decl>,
+}
+
+/// An `extension` declaration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Extension {
+    /// Attributes on the extension.
+    pub attributes: Vec<Attribute>,
+
+    /// Modifiers on the extension.
+    pub modifiers: Vec<String>,
+
+    /// The extended type.
+    pub extended: Type,
+
+    /// Conformances added by the extension.
+    pub inherited: Vec<Type>,
+
+    /// Requirements from a `where` clause.
+    pub requirements: Vec<Requirement>,
+
+    /// Member declarations.
+    pub decls: Vec<Decl>,
+}
+
+/// Generic parameters and requirements of a declaration.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Generics {
+    /// Parameters in angle brackets.
+    pub params: Vec<GenericParam>,
+
+    /// Requirements from inline constraints and the `where` clause.
+    pub requirements: Vec<Requirement>,
+}
+
+impl Generics {
+    /// Returns `true` if there are no parameters or requirements.
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty() && self.requirements.is_empty()
+    }
+}
+
+/// A generic parameter, such as `T` in `Array<T>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GenericParam {
+    /// The parameter's name.
+    pub name: String,
+}
+
+/// A generic requirement.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Requirement {
+    /// `T: P`, where `P` is a protocol, composition, class or layout
+    /// constraint such as `AnyObject`.
+    Conformance {
+        /// The constrained type.
+        subject: Type,
+        /// The constraint.
+        constraint: Type,
+    },
+
+    /// `T == U`
+    SameType {
+        /// The left-hand side.
+        left: Type,
+        /// The right-hand side.
+        right: Type,
+    },
+}
+
+/// A `func` declaration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Function {
+    /// Attributes such as `@discardableResult`.
+    pub attributes: Vec<Attribute>,
+
+    /// Modifiers such as `static` or `mutating`.
+    pub modifiers: Vec<String>,
+
+    /// The base name; for operators this is the operator itself, like `==`.
+    pub name: String,
+
+    /// Generic parameters and requirements.
+    pub generics: Generics,
+
+    /// The parameter list.
+    pub params: Vec<Param>,
+
+    /// Whether the function is `async`.
+    pub is_async: bool,
+
+    /// Whether the function `throws` or `rethrows`.
+    pub throws: bool,
+
+    /// The result type, which is `()` when omitted.
+    pub result: Type,
+}
+
+impl Function {
+    /// Returns `true` if the name is an operator rather than an identifier.
+    pub fn is_operator(&self) -> bool {
+        self.name
+            .chars()
+            .next()
+            .is_some_and(|c| !(c == '_' || c.is_alphanumeric()))
+    }
+}
+
+/// A parameter of a function, initializer or subscript.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Param {
+    /// The argument label, or `None` for `_`.
+    pub label: Option<String>,
+
+    /// The name used within the declaration.
+    pub name: String,
+
+    /// The parameter type.
+    pub ty: Type,
+
+    /// How ownership of the argument is passed.
+    pub ownership: Ownership,
+
+    /// Whether the parameter is variadic (`T...`).
+    pub is_variadic: bool,
+
+    /// Whether the parameter has a default argument.
+    pub has_default: bool,
+}
+
+/// Whether an initializer can fail.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Failability {
+    /// `init`
+    None,
+
+    /// `init?`
+    Optional,
+
+    /// `init!`
+    ImplicitlyUnwrapped,
+}
+
+/// An `init` declaration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Initializer {
+    /// Attributes on the initializer.
+    pub attributes: Vec<Attribute>,
+
+    /// Modifiers such as `convenience` or `required`.
+    pub modifiers: Vec<String>,
+
+    /// Whether the initializer can fail.
+    pub failability: Failability,
+
+    /// Generic parameters and requirements.
+    pub generics: Generics,
+
+    /// The parameter list.
+    pub params: Vec<Param>,
+
+    /// Whether the initializer is `async`.
+    pub is_async: bool,
+
+    /// Whether the initializer `throws` or `rethrows`.
+    pub throws: bool,
+}
+
+/// A `var` or `let` declaration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Variable {
+    /// Attributes such as `@_hasStorage`.
+    pub attributes: Vec<Attribute>,
+
+    /// Modifiers such as `static` or `weak`.
+    pub modifiers: Vec<String>,
+
+    /// Whether this was declared with `let`.
+    pub is_let: bool,
+
+    /// The variable's name.
+    pub name: String,
+
+    /// The variable's type.
+    pub ty: Type,
+
+    /// The accessors declared in braces after the type, if any.
+    pub accessors: Vec<Accessor>,
+}
+
+impl Variable {
+    /// Returns `true` if the variable has storage rather than being computed.
+    pub fn is_stored(&self) -> bool {
+        self.accessors.is_empty() || has_attribute(&self.attributes, "_hasStorage")
+    }
+
+    /// Returns `true` if the variable can be assigned from outside its module.
+    pub fn is_settable(&self) -> bool {
+        if self.is_let {
+            return false;
+        }
+        if self.accessors.is_empty() {
+            return true;
+        }
+        self.accessors.iter().any(|accessor| {
+            matches!(
+                accessor.kind,
+                AccessorKind::Set | AccessorKind::Modify | AccessorKind::MutableAddress
+            )
+        })
+    }
+}
+
+/// An accessor listed in a property or subscript declaration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Accessor {
+    /// Modifiers such as `mutating` or `nonmutating`.
+    pub modifiers: Vec<String>,
+
+    /// What the accessor does.
+    pub kind: AccessorKind,
+}
+
+/// The kind of an [`Accessor`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AccessorKind {
+    /// `get`
+    Get,
+
+    /// `set`
+    Set,
+
+    /// `_read`
+    Read,
+
+    /// `_modify`
+    Modify,
+
+    /// `unsafeAddress`
+    Address,
+
+    /// `unsafeMutableAddress`
+    MutableAddress,
+
+    /// `willSet`
+    WillSet,
+
+    /// `didSet`
+    DidSet,
+}
+
+impl AccessorKind {
+    /// Returns the accessor kind for a keyword within an accessor block.
+    pub fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword {
+            "get" => Some(Self::Get),
+            "set" => Some(Self::Set),
+            "_read" => Some(Self::Read),
+            "_modify" => Some(Self::Modify),
+            "unsafeAddress" => Some(Self::Address),
+            "unsafeMutableAddress" => Some(Self::MutableAddress),
+            "willSet" => Some(Self::WillSet),
+            "didSet" => Some(Self::DidSet),
+            _ => None,
+        }
+    }
+}
+
+/// A `subscript` declaration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Subscript {
+    /// Attributes on the subscript.
+    pub attributes: Vec<Attribute>,
+
+    /// Modifiers such as `static`.
+    pub modifiers: Vec<String>,
+
+    /// Generic parameters and requirements.
+    pub generics: Generics,
+
+    /// The index parameters.
+    pub params: Vec<Param>,
+
+    /// The element type.
+    pub result: Type,
+
+    /// The declared accessors.
+    pub accessors: Vec<Accessor>,
+}
+
+/// A `case` declaration, which may declare several elements.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnumCase {
+    /// Attributes on the case.
+    pub attributes: Vec<Attribute>,
+
+    /// Modifiers such as `indirect`.
+    pub modifiers: Vec<String>,
+
+    /// The declared elements.
+    pub elements: Vec<EnumElement>,
+}
+
+/// A single enum element, such as `circle(radius: Swift.Double)`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnumElement {
+    /// The element's name.
+    pub name: String,
+
+    /// The associated values, or `None` if the element has no payload.
+    pub payload: Option<Vec<TupleElement>>,
+}
+
+/// A `typealias` declaration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypeAlias {
+    /// Attributes on the alias.
+    pub attributes: Vec<Attribute>,
+
+    /// Modifiers on the alias.
+    pub modifiers: Vec<String>,
+
+    /// The alias name.
+    pub name: String,
+
+    /// Generic parameters and requirements.
+    pub generics: Generics,
+
+    /// The aliased type.
+    pub ty: Type,
+}
+
+/// An `associatedtype` declaration within a protocol.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssociatedType {
+    /// The associated type's name.
+    pub name: String,
+
+    /// Protocols or a superclass that the type must conform to.
+    pub inherited: Vec<Type>,
+
+    /// The default type, if any.
+    pub default: Option<Type>,
+
+    /// Requirements from a `where` clause.
+    pub requirements: Vec<Requirement>,
+}
+
+/// Returns `true` if `attributes` contains one named `name`.
+pub fn has_attribute(attributes: &[Attribute], name: &str) -> bool {
+    attributes.iter().any(|attribute| attribute.name == name)
+}
+
+/// Returns `true` if `modifiers` contains `modifier`.
+pub fn has_modifier(modifiers: &[String], modifier: &str) -> bool {
+    modifiers.iter().any(|m| m == modifier)
+}
@@ -0,0 +1,10 @@
+//! Intermediate representation of a Swift module's public interface.
+//!
+//! This is a syntactic model: types are kept as written (e.g. `Swift.Int`)
+//! and are only resolved when generating bindings.
+
+mod decl;
+mod ty;
+
+pub use decl::*;
+pub use ty::*;
@@ -0,0 +1,322 @@
+use std::fmt;
+
+/// A type as written in a Swift interface.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Type {
+    /// A named type, such as `Swift.Int` or `Swift.Array<Shapes.Point>`.
+    Named(TypePath),
+
+    /// A tuple type, such as `(x: Swift.Int, y: Swift.Int)`.
+    ///
+    /// The empty tuple is `()`, also known as `Void`.
+    Tuple(Vec<TupleElement>),
+
+    /// A function type, such as `(Swift.Int) throws -> Swift.Int`.
+    Function(Box<FunctionType>),
+
+    /// An optional type: `T?`.
+    Optional(Box<Type>),
+
+    /// An implicitly-unwrapped optional type: `T!`.
+    ImplicitlyUnwrappedOptional(Box<Type>),
+
+    /// Array sugar: `[T]`.
+    Array(Box<Type>),
+
+    /// Dictionary sugar: `[K: V]`.
+    Dictionary(Box<Type>, Box<Type>),
+
+    /// A metatype: `T.Type`.
+    Metatype(Box<Type>),
+
+    /// A protocol metatype: `P.Protocol`.
+    ProtocolMetatype(Box<Type>),
+
+    /// A protocol composition: `P & Q`.
+    Composition(Vec<Type>),
+
+    /// An opaque result type: `some P`.
+    Opaque(Box<Type>),
+
+    /// An explicit existential type: `any P`.
+    Existential(Box<Type>),
+}
+
+impl Type {
+    /// The empty tuple type, `()`.
+    pub const VOID: Self = Self::Tuple(Vec::new());
+
+    /// Creates a named type from a dot-separated path without generic
+    /// arguments.
+    pub fn named(path: &str) -> Self {
+        Self::Named(TypePath {
+            components: path
+                .split('.')
+                .map(|name| PathComponent {
+                    name: name.to_owned(),
+                    generic_args: Vec::new(),
+                })
+                .collect(),
+        })
+    }
+
+    /// Returns `true` if this is `()` or `Swift.Void`.
+    pub fn is_void(&self) -> bool {
+        match self {
+            Self::Tuple(elements) => elements.is_empty(),
+            Self::Named(path) => path.is_plain(&["Swift", "Void"]) || path.is_plain(&["Void"]),
+            _ => false,
+        }
+    }
+
+    /// Returns the path if this is a named type.
+    pub fn as_path(&self) -> Option<&TypePath> {
+        match self {
+            Self::Named(path) => Some(path),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Named(path) => path.fmt(f),
+            Self::Tuple(elements) => {
+                f.write_str("(")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i != 0 {
+                        f.write_str(", ")?;
+                    }
+                    if let Some(label) = &element.label {
+                        write!(f, "{}: ", label)?;
+                    }
+                    element.ty.fmt(f)?;
+                }
+                f.write_str(")")
+            }
+            Self::Function(function) => function.fmt(f),
+            Self::Optional(ty) => write!(f, "{}?", ty),
+            Self::ImplicitlyUnwrappedOptional(ty) => write!(f, "{}!", ty),
+            Self::Array(ty) => write!(f, "[{}]", ty),
+            Self::Dictionary(key, value) => write!(f, "[{}: {}]", key, value),
+            Self::Metatype(ty) => write!(f, "{}.Type", ty),
+            Self::ProtocolMetatype(ty) => write!(f, "{}.Protocol", ty),
+            Self::Composition(types) => {
+                for (i, ty) in types.iter().enumerate() {
+                    if i != 0 {
+                        f.write_str(" & ")?;
+                    }
+                    ty.fmt(f)?;
+                }
+                Ok(())
+            }
+            Self::Opaque(ty) => write!(f, "some {}", ty),
+            Self::Existential(ty) => write!(f, "any {}", ty),
+        }
+    }
+}
+
+/// A dot-separated type path, such as `Swift.Unicode.Scalar`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TypePath {
+    /// The components of the path, outermost first.
+    pub components: Vec<PathComponent>,
+}
+
+impl TypePath {
+    /// Returns `true` if the path's components have the given names and none
+    /// have generic arguments.
+    pub fn is_plain(&self, names: &[&str]) -> bool {
+        self.components.len() == names.len()
+            && self
+                .components
+                .iter()
+                .zip(names)
+                .all(|(c, name)| c.generic_args.is_empty() && c.name == *name)
+    }
+
+    /// Returns the last component of the path.
+    pub fn last(&self) -> &PathComponent {
+        self.components.last().expect("empty type path")
+    }
+
+    /// Returns the dot-separated names of the path without generic arguments.
+    pub fn qualified_name(&self) -> String {
+        let names: Vec<&str> = self.components.iter().map(|c| c.name.as_str()).collect();
+        names.join(".")
+    }
+}
+
+impl fmt::Display for TypePath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, component) in self.components.iter().enumerate() {
+            if i != 0 {
+                f.write_str(".")?;
+            }
+            f.write_str(&component.name)?;
+            if !component.generic_args.is_empty() {
+                f.write_str("<")?;
+                for (j, arg) in component.generic_args.iter().enumerate() {
+                    if j != 0 {
+                        f.write_str(", ")?;
+                    }
+                    arg.fmt(f)?;
+                }
+                f.write_str(">")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A single component of a [`TypePath`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PathComponent {
+    /// The identifier of this component.
+    pub name: String,
+
+    /// Generic arguments applied to this component, such as `Int` in
+    /// `Array<Int>`.
+    pub generic_args: Vec<Type>,
+}
+
+/// An element of a tuple type or an enum case payload.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TupleElement {
+    /// The element's label, if any.
+    pub label: Option<String>,
+
+    /// The element's type.
+    pub ty: Type,
+}
+
+/// A function type, such as `@escaping (Swift.Int) throws -> Swift.Bool`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FunctionType {
+    /// The parameter types.
+    pub params: Vec<FunctionTypeParam>,
+
+    /// The result type.
+    pub result: Type,
+
+    /// Whether the function is `async`.
+    pub is_async: bool,
+
+    /// Whether the function `throws`.
+    pub throws: bool,
+
+    /// Whether the function type was marked `@escaping`.
+    pub is_escaping: bool,
+
+    /// Attributes other than `@escaping`, such as `@Sendable` or
+    /// `@convention(c)`.
+    pub attributes: Vec<Attribute>,
+}
+
+impl fmt::Display for FunctionType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_escaping {
+            f.write_str("@escaping ")?;
+        }
+        for attribute in &self.attributes {
+            write!(f, "{} ", attribute)?;
+        }
+        f.write_str("(")?;
+        for (i, param) in self.params.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            param.fmt(f)?;
+        }
+        f.write_str(")")?;
+        if self.is_async {
+            f.write_str(" async")?;
+        }
+        if self.throws {
+            f.write_str(" throws")?;
+        }
+        write!(f, " -> {}", self.result)
+    }
+}
+
+/// A parameter of a [`FunctionType`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FunctionTypeParam {
+    /// The parameter type.
+    pub ty: Type,
+
+    /// How ownership of the argument is passed.
+    pub ownership: Ownership,
+
+    /// Whether the parameter is variadic (`T...`).
+    pub is_variadic: bool,
+}
+
+impl fmt::Display for FunctionTypeParam {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(keyword) = self.ownership.keyword() {
+            write!(f, "{} ", keyword)?;
+        }
+        self.ty.fmt(f)?;
+        if self.is_variadic {
+            f.write_str("...")?;
+        }
+        Ok(())
+    }
+}
+
+/// The explicit ownership convention of a parameter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Ownership {
+    /// No explicit convention was written.
+    Default,
+
+    /// `inout`
+    InOut,
+
+    /// `__owned` or `consuming`
+    Owned,
+
+    /// `__shared` or `borrowing`
+    Shared,
+}
+
+impl Default for Ownership {
+    #[inline]
+    fn default() -> Self {
+        Self::Default
+    }
+}
+
+impl Ownership {
+    /// Returns the keyword used to spell this convention.
+    pub fn keyword(self) -> Option<&'static str> {
+        match self {
+            Self::Default => None,
+            Self::InOut => Some("inout"),
+            Self::Owned => Some("__owned"),
+            Self::Shared => Some("__shared"),
+        }
+    }
+}
+
+/// An attribute such as `@frozen` or `@available(macOS 10.15, *)`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Attribute {
+    /// The attribute name without the `@`.
+    pub name: String,
+
+    /// The source text between the attribute's parentheses, if any.
+    pub arguments: Option<String>,
+}
+
+impl fmt::Display for Attribute {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "@{}", self.name)?;
+        if let Some(arguments) = &self.arguments {
+            write!(f, "({})", arguments)?;
+        }
+        Ok(())
+    }
+}
@@ -2,6 +2,35 @@
 //!
 //! Bindings generator for two-way bridging of Rust/Swift types.
 //!
+//! ## Usage
+//!
+//! Bindings are generated from the textual `.swiftinterface` file emitted by
+//! `swiftc -emit-module-interface`:
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let interface = std::fs::read_to_string("Shapes.swiftinterface")?;
+//! let bindings = swift_bindgen::Bindings::from_interface(&interface)?;
+//! bindings.write_to_file("src/shapes.rs")?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Types can also be recovered from the reflection metadata of a compiled ELF
+//! library with [`Bindings::from_binary`], for when no interface is available.
+//!
+//! In the other direction, [`export::Exports`] generates Swift wrappers and a C
+//! module for the `extern "C"` functions and `#[repr(C)]` types of Rust
+//! source, so that Swift can call into Rust.
+//!
+//! A [`Builder`] finds interfaces by module name, filters declarations, and
+//! tells Cargo how to link against Swift when used from a build script. The
+//! `swift-bindgen` binary exposes the same options on the command line; see
+//! `swift-bindgen --help`.
+//!
+//! The generated code refers to the [`swift`](https://docs.rs/swift) crate,
+//! which must be a dependency of the crate that includes it.
+//!
 //! ## Contribute
 //!
 //! This is a work-in-progress by [Nikolai Vazquez](https://twitter.com/nikolaivazquez).
@@ -14,3 +43,16 @@
 
 #![warn(missing_docs)]
 #![allow(clippy::module_inception)]
+
+pub mod binary;
+pub mod export;
+pub mod interface;
+pub mod ir;
+
+mod bindings;
+mod builder;
+mod codegen;
+mod mangle;
+
+pub use bindings::*;
+pub use builder::*;
@@ -0,0 +1,234 @@
+//! The `swift-bindgen` command-line interface to [`swift_bindgen::Builder`].
+
+use std::{
+    env,
+    ffi::OsString,
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+    process,
+};
+use swift_bindgen::Builder;
+
+const USAGE: &str = "\
+Generates Rust bindings for Swift modules.
+
+USAGE:
+    swift-bindgen [OPTIONS] [INTERFACE]...
+
+ARGS:
+    <INTERFACE>...    `.swiftinterface` files to generate bindings for
+
+OPTIONS:
+    -m, --module <NAME>                      Module to find in the search paths
+    -I, --module-search-path <DIR>           Directory in which to find modules
+        --binary <PATH>                      Swift library to link against or read types from
+        --link-name <NAME>                   Library that the bindings link against
+        --allowlist-type <REGEX>             Generate only matching types
+        --allowlist-function <REGEX>         Generate only matching functions
+        --blocklist-type <REGEX>             Omit the definitions of matching types
+        --blocklist-function <REGEX>         Omit matching functions
+    -o, --output <PATH>                      Write to a file instead of stdout
+        --no-rustfmt-bindings                Don't format the bindings with rustfmt
+        --rustfmt-path <PATH>                The rustfmt executable to use
+        --rustfmt-configuration-file <PATH>  The rustfmt.toml to format with
+        --emit-cargo-metadata                Print instructions for Cargo
+        --swift-library-path <DIR>           Directory of the Swift runtime libraries
+        --dump-ir                            Print the parsed modules instead of bindings
+    -h, --help                               Print this help
+    -V, --version                            Print the version
+";
+
+/// The action requested by the command-line arguments.
+#[derive(Debug)]
+enum Command {
+    Help,
+    Version,
+    Generate {
+        builder: Box<Builder>,
+        output: Option<PathBuf>,
+        dump_ir: bool,
+    },
+}
+
+fn main() {
+    let result = parse_args(env::args_os().skip(1)).and_then(|command| match command {
+        Command::Help => {
+            print!("{}", USAGE);
+            Ok(())
+        }
+        Command::Version => {
+            println!("swift-bindgen {}", env!("CARGO_PKG_VERSION"));
+            Ok(())
+        }
+        Command::Generate {
+            builder,
+            output,
+            dump_ir,
+        } => run(*builder, output, dump_ir),
+    });
+
+    if let Err(error) = result {
+        eprintln!("error: {}", error);
+        process::exit(1);
+    }
+}
+
+fn run(builder: Builder, output: Option<PathBuf>, dump_ir: bool) -> Result<(), String> {
+    let contents = if dump_ir {
+        let modules = builder.parse().map_err(|error| error.to_string())?;
+        modules
+            .iter()
+            .map(|module| format!("{:#?}\n", module))
+            .collect()
+    } else {
+        let bindings = builder.generate().map_err(|error| error.to_string())?;
+        bindings.to_string()
+    };
+
+    match output {
+        Some(path) => {
+            fs::write(&path, contents).map_err(|error| format!("{}: {}", path.display(), error))
+        }
+        None => io::stdout()
+            .write_all(contents.as_bytes())
+            .map_err(|error| error.to_string()),
+    }
+}
+
+fn parse_args(args: impl IntoIterator<Item = OsString>) -> Result<Command, String> {
+    let mut args = args.into_iter();
+    let mut builder = Builder::default().emit_cargo_metadata(false);
+    let mut output = None;
+    let mut dump_ir = false;
+
+    while let Some(arg) = args.next() {
+        let arg_str = match arg.to_str() {
+            Some(arg) if arg.starts_with('-') && arg != "-" => arg,
+            _ => {
+                builder = builder.interface(arg);
+                continue;
+            }
+        };
+        if arg_str == "--" {
+            for arg in args.by_ref() {
+                builder = builder.interface(arg);
+            }
+            break;
+        }
+
+        // Long options may also be written as `--option=value`.
+        let (option, mut inline) = match arg_str.find('=') {
+            Some(i) if arg_str.starts_with("--") => {
+                (&arg_str[..i], Some(OsString::from(&arg_str[i + 1..])))
+            }
+            _ => (arg_str, None),
+        };
+        let mut value = || {
+            inline
+                .take()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("`{}` requires a value", option))
+        };
+        let string = |value: OsString| {
+            value
+                .into_string()
+                .map_err(|value| format!("`{}` is not valid UTF-8: {:?}", option, value))
+        };
+
+        builder = match option {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-V" | "--version" => return Ok(Command::Version),
+            "-m" | "--module" => builder.module(string(value()?)?),
+            "-I" | "--module-search-path" => builder.module_search_path(value()?),
+            "--binary" => builder.binary(value()?),
+            "--link-name" => builder.link_name(string(value()?)?),
+            "--allowlist-type" => builder.allowlist_type(string(value()?)?),
+            "--allowlist-function" => builder.allowlist_function(string(value()?)?),
+            "--blocklist-type" => builder.blocklist_type(string(value()?)?),
+            "--blocklist-function" => builder.blocklist_function(string(value()?)?),
+            "-o" | "--output" => {
+                output = Some(PathBuf::from(value()?));
+                builder
+            }
+            "--no-rustfmt-bindings" => builder.rustfmt_bindings(false),
+            "--rustfmt-path" => builder.rustfmt_path(value()?),
+            "--rustfmt-configuration-file" => builder.rustfmt_configuration_file(value()?),
+            "--emit-cargo-metadata" => builder.emit_cargo_metadata(true),
+            "--swift-library-path" => builder.swift_library_path(value()?),
+            "--dump-ir" => {
+                dump_ir = true;
+                builder
+            }
+            _ => return Err(format!("unknown option `{}`\n\n{}", option, USAGE)),
+        };
+
+        if inline.is_some() {
+            return Err(format!("`{}` does not take a value", option));
+        }
+    }
+
+    Ok(Command::Generate {
+        builder: Box::new(builder),
+        output,
+        dump_ir,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        parse_args(args.iter().map(OsString::from))
+    }
+
+    #[test]
+    fn options() {
+        let command = parse(&[
+            "--module=Shapes",
+            "-I",
+            "fixtures",
+            "--allowlist-type",
+            "Point",
+            "-o",
+            "shapes.rs",
+            "--dump-ir",
+        ])
+        .unwrap();
+
+        match command {
+            Command::Generate {
+                output, dump_ir, ..
+            } => {
+                assert_eq!(output, Some(PathBuf::from("shapes.rs")));
+                assert!(dump_ir);
+            }
+            command => panic!("expected generate, found {:?}", command),
+        }
+        assert!(matches!(parse(&["-o", "x", "--help"]), Ok(Command::Help)));
+    }
+
+    #[test]
+    fn invalid_options() {
+        let error = parse(&["--output"]).unwrap_err();
+        assert_eq!(error, "`--output` requires a value");
+
+        let error = parse(&["--dump-ir=yes"]).unwrap_err();
+        assert_eq!(error, "`--dump-ir` does not take a value");
+
+        let error = parse(&["--frobnicate"]).unwrap_err();
+        assert!(error.starts_with("unknown option `--frobnicate`"));
+    }
+
+    #[test]
+    fn generate_interface() {
+        let command = parse(&["--no-rustfmt-bindings", "fixtures/Shapes.swiftinterface"]);
+        let builder = match command {
+            Ok(Command::Generate { builder, .. }) => builder,
+            command => panic!("expected generate, found {:?}", command),
+        };
+        let modules = builder.parse().unwrap();
+        assert_eq!(modules[0].name, "Shapes");
+    }
+}
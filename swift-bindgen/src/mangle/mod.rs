@@ -0,0 +1,27 @@
+//! Swift 5 symbol mangling, provided by `swift_sys::mangling`.
+
+use crate::ir;
+
+pub(crate) use swift_sys::mangling::*;
+
+impl From<ir::NominalKind> for NominalKind {
+    fn from(kind: ir::NominalKind) -> Self {
+        match kind {
+            ir::NominalKind::Struct => Self::Struct,
+            ir::NominalKind::Enum => Self::Enum,
+            ir::NominalKind::Class | ir::NominalKind::Actor => Self::Class,
+            ir::NominalKind::Protocol => Self::Protocol,
+        }
+    }
+}
+
+impl From<ir::Ownership> for Ownership {
+    fn from(ownership: ir::Ownership) -> Self {
+        match ownership {
+            ir::Ownership::Default => Self::Default,
+            ir::Ownership::InOut => Self::InOut,
+            ir::Ownership::Owned => Self::Owned,
+            ir::Ownership::Shared => Self::Shared,
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! Implementation of `#[swift::export]`.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    parse::Parser, punctuated::Punctuated, spanned::Spanned, Error, FnArg, GenericArgument, Ident,
+    ItemFn, LitStr, Meta, Pat, PathArguments, ReturnType, Token, Type,
+};
+
+/// The prefix of the symbols of exported shims.
+///
+/// `swift-bindgen` calls the shims by this symbol, so it must not change.
+const SYMBOL_PREFIX: &str = "__swift_export_";
+
+pub(crate) fn expand(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let item: ItemFn = syn::parse2(item)?;
+    parse_options(attr)?;
+    check_signature(&item)?;
+
+    let sig = &item.sig;
+    let name = &sig.ident;
+    let shim = format_ident!("{}{}", SYMBOL_PREFIX, unraw(name));
+
+    let mut params = Vec::new();
+    let mut args = Vec::new();
+    let mut types = Vec::new();
+    for (i, input) in sig.inputs.iter().enumerate() {
+        let ty = match input {
+            FnArg::Typed(input) => &input.ty,
+            FnArg::Receiver(_) => unreachable!("checked above"),
+        };
+        let arg = format_ident!("arg{}", i);
+        params.push(quote!(#arg: #ty));
+        args.push(arg);
+        types.push(&**ty);
+    }
+    let ret = match &sig.output {
+        ReturnType::Default => quote!(),
+        ReturnType::Type(arrow, ty) => {
+            types.push(ty);
+            quote!(#arrow #ty)
+        }
+    };
+
+    // Generic arguments are checked where they're written, so that errors
+    // point at the offending type.
+    let mut checks = Vec::new();
+    for ty in types {
+        generic_args(ty, &mut |arg| {
+            checks.push(quote_spanned! {arg.span()=>
+                let _ = ::swift::__private::assert_type::<#arg>;
+            });
+        });
+    }
+
+    Ok(quote! {
+        #item
+
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        #[unsafe(no_mangle)]
+        extern "C" fn #shim(#(#params),*) #ret {
+            #(#checks)*
+            #name(#(#args),*)
+        }
+    })
+}
+
+/// Parses the `name = "..."` option, which only affects the Swift side.
+fn parse_options(attr: TokenStream) -> syn::Result<Option<LitStr>> {
+    let metas = Punctuated::<Meta, Token![,]>::parse_terminated.parse2(attr)?;
+    let mut name = None;
+    for meta in metas {
+        match meta {
+            Meta::NameValue(meta) if meta.path.is_ident("name") => match meta.value {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(lit),
+                    ..
+                }) => name = Some(lit),
+                value => return Err(Error::new(value.span(), "expected a string literal")),
+            },
+            meta => return Err(Error::new(meta.span(), "unknown option")),
+        }
+    }
+    Ok(name)
+}
+
+fn check_signature(item: &ItemFn) -> syn::Result<()> {
+    let sig = &item.sig;
+    let error = |span: Span, message: &str| Err(Error::new(span, message));
+
+    if let Some(param) = sig.generics.params.first() {
+        return error(
+            param.span(),
+            "generic functions can't be exported; the shim needs concrete types",
+        );
+    }
+    if let Some(token) = &sig.asyncness {
+        return error(token.span, "async functions can't be exported");
+    }
+    if let Some(token) = &sig.unsafety {
+        return error(token.span, "unsafe functions can't be exported");
+    }
+    if let Some(abi) = &sig.abi {
+        return error(
+            abi.extern_token.span,
+            "exported functions must use the Rust ABI",
+        );
+    }
+    if let Some(variadic) = &sig.variadic {
+        return error(variadic.span(), "variadic functions can't be exported");
+    }
+    for input in &sig.inputs {
+        match input {
+            FnArg::Receiver(receiver) => {
+                return error(receiver.span(), "methods can't be exported")
+            }
+            FnArg::Typed(input) => match &*input.pat {
+                Pat::Ident(_) | Pat::Wild(_) => {}
+                pat => return error(pat.span(), "parameter patterns can't be exported"),
+            },
+        }
+    }
+    Ok(())
+}
+
+/// Calls `f` with each generic argument within `ty`.
+fn generic_args<'a>(ty: &'a Type, f: &mut impl FnMut(&'a Type)) {
+    match ty {
+        Type::Path(path) => {
+            for segment in &path.path.segments {
+                if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                    for arg in &args.args {
+                        if let GenericArgument::Type(arg) = arg {
+                            f(arg);
+                            generic_args(arg, f);
+                        }
+                    }
+                }
+            }
+        }
+        Type::Paren(paren) => generic_args(&paren.elem, f),
+        Type::Group(group) => generic_args(&group.elem, f),
+        Type::Ptr(ptr) => generic_args(&ptr.elem, f),
+        _ => {}
+    }
+}
+
+/// Returns the name of `ident` without the `r#` of raw identifiers.
+fn unraw(ident: &Ident) -> String {
+    let ident = ident.to_string();
+    match ident.strip_prefix("r#") {
+        Some(ident) => ident.to_owned(),
+        None => ident,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand_str(attr: &str, item: &str) -> Result<String, String> {
+        let attr = attr.parse().unwrap();
+        let item = item.parse().unwrap();
+        expand(attr, item)
+            .map(|tokens| tokens.to_string())
+            .map_err(|error| error.to_string())
+    }
+
+    #[test]
+    fn shim() {
+        let expanded = expand_str(
+            "",
+            "fn sum(values: swift::Array<swift::Array<Int>>, _: bool) -> Int { 0 }",
+        )
+        .unwrap();
+
+        let expected = quote! {
+            fn sum(values: swift::Array<swift::Array<Int>>, _: bool) -> Int { 0 }
+
+            #[doc(hidden)]
+            #[allow(non_snake_case)]
+            #[unsafe(no_mangle)]
+            extern "C" fn __swift_export_sum(
+                arg0: swift::Array<swift::Array<Int>>,
+                arg1: bool
+            ) -> Int {
+                let _ = ::swift::__private::assert_type::<swift::Array<Int>>;
+                let _ = ::swift::__private::assert_type::<Int>;
+                sum(arg0, arg1)
+            }
+        };
+        // Spacing differs between parsed and quoted tokens.
+        let strip = |s: &str| s.replace(' ', "");
+        assert_eq!(strip(&expanded), strip(&expected.to_string()));
+    }
+
+    #[test]
+    fn options() {
+        let expanded = expand_str("name = \"makeGreeting\"", "fn r#greet() {}").unwrap();
+        assert!(expanded.contains("fn __swift_export_greet ()"));
+        assert!(expanded.contains("r#greet ()"));
+
+        let error = expand_str("name = 1", "fn f() {}").unwrap_err();
+        assert_eq!(error, "expected a string literal");
+
+        let error = expand_str("rename = \"g\"", "fn f() {}").unwrap_err();
+        assert_eq!(error, "unknown option");
+    }
+
+    #[test]
+    fn unsupported() {
+        let cases = [
+            (
+                "fn f<T>(x: T) {}",
+                "generic functions can't be exported; the shim needs concrete types",
+            ),
+            ("async fn f() {}", "async functions can't be exported"),
+            ("unsafe fn f() {}", "unsafe functions can't be exported"),
+            (
+                "extern \"C\" fn f() {}",
+                "exported functions must use the Rust ABI",
+            ),
+            ("fn f(&self) {}", "methods can't be exported"),
+            (
+                "fn f((a, b): (i32, i32)) {}",
+                "parameter patterns can't be exported",
+            ),
+        ];
+        for (item, message) in cases.iter() {
+            assert_eq!(expand_str("", item).unwrap_err(), *message, "{}", item);
+        }
+    }
+}
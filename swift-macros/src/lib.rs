@@ -0,0 +1,54 @@
+//! # Swift Macros
+//!
+//! Procedural macros for bridging Rust to Swift, re-exported by the
+//! [`swift`](https://docs.rs/swift) crate.
+//!
+//! ## Contribute
+//!
+//! This is a work-in-progress by [Nikolai Vazquez](https://twitter.com/nikolaivazquez).
+//! If you would like to get involved,
+//! <a href="mailto:hello@nikolaivazquez.com?subject=I want to help with swift-bindgen&body=Hi Nikolai,%0A%0AMy name is YOUR NAME and I want to get involved with swift-bindgen by...">reach out</a>!
+//!
+//! If this project is useful to you, please support it by
+//! [sponsoring on GitHub](https://github.com/sponsors/nvzqz) or
+//! [donating directly](https://www.paypal.me/nvzqz)!
+
+#![warn(missing_docs)]
+
+use proc_macro::TokenStream;
+
+mod export;
+
+/// Exposes a Rust function to Swift.
+///
+/// The function may take and return `swift::String`, `swift::Array<T>`, the
+/// primitive aliases such as `swift::Int`, and `#[repr(C)]` types. A C-ABI
+/// shim that calls it is exported with the symbol `__swift_export_<name>`,
+/// where `<name>` is the name of the function.
+///
+/// The Swift declaration that calls the shim is generated at build time by
+/// `swift_bindgen::export::Exports`, which reads the same signature from the
+/// crate's source. The function is named in `lowerCamelCase` in Swift, unless
+/// a name is given with `#[swift::export(name = "...")]`.
+///
+/// Each generic argument in the signature, such as `T` in `swift::Array<T>`,
+/// must implement `swift::rt::metadata::Type` so that its metadata can be
+/// passed to Swift. Generic functions can't be exported, because the shim
+/// needs a concrete signature.
+///
+/// # Examples
+///
+/// ```ignore
+/// #[swift::export]
+/// fn greeting(name: swift::String) -> swift::String {
+///     // ...
+/// }
+/// ```
+///
+/// This can be called from Swift as `greeting(name: "World")`.
+#[proc_macro_attribute]
+pub fn export(attr: TokenStream, item: TokenStream) -> TokenStream {
+    export::expand(attr.into(), item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,374 @@
+//! Calls to functions with the Swift calling convention.
+//!
+//! `swiftcc` passes some values in registers that C has no parameters for:
+//! `self` in the context register, the error thrown by the callee in the
+//! error register, the context of an `async` function, and the buffer of a
+//! result that is returned indirectly. It also returns up to four values in
+//! registers, where C returns two.
+//!
+//! [`SwiftCall`] loads all of these registers in a trampoline that is
+//! written as a naked function, so calls work on stable Rust. Trampolines
+//! exist for x86_64 and aarch64.
+
+#[cfg(test)]
+mod tests;
+
+use std::{ffi::c_void, ptr::NonNull};
+
+/// The number of integer registers that arguments are passed in.
+#[cfg(target_arch = "x86_64")]
+pub const INT_ARGS: usize = 6;
+
+/// The number of integer registers that arguments are passed in.
+#[cfg(not(target_arch = "x86_64"))]
+pub const INT_ARGS: usize = 8;
+
+/// The number of floating-point registers that arguments are passed in.
+pub const FLOAT_ARGS: usize = 8;
+
+/// The number of integer registers that results are returned in.
+pub const INT_RESULTS: usize = 4;
+
+/// The number of floating-point registers that results are returned in.
+pub const FLOAT_RESULTS: usize = 4;
+
+/// The registers that are loaded before and stored after a call.
+///
+/// Floating-point values are stored as their bits, where a single-precision
+/// value occupies the low 32 bits.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Registers {
+    /// The context register (`r13` or `x20`), which holds `self`.
+    pub context: *mut c_void,
+
+    /// The error register (`r12` or `x21`), which holds the error thrown by
+    /// the callee, or null.
+    pub error: *mut c_void,
+
+    /// The async context register (`r14` or `x22`).
+    pub async_context: *mut c_void,
+
+    /// The indirect result register (`rax` or `x8`), which holds the
+    /// address that the result is written to.
+    pub indirect_result: *mut c_void,
+
+    /// The integer registers of arguments, then of results.
+    pub ints: [u64; 8],
+
+    /// The floating-point registers of arguments, then of results.
+    pub floats: [u64; 8],
+}
+
+impl Default for Registers {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            context: std::ptr::null_mut(),
+            error: std::ptr::null_mut(),
+            async_context: std::ptr::null_mut(),
+            indirect_result: std::ptr::null_mut(),
+            ints: [0; 8],
+            floats: [0; 8],
+        }
+    }
+}
+
+impl Registers {
+    /// Calls `function` with the values of these registers, and replaces them
+    /// with the values that it returns.
+    ///
+    /// # Safety
+    ///
+    /// `function` must use the Swift calling convention, and take its
+    /// arguments in these registers. Arguments that are passed on the stack
+    /// are not supported.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the target architecture is not x86_64 or aarch64.
+    #[inline]
+    pub unsafe fn call(&mut self, function: *const c_void) {
+        #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+        trampoline(function, self);
+
+        #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+        {
+            let _ = function;
+            unimplemented!("Swift calls are not implemented for this architecture");
+        }
+    }
+}
+
+/// A call to a function with the Swift calling convention.
+///
+/// Arguments are assigned to the next free register of their class, in the
+/// order that they're added.
+#[derive(Clone, Copy, Debug)]
+#[must_use = "a call does nothing unless `call` is called"]
+pub struct SwiftCall {
+    function: *const c_void,
+    registers: Registers,
+    ints: usize,
+    floats: usize,
+}
+
+impl SwiftCall {
+    /// Creates a call to `function` with no arguments.
+    #[inline]
+    pub fn new(function: *const c_void) -> Self {
+        Self {
+            function,
+            registers: Registers::default(),
+            ints: 0,
+            floats: 0,
+        }
+    }
+
+    /// Passes `context` in the context register, such as `self` of methods
+    /// or the metadata of static methods.
+    #[inline]
+    pub fn context<T>(mut self, context: *const T) -> Self {
+        self.registers.context = context as *mut c_void;
+        self
+    }
+
+    /// Passes `context` in the async context register.
+    #[inline]
+    pub fn async_context<T>(mut self, context: *const T) -> Self {
+        self.registers.async_context = context as *mut c_void;
+        self
+    }
+
+    /// Passes the buffer that an indirect result is written to.
+    #[inline]
+    pub fn indirect_result<T>(mut self, result: *mut T) -> Self {
+        self.registers.indirect_result = result.cast();
+        self
+    }
+
+    /// Passes `value` in the next integer register.
+    ///
+    /// Values smaller than 64 bits should be zero or sign extended, like C
+    /// does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if all [`INT_ARGS`] registers are used.
+    #[inline]
+    pub fn int(mut self, value: u64) -> Self {
+        assert!(self.ints < INT_ARGS, "too many integer arguments");
+        self.registers.ints[self.ints] = value;
+        self.ints += 1;
+        self
+    }
+
+    /// Passes `value` in the next floating-point register.
+    ///
+    /// # Panics
+    ///
+    /// Panics if all [`FLOAT_ARGS`] registers are used.
+    #[inline]
+    pub fn float(self, value: f64) -> Self {
+        self.float_bits(value.to_bits())
+    }
+
+    /// Passes `value` in the next floating-point register with single
+    /// precision.
+    ///
+    /// # Panics
+    ///
+    /// Panics if all [`FLOAT_ARGS`] registers are used.
+    #[inline]
+    pub fn float32(self, value: f32) -> Self {
+        self.float_bits(value.to_bits().into())
+    }
+
+    #[inline]
+    fn float_bits(mut self, bits: u64) -> Self {
+        assert!(
+            self.floats < FLOAT_ARGS,
+            "too many floating-point arguments"
+        );
+        self.registers.floats[self.floats] = bits;
+        self.floats += 1;
+        self
+    }
+
+    /// Makes the call, and returns the registers of its results.
+    ///
+    /// # Safety
+    ///
+    /// The function must use the Swift calling convention, and take the
+    /// arguments that were passed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the target architecture is not x86_64 or aarch64.
+    #[inline]
+    pub unsafe fn call(self) -> Return {
+        let mut registers = self.registers;
+        registers.call(self.function);
+        Return { registers }
+    }
+}
+
+/// The registers of the results of a [`SwiftCall`].
+#[derive(Clone, Copy, Debug)]
+pub struct Return {
+    registers: Registers,
+}
+
+impl Return {
+    /// Returns the integer result at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than [`INT_RESULTS`].
+    #[inline]
+    pub fn int(&self, index: usize) -> u64 {
+        assert!(index < INT_RESULTS, "no integer result at {}", index);
+        self.registers.ints[index]
+    }
+
+    /// Returns the floating-point result at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than [`FLOAT_RESULTS`].
+    #[inline]
+    pub fn float(&self, index: usize) -> f64 {
+        f64::from_bits(self.float_bits(index))
+    }
+
+    /// Returns the single-precision floating-point result at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than [`FLOAT_RESULTS`].
+    #[inline]
+    pub fn float32(&self, index: usize) -> f32 {
+        f32::from_bits(self.float_bits(index) as u32)
+    }
+
+    #[inline]
+    fn float_bits(&self, index: usize) -> u64 {
+        assert!(
+            index < FLOAT_RESULTS,
+            "no floating-point result at {}",
+            index
+        );
+        self.registers.floats[index]
+    }
+
+    /// Returns the error thrown by the callee, if any.
+    ///
+    /// The error is a `Swift.Error` existential box that the caller owns.
+    #[inline]
+    pub fn error(&self) -> Option<NonNull<c_void>> {
+        NonNull::new(self.registers.error)
+    }
+
+    /// Returns all registers after the call.
+    #[inline]
+    pub fn registers(&self) -> &Registers {
+        &self.registers
+    }
+}
+
+/// Loads `registers`, calls `function`, and stores the result registers.
+///
+/// The offsets are those of the fields of `Registers`.
+#[cfg(target_arch = "x86_64")]
+#[unsafe(naked)]
+unsafe extern "C" fn trampoline(function: *const c_void, registers: *mut Registers) {
+    std::arch::naked_asm!(
+        "push rbp",
+        "mov rbp, rsp",
+        // Swift's special registers are callee-saved in C.
+        "push rbx",
+        "push r12",
+        "push r13",
+        "push r14",
+        "push r15",
+        // Realign the stack to 16 bytes for the call.
+        "sub rsp, 8",
+        "mov r11, rdi",
+        "mov rbx, rsi",
+        "mov r13, qword ptr [rbx]",
+        "mov r12, qword ptr [rbx + 8]",
+        "mov r14, qword ptr [rbx + 16]",
+        "mov rax, qword ptr [rbx + 24]",
+        "mov rdi, qword ptr [rbx + 32]",
+        "mov rsi, qword ptr [rbx + 40]",
+        "mov rdx, qword ptr [rbx + 48]",
+        "mov rcx, qword ptr [rbx + 56]",
+        "mov r8, qword ptr [rbx + 64]",
+        "mov r9, qword ptr [rbx + 72]",
+        "movq xmm0, qword ptr [rbx + 96]",
+        "movq xmm1, qword ptr [rbx + 104]",
+        "movq xmm2, qword ptr [rbx + 112]",
+        "movq xmm3, qword ptr [rbx + 120]",
+        "movq xmm4, qword ptr [rbx + 128]",
+        "movq xmm5, qword ptr [rbx + 136]",
+        "movq xmm6, qword ptr [rbx + 144]",
+        "movq xmm7, qword ptr [rbx + 152]",
+        "call r11",
+        "mov qword ptr [rbx + 8], r12",
+        "mov qword ptr [rbx + 32], rax",
+        "mov qword ptr [rbx + 40], rdx",
+        "mov qword ptr [rbx + 48], rcx",
+        "mov qword ptr [rbx + 56], r8",
+        "movq qword ptr [rbx + 96], xmm0",
+        "movq qword ptr [rbx + 104], xmm1",
+        "movq qword ptr [rbx + 112], xmm2",
+        "movq qword ptr [rbx + 120], xmm3",
+        "add rsp, 8",
+        "pop r15",
+        "pop r14",
+        "pop r13",
+        "pop r12",
+        "pop rbx",
+        "pop rbp",
+        "ret",
+    )
+}
+
+/// Loads `registers`, calls `function`, and stores the result registers.
+///
+/// The offsets are those of the fields of `Registers`.
+#[cfg(target_arch = "aarch64")]
+#[unsafe(naked)]
+unsafe extern "C" fn trampoline(function: *const c_void, registers: *mut Registers) {
+    std::arch::naked_asm!(
+        "stp x29, x30, [sp, #-48]!",
+        "mov x29, sp",
+        // Swift's special registers are callee-saved in C.
+        "stp x19, x20, [sp, #16]",
+        "stp x21, x22, [sp, #32]",
+        "mov x9, x0",
+        "mov x19, x1",
+        "ldr x20, [x19]",
+        "ldr x21, [x19, #8]",
+        "ldr x22, [x19, #16]",
+        "ldr x8, [x19, #24]",
+        "ldp x0, x1, [x19, #32]",
+        "ldp x2, x3, [x19, #48]",
+        "ldp x4, x5, [x19, #64]",
+        "ldp x6, x7, [x19, #80]",
+        "ldp d0, d1, [x19, #96]",
+        "ldp d2, d3, [x19, #112]",
+        "ldp d4, d5, [x19, #128]",
+        "ldp d6, d7, [x19, #144]",
+        "blr x9",
+        "str x21, [x19, #8]",
+        "stp x0, x1, [x19, #32]",
+        "stp x2, x3, [x19, #48]",
+        "stp d0, d1, [x19, #96]",
+        "stp d2, d3, [x19, #112]",
+        "ldp x21, x22, [sp, #32]",
+        "ldp x19, x20, [sp, #16]",
+        "ldp x29, x30, [sp], #48",
+        "ret",
+    )
+}
@@ -0,0 +1,82 @@
+use super::*;
+use std::mem;
+
+/// Stands in for a Swift function that reads every special register.
+///
+/// It writes the first integer argument to the indirect result, returns the
+/// context, the last integer argument, the async context and the second
+/// integer argument, returns the last float argument second, and throws the
+/// context plus one.
+#[cfg(target_arch = "x86_64")]
+#[unsafe(naked)]
+unsafe extern "C" fn fake_swift_function() {
+    std::arch::naked_asm!(
+        "mov qword ptr [rax], rdi",
+        "mov rax, r13",
+        "mov rdx, r9",
+        "mov rcx, r14",
+        "mov r8, rsi",
+        "movaps xmm1, xmm7",
+        "lea r12, [r13 + 1]",
+        "ret",
+    )
+}
+
+#[cfg(target_arch = "aarch64")]
+#[unsafe(naked)]
+unsafe extern "C" fn fake_swift_function() {
+    std::arch::naked_asm!(
+        "str x0, [x8]",
+        "mov x3, x1",
+        "mov x0, x20",
+        "mov x1, x7",
+        "mov x2, x22",
+        "fmov d1, d7",
+        "add x21, x20, #1",
+        "ret",
+    )
+}
+
+#[test]
+fn layout() {
+    assert_eq!(mem::size_of::<Registers>(), 160);
+    assert_eq!(mem::align_of::<Registers>(), 8);
+}
+
+#[test]
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+fn special_registers() {
+    let context = 0x1000 as *const u8;
+    let async_context = 0x2000 as *const u8;
+    let mut indirect = 0u64;
+
+    let mut call = SwiftCall::new(fake_swift_function as *const c_void)
+        .context(context)
+        .async_context(async_context)
+        .indirect_result(&mut indirect);
+    for i in 0..INT_ARGS {
+        call = call.int(10 + i as u64);
+    }
+    for i in 0..FLOAT_ARGS {
+        call = call.float(i as f64);
+    }
+    let ret = unsafe { call.call() };
+
+    assert_eq!(indirect, 10);
+    assert_eq!(ret.int(0), 0x1000);
+    assert_eq!(ret.int(1), 10 + INT_ARGS as u64 - 1);
+    assert_eq!(ret.int(2), 0x2000);
+    assert_eq!(ret.int(3), 11);
+    assert_eq!(ret.float(0), 0.0);
+    assert_eq!(ret.float(1), 7.0);
+    assert_eq!(ret.error(), NonNull::new(0x1001 as *mut c_void));
+}
+
+#[test]
+#[should_panic(expected = "too many integer arguments")]
+fn too_many_ints() {
+    let mut call = SwiftCall::new(std::ptr::null());
+    for i in 0..=INT_ARGS {
+        call = call.int(i as u64);
+    }
+}
@@ -1,6 +1,9 @@
-use crate::ctx_desc::{
-    ContextDescriptorFlags, ContextDescriptorKind, ExtensionContextDescriptor,
-    ModuleContextDescriptor, ProtocolContextDescriptor, TypeContextDescriptor,
+use crate::{
+    ctx_desc::{
+        ContextDescriptorFlags, ContextDescriptorKind, ExtensionContextDescriptor,
+        ModuleContextDescriptor, ProtocolContextDescriptor, TypeContextDescriptor,
+    },
+    mangling::{self, qualified_name, Node},
 };
 use std::{fmt, hint, ptr};
 use swift_sys::{
@@ -54,6 +57,7 @@ impl fmt::Debug for ContextDescriptor {
             // Default to "unknown" descriptor.
             _ => f
                 .debug_struct("UnknownContextDescriptor")
+                .field("qualified_name", &qualified_name(self))
                 .field("flags", &self.flags())
                 .field("parent", &self.parent())
                 .finish(),
@@ -156,6 +160,15 @@ impl ContextDescriptor {
         Iter(self)
     }
 
+    /// Returns the fully-qualified name of this context, such as
+    /// `Swift.String`, or `None` if it can't be named.
+    ///
+    /// Anonymous contexts are named by their parent.
+    #[inline]
+    pub fn demangle(&self) -> Option<Node> {
+        mangling::demangle_context(self)
+    }
+
     /// Returns `true` if the given context descriptor is in the parent
     /// hierarchy of `self`.
     pub fn has_parent(&self, desc: &ContextDescriptor) -> bool {
@@ -202,6 +215,16 @@ impl ContextDescriptor {
         }
     }
 
+    /// Casts this context descriptor to a protocol descriptor if it is one.
+    #[inline]
+    pub fn as_protocol(&self) -> Option<&ProtocolContextDescriptor> {
+        if self.kind() == ContextDescriptorKind::PROTOCOL {
+            Some(unsafe { &*(self as *const _ as *const _) })
+        } else {
+            None
+        }
+    }
+
     /// Casts this context descriptor to a nominal type descriptor if it is one.
     #[inline]
     pub fn as_type(&self) -> Option<&TypeContextDescriptor> {
@@ -2,7 +2,7 @@ use crate::{
     ctx_desc::{
         ContextDescriptor, ContextDescriptorFlags, ContextDescriptorKind, ModuleContextDescriptor,
     },
-    mangling::Mangled,
+    mangling::{Demangled, Mangled},
 };
 use std::{fmt, ops::Deref};
 use swift_sys::{
@@ -40,7 +40,7 @@ impl fmt::Debug for ExtensionContextDescriptor {
         f.debug_struct("ExtensionContextDescriptor")
             .field("flags", &self.flags())
             .field("parent", self.parent())
-            .field("extended_context", &self.extended_context())
+            .field("extended_context", &self.extended_context().map(Demangled))
             .finish()
     }
 }
@@ -2,7 +2,11 @@
 
 // Re-export basic types that don't need to be wrapped.
 #[doc(no_inline)]
-pub use swift_sys::ctx_desc::{ContextDescriptorFlags, ContextDescriptorKind};
+pub use swift_sys::ctx_desc::{
+    ContextDescriptorFlags, ContextDescriptorKind, GenericContextDescriptorHeader,
+    GenericEnvironment, GenericParamDescriptor, MethodDescriptor,
+    TypeGenericContextDescriptorHeader, VTableDescriptorHeader,
+};
 
 mod base;
 mod extension;
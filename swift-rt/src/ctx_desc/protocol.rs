@@ -1,4 +1,4 @@
-use crate::ctx_desc::ContextDescriptor;
+use crate::{ctx_desc::ContextDescriptor, mangling::qualified_name};
 use std::{fmt, ops::Deref, os::raw::c_char};
 use swift_sys::{
     ctx_desc::ProtocolContextDescriptor as RawProtocolContextDescriptor,
@@ -55,6 +55,7 @@ impl fmt::Debug for ProtocolContextDescriptor {
         // output easier to follow.
         f.debug_struct("ProtocolContextDescriptor")
             .field("name", &self.name())
+            .field("qualified_name", &qualified_name(self))
             .field("associated_type_names", &associated_type_names)
             .field(
                 // TODO: Format protocol-specific flags as part of this
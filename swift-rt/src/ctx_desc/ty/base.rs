@@ -1,14 +1,19 @@
 use crate::{
     ctx_desc::{
         ClassDescriptor, ContextDescriptor, ContextDescriptorFlags, ContextDescriptorKind,
-        StructDescriptor,
+        GenericParamDescriptor, StructDescriptor, TypeGenericContextDescriptorHeader,
     },
+    mangling::qualified_name,
+    metadata::{Metadata, MetadataResponse, WitnessTable},
     reflection::FieldDescriptor,
 };
-use std::{fmt, ops::Deref, os::raw::c_char};
+use std::{ffi::c_void, fmt, mem, ops::Deref, os::raw::c_char, slice};
 use swift_sys::{
-    ctx_desc::TypeContextDescriptor as RawTypeContextDescriptor,
-    metadata::MetadataAccessFunction,
+    ctx_desc::{
+        ClassDescriptor as RawClassDescriptor, EnumDescriptor as RawEnumDescriptor,
+        StructDescriptor as RawStructDescriptor, TypeContextDescriptor as RawTypeContextDescriptor,
+    },
+    metadata::{MetadataAccessFunction, MetadataRequest},
     ptr::{
         RelativeDirectPointer, RelativeDirectPointerNonNull, RelativeIndirectablePointerNonNull,
     },
@@ -62,6 +67,7 @@ impl fmt::Debug for TypeContextDescriptor {
             _ => f
                 .debug_struct("TypeContextDescriptor")
                 .field("name", &self.name())
+                .field("qualified_name", &qualified_name(self))
                 .field("flags", &self.flags())
                 .field("parent", self.parent())
                 .field("access_function", &self.access_function())
@@ -189,3 +195,124 @@ impl TypeContextDescriptor {
         !self.fields_ptr().is_null()
     }
 }
+
+/// Generic types.
+impl TypeContextDescriptor {
+    /// Returns the header of the generic context of the type, or `None` if the
+    /// type is not generic.
+    pub fn generic_context_header(&self) -> Option<&TypeGenericContextDescriptorHeader> {
+        if !self.flags().is_generic() {
+            return None;
+        }
+
+        // The header is the first object that trails the descriptor.
+        let size = match self.kind() {
+            ContextDescriptorKind::CLASS => mem::size_of::<RawClassDescriptor>(),
+            ContextDescriptorKind::STRUCT => mem::size_of::<RawStructDescriptor>(),
+            ContextDescriptorKind::ENUM => mem::size_of::<RawEnumDescriptor>(),
+            _ => return None,
+        };
+        unsafe {
+            let header = (self as *const Self as *const u8).add(size);
+            Some(&*header.cast())
+        }
+    }
+
+    /// Returns the generic parameters of the type, including those of its
+    /// parent contexts.
+    pub fn generic_params(&self) -> &[GenericParamDescriptor] {
+        match self.generic_context_header() {
+            Some(header) => unsafe {
+                let params = (header as *const TypeGenericContextDescriptorHeader).add(1);
+                slice::from_raw_parts(params.cast(), header.base.num_params.into())
+            },
+            None => &[],
+        }
+    }
+
+    /// Returns the number of arguments that the access function takes: the
+    /// metadata of each key generic parameter, followed by the witness table
+    /// of each protocol conformance requirement.
+    #[inline]
+    pub fn num_key_arguments(&self) -> usize {
+        self.generic_context_header()
+            .map_or(0, |header| header.base.num_key_arguments.into())
+    }
+
+    /// Returns the number of generic parameters whose metadata is passed to
+    /// the access function.
+    #[inline]
+    pub fn num_key_generic_params(&self) -> usize {
+        self.generic_params()
+            .iter()
+            .filter(|param| param.has_key_argument())
+            .count()
+    }
+
+    /// Requests the metadata of the type through its access function.
+    ///
+    /// Returns `None` if the type is generic or has no access function.
+    #[inline]
+    pub fn metadata(&self, request: MetadataRequest) -> Option<MetadataResponse> {
+        self.metadata_with_args(request, &[])
+    }
+
+    /// Requests the metadata of the type with `args` as its key generic
+    /// arguments.
+    ///
+    /// Returns `None` if the type has no access function, if `args` doesn't
+    /// have one argument for each key generic parameter, or if the type has
+    /// protocol conformance requirements. Metadata of the latter are
+    /// requested with
+    /// [`metadata_with_witness_tables`](Self::metadata_with_witness_tables).
+    #[inline]
+    pub fn metadata_with_args(
+        &self,
+        request: MetadataRequest,
+        args: &[&Metadata],
+    ) -> Option<MetadataResponse> {
+        if self.num_key_arguments() != args.len() {
+            return None;
+        }
+        // SAFETY: There are no witness tables.
+        unsafe { self.metadata_with_witness_tables(request, args, &[]) }
+    }
+
+    /// Requests the metadata of the type with `args` as its key generic
+    /// arguments, and `witness_tables` as the conformances of its protocol
+    /// conformance requirements.
+    ///
+    /// Returns `None` if the type has no access function, or if the number of
+    /// `args` or `witness_tables` doesn't match the generic context.
+    ///
+    /// # Safety
+    ///
+    /// Each witness table must be the conformance to the protocol of the
+    /// corresponding requirement, by the type that it constrains.
+    pub unsafe fn metadata_with_witness_tables(
+        &self,
+        request: MetadataRequest,
+        args: &[&Metadata],
+        witness_tables: &[&WitnessTable],
+    ) -> Option<MetadataResponse> {
+        let num_key_params = self.num_key_generic_params();
+        if num_key_params != args.len()
+            || self.num_key_arguments() != num_key_params + witness_tables.len()
+        {
+            return None;
+        }
+        let function = self.access_function()?;
+
+        let key_args: Vec<*const c_void> = args
+            .iter()
+            .map(|arg| (*arg as *const Metadata).cast())
+            .chain(
+                witness_tables
+                    .iter()
+                    .map(|table| (*table as *const WitnessTable).cast()),
+            )
+            .collect();
+        let response = function.call(request, &key_args);
+        Some(MetadataResponse::from_raw(response))
+    }
+}
@@ -1,6 +1,18 @@
-use crate::{ctx_desc::TypeContextDescriptor, mangling::Mangled};
-use std::{fmt, ops::Deref};
-use swift_sys::{ctx_desc::ClassDescriptor as RawClassDescriptor, ptr::RelativeDirectPointer};
+use crate::{
+    ctx_desc::{MethodDescriptor, TypeContextDescriptor, VTableDescriptorHeader},
+    mangling::{qualified_name, Demangled, Mangled},
+};
+use std::{fmt, mem, ops::Deref, slice};
+use swift_sys::{
+    ctx_desc::{ClassDescriptor as RawClassDescriptor, TypeGenericContextDescriptorHeader},
+    ptr::RelativeDirectPointer,
+};
+
+/// The size of a generic requirement descriptor, which isn't otherwise
+/// modeled.
+const GENERIC_REQUIREMENT_SIZE: usize = 12;
+
+const RELATIVE_POINTER_SIZE: usize = mem::size_of::<RelativeDirectPointer<()>>();
 
 /// Context descriptor for a class type.
 #[repr(transparent)]
@@ -32,6 +44,7 @@ impl fmt::Debug for ClassDescriptor {
         // Format name field first to make nested output easier to follow.
         f.debug_struct("ClassDescriptor")
             .field("name", &self.name())
+            .field("qualified_name", &qualified_name(self))
             .field(
                 // TODO: Format class-specific flags as part of this property.
                 "flags",
@@ -40,13 +53,14 @@ impl fmt::Debug for ClassDescriptor {
             .field("parent", self.parent())
             .field("access_function", &self.access_function())
             .field("fields", &self.fields())
-            .field("superclass_type", &self.superclass_type())
+            .field("superclass_type", &self.superclass_type().map(Demangled))
             .field("num_immediate_members", &self.num_immediate_members())
             .field("num_fields", &self.num_fields())
             .field(
                 "field_offset_vector_offset",
                 &self.field_offset_vector_offset(),
             )
+            .field("vtable_header", &self.vtable_header())
             .finish()
     }
 }
@@ -77,6 +91,8 @@ impl ClassDescriptor {
 impl ClassDescriptor {
     /// Returns the type of the superclass, expressed as a mangled type name
     /// that can refer to the generic arguments of the subclass type.
+    ///
+    /// Its symbolic references are resolved by [`Mangled::demangle`].
     #[inline]
     pub fn superclass_type(&self) -> Option<&Mangled> {
         unsafe { self.superclass_type_ptr().as_ref() }
@@ -119,3 +135,101 @@ impl ClassDescriptor {
         self.raw.field_offset_vector_offset
     }
 }
+
+/// Metadata layout.
+impl ClassDescriptor {
+    #[inline]
+    fn class_flag(&self, bit: u16) -> bool {
+        self.flags().kind_specific_flags() & (1 << bit) != 0
+    }
+
+    /// Returns `true` if the superclass is defined in a resilient module,
+    /// which makes offsets into the metadata relative to the start of the
+    /// class's immediate members.
+    #[inline]
+    pub fn has_resilient_superclass(&self) -> bool {
+        self.class_flag(13)
+    }
+
+    /// Returns `true` if the immediate members of the class are placed
+    /// before the address point of its metadata.
+    #[inline]
+    pub fn are_immediate_members_negative(&self) -> bool {
+        self.class_flag(12)
+    }
+
+    /// Returns `true` if the class has a vtable.
+    #[inline]
+    pub fn has_vtable(&self) -> bool {
+        self.class_flag(15)
+    }
+
+    /// Returns the offset in words of the immediate members from the address
+    /// point of the metadata, if the class doesn't have a resilient
+    /// superclass.
+    #[inline]
+    pub(crate) fn immediate_members_offset(&self) -> Option<isize> {
+        if self.has_resilient_superclass() {
+            None
+        } else if self.are_immediate_members_negative() {
+            Some(-(self.raw.metadata_negative_size_in_words as isize))
+        } else {
+            let positive = self.raw.metadata_positive_size_in_words as isize;
+            Some(positive - self.num_immediate_members() as isize)
+        }
+    }
+
+    /// Returns the header of the vtable, if the class has one.
+    pub fn vtable_header(&self) -> Option<&VTableDescriptorHeader> {
+        if !self.has_vtable() {
+            return None;
+        }
+
+        // The header follows the generic context, the resilient superclass
+        // and the metadata initialization.
+        let mut size = mem::size_of::<RawClassDescriptor>();
+        if let Some(header) = self.generic_context_header() {
+            // TODO: Account for generic parameter packs, which are flagged in
+            // the last field of newer headers.
+            if header.base.num_extra_arguments != 0 {
+                return None;
+            }
+
+            // Requirements are aligned to 4 bytes after the parameters.
+            let params = (usize::from(header.base.num_params) + 3) & !3;
+            let requirements = usize::from(header.base.num_requirements);
+            size += mem::size_of::<TypeGenericContextDescriptorHeader>()
+                + params
+                + requirements * GENERIC_REQUIREMENT_SIZE;
+        }
+        if self.has_resilient_superclass() {
+            size += RELATIVE_POINTER_SIZE;
+        }
+        size += match self.flags().kind_specific_flags() & 0x3 {
+            0 => 0,
+            // Singleton initialization has a cache, the incomplete metadata
+            // and a completion function.
+            1 => 3 * RELATIVE_POINTER_SIZE,
+            // Foreign initialization has a completion function.
+            2 => RELATIVE_POINTER_SIZE,
+            _ => return None,
+        };
+
+        unsafe {
+            let header = (self as *const Self as *const u8).add(size);
+            Some(&*header.cast())
+        }
+    }
+
+    /// Returns the descriptors of the methods in the vtable, in the order of
+    /// their slots.
+    pub fn methods(&self) -> &[MethodDescriptor] {
+        match self.vtable_header() {
+            Some(header) => unsafe {
+                let methods = (header as *const VTableDescriptorHeader).add(1);
+                slice::from_raw_parts(methods.cast(), header.vtable_size as usize)
+            },
+            None => &[],
+        }
+    }
+}
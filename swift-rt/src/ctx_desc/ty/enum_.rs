@@ -1,4 +1,4 @@
-use crate::ctx_desc::TypeContextDescriptor;
+use crate::{ctx_desc::TypeContextDescriptor, mangling::qualified_name};
 use std::{fmt, ops::Deref};
 use swift_sys::ctx_desc::EnumDescriptor as RawEnumDescriptor;
 
@@ -32,6 +32,7 @@ impl fmt::Debug for EnumDescriptor {
         // Format name field first to make nested output easier to follow.
         f.debug_struct("EnumDescriptor")
             .field("name", &self.name())
+            .field("qualified_name", &qualified_name(self))
             .field("flags", &self.flags())
             .field("parent", self.parent())
             .field("access_function", &self.access_function())
@@ -22,7 +22,9 @@
 pub use swift_sys as sys;
 
 pub mod borrow;
+pub mod call;
 pub mod ctx_desc;
 pub mod mangling;
 pub mod metadata;
 pub mod reflection;
+pub mod value;
@@ -1,10 +1,14 @@
+use crate::ctx_desc::{ContextDescriptor, ContextDescriptorKind};
 use std::{
     ascii,
     fmt::{self, Write},
+    iter::FusedIterator,
+    marker::PhantomData,
     mem,
     os::raw::c_void,
-    slice, str,
+    ptr, slice, str,
 };
+use swift_sys::mangling::{self, Node, NodeKind, SymbolicReference};
 
 /// A mangled Swift symbol.
 ///
@@ -16,6 +20,12 @@ use std::{
 ///
 /// References are formatted as `<` + `$delim` (hex) + `:` + `$offset` or
 /// `$address` + `>`.
+///
+/// # Symbolic references
+///
+/// References to context descriptors are resolved by
+/// [`components`](Self::components), and [`demangle`](Self::demangle)
+/// replaces them with the fully-qualified names of the descriptors.
 #[repr(C)]
 pub struct Mangled {
     data: [u8; 0],
@@ -31,9 +41,9 @@ impl fmt::Debug for Mangled {
             // SAFETY: `current` always refers to a byte within bounds.
             let next = unsafe { *current };
 
-            match Component::new(next) {
-                Component::Null => break,
-                Component::SymbolicReference(reference) => {
+            match ByteKind::new(next) {
+                ByteKind::Null => break,
+                ByteKind::SymbolicReference(reference) => {
                     fn hexify(b: u8) -> u8 {
                         match b {
                             0..=9 => b'0' + b,
@@ -50,7 +60,7 @@ impl fmt::Debug for Mangled {
                         // SAFETY: A relative symbolic reference delimiter
                         // ensures that the value following the delimiter is a
                         // 32-bit integer for the offset.
-                        SymbolicReference::Relative => unsafe {
+                        ReferenceKind::Relative => unsafe {
                             let offset_ptr = current.add(1).cast::<i32>();
                             current = offset_ptr.add(1).cast();
 
@@ -61,7 +71,7 @@ impl fmt::Debug for Mangled {
                         // SAFETY: An absolute symbolic reference delimiter
                         // ensures that the value following the delimiter is a
                         // pointer.
-                        SymbolicReference::Absolute => unsafe {
+                        ReferenceKind::Absolute => unsafe {
                             let addr_ptr = current.add(1).cast::<*const c_void>();
                             current = addr_ptr.add(1).cast();
 
@@ -72,7 +82,7 @@ impl fmt::Debug for Mangled {
 
                     f.write_char('>')?;
                 }
-                Component::Normal => {
+                ByteKind::Normal => {
                     for escaped in ascii::escape_default(next) {
                         f.write_char(escaped as char)?;
                     }
@@ -88,26 +98,26 @@ impl fmt::Debug for Mangled {
 }
 
 #[derive(Clone, Copy)]
-enum Component {
+enum ByteKind {
     Null,
-    SymbolicReference(SymbolicReference),
+    SymbolicReference(ReferenceKind),
     Normal,
 }
 
 #[derive(Clone, Copy)]
-enum SymbolicReference {
+enum ReferenceKind {
     Relative,
     Absolute,
 }
 
 // See `makeSymbolicMangledNameStringRef` implementation.
-impl Component {
+impl ByteKind {
     #[inline]
     pub fn new(byte: u8) -> Self {
         match byte {
             0 => Self::Null,
-            0x01..=0x17 => Self::SymbolicReference(SymbolicReference::Relative),
-            0x18..=0x1F => Self::SymbolicReference(SymbolicReference::Absolute),
+            0x01..=0x17 => Self::SymbolicReference(ReferenceKind::Relative),
+            0x18..=0x1F => Self::SymbolicReference(ReferenceKind::Absolute),
             _ => Self::Normal,
         }
     }
@@ -170,3 +180,199 @@ impl Mangled {
         unsafe { slice::from_raw_parts(self.as_ptr(), self.len() + 1) }
     }
 }
+
+impl Mangled {
+    /// Returns an iterator over the text and symbolic references of this
+    /// name, with references to context descriptors resolved.
+    #[inline]
+    pub fn components(&self) -> Components<'_> {
+        Components {
+            current: self.as_ptr(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Demangles this name as a type, with symbolic references to context
+    /// descriptors replaced by their fully-qualified names.
+    ///
+    /// Returns `None` if the name is invalid or refers to anything other
+    /// than context descriptors.
+    pub fn demangle(&self) -> Option<Node> {
+        mangling::demangle_type_with(self.to_bytes(), |reference| {
+            // SAFETY: The reference is within the name.
+            let context = unsafe { resolve_context(reference) }?;
+            let node = context.demangle()?;
+            match node.kind() {
+                NodeKind::Module | NodeKind::Extension => Some(node),
+                _ => Some(Node::with_children(NodeKind::Type, vec![node])),
+            }
+        })
+    }
+}
+
+/// Formats a mangled name as its demangling, or as its bytes if it can't be
+/// demangled.
+pub(crate) struct Demangled<'a>(pub &'a Mangled);
+
+impl fmt::Debug for Demangled<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0.demangle() {
+            Some(node) => fmt::Debug::fmt(&node.to_string(), f),
+            None => fmt::Debug::fmt(self.0, f),
+        }
+    }
+}
+
+/// Returns a value that formats `context` as its fully-qualified name.
+pub(crate) fn qualified_name(context: &ContextDescriptor) -> impl fmt::Debug + '_ {
+    struct QualifiedName<'a>(&'a ContextDescriptor);
+
+    impl fmt::Debug for QualifiedName<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self.0.demangle() {
+                Some(node) => fmt::Debug::fmt(&node.to_string(), f),
+                None => f.write_str("None"),
+            }
+        }
+    }
+
+    QualifiedName(context)
+}
+
+/// A part of a [`Mangled`] name.
+#[derive(Clone, Copy, Debug)]
+pub enum Component<'a> {
+    /// Mangled text without symbolic references.
+    Text(&'a [u8]),
+
+    /// A direct (`0x01`) or indirect (`0x02`) reference to a context
+    /// descriptor.
+    Context(&'a ContextDescriptor),
+
+    /// Any other symbolic reference, such as to an accessor function, or an
+    /// indirect reference to a context descriptor that is null.
+    Reference(SymbolicReference),
+}
+
+/// An iterator over the [`Component`]s of a [`Mangled`] name.
+///
+/// This is returned by [`Mangled::components`].
+#[derive(Clone)]
+pub struct Components<'a> {
+    current: *const u8,
+    marker: PhantomData<&'a Mangled>,
+}
+
+impl fmt::Debug for Components<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a> Iterator for Components<'a> {
+    type Item = Component<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.current;
+
+        // SAFETY: `current` always refers to a byte within bounds.
+        let control = unsafe { *start };
+        match ByteKind::new(control) {
+            ByteKind::Null => None,
+            ByteKind::SymbolicReference(_) => {
+                let reference = SymbolicReference {
+                    control,
+                    payload: unsafe { start.add(1) },
+                };
+                self.current = unsafe { start.add(Mangled::offset_of(control)) };
+
+                // SAFETY: The reference is within the name.
+                match unsafe { resolve_context(reference) } {
+                    Some(context) => Some(Component::Context(context)),
+                    None => Some(Component::Reference(reference)),
+                }
+            }
+            ByteKind::Normal => {
+                let mut len = 0;
+                while let ByteKind::Normal = ByteKind::new(unsafe { *start.add(len) }) {
+                    len += 1;
+                }
+                self.current = unsafe { start.add(len) };
+                Some(Component::Text(unsafe {
+                    slice::from_raw_parts(start, len)
+                }))
+            }
+        }
+    }
+}
+
+impl FusedIterator for Components<'_> {}
+
+/// Returns the context descriptor that `reference` refers to, if it's a
+/// context descriptor reference.
+///
+/// # Safety
+///
+/// `reference` must be within a mangled name emitted by the compiler.
+unsafe fn resolve_context<'a>(reference: SymbolicReference) -> Option<&'a ContextDescriptor> {
+    let target = |payload: *const u8| {
+        let offset = ptr::read_unaligned(payload.cast::<i32>());
+        payload.offset(offset as isize)
+    };
+    match reference.control {
+        0x01 => target(reference.payload)
+            .cast::<ContextDescriptor>()
+            .as_ref(),
+        0x02 => {
+            let target = target(reference.payload).cast::<*const ContextDescriptor>();
+            ptr::read_unaligned(target).as_ref()
+        }
+        _ => None,
+    }
+}
+
+/// Returns the node for the fully-qualified name of `context`, like
+/// `_buildDemanglingForContext` in the Swift runtime.
+pub(crate) fn demangle_context(context: &ContextDescriptor) -> Option<Node> {
+    let kind = context.kind();
+    if let Some(module) = context.as_module() {
+        return Some(Node::with_text(NodeKind::Module, module.name()));
+    }
+    if let Some(extension) = context.as_extension() {
+        let module = demangle_context(extension.parent())?;
+        let extended = extension.extended_context()?.demangle()?;
+
+        // The extended type is the `Type` node's only child.
+        let extended = match extended.kind() {
+            NodeKind::Type => extended.child(0)?.clone(),
+            _ => extended,
+        };
+        return Some(Node::with_children(
+            NodeKind::Extension,
+            vec![module, extended],
+        ));
+    }
+
+    // Anonymous contexts, such as those of private declarations, are named
+    // by their parent.
+    let parent = context.parent()?;
+    if kind == ContextDescriptorKind::ANONYMOUS {
+        return demangle_context(parent);
+    }
+
+    let (node_kind, name) = match kind {
+        ContextDescriptorKind::PROTOCOL => (NodeKind::Protocol, context.as_protocol()?.name()),
+        ContextDescriptorKind::CLASS => (NodeKind::Class, context.as_type()?.name()),
+        ContextDescriptorKind::STRUCT => (NodeKind::Structure, context.as_type()?.name()),
+        ContextDescriptorKind::ENUM => (NodeKind::Enum, context.as_type()?.name()),
+        kind if kind.is_type() => (NodeKind::OtherNominalType, context.as_type()?.name()),
+        _ => return None,
+    };
+    Some(Node::with_children(
+        node_kind,
+        vec![
+            demangle_context(parent)?,
+            Node::with_text(NodeKind::Identifier, name),
+        ],
+    ))
+}
@@ -1,5 +1,11 @@
 //! Working with mangled Swift symbols.
+//!
+//! Symbols are encoded, demangled and classified by the items re-exported
+//! from [`swift_sys::mangling`](crate::sys::mangling), which accept the
+//! prefixes of Swift 4 and later such as `_T0`, `$S` and `_$s`.
 
 mod mangled;
 
 pub use mangled::*;
+pub(crate) use mangled::{demangle_context, qualified_name, Demangled};
+pub use swift_sys::mangling::*;
@@ -0,0 +1,250 @@
+use crate::{ctx_desc::ClassDescriptor, metadata::Metadata};
+use std::{ffi::c_void, fmt, mem, ptr::NonNull, slice};
+use swift_sys::metadata::{ClassFlags, ClassMetadata as RawClassMetadata};
+
+/// Metadata for classes.
+#[repr(transparent)]
+pub struct ClassMetadata {
+    raw: RawClassMetadata,
+}
+
+impl AsRef<Metadata> for ClassMetadata {
+    #[inline]
+    fn as_ref(&self) -> &Metadata {
+        unsafe { &*(self as *const _ as *const _) }
+    }
+}
+
+unsafe impl Send for ClassMetadata {}
+unsafe impl Sync for ClassMetadata {}
+
+impl fmt::Debug for ClassMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ClassMetadata")
+            .field("kind", &self.as_metadata().kind())
+            .field("superclass", &self.raw.superclass)
+            .field("flags", &self.flags())
+            .field("instance_size", &self.instance_size())
+            .field("instance_align_mask", &self.instance_align_mask())
+            .field("description", &self.description())
+            .finish()
+    }
+}
+
+impl ClassMetadata {
+    /// Creates an instance from a raw class metadata value.
+    ///
+    /// # Safety
+    ///
+    /// The resulting context where `self` is placed must be correct for the
+    /// value of the raw value.
+    #[inline]
+    pub const unsafe fn from_raw(raw: RawClassMetadata) -> Self {
+        Self { raw }
+    }
+
+    /// Extracts the inner raw class metadata value.
+    #[inline]
+    pub const fn into_raw(self) -> RawClassMetadata {
+        self.raw
+    }
+
+    /// Returns a reference to the inner raw class metadata value.
+    #[inline]
+    pub const fn as_raw(&self) -> &RawClassMetadata {
+        &self.raw
+    }
+}
+
+impl ClassMetadata {
+    /// Casts the class metadata to a type-erased metadata.
+    #[inline]
+    pub fn as_metadata(&self) -> &Metadata {
+        self.as_ref()
+    }
+
+    /// Returns `true` if the metadata is of a Swift class, rather than only
+    /// the Objective-C parts of a class.
+    ///
+    /// This is always `true` on platforms without Objective-C interop.
+    #[inline]
+    pub fn is_swift_class(&self) -> bool {
+        #[cfg(target_vendor = "apple")]
+        {
+            // Older runtimes set the low bit and newer runtimes set the next.
+            self.raw.data & 0b11 != 0
+        }
+
+        #[cfg(not(target_vendor = "apple"))]
+        {
+            true
+        }
+    }
+
+    /// Returns the metadata of the superclass, or `None` for root classes.
+    ///
+    /// Superclasses defined in Objective-C, such as `NSObject`, are also
+    /// `None`, since they have no Swift class metadata.
+    #[inline]
+    pub fn superclass(&self) -> Option<&ClassMetadata> {
+        let superclass = unsafe { self.raw.superclass.cast::<ClassMetadata>().as_ref()? };
+        if superclass.is_swift_class() {
+            Some(superclass)
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the superclasses, starting with the nearest.
+    #[inline]
+    pub fn superclasses(&self) -> impl Iterator<Item = &ClassMetadata> {
+        std::iter::successors(self.superclass(), |class| class.superclass())
+    }
+
+    /// Returns the Swift-specific class flags.
+    #[inline]
+    pub fn flags(&self) -> ClassFlags {
+        self.raw.flags
+    }
+
+    /// Returns the address point of instances of this type.
+    #[inline]
+    pub fn instance_address_point(&self) -> u32 {
+        self.raw.instance_address_point
+    }
+
+    /// Returns the required size of instances of this type, including the
+    /// object header.
+    #[inline]
+    pub fn instance_size(&self) -> u32 {
+        self.raw.instance_size
+    }
+
+    /// Returns the alignment mask of the address point of instances of this
+    /// type.
+    #[inline]
+    pub fn instance_align_mask(&self) -> u16 {
+        self.raw.instance_align_mask
+    }
+
+    /// Returns the total size of the class object, including prefix and
+    /// suffix extents.
+    #[inline]
+    pub fn class_size(&self) -> u32 {
+        self.raw.class_size
+    }
+
+    /// Returns the offset of the address point within the class object.
+    #[inline]
+    pub fn class_address_point(&self) -> u32 {
+        self.raw.class_address_point
+    }
+
+    /// Returns an out-of-line description of the type, or `None` for
+    /// artificial subclasses.
+    #[inline]
+    pub fn description(&self) -> Option<&ClassDescriptor> {
+        unsafe { self.raw.description.cast::<ClassDescriptor>().as_ref() }
+    }
+
+    /// Returns the function that destroys the instance variables of an
+    /// object after an early return from a constructor.
+    ///
+    /// The function uses the Swift calling convention and takes the object
+    /// in the context register, so it can be called with
+    /// [`SwiftCall`](crate::call::SwiftCall).
+    #[inline]
+    pub fn ivar_destroyer(&self) -> Option<NonNull<c_void>> {
+        NonNull::new(self.raw.ivar_destroyer as *mut c_void)
+    }
+}
+
+/// Immediate members.
+///
+/// Each class in the hierarchy adds its generic arguments, field offsets and
+/// vtable to the metadata, after those of its superclass.
+impl ClassMetadata {
+    /// Returns the offset in words of the immediate members of the class
+    /// from the address point.
+    fn immediate_members_offset(&self) -> Option<isize> {
+        let description = self.description()?;
+        match description.immediate_members_offset() {
+            Some(offset) => Some(offset),
+
+            // The immediate members of a class with a resilient superclass
+            // follow those of its superclass.
+            None => {
+                let superclass = self.superclass()?;
+                let size = superclass.class_size() - superclass.class_address_point();
+                Some(size as isize / mem::size_of::<usize>() as isize)
+            }
+        }
+    }
+
+    /// Returns the offset in words from the address point of a member at
+    /// `offset`, which is relative to the immediate members if the class has
+    /// a resilient superclass.
+    fn member_offset(&self, description: &ClassDescriptor, offset: u32) -> Option<isize> {
+        if description.has_resilient_superclass() {
+            Some(self.immediate_members_offset()? + offset as isize)
+        } else {
+            Some(offset as isize)
+        }
+    }
+
+    /// Returns `len` words at `offset` words from the address point.
+    #[inline]
+    unsafe fn words<T>(&self, offset: isize, len: usize) -> &[T] {
+        let words = (self as *const Self as *const usize).offset(offset);
+        slice::from_raw_parts(words.cast(), len)
+    }
+
+    /// Returns the generic arguments of the class: the metadata of each key
+    /// generic parameter, followed by the witness table of each protocol
+    /// conformance requirement.
+    ///
+    /// The arguments of generic superclasses are found in their metadata.
+    pub fn generic_args(&self) -> &[*const c_void] {
+        let description = match self.description() {
+            Some(description) if description.flags().is_generic() => description,
+            _ => return &[],
+        };
+        match self.immediate_members_offset() {
+            Some(offset) => unsafe { self.words(offset, description.num_key_arguments()) },
+            None => &[],
+        }
+    }
+
+    /// Returns the offsets in bytes of the stored properties of the class
+    /// within its instances, not including those of its superclasses.
+    pub fn field_offsets(&self) -> &[usize] {
+        let description = match self.description() {
+            Some(description) if description.field_offset_vector_offset() != 0 => description,
+            _ => return &[],
+        };
+        match self.member_offset(description, description.field_offset_vector_offset()) {
+            Some(offset) => unsafe { self.words(offset, description.num_fields() as usize) },
+            None => &[],
+        }
+    }
+
+    /// Returns the implementations of the methods in the vtable of the class,
+    /// which may override those of its superclasses.
+    ///
+    /// The slots correspond to [`ClassDescriptor::methods`]. Methods that the
+    /// class inherits are found in the vtables of its superclasses.
+    pub fn vtable(&self) -> &[*const c_void] {
+        let description = match self.description() {
+            Some(description) => description,
+            None => return &[],
+        };
+        let header = match description.vtable_header() {
+            Some(header) => header,
+            None => return &[],
+        };
+        match self.member_offset(description, header.vtable_offset) {
+            Some(offset) => unsafe { self.words(offset, header.vtable_size as usize) },
+            None => &[],
+        }
+    }
+}
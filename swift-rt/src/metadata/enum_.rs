@@ -2,7 +2,10 @@ use crate::{
     ctx_desc::EnumDescriptor,
     metadata::{Metadata, MetadataKind},
 };
-use std::{fmt, os::raw::c_uint};
+use std::{
+    fmt,
+    os::raw::{c_uint, c_void},
+};
 use swift_sys::metadata::{EnumMetadata as RawEnumMetadata, EnumValueWitnessTable};
 
 /// Metadata for enums.
@@ -107,6 +110,15 @@ impl EnumMetadata {
     pub fn type_descriptor(&self) -> &EnumDescriptor {
         unsafe { &*self.raw.type_descriptor.cast() }
     }
+
+    /// Returns a pointer to the generic arguments of the type, which follow
+    /// its descriptor.
+    ///
+    /// The arguments are only valid to read if the enum is generic.
+    #[inline]
+    pub fn generic_args(&self) -> *const *const c_void {
+        unsafe { (self as *const Self).add(1).cast() }
+    }
 }
 
 /// Value-witness function invocation.
@@ -0,0 +1,229 @@
+use crate::{
+    ctx_desc::ProtocolContextDescriptor,
+    metadata::{
+        ExistentialTypeFlags, ExistentialTypeRepresentation, Metadata, ProtocolDescriptorRef,
+        SpecialProtocol,
+    },
+};
+use std::{fmt, slice};
+use swift_sys::metadata::{
+    ExistentialMetatypeMetadata as RawExistentialMetatypeMetadata,
+    ExistentialTypeMetadata as RawExistentialTypeMetadata, ValueWitnessTable,
+};
+
+/// Metadata for existential types, such as `Any`, `AnyObject` and `any P`.
+#[repr(transparent)]
+pub struct ExistentialTypeMetadata {
+    raw: RawExistentialTypeMetadata,
+}
+
+impl AsRef<Metadata> for ExistentialTypeMetadata {
+    #[inline]
+    fn as_ref(&self) -> &Metadata {
+        unsafe { &*(self as *const _ as *const _) }
+    }
+}
+
+unsafe impl Send for ExistentialTypeMetadata {}
+unsafe impl Sync for ExistentialTypeMetadata {}
+
+impl fmt::Debug for ExistentialTypeMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ExistentialTypeMetadata")
+            .field("flags", &self.flags())
+            .field("representation", &self.representation())
+            .field("superclass_constraint", &self.superclass_constraint())
+            .field("protocols", &self.protocols())
+            .field("value_witnesses", self.value_witnesses())
+            .finish()
+    }
+}
+
+impl ExistentialTypeMetadata {
+    /// Creates an instance from a raw existential type metadata value.
+    ///
+    /// # Safety
+    ///
+    /// The resulting context where `self` is placed must be correct for the
+    /// value of the raw value.
+    #[inline]
+    pub const unsafe fn from_raw(raw: RawExistentialTypeMetadata) -> Self {
+        Self { raw }
+    }
+
+    /// Extracts the inner raw existential type metadata value.
+    #[inline]
+    pub const fn into_raw(self) -> RawExistentialTypeMetadata {
+        self.raw
+    }
+
+    /// Returns a reference to the inner raw existential type metadata value.
+    #[inline]
+    pub const fn as_raw(&self) -> &RawExistentialTypeMetadata {
+        &self.raw
+    }
+}
+
+impl ExistentialTypeMetadata {
+    /// Casts the existential type metadata to a type-erased metadata.
+    #[inline]
+    pub fn as_metadata(&self) -> &Metadata {
+        self.as_ref()
+    }
+
+    /// Returns the value-witness table.
+    #[inline]
+    pub fn value_witnesses(&self) -> &ValueWitnessTable {
+        self.as_metadata().value_witnesses()
+    }
+
+    /// Returns the flags describing the existential type.
+    #[inline]
+    pub fn flags(&self) -> ExistentialTypeFlags {
+        self.raw.flags
+    }
+
+    /// Returns the representation of values of the existential type.
+    #[inline]
+    pub fn representation(&self) -> ExistentialTypeRepresentation {
+        self.flags().representation()
+    }
+
+    /// Returns the special protocol of the existential type.
+    #[inline]
+    pub fn special_protocol(&self) -> SpecialProtocol {
+        self.flags().special_protocol()
+    }
+
+    /// Returns `true` if values of the existential type are class instances,
+    /// such as `AnyObject`.
+    #[inline]
+    pub fn is_class_constrained(&self) -> bool {
+        self.flags().is_class_constrained()
+    }
+
+    /// Returns the number of witness tables that values of the existential
+    /// type store.
+    #[inline]
+    pub fn num_witness_tables(&self) -> usize {
+        self.flags().num_witness_tables()
+    }
+
+    #[inline]
+    fn trailing_objects(&self) -> *const *const Metadata {
+        unsafe { (self as *const Self).add(1).cast() }
+    }
+
+    /// Returns the metadata of the superclass that values must inherit from,
+    /// such as `C` in `any C & P`.
+    #[inline]
+    pub fn superclass_constraint(&self) -> Option<&Metadata> {
+        if self.flags().has_superclass_constraint() {
+            unsafe { Some(&**self.trailing_objects()) }
+        } else {
+            None
+        }
+    }
+
+    /// Returns the protocols that the existential type is composed of.
+    #[inline]
+    pub fn protocols(&self) -> &[ProtocolDescriptorRef] {
+        let skip = self.flags().has_superclass_constraint() as usize;
+        unsafe {
+            let protocols = self.trailing_objects().add(skip);
+            slice::from_raw_parts(protocols.cast(), self.raw.num_protocols as usize)
+        }
+    }
+
+    /// Returns an iterator over the Swift protocols that the existential type
+    /// is composed of, which skips Objective-C protocols.
+    #[inline]
+    pub fn swift_protocols(&self) -> impl Iterator<Item = &ProtocolContextDescriptor> {
+        self.protocols().iter().filter_map(|protocol| unsafe {
+            protocol
+                .swift_protocol()?
+                .cast::<ProtocolContextDescriptor>()
+                .as_ref()
+        })
+    }
+}
+
+/// Metadata for the metatype of an existential type, such as `Any.Type`.
+#[repr(transparent)]
+pub struct ExistentialMetatypeMetadata {
+    raw: RawExistentialMetatypeMetadata,
+}
+
+impl AsRef<Metadata> for ExistentialMetatypeMetadata {
+    #[inline]
+    fn as_ref(&self) -> &Metadata {
+        unsafe { &*(self as *const _ as *const _) }
+    }
+}
+
+unsafe impl Send for ExistentialMetatypeMetadata {}
+unsafe impl Sync for ExistentialMetatypeMetadata {}
+
+impl fmt::Debug for ExistentialMetatypeMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ExistentialMetatypeMetadata")
+            .field("instance_type", self.instance_type())
+            .field("flags", &self.flags())
+            .field("value_witnesses", self.value_witnesses())
+            .finish()
+    }
+}
+
+impl ExistentialMetatypeMetadata {
+    /// Creates an instance from a raw existential metatype metadata value.
+    ///
+    /// # Safety
+    ///
+    /// The resulting context where `self` is placed must be correct for the
+    /// value of the raw value.
+    #[inline]
+    pub const unsafe fn from_raw(raw: RawExistentialMetatypeMetadata) -> Self {
+        Self { raw }
+    }
+
+    /// Extracts the inner raw existential metatype metadata value.
+    #[inline]
+    pub const fn into_raw(self) -> RawExistentialMetatypeMetadata {
+        self.raw
+    }
+
+    /// Returns a reference to the inner raw existential metatype metadata
+    /// value.
+    #[inline]
+    pub const fn as_raw(&self) -> &RawExistentialMetatypeMetadata {
+        &self.raw
+    }
+}
+
+impl ExistentialMetatypeMetadata {
+    /// Casts the existential metatype metadata to a type-erased metadata.
+    #[inline]
+    pub fn as_metadata(&self) -> &Metadata {
+        self.as_ref()
+    }
+
+    /// Returns the value-witness table.
+    #[inline]
+    pub fn value_witnesses(&self) -> &ValueWitnessTable {
+        self.as_metadata().value_witnesses()
+    }
+
+    /// Returns the metadata of the instance type, which is either an
+    /// existential type or an existential metatype.
+    #[inline]
+    pub fn instance_type(&self) -> &Metadata {
+        unsafe { &*self.raw.instance_type.cast() }
+    }
+
+    /// Returns the flags of the existential type at the root of the instance
+    /// type.
+    #[inline]
+    pub fn flags(&self) -> ExistentialTypeFlags {
+        self.raw.flags
+    }
+}
@@ -0,0 +1,219 @@
+use crate::metadata::{FunctionMetadataConvention, FunctionTypeFlags, Metadata, ParameterFlags};
+use std::{fmt, mem, slice};
+use swift_sys::metadata::{
+    fns, FunctionTypeMetadata as RawFunctionTypeMetadata, ValueWitnessTable,
+};
+
+/// Metadata for function types.
+#[repr(transparent)]
+pub struct FunctionTypeMetadata {
+    raw: RawFunctionTypeMetadata,
+}
+
+impl AsRef<Metadata> for FunctionTypeMetadata {
+    #[inline]
+    fn as_ref(&self) -> &Metadata {
+        unsafe { &*(self as *const _ as *const _) }
+    }
+}
+
+unsafe impl Send for FunctionTypeMetadata {}
+unsafe impl Sync for FunctionTypeMetadata {}
+
+impl fmt::Debug for FunctionTypeMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FunctionTypeMetadata")
+            .field("flags", &self.flags())
+            .field("params", &self.params())
+            .field("param_flags", &self.param_flags())
+            .field("result_type", self.result_type())
+            .field("global_actor", &self.global_actor())
+            .field("value_witnesses", self.value_witnesses())
+            .finish()
+    }
+}
+
+impl FunctionTypeMetadata {
+    /// Creates an instance from a raw function type metadata value.
+    ///
+    /// # Safety
+    ///
+    /// The resulting context where `self` is placed must be correct for the
+    /// value of the raw value.
+    #[inline]
+    pub const unsafe fn from_raw(raw: RawFunctionTypeMetadata) -> Self {
+        Self { raw }
+    }
+
+    /// Extracts the inner raw function type metadata value.
+    #[inline]
+    pub const fn into_raw(self) -> RawFunctionTypeMetadata {
+        self.raw
+    }
+
+    /// Returns a reference to the inner raw function type metadata value.
+    #[inline]
+    pub const fn as_raw(&self) -> &RawFunctionTypeMetadata {
+        &self.raw
+    }
+}
+
+impl FunctionTypeMetadata {
+    /// Returns the uniqued metadata for the function type with `params` and
+    /// `result`.
+    ///
+    /// The number of parameters in `flags` is set from `params`, and the flags
+    /// are marked as having parameter flags if `param_flags` is not empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `param_flags` is neither empty nor the same length as
+    /// `params`, or if `flags` has a global actor or is differentiable, which
+    /// requires arguments that this function doesn't take.
+    #[doc(alias = "swift_getFunctionTypeMetadata")]
+    pub fn get(
+        flags: FunctionTypeFlags,
+        params: &[&Metadata],
+        param_flags: &[ParameterFlags],
+        result: &Metadata,
+    ) -> &'static Self {
+        assert!(
+            param_flags.is_empty() || param_flags.len() == params.len(),
+            "expected {} parameter flags, found {}",
+            params.len(),
+            param_flags.len(),
+        );
+        assert!(
+            !flags.has_global_actor() && !flags.is_differentiable(),
+            "function types with a global actor or differentiability are unsupported",
+        );
+        assert!(params.len() <= 0xFFFF, "too many parameters");
+
+        let flags = flags
+            .with_num_params(params.len())
+            .with_param_flags(!param_flags.is_empty());
+
+        unsafe {
+            // `&Metadata` has the same layout as `*const Metadata`.
+            let metadata = fns::swift_getFunctionTypeMetadata(
+                flags,
+                params.as_ptr().cast(),
+                param_flags.as_ptr(),
+                result.as_raw(),
+            );
+            &*metadata.cast()
+        }
+    }
+
+    /// Casts the function type metadata to a type-erased metadata.
+    #[inline]
+    pub fn as_metadata(&self) -> &Metadata {
+        self.as_ref()
+    }
+
+    /// Returns the value-witness table.
+    #[inline]
+    pub fn value_witnesses(&self) -> &ValueWitnessTable {
+        self.as_metadata().value_witnesses()
+    }
+
+    /// Returns the flags describing the function type.
+    #[inline]
+    pub fn flags(&self) -> FunctionTypeFlags {
+        self.raw.flags
+    }
+
+    /// Returns the calling convention of the function type.
+    #[inline]
+    pub fn convention(&self) -> FunctionMetadataConvention {
+        self.flags().convention()
+    }
+
+    /// Returns `true` if the function `throws`.
+    #[inline]
+    pub fn is_throwing(&self) -> bool {
+        self.flags().is_throwing()
+    }
+
+    /// Returns `true` if the function is `async`.
+    #[inline]
+    pub fn is_async(&self) -> bool {
+        self.flags().is_async()
+    }
+
+    /// Returns `true` if the function is `@escaping`.
+    #[inline]
+    pub fn is_escaping(&self) -> bool {
+        self.flags().is_escaping()
+    }
+
+    /// Returns `true` if the function is isolated to a global actor.
+    #[inline]
+    pub fn has_global_actor(&self) -> bool {
+        self.flags().has_global_actor()
+    }
+
+    /// Returns the metadata of the result type.
+    #[inline]
+    pub fn result_type(&self) -> &Metadata {
+        unsafe { &*self.raw.result_type.cast() }
+    }
+
+    /// Returns the metadata of the parameter types.
+    #[inline]
+    pub fn params(&self) -> &[&Metadata] {
+        unsafe {
+            let params = (self as *const Self).add(1);
+            slice::from_raw_parts(params.cast(), self.flags().num_params())
+        }
+    }
+
+    /// Returns the flags of the parameters, which are empty if no parameter
+    /// has flags.
+    #[inline]
+    pub fn param_flags(&self) -> &[ParameterFlags] {
+        if !self.flags().has_param_flags() {
+            return &[];
+        }
+        unsafe {
+            let params = self.params();
+            let flags = params.as_ptr().add(params.len());
+            slice::from_raw_parts(flags.cast(), params.len())
+        }
+    }
+
+    /// Returns the flags of the parameter at `index`, or `None` if there is
+    /// no such parameter.
+    #[inline]
+    pub fn param_flags_at(&self, index: usize) -> Option<ParameterFlags> {
+        if index >= self.flags().num_params() {
+            None
+        } else {
+            Some(self.param_flags().get(index).copied().unwrap_or_default())
+        }
+    }
+
+    /// Returns the metadata of the global actor that the function is isolated
+    /// to, if any.
+    pub fn global_actor(&self) -> Option<&Metadata> {
+        if !self.has_global_actor() {
+            return None;
+        }
+
+        // The global actor follows the parameter flags, which are padded to
+        // pointer alignment, and the differentiability kind.
+        let params = self.params();
+        let flags = self.param_flags();
+        let mut offset = mem::size_of_val(params) + mem::size_of_val(flags);
+        let align = mem::align_of::<usize>();
+        offset = (offset + align - 1) & !(align - 1);
+        if self.flags().is_differentiable() {
+            offset += mem::size_of::<usize>();
+        }
+
+        unsafe {
+            let actor = (params.as_ptr() as *const u8).add(offset);
+            Some(*actor.cast::<&Metadata>())
+        }
+    }
+}
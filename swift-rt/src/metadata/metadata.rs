@@ -1,13 +1,18 @@
 use crate::{
-    ctx_desc::TypeContextDescriptor,
+    ctx_desc::{
+        ContextDescriptor, GenericEnvironment, ProtocolContextDescriptor, TypeContextDescriptor,
+    },
+    mangling::Mangled,
     metadata::{
-        EnumMetadata, MetadataKind, MetadataResponse, MetatypeMetadata, StructMetadata,
-        TupleMetadata,
+        ClassMetadata, EnumMetadata, ExistentialMetatypeMetadata, ExistentialTypeMetadata,
+        FunctionTypeMetadata, MetadataKind, MetadataResponse, MetatypeMetadata, StructMetadata,
+        TupleMetadata, WitnessTable,
     },
 };
 use std::{
     fmt,
     os::raw::{c_uint, c_void},
+    ptr,
 };
 use swift_sys::metadata::{
     EnumValueWitnessTable, Metadata as RawMetadata, MetadataRequest, ValueWitnessTable,
@@ -47,10 +52,30 @@ impl fmt::Debug for Metadata {
         // `fmt` is called with the type's name to ensure that the correct
         // implementation calls, and that this does not infinitely recurse.
         match self.kind() {
+            MetadataKind::CLASS if self.as_class().is_some() => ClassMetadata::fmt(
+                unsafe { &*(self as *const Self as *const ClassMetadata) },
+                f,
+            ),
+
             MetadataKind::ENUM | MetadataKind::OPTIONAL => {
                 EnumMetadata::fmt(unsafe { &*(self as *const Self as *const EnumMetadata) }, f)
             }
 
+            MetadataKind::EXISTENTIAL => ExistentialTypeMetadata::fmt(
+                unsafe { &*(self as *const Self as *const ExistentialTypeMetadata) },
+                f,
+            ),
+
+            MetadataKind::EXISTENTIAL_METATYPE => ExistentialMetatypeMetadata::fmt(
+                unsafe { &*(self as *const Self as *const ExistentialMetatypeMetadata) },
+                f,
+            ),
+
+            MetadataKind::FUNCTION => FunctionTypeMetadata::fmt(
+                unsafe { &*(self as *const Self as *const FunctionTypeMetadata) },
+                f,
+            ),
+
             MetadataKind::STRUCT => StructMetadata::fmt(
                 unsafe { &*(self as *const Self as *const StructMetadata) },
                 f,
@@ -137,6 +162,66 @@ impl Metadata {
         ))
     }
 
+    /// Returns the metadata of the type named by `name`, or `None` if it
+    /// can't be found.
+    ///
+    /// Generic parameters in `name` refer to `generic_args`, which are the
+    /// generic arguments of `context`. This resolves the type of a field
+    /// from [`FieldRecord::type_name`](crate::reflection::FieldRecord::type_name)
+    /// with the descriptor and generic arguments of the type declaring it,
+    /// such as from [`StructMetadata::generic_args`].
+    ///
+    /// # Safety
+    ///
+    /// `generic_args` must point to the generic arguments of `context` if it
+    /// is generic.
+    #[inline]
+    #[doc(alias = "swift_getTypeByMangledNameInContext")]
+    pub unsafe fn from_mangled_name(
+        name: &Mangled,
+        context: Option<&ContextDescriptor>,
+        generic_args: *const *const c_void,
+    ) -> Option<&'static Metadata> {
+        let context = context.map_or(ptr::null(), |context| context as *const ContextDescriptor);
+        RawMetadata::from_mangled_name_in_context(
+            name.as_ptr(),
+            name.len(),
+            context.cast(),
+            generic_args,
+        )
+        .cast::<Metadata>()
+        .as_ref()
+    }
+
+    /// Returns the metadata of the type named by `name`, or `None` if it
+    /// can't be found.
+    ///
+    /// Generic parameters in `name` refer to `generic_args`, which are the
+    /// generic arguments of `environment`.
+    ///
+    /// # Safety
+    ///
+    /// `generic_args` must point to the generic arguments of `environment`.
+    #[inline]
+    #[doc(alias = "swift_getTypeByMangledNameInEnvironment")]
+    pub unsafe fn from_mangled_name_in_environment(
+        name: &Mangled,
+        environment: Option<&GenericEnvironment>,
+        generic_args: *const *const c_void,
+    ) -> Option<&'static Metadata> {
+        let environment = environment.map_or(ptr::null(), |environment| {
+            environment as *const GenericEnvironment
+        });
+        RawMetadata::from_mangled_name_in_environment(
+            name.as_ptr(),
+            name.len(),
+            environment,
+            generic_args,
+        )
+        .cast::<Metadata>()
+        .as_ref()
+    }
+
     /// Return the name of a Swift type represented by a metadata object.
     #[inline]
     #[doc(alias = "swift_getTypeName")]
@@ -223,10 +308,41 @@ impl Metadata {
                 .as_ref()
         }
     }
+
+    /// Returns the witness table of this type's conformance to `protocol`, or
+    /// `None` if it doesn't conform.
+    #[inline]
+    #[doc(alias = "swift_conformsToProtocol")]
+    pub fn conformance(&self, protocol: &ProtocolContextDescriptor) -> Option<&WitnessTable> {
+        // SAFETY: Both references are to valid runtime structures, and
+        // conformances live as long as the type.
+        unsafe {
+            RawMetadata::conformance(
+                self.as_raw(),
+                (protocol as *const ProtocolContextDescriptor).cast(),
+            )
+            .as_ref()
+        }
+    }
 }
 
 /// Casting to subtypes.
 impl Metadata {
+    /// Casts this metadata to a class metadata if it is one.
+    ///
+    /// Classes defined in Objective-C are not cast, since they have no Swift
+    /// class metadata.
+    #[inline]
+    pub fn as_class(&self) -> Option<&ClassMetadata> {
+        if self.kind().is_class() {
+            let class = unsafe { &*(self as *const Self as *const ClassMetadata) };
+            if class.is_swift_class() {
+                return Some(class);
+            }
+        }
+        None
+    }
+
     /// Casts this metadata to an enum metadata if it is one.
     #[inline]
     pub fn as_enum(&self) -> Option<&EnumMetadata> {
@@ -237,6 +353,36 @@ impl Metadata {
         }
     }
 
+    /// Casts this metadata to an existential type metadata if it is one.
+    #[inline]
+    pub fn as_existential(&self) -> Option<&ExistentialTypeMetadata> {
+        if self.kind().is_existential() {
+            Some(unsafe { &*(self as *const Self as *const ExistentialTypeMetadata) })
+        } else {
+            None
+        }
+    }
+
+    /// Casts this metadata to an existential metatype metadata if it is one.
+    #[inline]
+    pub fn as_existential_metatype(&self) -> Option<&ExistentialMetatypeMetadata> {
+        if self.kind().is_existential_metatype() {
+            Some(unsafe { &*(self as *const Self as *const ExistentialMetatypeMetadata) })
+        } else {
+            None
+        }
+    }
+
+    /// Casts this metadata to a function type metadata if it is one.
+    #[inline]
+    pub fn as_function(&self) -> Option<&FunctionTypeMetadata> {
+        if self.kind().is_function() {
+            Some(unsafe { &*(self as *const Self as *const FunctionTypeMetadata) })
+        } else {
+            None
+        }
+    }
+
     /// Casts this metadata to a struct metadata if it is one.
     #[inline]
     pub fn as_struct(&self) -> Option<&StructMetadata> {
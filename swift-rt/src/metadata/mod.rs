@@ -2,9 +2,16 @@
 
 // Re-export basic types that don't need to be wrapped.
 #[doc(no_inline)]
-pub use swift_sys::metadata::{MetadataKind, MetadataState};
+pub use swift_sys::metadata::{
+    ExistentialTypeFlags, ExistentialTypeRepresentation, FunctionMetadataConvention,
+    FunctionTypeFlags, MetadataKind, MetadataState, OpaqueExistentialContainer, ParameterFlags,
+    ProtocolDescriptorRef, SpecialProtocol, ValueOwnership, WitnessTable,
+};
 
+mod class;
 mod enum_;
+mod existential;
+mod function;
 mod metadata;
 mod metatype;
 mod response;
@@ -12,7 +19,10 @@ mod struct_;
 mod tuple;
 mod ty;
 
+pub use class::*;
 pub use enum_::*;
+pub use existential::*;
+pub use function::*;
 pub use metadata::*;
 pub use metatype::*;
 pub use response::*;
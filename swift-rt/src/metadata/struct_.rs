@@ -2,7 +2,7 @@ use crate::{
     ctx_desc::StructDescriptor,
     metadata::{Metadata, MetadataKind},
 };
-use std::fmt;
+use std::{fmt, os::raw::c_void};
 use swift_sys::metadata::{StructMetadata as RawStructMetadata, ValueWitnessTable};
 
 /// Metadata for structs.
@@ -91,4 +91,13 @@ impl StructMetadata {
     pub fn type_descriptor(&self) -> &StructDescriptor {
         unsafe { &*self.raw.type_descriptor.cast() }
     }
+
+    /// Returns a pointer to the generic arguments of the type, which follow
+    /// its descriptor.
+    ///
+    /// The arguments are only valid to read if the struct is generic.
+    #[inline]
+    pub fn generic_args(&self) -> *const *const c_void {
+        unsafe { (self as *const Self).add(1).cast() }
+    }
 }
@@ -1,6 +1,8 @@
-use crate::metadata::Metadata;
-use std::fmt;
-use swift_sys::metadata::{TupleMetadata as RawTupleMetadata, ValueWitnessTable};
+use crate::metadata::{Metadata, MetadataResponse, MetadataState};
+use std::{fmt, ptr};
+use swift_sys::metadata::{
+    fns, MetadataRequest, TupleMetadata as RawTupleMetadata, TupleTypeFlags, ValueWitnessTable,
+};
 
 mod element;
 mod labeled_element_iter;
@@ -83,6 +85,130 @@ impl TupleMetadata {
 }
 
 impl TupleMetadata {
+    /// Returns the uniqued metadata for the tuple type with `elements` and
+    /// optional `labels`, blocking until it is complete.
+    ///
+    /// An empty label leaves its element unlabeled, so the labels
+    /// `["x", "", "z"]` make the tuple type `(x: A, B, z: C)`.
+    ///
+    /// # Panics
+    ///
+    /// See [`request`](Self::request).
+    #[doc(alias = "swift_getTupleTypeMetadata")]
+    pub fn get(elements: &[&Metadata], labels: Option<&[&str]>) -> &'static Self {
+        let request = MetadataRequest::blocking(MetadataState::COMPLETE);
+        let response = Self::request(request, elements, labels);
+
+        unsafe { &*response.value_ptr().cast() }
+    }
+
+    /// Requests the uniqued metadata for the tuple type with `elements` and
+    /// optional `labels`.
+    ///
+    /// The labels are copied by the runtime, so they don't need to outlive the
+    /// returned metadata.
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    ///
+    /// - `labels` is not the same length as `elements`.
+    ///
+    /// - A label contains a space or a null byte.
+    ///
+    /// - There is exactly one element and no labels, since Swift has no
+    ///   unlabeled single-element tuple types.
+    ///
+    /// - There are more than 65535 elements.
+    #[doc(alias = "swift_getTupleTypeMetadata")]
+    #[doc(alias = "swift_getTupleTypeMetadata2")]
+    #[doc(alias = "swift_getTupleTypeMetadata3")]
+    pub fn request(
+        request: MetadataRequest,
+        elements: &[&Metadata],
+        labels: Option<&[&str]>,
+    ) -> MetadataResponse {
+        if let Some(labels) = labels {
+            assert_eq!(
+                labels.len(),
+                elements.len(),
+                "expected {} tuple labels, found {}",
+                elements.len(),
+                labels.len(),
+            );
+        }
+
+        // Tuples without any labels are the same type as unlabeled tuples.
+        let labels = labels.filter(|labels| labels.iter().any(|label| !label.is_empty()));
+
+        // Encode labels as a null-terminated string with each label followed
+        // by a space.
+        let labels: Option<Vec<u8>> = labels.map(|labels| {
+            let mut string =
+                Vec::with_capacity(labels.iter().map(|l| l.len() + 1).sum::<usize>() + 1);
+            for label in labels {
+                assert!(
+                    !label.contains(&[' ', '\0'][..]),
+                    "invalid tuple label {:?}",
+                    label,
+                );
+                string.extend_from_slice(label.as_bytes());
+                string.push(b' ');
+            }
+            string.push(0);
+            string
+        });
+
+        assert!(
+            elements.len() != 1 || labels.is_some(),
+            "a single unlabeled element is not a tuple type",
+        );
+        assert!(elements.len() <= 0xFFFF, "too many tuple elements");
+
+        let labels_ptr = match &labels {
+            Some(labels) => labels.as_ptr().cast(),
+            None => ptr::null(),
+        };
+        let proposed_witnesses = ptr::null();
+
+        let response = unsafe {
+            match (elements, &labels) {
+                // The specialized functions expect labels to be constant.
+                ([a, b], None) => fns::swift_getTupleTypeMetadata2(
+                    request,
+                    a.as_raw(),
+                    b.as_raw(),
+                    labels_ptr,
+                    proposed_witnesses,
+                ),
+                ([a, b, c], None) => fns::swift_getTupleTypeMetadata3(
+                    request,
+                    a.as_raw(),
+                    b.as_raw(),
+                    c.as_raw(),
+                    labels_ptr,
+                    proposed_witnesses,
+                ),
+                _ => {
+                    let flags = TupleTypeFlags::empty()
+                        .with_num_elements(elements.len())
+                        .with_non_constant_labels(labels.is_some());
+
+                    // `&Metadata` has the same layout as `*const Metadata`.
+                    fns::swift_getTupleTypeMetadata(
+                        request,
+                        flags,
+                        elements.as_ptr().cast(),
+                        labels_ptr,
+                        proposed_witnesses,
+                    )
+                }
+            }
+        };
+
+        unsafe { MetadataResponse::from_raw(response) }
+    }
+
     /// Casts the tuple metadata to a type-erased metadata.
     #[inline]
     pub fn as_metadata(&self) -> &Metadata {
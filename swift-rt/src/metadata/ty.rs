@@ -1,4 +1,9 @@
-use crate::metadata::{Metadata, StructMetadata, TupleMetadata};
+use crate::metadata::{
+    FunctionMetadataConvention, FunctionTypeFlags, FunctionTypeMetadata, Metadata, MetadataState,
+    StructMetadata, TupleMetadata,
+};
+use std::any;
+use swift_sys::metadata::MetadataRequest;
 
 // Used for simplifying doc comments.
 #[allow(unused_imports)]
@@ -43,10 +48,6 @@ pub trait Type: 'static {
     fn get_metadata_blocking(blocking: bool) -> Option<&'static Self::Metadata>;
 }
 
-// TODO: Use `swift_getTupleTypeMetadata2` for 2-ary tuples.
-// TODO: Use `swift_getTupleTypeMetadata3` for 3-ary tuples.
-// TODO: Use `swift_getTupleTypeMetadata` for n-ary tuples.
-
 macro_rules! imp_static {
     ($($ty:ty => $metadata_ty:ty, $sym:expr;)+) => {
         $(
@@ -136,6 +137,134 @@ imp_static! {
     char /* Unicode.Scalar */ => StructMetadata, "$ss7UnicodeO6ScalarVN";
 }
 
+/// Requests the metadata of the unlabeled tuple type of `elements` and checks
+/// that it has the same layout as the Rust tuple `T`.
+fn tuple_metadata<T>(
+    elements: &[&Metadata],
+    offsets: &[usize],
+    blocking: bool,
+) -> Option<&'static TupleMetadata> {
+    let request = MetadataRequest::new(MetadataState::COMPLETE, !blocking);
+    let response = TupleMetadata::request(request, elements, None);
+    let metadata = response.completed_value()?.as_tuple()?;
+
+    let layout = metadata.value_witnesses().memory_layout();
+    let layout_matches = layout.stride == std::mem::size_of::<T>()
+        && layout.align == std::mem::align_of::<T>()
+        && metadata
+            .elements()
+            .iter()
+            .zip(offsets)
+            .all(|(element, &offset)| element.offset() as usize == offset);
+
+    assert!(
+        layout_matches,
+        "layout of Rust tuple `{}` does not match its Swift tuple type",
+        any::type_name::<T>(),
+    );
+
+    Some(metadata)
+}
+
+macro_rules! imp_tuple {
+    ($($elem:ident . $index:tt),+) => {
+        /// Tuples have the metadata of the unlabeled Swift tuple type of their
+        /// elements.
+        ///
+        /// # Panics
+        ///
+        /// Rust does not guarantee the layout of tuples, so requesting metadata
+        /// panics if the element offsets, size or alignment of the Rust tuple
+        /// differ from those of the Swift tuple. This can happen when elements
+        /// are not in order of decreasing alignment.
+        impl<$($elem: Type),+> Type for ($($elem,)+) {
+            type Metadata = TupleMetadata;
+
+            #[inline]
+            fn is_pod() -> bool {
+                $($elem::is_pod())&&+
+            }
+
+            #[inline]
+            fn is_bitwise_takable() -> bool {
+                $($elem::is_bitwise_takable())&&+
+            }
+
+            #[doc(alias = "swift_getTupleTypeMetadata")]
+            fn get_metadata() -> &'static TupleMetadata {
+                Self::get_metadata_blocking(true).unwrap()
+            }
+
+            #[doc(alias = "swift_getTupleTypeMetadata")]
+            fn get_metadata_blocking(blocking: bool) -> Option<&'static TupleMetadata> {
+                let elements: &[&Metadata] =
+                    &[$($elem::get_metadata_blocking(blocking)?.as_ref()),+];
+                let offsets = [$(std::mem::offset_of!(Self, $index)),+];
+                tuple_metadata::<Self>(elements, &offsets, blocking)
+            }
+        }
+    };
+}
+
+imp_tuple!(A.0, B.1);
+imp_tuple!(A.0, B.1, C.2);
+imp_tuple!(A.0, B.1, C.2, D.3);
+imp_tuple!(A.0, B.1, C.2, D.3, E.4);
+imp_tuple!(A.0, B.1, C.2, D.3, E.4, F.5);
+imp_tuple!(A.0, B.1, C.2, D.3, E.4, F.5, G.6);
+imp_tuple!(A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7);
+
+/// Returns the metadata of a `@convention(c)` function type.
+fn c_function_metadata(params: &[&Metadata], result: &Metadata) -> &'static FunctionTypeMetadata {
+    let flags = FunctionTypeFlags::empty()
+        .with_convention(FunctionMetadataConvention::C)
+        .with_escaping(true);
+    FunctionTypeMetadata::get(flags, params, &[], result)
+}
+
+macro_rules! imp_c_fn {
+    ($($arg:ident),*) => {
+        imp_c_fn!(@imp extern "C" fn($($arg),*) -> R; $($arg),*);
+        imp_c_fn!(@imp unsafe extern "C" fn($($arg),*) -> R; $($arg),*);
+    };
+    (@imp $fn:ty; $($arg:ident),*) => {
+        impl<R: Type, $($arg: Type),*> Type for $fn {
+            type Metadata = FunctionTypeMetadata;
+
+            #[inline]
+            fn is_pod() -> bool {
+                true
+            }
+
+            #[inline]
+            fn is_bitwise_takable() -> bool {
+                true
+            }
+
+            #[doc(alias = "swift_getFunctionTypeMetadata")]
+            fn get_metadata() -> &'static FunctionTypeMetadata {
+                let params: &[&Metadata] = &[$($arg::get_metadata().as_ref()),*];
+                c_function_metadata(params, R::get_metadata().as_ref())
+            }
+
+            #[doc(alias = "swift_getFunctionTypeMetadata")]
+            fn get_metadata_blocking(blocking: bool) -> Option<&'static FunctionTypeMetadata> {
+                let params: &[&Metadata] = &[$($arg::get_metadata_blocking(blocking)?.as_ref()),*];
+                let result = R::get_metadata_blocking(blocking)?.as_ref();
+                Some(c_function_metadata(params, result))
+            }
+        }
+    };
+}
+
+imp_c_fn!();
+imp_c_fn!(A);
+imp_c_fn!(A, B);
+imp_c_fn!(A, B, C);
+imp_c_fn!(A, B, C, D);
+imp_c_fn!(A, B, C, D, E);
+imp_c_fn!(A, B, C, D, E, F);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -146,4 +275,76 @@ mod tests {
         let metadata: &Metadata = <()>::get_metadata().as_ref();
         assert_eq!(metadata.kind(), MetadataKind::TUPLE);
     }
+
+    #[test]
+    fn tuple_2() {
+        let metadata = <(i64, u8)>::get_metadata();
+        assert_eq!(metadata.as_metadata().kind(), MetadataKind::TUPLE);
+        assert!(!metadata.has_labels());
+
+        let elements: Vec<(*const Metadata, usize)> = metadata
+            .elements()
+            .iter()
+            .map(|element| (element.ty() as _, element.offset() as usize))
+            .collect();
+        assert_eq!(
+            elements,
+            [
+                (i64::get_metadata().as_metadata() as *const Metadata, 0),
+                (u8::get_metadata().as_metadata(), 8),
+            ]
+        );
+        assert!(<(i64, u8)>::is_pod());
+        assert!(std::ptr::eq(
+            metadata,
+            TupleMetadata::get(
+                &[
+                    i64::get_metadata().as_metadata(),
+                    u8::get_metadata().as_metadata()
+                ],
+                None
+            )
+        ));
+    }
+
+    #[test]
+    fn tuple_labels() {
+        let int = isize::get_metadata().as_metadata();
+        let metadata = TupleMetadata::get(&[int, int, int], Some(&["x", "", "z"]));
+        let labels: Vec<Option<&str>> = metadata.labels().unwrap().into_iter().collect();
+        assert_eq!(labels, [Some("x"), None, Some("z")]);
+
+        // Empty labels are the same as no labels.
+        assert!(std::ptr::eq(
+            TupleMetadata::get(&[int, int, int], Some(&["", "", ""])),
+            <(isize, isize, isize)>::get_metadata()
+        ));
+    }
+
+    #[test]
+    fn c_function() {
+        let metadata = <extern "C" fn(i32, f64) -> bool>::get_metadata();
+        assert_eq!(metadata.as_metadata().kind(), MetadataKind::FUNCTION);
+        assert_eq!(metadata.convention(), FunctionMetadataConvention::C);
+        assert!(!metadata.is_throwing() && !metadata.is_async());
+
+        let params: Vec<*const Metadata> = metadata.params().iter().map(|&p| p as _).collect();
+        assert_eq!(
+            params,
+            [
+                i32::get_metadata().as_metadata() as *const Metadata,
+                f64::get_metadata().as_metadata(),
+            ]
+        );
+        assert!(std::ptr::eq(
+            metadata.result_type(),
+            bool::get_metadata().as_metadata()
+        ));
+
+        // Safety doesn't change the Swift type.
+        assert!(std::ptr::eq(
+            metadata,
+            <unsafe extern "C" fn(i32, f64) -> bool>::get_metadata()
+        ));
+    }
 }
@@ -1,5 +1,5 @@
 use crate::{
-    mangling::Mangled,
+    mangling::{Demangled, Mangled},
     reflection::{FieldDescriptorKind, FieldRecord},
 };
 use std::{fmt, slice};
@@ -19,8 +19,8 @@ unsafe impl Sync for FieldDescriptor {}
 impl fmt::Debug for FieldDescriptor {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("FieldDescriptor")
-            .field("type_name", &self.type_name())
-            .field("superclass_name", &self.superclass_name())
+            .field("type_name", &self.type_name().map(Demangled))
+            .field("superclass_name", &self.superclass_name().map(Demangled))
             .field("kind", &self.raw.kind)
             .field("field_record_size", &self.raw.field_record_size)
             .field("num_fields", &self.raw.num_fields)
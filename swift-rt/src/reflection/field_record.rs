@@ -1,4 +1,7 @@
-use crate::{mangling::Mangled, reflection::FieldRecordFlags};
+use crate::{
+    mangling::{Demangled, Mangled},
+    reflection::FieldRecordFlags,
+};
 use std::{fmt, os::raw::c_char};
 use swift_sys::{ptr::RelativeDirectPointer, reflection::FieldRecord as RawFieldRecord};
 
@@ -40,7 +43,7 @@ impl fmt::Debug for FieldRecord {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("FieldRecord")
             .field("flags", &self.raw.flags)
-            .field("type_name", &self.type_name())
+            .field("type_name", &self.type_name().map(Demangled))
             .field("field_name", &self.field_name())
             .finish()
     }
@@ -54,6 +57,8 @@ impl FieldRecord {
     }
 
     /// Returns the mangled name of the field's type.
+    ///
+    /// Its symbolic references are resolved by [`Mangled::demangle`].
     #[inline]
     pub fn type_name(&self) -> Option<&Mangled> {
         unsafe { self.type_name_ptr().as_ref() }
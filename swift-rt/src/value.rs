@@ -0,0 +1,110 @@
+//! Values of types whose layout is only known at runtime.
+
+use crate::metadata::Metadata;
+use std::{alloc::Layout, fmt, ptr::NonNull};
+
+/// An owned value of a Swift type, stored on the heap with the size and
+/// alignment from the type's value witnesses.
+///
+/// Copying and destroying the value is done through the value witnesses too,
+/// so this works for any type without knowing its layout at compile time.
+pub struct ValueBox {
+    ptr: NonNull<u8>,
+    metadata: &'static Metadata,
+}
+
+impl Drop for ValueBox {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            self.metadata.vw_destroy(self.ptr.as_ptr());
+            self.deallocate();
+        }
+    }
+}
+
+impl Clone for ValueBox {
+    #[inline]
+    fn clone(&self) -> Self {
+        unsafe {
+            let mut clone = Self::new_uninit(self.metadata);
+            self.metadata
+                .vw_initialize_with_copy(clone.as_mut_ptr(), self.as_ptr());
+            clone
+        }
+    }
+}
+
+impl fmt::Debug for ValueBox {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ValueBox")
+            .field("ptr", &self.ptr)
+            .field("metadata", &self.metadata.name(true))
+            .finish()
+    }
+}
+
+impl ValueBox {
+    /// Allocates storage for a value of the type described by `metadata`.
+    ///
+    /// # Safety
+    ///
+    /// The value must be initialized through [`as_mut_ptr`](Self::as_mut_ptr)
+    /// before the box is cloned or dropped.
+    #[inline]
+    pub unsafe fn new_uninit(metadata: &'static Metadata) -> Self {
+        let layout = Self::layout(metadata);
+        let ptr = if layout.size() == 0 {
+            // A dangling pointer with the right alignment.
+            NonNull::new_unchecked(layout.align() as *mut u8)
+        } else {
+            match NonNull::new(std::alloc::alloc(layout)) {
+                Some(ptr) => ptr,
+                None => std::alloc::handle_alloc_error(layout),
+            }
+        };
+        Self { ptr, metadata }
+    }
+
+    /// Returns the metadata of the type of the value.
+    #[inline]
+    pub fn metadata(&self) -> &'static Metadata {
+        self.metadata
+    }
+
+    /// Returns a pointer to the value.
+    #[inline]
+    pub fn as_ptr(&self) -> *const u8 {
+        self.ptr.as_ptr()
+    }
+
+    /// Returns a mutable pointer to the value.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.ptr.as_ptr()
+    }
+
+    /// Frees the storage without destroying the value.
+    ///
+    /// # Safety
+    ///
+    /// The value must be uninitialized or have been moved out, such as after
+    /// reading the payload of an enum that was projected.
+    #[inline]
+    pub unsafe fn forget_value(self) {
+        let mut this = std::mem::ManuallyDrop::new(self);
+        this.deallocate();
+    }
+
+    unsafe fn deallocate(&mut self) {
+        let layout = Self::layout(self.metadata);
+        if layout.size() != 0 {
+            std::alloc::dealloc(self.ptr.as_ptr(), layout);
+        }
+    }
+
+    fn layout(metadata: &Metadata) -> Layout {
+        let layout = metadata.value_witnesses().memory_layout();
+        Layout::from_size_align(layout.size, layout.align).expect("invalid value layout")
+    }
+}
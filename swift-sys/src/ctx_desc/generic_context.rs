@@ -0,0 +1,216 @@
+use crate::ptr::RelativeDirectPointer;
+use std::{ffi::c_void, fmt, os::raw::c_char};
+
+/// The header of the generic context of a generic type, which trails its
+/// type context descriptor.
+#[repr(C)]
+#[derive(Clone, Debug)]
+pub struct TypeGenericContextDescriptorHeader {
+    /// The cache of instantiations of the type's metadata.
+    pub instantiation_cache: RelativeDirectPointer<c_void>,
+
+    /// The pattern that metadata of the type are instantiated from.
+    pub default_instantiation_pattern: RelativeDirectPointer<c_void>,
+
+    /// The base header.
+    pub base: GenericContextDescriptorHeader,
+}
+
+/// The header of a generic context, which is followed by its
+/// [`GenericParamDescriptor`]s and then, aligned to 4 bytes, its
+/// [`GenericRequirementDescriptor`]s.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GenericContextDescriptorHeader {
+    /// The number of generic parameters, including those of parent contexts.
+    pub num_params: u16,
+
+    /// The number of generic requirements, including those of parent
+    /// contexts.
+    pub num_requirements: u16,
+
+    /// The number of arguments that metadata of the context are keyed by.
+    ///
+    /// These are the metadata of each key parameter, followed by the witness
+    /// table of each protocol conformance requirement.
+    pub num_key_arguments: u16,
+
+    /// The number of arguments that are not part of the key.
+    pub num_extra_arguments: u16,
+}
+
+/// A generic parameter of a generic context.
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GenericParamDescriptor(u8);
+
+impl fmt::Debug for GenericParamDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GenericParamDescriptor")
+            .field("kind", &self.kind())
+            .field("has_key_argument", &self.has_key_argument())
+            .field("has_extra_argument", &self.has_extra_argument())
+            .finish()
+    }
+}
+
+impl GenericParamDescriptor {
+    /// The kind of a type parameter.
+    pub const KIND_TYPE: u8 = 0;
+
+    /// Creates an instance from `bits`.
+    #[inline]
+    pub const fn from_bits(bits: u8) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the underlying bits of `self`.
+    #[inline]
+    pub const fn into_bits(self) -> u8 {
+        self.0
+    }
+
+    /// Returns the kind of the parameter, such as [`Self::KIND_TYPE`].
+    #[inline]
+    pub const fn kind(self) -> u8 {
+        self.0 & 0x3F
+    }
+
+    /// Returns `true` if the metadata of the parameter's argument is a key
+    /// argument.
+    #[inline]
+    pub const fn has_key_argument(self) -> bool {
+        self.0 & 0x80 != 0
+    }
+
+    /// Returns `true` if the parameter has an extra argument.
+    #[inline]
+    pub const fn has_extra_argument(self) -> bool {
+        self.0 & 0x40 != 0
+    }
+}
+
+/// A generic requirement of a generic context.
+#[repr(C)]
+#[derive(Clone, Debug)]
+pub struct GenericRequirementDescriptor {
+    /// Flags describing the requirement.
+    pub flags: GenericRequirementFlags,
+
+    /// The mangled name of the constrained type, which refers to the generic
+    /// parameters of the context.
+    pub param: RelativeDirectPointer<c_char>,
+
+    /// The constraint, which depends on the [kind](GenericRequirementFlags::kind):
+    ///
+    /// - [`PROTOCOL`](GenericRequirementKind::PROTOCOL): a relative pointer to
+    ///   the protocol descriptor, whose low bit is set if it is indirect.
+    ///
+    /// - [`SAME_TYPE`](GenericRequirementKind::SAME_TYPE) and
+    ///   [`BASE_CLASS`](GenericRequirementKind::BASE_CLASS): a relative
+    ///   pointer to the mangled name of the type.
+    ///
+    /// - [`LAYOUT`](GenericRequirementKind::LAYOUT): the kind of layout, where
+    ///   0 is a class.
+    pub content: i32,
+}
+
+/// Flags for [`GenericRequirementDescriptor`].
+#[repr(transparent)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GenericRequirementFlags(u32);
+
+impl fmt::Debug for GenericRequirementFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GenericRequirementFlags")
+            .field("kind", &self.kind())
+            .field("has_key_argument", &self.has_key_argument())
+            .finish()
+    }
+}
+
+impl GenericRequirementFlags {
+    const KIND_MASK: u32 = 0x1F;
+    const HAS_KEY_ARGUMENT: u32 = 0x80;
+
+    /// Creates a new instance from `bits`.
+    #[inline]
+    pub const fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the underlying bits of `self`.
+    #[inline]
+    pub const fn into_bits(self) -> u32 {
+        self.0
+    }
+
+    /// Returns the kind of the requirement.
+    #[inline]
+    pub const fn kind(self) -> GenericRequirementKind {
+        GenericRequirementKind((self.0 & Self::KIND_MASK) as u8)
+    }
+
+    /// Returns `true` if the requirement is satisfied by a key argument, such
+    /// as the witness table of a protocol conformance.
+    #[inline]
+    pub const fn has_key_argument(self) -> bool {
+        self.0 & Self::HAS_KEY_ARGUMENT != 0
+    }
+}
+
+/// The kind of a [`GenericRequirementDescriptor`].
+///
+/// This is semantically an `enum`. However, it is defined as a `struct` in
+/// order to be future-compatible.
+#[repr(transparent)]
+#[derive(Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct GenericRequirementKind(u8);
+
+impl fmt::Debug for GenericRequirementKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Format like an `enum`.
+        let known_kind = match *self {
+            Self::PROTOCOL => "PROTOCOL",
+            Self::SAME_TYPE => "SAME_TYPE",
+            Self::BASE_CLASS => "BASE_CLASS",
+            Self::SAME_CONFORMANCE => "SAME_CONFORMANCE",
+            Self::SAME_SHAPE => "SAME_SHAPE",
+            Self::INVERTED_PROTOCOLS => "INVERTED_PROTOCOLS",
+            Self::LAYOUT => "LAYOUT",
+            _ => return f.debug_tuple("UNKNOWN").field(&self.0).finish(),
+        };
+
+        f.write_str(known_kind)
+    }
+}
+
+impl GenericRequirementKind {
+    /// A protocol conformance, `T: P`.
+    pub const PROTOCOL: Self = Self(0);
+
+    /// A same-type requirement, `T == U`.
+    pub const SAME_TYPE: Self = Self(1);
+
+    /// A superclass requirement, `T: C`.
+    pub const BASE_CLASS: Self = Self(2);
+
+    /// A conformance implied by a same-type requirement.
+    pub const SAME_CONFORMANCE: Self = Self(3);
+
+    /// A same-shape requirement between parameter packs.
+    pub const SAME_SHAPE: Self = Self(4);
+
+    /// Protocols that a parameter doesn't need to conform to, such as
+    /// `~Copyable`.
+    pub const INVERTED_PROTOCOLS: Self = Self(5);
+
+    /// A layout requirement, such as `T: AnyObject`.
+    pub const LAYOUT: Self = Self(0x1F);
+
+    /// Returns this kind's inner value.
+    #[inline]
+    pub const fn value(self) -> u8 {
+        self.0
+    }
+}
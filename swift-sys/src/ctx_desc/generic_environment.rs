@@ -0,0 +1,15 @@
+use std::marker::PhantomData;
+
+/// The generic parameters and requirements of a generic signature, which
+/// resolves the generic parameters of mangled names outside of a context
+/// descriptor.
+///
+/// Its layout is private to the runtime.
+#[repr(C)]
+pub struct GenericEnvironment {
+    // TODO: Replace with an `extern type` once stabilized.
+    _private: [u8; 0],
+
+    // !Send + !Sync
+    _marker: PhantomData<*mut ()>,
+}
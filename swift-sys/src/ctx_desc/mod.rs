@@ -3,6 +3,8 @@
 mod base;
 mod extension;
 mod flags;
+mod generic_context;
+mod generic_environment;
 mod kind;
 mod module;
 mod protocol;
@@ -11,6 +13,8 @@ mod ty;
 pub use base::*;
 pub use extension::*;
 pub use flags::*;
+pub use generic_context::*;
+pub use generic_environment::*;
 pub use kind::*;
 pub use module::*;
 pub use protocol::*;
@@ -1,5 +1,5 @@
 use crate::{ctx_desc::TypeContextDescriptor, ptr::RelativeDirectPointer};
-use std::os::raw::c_char;
+use std::{ffi::c_void, os::raw::c_char};
 
 /// Context descriptor for a class type.
 #[repr(C)]
@@ -51,3 +51,33 @@ pub struct ClassDescriptor {
     /// size of the resilient superclass metadata. Otherwise, it is absolute.
     pub field_offset_vector_offset: u32,
 }
+
+/// The header of the vtable of a [`ClassDescriptor`], which trails the
+/// descriptor and is followed by a [`MethodDescriptor`] for each vtable slot.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VTableDescriptorHeader {
+    /// The offset of the vtable in the class metadata, in words.
+    ///
+    /// If the class has a resilient superclass, this offset is relative to
+    /// the start of the class's immediate members. Otherwise, it is absolute.
+    pub vtable_offset: u32,
+
+    /// The number of slots in the vtable.
+    pub vtable_size: u32,
+}
+
+/// An entry in the vtable of a [`ClassDescriptor`], which describes a method
+/// that subclasses may override.
+///
+/// The position of a method descriptor in its class's descriptor determines
+/// the slot of the method in the vtable of class metadata.
+#[repr(C)]
+#[derive(Clone, Debug)]
+pub struct MethodDescriptor {
+    /// Flags describing the method.
+    pub flags: u32,
+
+    /// The method implementation.
+    pub implementation: RelativeDirectPointer<c_void>,
+}
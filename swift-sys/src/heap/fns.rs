@@ -2,12 +2,33 @@
 
 #![cfg(feature = "link")]
 
+use crate::{metadata::Metadata, OpaqueValue};
 use std::os::raw::{c_int, c_void};
 
+/// A heap box and the address of the value that it holds.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct BoxPair {
+    /// The box, which is a native Swift object.
+    pub object: *mut c_void,
+
+    /// The uninitialized value in the box.
+    pub buffer: *mut OpaqueValue,
+}
+
 // TODO: Enable weak linking for crates that conditionally interop with Swift
 // based on its existence.
 #[link(name = "swiftCore", kind = "dylib")]
 extern "C" {
+    // Boxes
+
+    /// Allocate a box for a value of type `ty`, such as the out-of-line
+    /// storage of an existential container.
+    ///
+    /// This uses the Swift calling convention, which matches C for this
+    /// signature.
+    pub fn swift_allocBox(ty: *const Metadata) -> BoxPair;
+
     // Native Objects
 
     /// Increment the strong retain count of an object.
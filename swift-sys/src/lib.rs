@@ -22,6 +22,7 @@
 pub mod casting;
 pub mod ctx_desc;
 pub mod heap;
+pub mod mangling;
 pub mod mem;
 pub mod metadata;
 pub mod ptr;
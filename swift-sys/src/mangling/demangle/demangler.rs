@@ -0,0 +1,1593 @@
+use super::{
+    node::{Node, NodeKind as Kind},
+    SymbolicReference, ValueWitnessKind,
+};
+use crate::mangling::punycode;
+use std::{convert::TryFrom, mem};
+
+/// The maximum number of words that may be substituted.
+const MAX_WORDS: usize = 26;
+
+/// The maximum repeat count of a merged substitution, such as `A5B`.
+const MAX_REPEAT_COUNT: u64 = 2048;
+
+/// A port of the Swift runtime's `Demangler`, which parses a mangled name
+/// into a stack of nodes.
+///
+/// Operators are postfix: each one pops its operands off the stack and pushes
+/// its result.
+pub(super) struct Demangler<'a> {
+    text: &'a [u8],
+    pos: usize,
+    stack: Vec<Node>,
+    substitutions: Vec<Node>,
+    words: Vec<String>,
+    old_function_types: bool,
+    resolver: Option<&'a mut dyn FnMut(SymbolicReference) -> Option<Node>>,
+}
+
+impl<'a> Demangler<'a> {
+    pub fn new(text: &'a [u8]) -> Self {
+        Self {
+            text,
+            pos: 0,
+            stack: Vec::new(),
+            substitutions: Vec::new(),
+            words: Vec::new(),
+            old_function_types: false,
+            resolver: None,
+        }
+    }
+
+    /// Demangles function types as in Swift 4.0, whose labels are in their
+    /// parameter tuples.
+    pub fn with_old_function_types(mut self) -> Self {
+        self.old_function_types = true;
+        self
+    }
+
+    /// Resolves symbolic references with `resolver`, without which they fail
+    /// to demangle.
+    pub fn with_resolver(
+        mut self,
+        resolver: &'a mut dyn FnMut(SymbolicReference) -> Option<Node>,
+    ) -> Self {
+        self.resolver = Some(resolver);
+        self
+    }
+
+    /// Demangles a symbol after its prefix.
+    pub fn demangle_symbol(mut self) -> Option<Node> {
+        self.parse_and_push_nodes()?;
+
+        let mut global = Node::new(Kind::Global);
+        while let Some(attr) = self.pop_if(is_function_attr) {
+            global.push(attr);
+        }
+        for node in self.stack.drain(..) {
+            match node.kind() {
+                Kind::Type => global.push(node.children[0].clone()),
+                _ => global.push(node),
+            }
+        }
+        match global.children().is_empty() {
+            true => None,
+            false => Some(global),
+        }
+    }
+
+    /// Demangles a type without a prefix, such as in a field record.
+    pub fn demangle_type(mut self) -> Option<Node> {
+        self.parse_and_push_nodes()?;
+        match self.stack.len() {
+            1 => self.stack.pop(),
+            _ => None,
+        }
+    }
+
+    fn parse_and_push_nodes(&mut self) -> Option<()> {
+        while self.pos < self.text.len() {
+            let node = self.demangle_operator()?;
+            self.stack.push(node);
+        }
+        Some(())
+    }
+
+    // Input -------------------------------------------------------------------
+
+    fn peek(&self) -> u8 {
+        self.text.get(self.pos).copied().unwrap_or(0)
+    }
+
+    /// Returns the next character, or 0 past the end, which is consumed
+    /// either way so that it can be pushed back.
+    fn next(&mut self) -> u8 {
+        let c = self.peek();
+        self.pos += 1;
+        c
+    }
+
+    fn next_if(&mut self, c: u8) -> bool {
+        let matches = self.pos < self.text.len() && self.peek() == c;
+        if matches {
+            self.pos += 1;
+        }
+        matches
+    }
+
+    fn push_back(&mut self) {
+        self.pos -= 1;
+    }
+
+    /// Parses a decimal number.
+    fn demangle_natural(&mut self) -> Option<u64> {
+        let start = self.pos;
+        while self.peek().is_ascii_digit() {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return None;
+        }
+        std::str::from_utf8(&self.text[start..self.pos])
+            .ok()?
+            .parse()
+            .ok()
+    }
+
+    /// Parses `_` as 0 or `<n>_` as `n + 1`.
+    fn demangle_index(&mut self) -> Option<u64> {
+        if self.next_if(b'_') {
+            return Some(0);
+        }
+        let n = self.demangle_natural()?;
+        match self.next_if(b'_') {
+            true => n.checked_add(1),
+            false => None,
+        }
+    }
+
+    fn demangle_index_as_node(&mut self) -> Option<Node> {
+        let index = self.demangle_index()?;
+        Some(Node::with_index(Kind::Index, index))
+    }
+
+    // Stack -------------------------------------------------------------------
+
+    fn pop(&mut self) -> Option<Node> {
+        self.stack.pop()
+    }
+
+    fn pop_kind(&mut self, kind: Kind) -> Option<Node> {
+        self.pop_if(|k| k == kind)
+    }
+
+    fn pop_if(&mut self, pred: impl Fn(Kind) -> bool) -> Option<Node> {
+        match self.stack.last() {
+            Some(node) if pred(node.kind()) => self.stack.pop(),
+            _ => None,
+        }
+    }
+
+    fn pop_type_and_get_child(&mut self) -> Option<Node> {
+        let ty = self.pop_kind(Kind::Type)?;
+        match <[Node; 1]>::try_from(ty.children) {
+            Ok([child]) => Some(child),
+            Err(_) => None,
+        }
+    }
+
+    fn pop_type_and_get_any_generic(&mut self) -> Option<Node> {
+        let child = self.pop_type_and_get_child()?;
+        match is_any_generic(child.kind()) {
+            true => Some(child),
+            false => None,
+        }
+    }
+
+    fn pop_module(&mut self) -> Option<Node> {
+        if let Some(ident) = self.pop_kind(Kind::Identifier) {
+            return Some(ident.into_kind(Kind::Module));
+        }
+        self.pop_kind(Kind::Module)
+    }
+
+    fn pop_context(&mut self) -> Option<Node> {
+        if let Some(module) = self.pop_module() {
+            return Some(module);
+        }
+        if let Some(ty) = self.pop_kind(Kind::Type) {
+            let [child] = <[Node; 1]>::try_from(ty.children).ok()?;
+            return match is_context(child.kind()) {
+                true => Some(child),
+                false => None,
+            };
+        }
+        self.pop_if(is_context)
+    }
+
+    fn pop_protocol(&mut self) -> Option<Node> {
+        if let Some(ty) = self.pop_kind(Kind::Type) {
+            return match is_protocol_node(&ty) {
+                true => Some(ty),
+                false => None,
+            };
+        }
+        let name = self.pop_if(is_decl_name)?;
+        let context = self.pop_context()?;
+        Some(ty(Node::with_children(Kind::Protocol, vec![context, name])))
+    }
+
+    fn add_substitution(&mut self, node: &Node) {
+        self.substitutions.push(node.clone());
+    }
+
+    // Operators ---------------------------------------------------------------
+
+    fn demangle_operator(&mut self) -> Option<Node> {
+        let c = self.next();
+        let node = match c {
+            b'A' => return self.demangle_multi_substitutions(),
+            b'B' => return self.demangle_builtin_type(),
+            b'C' => return self.demangle_any_generic_type(Kind::Class),
+            b'D' => return self.demangle_type_mangling(),
+            b'E' => return self.demangle_extension_context(),
+            b'F' => return self.demangle_plain_function(),
+            b'G' => return self.demangle_bound_generic_type(),
+            b'K' => Node::new(Kind::ThrowsAnnotation),
+            b'L' => return self.demangle_local_identifier(),
+            b'M' => return self.demangle_metatype(),
+            b'N' => with_child(Kind::TypeMetadata, self.pop_kind(Kind::Type)?),
+            b'O' => return self.demangle_any_generic_type(Kind::Enum),
+            b'P' => return self.demangle_any_generic_type(Kind::Protocol),
+            b'Q' => return self.demangle_archetype(),
+            b'R' => return self.demangle_generic_requirement(),
+            b'S' => return self.demangle_standard_substitution(),
+            b'T' => return self.demangle_thunk_or_specialization(),
+            b'V' => return self.demangle_any_generic_type(Kind::Structure),
+            b'W' => return self.demangle_witness(),
+            b'X' => return self.demangle_special_type(),
+            b'Y' => return self.demangle_type_annotation(),
+            b'Z' => with_child(Kind::Static, self.pop_if(is_entity)?),
+            b'a' => return self.demangle_any_generic_type(Kind::TypeAlias),
+            b'c' => return self.pop_function_type(Kind::FunctionType),
+            b'd' => Node::new(Kind::VariadicMarker),
+            b'f' => return self.demangle_function_entity(),
+            b'h' => ty(with_child(Kind::Shared, self.pop_type_and_get_child()?)),
+            b'i' => return self.demangle_subscript(),
+            b'l' => return self.demangle_generic_signature(false),
+            b'm' => ty(with_child(Kind::Metatype, self.pop_kind(Kind::Type)?)),
+            b'n' => ty(with_child(Kind::Owned, self.pop_type_and_get_child()?)),
+            b'o' => return self.demangle_operator_identifier(),
+            b'p' => return self.demangle_protocol_list_type(),
+            b'q' => ty(self.demangle_generic_param_index()?),
+            b'r' => return self.demangle_generic_signature(true),
+            b's' => Node::with_text(Kind::Module, "Swift"),
+            b't' => return self.pop_tuple(),
+            b'u' => return self.demangle_generic_type(),
+            b'v' => return self.demangle_variable(),
+            b'w' => return self.demangle_value_witness(),
+            b'x' => ty(generic_param(0, 0)),
+            b'y' => Node::new(Kind::EmptyList),
+            b'z' => ty(with_child(Kind::InOut, self.pop_type_and_get_child()?)),
+            b'_' => Node::new(Kind::FirstElementMarker),
+            b'.' => {
+                self.push_back();
+                let suffix = std::str::from_utf8(&self.text[self.pos..]).ok()?;
+                self.pos = self.text.len();
+                Node::with_text(Kind::Suffix, suffix)
+            }
+            0x01..=0x1F => return self.demangle_symbolic_reference(c),
+            _ => {
+                self.push_back();
+                return self.demangle_identifier();
+            }
+        };
+        Some(node)
+    }
+
+    fn demangle_symbolic_reference(&mut self, control: u8) -> Option<Node> {
+        // Relative references have a 32-bit offset and absolute references
+        // have a pointer.
+        let size = match control {
+            0x01..=0x17 => mem::size_of::<i32>(),
+            _ => mem::size_of::<usize>(),
+        };
+        let payload = self.text.get(self.pos..self.pos.checked_add(size)?)?;
+        self.pos += size;
+
+        let resolver = self.resolver.as_mut()?;
+        let node = resolver(SymbolicReference {
+            control,
+            payload: payload.as_ptr(),
+        })?;
+
+        // Types are substitutions even when referenced symbolically.
+        if node.kind() == Kind::Type {
+            self.add_substitution(&node);
+        }
+        Some(node)
+    }
+
+    fn demangle_type_mangling(&mut self) -> Option<Node> {
+        let mut mangled = self.pop_kind(Kind::Type)?;
+        let mut children: Vec<Node> = self
+            .pop_function_param_labels(&mut mangled)
+            .into_iter()
+            .collect();
+        children.push(mangled);
+        Some(Node::with_children(Kind::TypeMangling, children))
+    }
+
+    fn demangle_identifier(&mut self) -> Option<Node> {
+        let mut has_word_substs = false;
+        let mut is_punycoded = false;
+        if !self.peek().is_ascii_digit() {
+            return None;
+        }
+        if self.next_if(b'0') {
+            if self.next_if(b'0') {
+                is_punycoded = true;
+            } else {
+                has_word_substs = true;
+            }
+        }
+
+        let mut identifier = String::new();
+        loop {
+            while has_word_substs && self.peek().is_ascii_alphabetic() {
+                let c = self.next();
+                let index = match c {
+                    b'a'..=b'z' => c - b'a',
+                    _ => {
+                        has_word_substs = false;
+                        c - b'A'
+                    }
+                };
+                identifier.push_str(self.words.get(index as usize)?);
+            }
+            if self.next_if(b'0') {
+                break;
+            }
+            let len = self.demangle_natural()? as usize;
+            if len == 0 {
+                return None;
+            }
+            if is_punycoded {
+                self.next_if(b'_');
+            }
+            let slice = self.text.get(self.pos..self.pos.checked_add(len)?)?;
+            let slice = std::str::from_utf8(slice).ok()?;
+            if is_punycoded {
+                identifier.push_str(&punycode::decode(slice)?);
+            } else {
+                identifier.push_str(slice);
+                self.add_words(slice);
+            }
+            self.pos += len;
+            if !has_word_substs {
+                break;
+            }
+        }
+
+        if identifier.is_empty() {
+            return None;
+        }
+        let ident = Node::with_text(Kind::Identifier, identifier);
+        self.add_substitution(&ident);
+        Some(ident)
+    }
+
+    /// Records the words of `slice` for later word substitutions.
+    fn add_words(&mut self, slice: &str) {
+        let bytes = slice.as_bytes();
+        let mut word_start = None;
+        for pos in 0..=bytes.len() {
+            let c = bytes.get(pos).copied().unwrap_or(0);
+            if let Some(start) = word_start {
+                if is_word_end(c, bytes[pos - 1]) {
+                    if pos - start >= 2 && self.words.len() < MAX_WORDS {
+                        self.words.push(slice[start..pos].to_owned());
+                    }
+                    word_start = None;
+                }
+            }
+            if word_start.is_none() && is_word_start(c) {
+                word_start = Some(pos);
+            }
+        }
+    }
+
+    fn demangle_multi_substitutions(&mut self) -> Option<Node> {
+        let mut repeat_count: Option<u64> = None;
+        loop {
+            let c = self.next();
+            match c {
+                0 => return None,
+                b'a'..=b'z' => {
+                    let node = self.push_multi_substitutions(repeat_count, c - b'a')?;
+                    self.stack.push(node);
+                    repeat_count = None;
+                }
+                b'A'..=b'Z' => return self.push_multi_substitutions(repeat_count, c - b'A'),
+                b'_' => {
+                    let index = repeat_count.map_or(Some(26), |count| count.checked_add(27))?;
+                    return self.substitutions.get(index as usize).cloned();
+                }
+                _ => {
+                    self.push_back();
+                    repeat_count = Some(self.demangle_natural()?);
+                }
+            }
+        }
+    }
+
+    fn push_multi_substitutions(&mut self, repeat_count: Option<u64>, index: u8) -> Option<Node> {
+        let node = self.substitutions.get(index as usize)?.clone();
+        let repeat_count = repeat_count.unwrap_or(1);
+        if repeat_count > MAX_REPEAT_COUNT {
+            return None;
+        }
+        for _ in 1..repeat_count {
+            self.stack.push(node.clone());
+        }
+        Some(node)
+    }
+
+    fn demangle_standard_substitution(&mut self) -> Option<Node> {
+        match self.next() {
+            b'o' => Some(Node::with_text(Kind::Module, "__C")),
+            b'C' => Some(Node::with_text(Kind::Module, "__C_Synthesized")),
+            b'g' => {
+                let wrapped = self.pop_kind(Kind::Type)?;
+                let optional = ty(Node::with_children(
+                    Kind::BoundGenericEnum,
+                    vec![
+                        swift_type(Kind::Enum, "Swift", "Optional"),
+                        with_child(Kind::TypeList, wrapped),
+                    ],
+                ));
+                self.add_substitution(&optional);
+                Some(optional)
+            }
+            _ => {
+                self.push_back();
+                let repeat_count = match self.peek().is_ascii_digit() {
+                    true => self.demangle_natural()?,
+                    false => 1,
+                };
+                if repeat_count > MAX_REPEAT_COUNT {
+                    return None;
+                }
+                let second_level = self.next_if(b'c');
+                let (kind, module, name) = standard_substitution(self.next(), second_level)?;
+                let node = swift_type(kind, module, name);
+                for _ in 1..repeat_count {
+                    self.stack.push(node.clone());
+                }
+                Some(node)
+            }
+        }
+    }
+
+    fn demangle_builtin_type(&mut self) -> Option<Node> {
+        const MAX_TYPE_SIZE: u64 = 4096;
+
+        let name = match self.next() {
+            b'b' => "Builtin.BridgeObject".to_owned(),
+            b'B' => "Builtin.UnsafeValueBuffer".to_owned(),
+            b'c' => "Builtin.RawUnsafeContinuation".to_owned(),
+            b'D' => "Builtin.DefaultActorStorage".to_owned(),
+            b'e' => "Builtin.Executor".to_owned(),
+            b'f' => {
+                let size = self.demangle_index()?.checked_sub(1)?;
+                if size == 0 || size > MAX_TYPE_SIZE {
+                    return None;
+                }
+                format!("Builtin.FPIEEE{}", size)
+            }
+            b'i' => {
+                let size = self.demangle_index()?.checked_sub(1)?;
+                if size == 0 || size > MAX_TYPE_SIZE {
+                    return None;
+                }
+                format!("Builtin.Int{}", size)
+            }
+            b'I' => "Builtin.IntLiteral".to_owned(),
+            b'j' => "Builtin.Job".to_owned(),
+            b'v' => {
+                let elements = self.demangle_index()?.checked_sub(1)?;
+                if elements == 0 || elements > MAX_TYPE_SIZE {
+                    return None;
+                }
+                let element = self.pop_type_and_get_child()?;
+                let element = element.text()?.strip_prefix("Builtin.")?;
+                format!("Builtin.Vec{}x{}", elements, element)
+            }
+            b'O' => "Builtin.UnknownObject".to_owned(),
+            b'o' => "Builtin.NativeObject".to_owned(),
+            b'p' => "Builtin.RawPointer".to_owned(),
+            b't' => "Builtin.SILToken".to_owned(),
+            b'w' => "Builtin.Word".to_owned(),
+            _ => return None,
+        };
+        Some(ty(Node::with_text(Kind::BuiltinTypeName, name)))
+    }
+
+    fn demangle_any_generic_type(&mut self, kind: Kind) -> Option<Node> {
+        let name = self.pop_if(is_decl_name)?;
+        let context = self.pop_context()?;
+        let nominal = ty(Node::with_children(kind, vec![context, name]));
+        self.add_substitution(&nominal);
+        Some(nominal)
+    }
+
+    fn demangle_extension_context(&mut self) -> Option<Node> {
+        let signature = self.pop_kind(Kind::DependentGenericSignature);
+        let module = self.pop_module()?;
+        let extended = self.pop_type_and_get_any_generic()?;
+        let mut extension = Node::with_children(Kind::Extension, vec![module, extended]);
+        if let Some(signature) = signature {
+            extension.push(signature);
+        }
+        Some(extension)
+    }
+
+    fn demangle_local_identifier(&mut self) -> Option<Node> {
+        if self.next_if(b'L') {
+            let discriminator = self.pop_kind(Kind::Identifier)?;
+            let name = self.pop_if(is_decl_name)?;
+            return Some(Node::with_children(
+                Kind::PrivateDeclName,
+                vec![discriminator, name],
+            ));
+        }
+        if self.next_if(b'l') {
+            let discriminator = self.pop_kind(Kind::Identifier)?;
+            return Some(with_child(Kind::PrivateDeclName, discriminator));
+        }
+        if matches!(self.peek(), b'a'..=b'j' | b'A'..=b'J') {
+            let kind = self.next() as char;
+            let name = self.pop()?;
+            return Some(Node::with_children(
+                Kind::RelatedEntityDeclName,
+                vec![Node::with_text(Kind::Identifier, kind.to_string()), name],
+            ));
+        }
+        let discriminator = self.demangle_index_as_node()?;
+        let name = self.pop_if(is_decl_name)?;
+        Some(Node::with_children(
+            Kind::LocalDeclName,
+            vec![discriminator, name],
+        ))
+    }
+
+    fn demangle_operator_identifier(&mut self) -> Option<Node> {
+        const TABLE: &[u8; 26] = b"& @/= >    <*!|+?%-~   ^ .";
+
+        let ident = self.pop_kind(Kind::Identifier)?;
+        let mut op = String::new();
+        for c in ident.text()?.chars() {
+            if !c.is_ascii() {
+                // Unicode characters are passed through.
+                op.push(c);
+                continue;
+            }
+            if !c.is_ascii_lowercase() {
+                return None;
+            }
+            match TABLE[c as usize - 'a' as usize] {
+                b' ' => return None,
+                c => op.push(c as char),
+            }
+        }
+        let kind = match self.next() {
+            b'i' => Kind::InfixOperator,
+            b'p' => Kind::PrefixOperator,
+            b'P' => Kind::PostfixOperator,
+            _ => return None,
+        };
+        Some(Node::with_text(kind, op))
+    }
+
+    // Types -------------------------------------------------------------------
+
+    fn demangle_bound_generic_type(&mut self) -> Option<Node> {
+        let type_lists = self.demangle_bound_generics()?;
+        let nominal = self.pop_type_and_get_any_generic()?;
+        let bound = ty(self.demangle_bound_generic_args(nominal, &type_lists, 0)?);
+        self.add_substitution(&bound);
+        Some(bound)
+    }
+
+    /// Pops the lists of generic arguments of a type and its parents,
+    /// innermost first.
+    fn demangle_bound_generics(&mut self) -> Option<Vec<Node>> {
+        let mut type_lists = Vec::new();
+        loop {
+            let mut list = Node::new(Kind::TypeList);
+            while let Some(ty) = self.pop_kind(Kind::Type) {
+                list.push(ty);
+            }
+            list.reverse_children(0);
+            type_lists.push(list);
+
+            if self.pop_kind(Kind::EmptyList).is_some() {
+                return Some(type_lists);
+            }
+            self.pop_kind(Kind::FirstElementMarker)?;
+        }
+    }
+
+    fn demangle_bound_generic_args(
+        &mut self,
+        nominal: Node,
+        type_lists: &[Node],
+        mut index: usize,
+    ) -> Option<Node> {
+        let args = type_lists.get(index)?.clone();
+        let context = nominal.child(0)?.clone();
+
+        // Generic arguments for the outermost type come first.
+        let consumes_args = !matches!(
+            nominal.kind(),
+            Kind::Variable | Kind::ExplicitClosure | Kind::Subscript
+        );
+        if consumes_args {
+            index += 1;
+        }
+        let mut nominal = nominal;
+        if index < type_lists.len() {
+            let parent = match context.kind() {
+                Kind::Extension => {
+                    let extended = context.child(1)?.clone();
+                    let mut children = context.children.clone();
+                    children[1] = self.demangle_bound_generic_args(extended, type_lists, index)?;
+                    Node::with_children(Kind::Extension, children)
+                }
+                _ => self.demangle_bound_generic_args(context, type_lists, index)?,
+            };
+            // Rebuild this type with the parent, which may have its generic
+            // arguments applied.
+            nominal.children[0] = parent;
+        }
+        if !consumes_args || args.children().is_empty() {
+            return Some(nominal);
+        }
+
+        let kind = match nominal.kind() {
+            Kind::Class => Kind::BoundGenericClass,
+            Kind::Structure => Kind::BoundGenericStructure,
+            Kind::Enum => Kind::BoundGenericEnum,
+            Kind::Protocol => Kind::BoundGenericProtocol,
+            Kind::OtherNominalType => Kind::BoundGenericOtherNominalType,
+            Kind::TypeAlias => Kind::BoundGenericTypeAlias,
+            Kind::Function | Kind::Constructor => {
+                return Some(Node::with_children(
+                    Kind::BoundGenericFunction,
+                    vec![nominal, args],
+                ));
+            }
+            _ => return None,
+        };
+        Some(Node::with_children(kind, vec![ty(nominal), args]))
+    }
+
+    fn pop_tuple(&mut self) -> Option<Node> {
+        let mut tuple = Node::new(Kind::Tuple);
+        if self.pop_kind(Kind::EmptyList).is_none() {
+            loop {
+                let is_first = self.pop_kind(Kind::FirstElementMarker).is_some();
+                let mut element = Node::new(Kind::TupleElement);
+                if let Some(marker) = self.pop_kind(Kind::VariadicMarker) {
+                    element.push(marker);
+                }
+                if let Some(ident) = self.pop_kind(Kind::Identifier) {
+                    element.push(ident.into_kind(Kind::TupleElementName));
+                }
+                element.push(self.pop_kind(Kind::Type)?);
+                tuple.push(element);
+                if is_first {
+                    break;
+                }
+            }
+            tuple.reverse_children(0);
+        }
+        Some(ty(tuple))
+    }
+
+    fn pop_function_type(&mut self, kind: Kind) -> Option<Node> {
+        let mut function = Node::new(kind);
+        for attr in [
+            Kind::GlobalActorFunctionType,
+            Kind::ThrowsAnnotation,
+            Kind::ConcurrentFunctionType,
+            Kind::AsyncAnnotation,
+        ] {
+            if let Some(attr) = self.pop_kind(attr) {
+                function.push(attr);
+            }
+        }
+        function.push(self.pop_function_params(Kind::ArgumentTuple)?);
+        function.push(self.pop_function_params(Kind::ReturnType)?);
+        Some(ty(function))
+    }
+
+    fn pop_function_params(&mut self, kind: Kind) -> Option<Node> {
+        let params = match self.pop_kind(Kind::EmptyList) {
+            Some(_) => ty(Node::new(Kind::Tuple)),
+            None => self.pop_kind(Kind::Type)?,
+        };
+        let mut node = match kind {
+            Kind::ArgumentTuple => {
+                let inner = params.child(0)?;
+                let count = match inner.kind() {
+                    Kind::Tuple => inner.children().len(),
+                    _ => 1,
+                };
+                Node::with_index(kind, count as u64)
+            }
+            _ => Node::new(kind),
+        };
+        node.push(params);
+        Some(node)
+    }
+
+    /// Pops the argument labels of a function of type `function`.
+    ///
+    /// Old manglings have labels in the parameter tuple instead, which are
+    /// moved out of it.
+    fn pop_function_param_labels(&mut self, function: &mut Node) -> Option<Node> {
+        if !self.old_function_types && self.pop_kind(Kind::EmptyList).is_some() {
+            return Some(Node::new(Kind::LabelList));
+        }
+        if function.kind() != Kind::Type {
+            return None;
+        }
+        let mut function = function.children.get_mut(0)?;
+        if function.kind() == Kind::DependentGenericType {
+            function = function.children.get_mut(1)?.children.get_mut(0)?;
+        }
+        if !matches!(
+            function.kind(),
+            Kind::FunctionType | Kind::NoEscapeFunctionType
+        ) {
+            return None;
+        }
+        let args = function
+            .children
+            .iter_mut()
+            .find(|child| child.kind() == Kind::ArgumentTuple)?;
+        let count = args.index()?;
+        if count == 0 {
+            return None;
+        }
+
+        let mut labels = Node::new(Kind::LabelList);
+        let mut has_labels = false;
+        if self.old_function_types {
+            let params = args.children.get_mut(0)?.children.get_mut(0)?;
+            if params.kind() != Kind::Tuple {
+                return Some(labels);
+            }
+            for param in &mut params.children {
+                let name = param
+                    .children
+                    .iter()
+                    .position(|child| child.kind() == Kind::TupleElementName);
+                let label = match name {
+                    Some(index) => {
+                        let name = param.children.remove(index);
+                        Node::with_text(Kind::Identifier, name.text()?)
+                    }
+                    None => Node::new(Kind::FirstElementMarker),
+                };
+                has_labels |= label.kind() == Kind::Identifier;
+                labels.push(label);
+            }
+        } else {
+            for _ in 0..count {
+                let label = self
+                    .pop_if(|kind| matches!(kind, Kind::Identifier | Kind::FirstElementMarker))?;
+                has_labels |= label.kind() == Kind::Identifier;
+                labels.push(label);
+            }
+            labels.reverse_children(0);
+        }
+        match has_labels {
+            true => Some(labels),
+            false => Some(Node::new(Kind::LabelList)),
+        }
+    }
+
+    fn demangle_generic_type(&mut self) -> Option<Node> {
+        let signature = self.pop_kind(Kind::DependentGenericSignature)?;
+        let inner = self.pop_kind(Kind::Type)?;
+        Some(ty(Node::with_children(
+            Kind::DependentGenericType,
+            vec![signature, inner],
+        )))
+    }
+
+    fn demangle_protocol_list_type(&mut self) -> Option<Node> {
+        Some(ty(self.demangle_protocol_list()?))
+    }
+
+    fn demangle_protocol_list(&mut self) -> Option<Node> {
+        let mut list = Node::new(Kind::TypeList);
+        if self.pop_kind(Kind::EmptyList).is_none() {
+            loop {
+                let is_first = self.pop_kind(Kind::FirstElementMarker).is_some();
+                list.push(self.pop_protocol()?);
+                if is_first {
+                    break;
+                }
+            }
+            list.reverse_children(0);
+        }
+        Some(with_child(Kind::ProtocolList, list))
+    }
+
+    fn demangle_special_type(&mut self) -> Option<Node> {
+        let node = match self.next() {
+            b'E' => return self.pop_function_type(Kind::NoEscapeFunctionType),
+            b'A' => return self.pop_function_type(Kind::EscapingAutoClosureType),
+            b'f' => return self.pop_function_type(Kind::ThinFunctionType),
+            b'K' => return self.pop_function_type(Kind::AutoClosureType),
+            b'U' => return self.pop_function_type(Kind::UncurriedFunctionType),
+            b'L' => return self.pop_function_type(Kind::EscapingObjCBlock),
+            b'B' => return self.pop_function_type(Kind::ObjCBlock),
+            b'C' => return self.pop_function_type(Kind::CFunctionPointer),
+            b'o' => with_child(Kind::Unmanaged, self.pop_kind(Kind::Type)?),
+            b'u' => with_child(Kind::Unowned, self.pop_kind(Kind::Type)?),
+            b'w' => with_child(Kind::Weak, self.pop_kind(Kind::Type)?),
+            b'D' => with_child(Kind::DynamicSelf, self.pop_kind(Kind::Type)?),
+            b'M' => {
+                let repr = self.demangle_metatype_representation()?;
+                let instance = self.pop_kind(Kind::Type)?;
+                Node::with_children(Kind::Metatype, vec![repr, instance])
+            }
+            b'm' => {
+                let repr = self.demangle_metatype_representation()?;
+                let instance = self.pop_kind(Kind::Type)?;
+                Node::with_children(Kind::ExistentialMetatype, vec![repr, instance])
+            }
+            b'p' => with_child(Kind::ExistentialMetatype, self.pop_kind(Kind::Type)?),
+            b'c' => {
+                let superclass = self.pop_kind(Kind::Type)?;
+                let protocols = self.demangle_protocol_list()?;
+                Node::with_children(Kind::ProtocolListWithClass, vec![protocols, superclass])
+            }
+            b'l' => with_child(
+                Kind::ProtocolListWithAnyObject,
+                self.demangle_protocol_list()?,
+            ),
+            _ => return None,
+        };
+        Some(ty(node))
+    }
+
+    fn demangle_metatype_representation(&mut self) -> Option<Node> {
+        let repr = match self.next() {
+            b't' => "@thin",
+            b'T' => "@thick",
+            b'o' => "@objc_metatype",
+            _ => return None,
+        };
+        Some(Node::with_text(Kind::MetatypeRepresentation, repr))
+    }
+
+    fn demangle_type_annotation(&mut self) -> Option<Node> {
+        let node = match self.next() {
+            b'a' => Node::new(Kind::AsyncAnnotation),
+            b'b' => Node::new(Kind::ConcurrentFunctionType),
+            b'c' => with_child(Kind::GlobalActorFunctionType, self.pop_kind(Kind::Type)?),
+            _ => return None,
+        };
+        Some(node)
+    }
+
+    fn demangle_archetype(&mut self) -> Option<Node> {
+        let base = match self.next() {
+            b'a' => {
+                let name = self.pop_kind(Kind::Identifier)?;
+                let archetype = self.pop_type_and_get_child()?;
+                let associated = ty(Node::with_children(
+                    Kind::AssociatedTypeRef,
+                    vec![archetype, name],
+                ));
+                self.add_substitution(&associated);
+                return Some(associated);
+            }
+            b'r' => return Some(ty(Node::new(Kind::OpaqueReturnType))),
+            b'x' => None,
+            b'y' => Some(self.demangle_generic_param_index()?),
+            b'z' => Some(generic_param(0, 0)),
+            _ => return None,
+        };
+        let associated = self.demangle_associated_type_simple(base)?;
+        self.add_substitution(&associated);
+        Some(associated)
+    }
+
+    /// Returns the associated type of `base`, or of a popped type.
+    fn demangle_associated_type_simple(&mut self, base: Option<Node>) -> Option<Node> {
+        let name = self.pop_assoc_type_name()?;
+        let base = match base {
+            Some(base) => ty(base),
+            None => self.pop_kind(Kind::Type)?,
+        };
+        Some(ty(Node::with_children(
+            Kind::DependentMemberType,
+            vec![base, name],
+        )))
+    }
+
+    fn pop_assoc_type_name(&mut self) -> Option<Node> {
+        let protocol = self.pop_kind(Kind::Type);
+        if let Some(protocol) = &protocol {
+            if !is_protocol_node(protocol) {
+                return None;
+            }
+        }
+        let name = self.pop_kind(Kind::Identifier)?;
+        let mut assoc = with_child(Kind::DependentAssociatedTypeRef, name);
+        if let Some(protocol) = protocol {
+            assoc.push(protocol);
+        }
+        Some(assoc)
+    }
+
+    // Generics ----------------------------------------------------------------
+
+    fn demangle_generic_param_index(&mut self) -> Option<Node> {
+        if self.next_if(b'd') {
+            let depth = self.demangle_index()?.checked_add(1)?;
+            let index = self.demangle_index()?;
+            return Some(generic_param(depth, index));
+        }
+        if self.next_if(b'z') {
+            return Some(generic_param(0, 0));
+        }
+        let index = self.demangle_index()?.checked_add(1)?;
+        Some(generic_param(0, index))
+    }
+
+    fn demangle_generic_signature(&mut self, has_param_counts: bool) -> Option<Node> {
+        let mut signature = Node::new(Kind::DependentGenericSignature);
+        if has_param_counts {
+            while !self.next_if(b'l') {
+                let count = match self.next_if(b'z') {
+                    true => 0,
+                    false => self.demangle_index()?.checked_add(1)?,
+                };
+                signature.push(Node::with_index(Kind::DependentGenericParamCount, count));
+            }
+        } else {
+            signature.push(Node::with_index(Kind::DependentGenericParamCount, 1));
+        }
+        let counts = signature.children().len();
+        while let Some(requirement) = self.pop_if(is_requirement) {
+            signature.push(requirement);
+        }
+        signature.reverse_children(counts);
+        Some(signature)
+    }
+
+    fn demangle_generic_requirement(&mut self) -> Option<Node> {
+        enum Subject {
+            Generic,
+            Assoc,
+            CompoundAssoc,
+            Substitution,
+        }
+        enum Constraint {
+            Protocol,
+            BaseClass,
+            SameType,
+            Layout,
+        }
+
+        use Constraint::*;
+        use Subject::*;
+        let (constraint, subject) = match self.next() {
+            b'c' => (BaseClass, Assoc),
+            b'C' => (BaseClass, CompoundAssoc),
+            b'b' => (BaseClass, Generic),
+            b'B' => (BaseClass, Substitution),
+            b't' => (SameType, Assoc),
+            b'T' => (SameType, CompoundAssoc),
+            b's' => (SameType, Generic),
+            b'S' => (SameType, Substitution),
+            b'm' => (Layout, Assoc),
+            b'M' => (Layout, CompoundAssoc),
+            b'l' => (Layout, Generic),
+            b'L' => (Layout, Substitution),
+            b'p' => (Protocol, Assoc),
+            b'P' => (Protocol, CompoundAssoc),
+            b'Q' => (Protocol, Substitution),
+            _ => {
+                self.push_back();
+                (Protocol, Generic)
+            }
+        };
+
+        let subject = match subject {
+            Generic => ty(self.demangle_generic_param_index()?),
+            Assoc => {
+                let base = self.demangle_generic_param_index()?;
+                let assoc = self.demangle_associated_type_simple(Some(base))?;
+                self.add_substitution(&assoc);
+                assoc
+            }
+            CompoundAssoc => {
+                let base = self.demangle_generic_param_index()?;
+                let assoc = self.demangle_associated_type_compound(base)?;
+                self.add_substitution(&assoc);
+                assoc
+            }
+            Substitution => self.pop_kind(Kind::Type)?,
+        };
+
+        let requirement = match constraint {
+            Protocol => Node::with_children(
+                Kind::DependentGenericConformanceRequirement,
+                vec![subject, self.pop_protocol()?],
+            ),
+            BaseClass => Node::with_children(
+                Kind::DependentGenericConformanceRequirement,
+                vec![subject, self.pop_kind(Kind::Type)?],
+            ),
+            SameType => Node::with_children(
+                Kind::DependentGenericSameTypeRequirement,
+                vec![subject, self.pop_kind(Kind::Type)?],
+            ),
+            Layout => {
+                let name = match self.next() {
+                    c @ (b'U' | b'R' | b'N' | b'C' | b'D' | b'T' | b'E' | b'e' | b'M' | b'm') => c,
+                    _ => return None,
+                };
+                let mut requirement = Node::with_children(
+                    Kind::DependentGenericLayoutRequirement,
+                    vec![
+                        subject,
+                        Node::with_text(Kind::Identifier, (name as char).to_string()),
+                    ],
+                );
+                if matches!(name, b'E' | b'e' | b'M' | b'm') {
+                    requirement.push(self.demangle_index_as_node()?);
+                    if name.is_ascii_uppercase() {
+                        requirement.push(self.demangle_index_as_node()?);
+                    }
+                }
+                requirement
+            }
+        };
+        Some(requirement)
+    }
+
+    /// Returns a nested associated type of `base`, such as `A.Iterator.Element`.
+    fn demangle_associated_type_compound(&mut self, base: Node) -> Option<Node> {
+        let mut names = Vec::new();
+        loop {
+            let is_first = self.pop_kind(Kind::FirstElementMarker).is_some();
+            names.push(self.pop_assoc_type_name()?);
+            if is_first {
+                break;
+            }
+        }
+        let mut base = ty(base);
+        while let Some(name) = names.pop() {
+            base = ty(Node::with_children(
+                Kind::DependentMemberType,
+                vec![base, name],
+            ));
+        }
+        Some(base)
+    }
+
+    fn pop_protocol_conformance(&mut self) -> Option<Node> {
+        let signature = self.pop_kind(Kind::DependentGenericSignature);
+        let module = self.pop_module()?;
+        let protocol = self.pop_protocol()?;
+        let mut conforming = self.pop_kind(Kind::Type)?;
+        if let Some(signature) = signature {
+            conforming = ty(Node::with_children(
+                Kind::DependentGenericType,
+                vec![signature, conforming],
+            ));
+        }
+        Some(Node::with_children(
+            Kind::ProtocolConformance,
+            vec![conforming, protocol, module],
+        ))
+    }
+
+    // Entities ----------------------------------------------------------------
+
+    fn demangle_plain_function(&mut self) -> Option<Node> {
+        let signature = self.pop_kind(Kind::DependentGenericSignature);
+        let mut function = self.pop_function_type(Kind::FunctionType)?;
+        let labels = self.pop_function_param_labels(&mut function);
+        if let Some(signature) = signature {
+            function = ty(Node::with_children(
+                Kind::DependentGenericType,
+                vec![signature, function],
+            ));
+        }
+        let name = self.pop_if(is_decl_name)?;
+        let context = self.pop_context()?;
+        let mut children = vec![context, name];
+        children.extend(labels);
+        children.push(function);
+        Some(Node::with_children(Kind::Function, children))
+    }
+
+    fn demangle_function_entity(&mut self) -> Option<Node> {
+        enum Args {
+            None,
+            TypeAndMaybePrivateName,
+            TypeAndIndex,
+            Index,
+        }
+
+        let (args, kind) = match self.next() {
+            b'D' => (Args::None, Kind::Deallocator),
+            b'd' => (Args::None, Kind::Destructor),
+            b'E' => (Args::None, Kind::IVarDestroyer),
+            b'e' => (Args::None, Kind::IVarInitializer),
+            b'i' => (Args::None, Kind::Initializer),
+            b'C' => (Args::TypeAndMaybePrivateName, Kind::Allocator),
+            b'c' => (Args::TypeAndMaybePrivateName, Kind::Constructor),
+            b'U' => (Args::TypeAndIndex, Kind::ExplicitClosure),
+            b'u' => (Args::TypeAndIndex, Kind::ImplicitClosure),
+            b'A' => (Args::Index, Kind::DefaultArgumentInitializer),
+            b'P' => (Args::None, Kind::PropertyWrapperBackingInitializer),
+            _ => return None,
+        };
+
+        let mut extra = Vec::new();
+        match args {
+            Args::None => {}
+            Args::TypeAndMaybePrivateName => {
+                let private_name = self.pop_kind(Kind::PrivateDeclName);
+                let mut function = self.pop_kind(Kind::Type)?;
+                extra.extend(self.pop_function_param_labels(&mut function));
+                extra.push(function);
+                extra.extend(private_name);
+            }
+            Args::TypeAndIndex => {
+                let index = self.demangle_index_as_node()?;
+                extra.push(index);
+                extra.push(self.pop_kind(Kind::Type)?);
+            }
+            Args::Index => extra.push(self.demangle_index_as_node()?),
+        }
+        let mut children = vec![self.pop_context()?];
+        children.extend(extra);
+        Some(Node::with_children(kind, children))
+    }
+
+    fn demangle_entity(&mut self, kind: Kind) -> Option<Node> {
+        let mut entity_ty = self.pop_kind(Kind::Type)?;
+        let labels = self.pop_function_param_labels(&mut entity_ty);
+        let name = self.pop_if(is_decl_name)?;
+        let context = self.pop_context()?;
+        let mut children = vec![context, name];
+        children.extend(labels);
+        children.push(entity_ty);
+        Some(Node::with_children(kind, children))
+    }
+
+    fn demangle_variable(&mut self) -> Option<Node> {
+        let variable = self.demangle_entity(Kind::Variable)?;
+        self.demangle_accessor(variable)
+    }
+
+    fn demangle_subscript(&mut self) -> Option<Node> {
+        let private_name = self.pop_kind(Kind::PrivateDeclName);
+        let mut function = self.pop_kind(Kind::Type)?;
+        let labels = self.pop_function_param_labels(&mut function);
+        let mut children = vec![self.pop_context()?];
+        children.extend(labels);
+        children.push(function);
+        children.extend(private_name);
+        self.demangle_accessor(Node::with_children(Kind::Subscript, children))
+    }
+
+    fn demangle_accessor(&mut self, storage: Node) -> Option<Node> {
+        let kind = match self.next() {
+            b'm' => Kind::MaterializeForSet,
+            b's' => Kind::Setter,
+            b'g' => Kind::Getter,
+            b'G' => Kind::GlobalGetter,
+            b'w' => Kind::WillSet,
+            b'W' => Kind::DidSet,
+            b'r' => Kind::ReadAccessor,
+            b'M' => Kind::ModifyAccessor,
+            b'i' => Kind::InitAccessor,
+            b'a' => match self.next() {
+                b'O' => Kind::OwningMutableAddressor,
+                b'o' => Kind::NativeOwningMutableAddressor,
+                b'P' => Kind::NativePinningMutableAddressor,
+                b'u' => Kind::UnsafeMutableAddressor,
+                _ => return None,
+            },
+            b'l' => match self.next() {
+                b'O' => Kind::OwningAddressor,
+                b'o' => Kind::NativeOwningAddressor,
+                b'p' => Kind::NativePinningAddressor,
+                b'u' => Kind::UnsafeAddressor,
+                _ => return None,
+            },
+            // The storage itself.
+            b'p' => return Some(storage),
+            _ => return None,
+        };
+        Some(with_child(kind, storage))
+    }
+
+    // Symbols -----------------------------------------------------------------
+
+    fn with_popped_type(&mut self, kind: Kind) -> Option<Node> {
+        let ty = self.pop_kind(Kind::Type)?;
+        Some(with_child(kind, ty))
+    }
+
+    fn with_popped_conformance(&mut self, kind: Kind) -> Option<Node> {
+        let conformance = self.pop_protocol_conformance()?;
+        Some(with_child(kind, conformance))
+    }
+
+    fn demangle_metatype(&mut self) -> Option<Node> {
+        match self.next() {
+            b'a' => self.with_popped_type(Kind::TypeMetadataAccessFunction),
+            b'A' => self.with_popped_conformance(Kind::ReflectionMetadataAssocTypeDescriptor),
+            b'B' => self.with_popped_type(Kind::ReflectionMetadataBuiltinDescriptor),
+            b'c' => self.with_popped_conformance(Kind::ProtocolConformanceDescriptor),
+            b'C' => {
+                let class = self.pop_type_and_get_any_generic()?;
+                Some(with_child(
+                    Kind::ReflectionMetadataSuperclassDescriptor,
+                    class,
+                ))
+            }
+            b'D' => self.with_popped_type(Kind::TypeMetadataDemanglingCache),
+            b'f' => self.with_popped_type(Kind::FullTypeMetadata),
+            b'F' => self.with_popped_type(Kind::ReflectionMetadataFieldDescriptor),
+            b'i' => self.with_popped_type(Kind::TypeMetadataInstantiationFunction),
+            b'I' => self.with_popped_type(Kind::TypeMetadataInstantiationCache),
+            b'l' => self.with_popped_type(Kind::TypeMetadataSingletonInitializationCache),
+            b'L' => self.with_popped_type(Kind::TypeMetadataLazyCache),
+            b'm' => self.with_popped_type(Kind::Metaclass),
+            b'n' => self.with_popped_type(Kind::NominalTypeDescriptor),
+            b'o' => self.with_popped_type(Kind::ClassMetadataBaseOffset),
+            b'p' => {
+                let protocol = self.pop_protocol()?;
+                Some(with_child(Kind::ProtocolDescriptor, protocol))
+            }
+            b'P' => self.with_popped_type(Kind::GenericTypeMetadataPattern),
+            b'r' => self.with_popped_type(Kind::TypeMetadataCompletionFunction),
+            b's' => self.with_popped_type(Kind::ObjCResilientClassStub),
+            b'S' => {
+                let protocol = self.pop_protocol()?;
+                Some(with_child(
+                    Kind::ProtocolSelfConformanceDescriptor,
+                    protocol,
+                ))
+            }
+            b't' => self.with_popped_type(Kind::FullObjCResilientClassStub),
+            b'u' => self.with_popped_type(Kind::MethodLookupFunction),
+            b'U' => self.with_popped_type(Kind::ObjCMetadataUpdateFunction),
+            b'V' => {
+                let entity = self.pop_if(is_entity)?;
+                Some(with_child(Kind::PropertyDescriptor, entity))
+            }
+            _ => None,
+        }
+    }
+
+    fn demangle_witness(&mut self) -> Option<Node> {
+        match self.next() {
+            b'C' => Some(with_child(Kind::EnumCase, self.pop_if(is_entity)?)),
+            b'V' => self.with_popped_type(Kind::ValueWitnessTable),
+            b'v' => {
+                let directness = match self.next() {
+                    b'd' => 0,
+                    b'i' => 1,
+                    _ => return None,
+                };
+                let entity = self.pop_if(is_entity)?;
+                Some(Node::with_children(
+                    Kind::FieldOffset,
+                    vec![Node::with_index(Kind::Directness, directness), entity],
+                ))
+            }
+            b'P' => self.with_popped_conformance(Kind::ProtocolWitnessTable),
+            b'p' => self.with_popped_conformance(Kind::ProtocolWitnessTablePattern),
+            b'G' => self.with_popped_conformance(Kind::GenericProtocolWitnessTable),
+            b'I' => {
+                self.with_popped_conformance(Kind::GenericProtocolWitnessTableInstantiationFunction)
+            }
+            b'r' => self.with_popped_conformance(Kind::ResilientProtocolWitnessTable),
+            b'a' => self.with_popped_conformance(Kind::ProtocolWitnessTableAccessor),
+            b'l' => self.demangle_lazy_witness_table(Kind::LazyProtocolWitnessTableAccessor),
+            b'L' => self.demangle_lazy_witness_table(Kind::LazyProtocolWitnessTableCacheVariable),
+            b't' => {
+                let name = self.pop_if(is_decl_name)?;
+                let conformance = self.pop_protocol_conformance()?;
+                Some(Node::with_children(
+                    Kind::AssociatedTypeMetadataAccessor,
+                    vec![conformance, name],
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    fn demangle_lazy_witness_table(&mut self, kind: Kind) -> Option<Node> {
+        let conformance = self.pop_protocol_conformance()?;
+        let conforming = self.pop_kind(Kind::Type)?;
+        Some(Node::with_children(kind, vec![conforming, conformance]))
+    }
+
+    fn demangle_value_witness(&mut self) -> Option<Node> {
+        let code = [self.next(), self.next()];
+        let kind = ValueWitnessKind::from_code(&code)?;
+        let ty = self.pop_kind(Kind::Type)?;
+        Some(Node::with_children(
+            Kind::ValueWitness,
+            vec![Node::with_index(Kind::Index, kind as u64), ty],
+        ))
+    }
+
+    fn demangle_thunk_or_specialization(&mut self) -> Option<Node> {
+        let node = match self.next() {
+            b'A' => Node::new(Kind::PartialApplyForwarder),
+            b'a' => Node::new(Kind::PartialApplyObjCForwarder),
+            b'm' => Node::new(Kind::MergedFunction),
+            b'o' => Node::new(Kind::ObjCAttribute),
+            b'O' => Node::new(Kind::NonObjCAttribute),
+            b'D' => Node::new(Kind::DynamicAttribute),
+            b'd' => Node::new(Kind::DirectMethodReferenceAttribute),
+            b'u' => Node::new(Kind::AsyncFunctionPointer),
+            b'c' => with_child(Kind::CurryThunk, self.pop_if(is_entity)?),
+            b'j' => with_child(Kind::DispatchThunk, self.pop_if(is_entity)?),
+            b'q' => with_child(Kind::MethodDescriptor, self.pop_if(is_entity)?),
+            b'V' => {
+                let base = self.pop_if(is_entity)?;
+                let derived = self.pop_if(is_entity)?;
+                Node::with_children(Kind::VTableThunk, vec![derived, base])
+            }
+            b'W' => {
+                let entity = self.pop_if(is_entity)?;
+                let conformance = self.pop_protocol_conformance()?;
+                Node::with_children(Kind::ProtocolWitness, vec![conformance, entity])
+            }
+            _ => return None,
+        };
+        Some(node)
+    }
+}
+
+fn ty(child: Node) -> Node {
+    with_child(Kind::Type, child)
+}
+
+fn with_child(kind: Kind, child: Node) -> Node {
+    Node::with_children(kind, vec![child])
+}
+
+fn generic_param(depth: u64, index: u64) -> Node {
+    Node::with_children(
+        Kind::DependentGenericParamType,
+        vec![
+            Node::with_index(Kind::Index, depth),
+            Node::with_index(Kind::Index, index),
+        ],
+    )
+}
+
+/// Returns the type `module.name`.
+fn swift_type(kind: Kind, module: &str, name: &str) -> Node {
+    ty(Node::with_children(
+        kind,
+        vec![
+            Node::with_text(Kind::Module, module),
+            Node::with_text(Kind::Identifier, name),
+        ],
+    ))
+}
+
+/// Returns the type of the standard substitution `S<c>`, or `Sc<c>` if
+/// `second_level`.
+fn standard_substitution(c: u8, second_level: bool) -> Option<(Kind, &'static str, &'static str)> {
+    use Kind::{Class, Enum, Protocol, Structure};
+
+    if second_level {
+        let (kind, name) = match c {
+            b'A' => (Protocol, "Actor"),
+            b'C' => (Structure, "CheckedContinuation"),
+            b'c' => (Structure, "UnsafeContinuation"),
+            b'E' => (Structure, "CancellationError"),
+            b'e' => (Structure, "UnownedSerialExecutor"),
+            b'F' => (Protocol, "Executor"),
+            b'f' => (Protocol, "SerialExecutor"),
+            b'G' => (Structure, "TaskGroup"),
+            b'g' => (Structure, "ThrowingTaskGroup"),
+            b'I' => (Protocol, "AsyncIteratorProtocol"),
+            b'i' => (Protocol, "AsyncSequence"),
+            b'J' => (Structure, "UnownedJob"),
+            b'M' => (Class, "MainActor"),
+            b'P' => (Structure, "TaskPriority"),
+            b'S' => (Structure, "AsyncStream"),
+            b's' => (Structure, "AsyncThrowingStream"),
+            b'T' => (Structure, "Task"),
+            b't' => (Structure, "UnsafeCurrentTask"),
+            _ => return None,
+        };
+        return Some((kind, "_Concurrency", name));
+    }
+
+    let (kind, name) = match c {
+        b'A' => (Structure, "AutoreleasingUnsafeMutablePointer"),
+        b'a' => (Structure, "Array"),
+        b'B' => (Protocol, "BinaryFloatingPoint"),
+        b'b' => (Structure, "Bool"),
+        b'D' => (Structure, "Dictionary"),
+        b'd' => (Structure, "Double"),
+        b'E' => (Protocol, "Encodable"),
+        b'e' => (Protocol, "Decodable"),
+        b'F' => (Protocol, "FloatingPoint"),
+        b'f' => (Structure, "Float"),
+        b'G' => (Protocol, "RandomNumberGenerator"),
+        b'H' => (Protocol, "Hashable"),
+        b'h' => (Structure, "Set"),
+        b'I' => (Structure, "DefaultIndices"),
+        b'i' => (Structure, "Int"),
+        b'J' => (Structure, "Character"),
+        b'j' => (Protocol, "Numeric"),
+        b'K' => (Protocol, "BidirectionalCollection"),
+        b'k' => (Protocol, "RandomAccessCollection"),
+        b'L' => (Protocol, "Comparable"),
+        b'l' => (Protocol, "Collection"),
+        b'M' => (Protocol, "MutableCollection"),
+        b'm' => (Protocol, "RangeReplaceableCollection"),
+        b'N' => (Structure, "ClosedRange"),
+        b'n' => (Structure, "Range"),
+        b'O' => (Structure, "ObjectIdentifier"),
+        b'P' => (Structure, "UnsafePointer"),
+        b'p' => (Structure, "UnsafeMutablePointer"),
+        b'Q' => (Protocol, "Equatable"),
+        b'q' => (Enum, "Optional"),
+        b'R' => (Structure, "UnsafeBufferPointer"),
+        b'r' => (Structure, "UnsafeMutableBufferPointer"),
+        b'S' => (Structure, "String"),
+        b's' => (Structure, "Substring"),
+        b'T' => (Protocol, "Sequence"),
+        b't' => (Protocol, "IteratorProtocol"),
+        b'U' => (Protocol, "UnsignedInteger"),
+        b'u' => (Structure, "UInt"),
+        b'V' => (Structure, "UnsafeRawPointer"),
+        b'v' => (Structure, "UnsafeMutableRawPointer"),
+        b'W' => (Structure, "UnsafeRawBufferPointer"),
+        b'w' => (Structure, "UnsafeMutableRawBufferPointer"),
+        b'X' => (Protocol, "RangeExpression"),
+        b'x' => (Protocol, "Strideable"),
+        b'Y' => (Protocol, "RawRepresentable"),
+        b'y' => (Protocol, "StringProtocol"),
+        b'Z' => (Protocol, "SignedInteger"),
+        b'z' => (Protocol, "BinaryInteger"),
+        _ => return None,
+    };
+    Some((kind, "Swift", name))
+}
+
+fn is_word_start(c: u8) -> bool {
+    !c.is_ascii_digit() && c != b'_' && c != 0
+}
+
+fn is_word_end(c: u8, prev: u8) -> bool {
+    c == b'_' || c == 0 || (!prev.is_ascii_uppercase() && c.is_ascii_uppercase())
+}
+
+fn is_decl_name(kind: Kind) -> bool {
+    matches!(
+        kind,
+        Kind::Identifier
+            | Kind::LocalDeclName
+            | Kind::PrivateDeclName
+            | Kind::RelatedEntityDeclName
+            | Kind::PrefixOperator
+            | Kind::PostfixOperator
+            | Kind::InfixOperator
+    )
+}
+
+fn is_any_generic(kind: Kind) -> bool {
+    matches!(
+        kind,
+        Kind::Structure
+            | Kind::Class
+            | Kind::Enum
+            | Kind::Protocol
+            | Kind::OtherNominalType
+            | Kind::TypeAlias
+    )
+}
+
+pub(super) fn is_context(kind: Kind) -> bool {
+    is_any_generic(kind)
+        || matches!(
+            kind,
+            Kind::Allocator
+                | Kind::Constructor
+                | Kind::Deallocator
+                | Kind::DefaultArgumentInitializer
+                | Kind::Destructor
+                | Kind::DidSet
+                | Kind::ExplicitClosure
+                | Kind::Extension
+                | Kind::Function
+                | Kind::Getter
+                | Kind::GlobalGetter
+                | Kind::IVarInitializer
+                | Kind::IVarDestroyer
+                | Kind::ImplicitClosure
+                | Kind::Initializer
+                | Kind::InitAccessor
+                | Kind::MaterializeForSet
+                | Kind::ModifyAccessor
+                | Kind::Module
+                | Kind::NativeOwningAddressor
+                | Kind::NativeOwningMutableAddressor
+                | Kind::NativePinningAddressor
+                | Kind::NativePinningMutableAddressor
+                | Kind::OwningAddressor
+                | Kind::OwningMutableAddressor
+                | Kind::PropertyWrapperBackingInitializer
+                | Kind::ReadAccessor
+                | Kind::Setter
+                | Kind::Static
+                | Kind::Subscript
+                | Kind::UnsafeAddressor
+                | Kind::UnsafeMutableAddressor
+                | Kind::Variable
+                | Kind::WillSet
+        )
+}
+
+fn is_entity(kind: Kind) -> bool {
+    // Also accepts types, as in the Swift runtime.
+    kind == Kind::Type || is_context(kind)
+}
+
+fn is_requirement(kind: Kind) -> bool {
+    matches!(
+        kind,
+        Kind::DependentGenericConformanceRequirement
+            | Kind::DependentGenericSameTypeRequirement
+            | Kind::DependentGenericLayoutRequirement
+    )
+}
+
+fn is_function_attr(kind: Kind) -> bool {
+    matches!(
+        kind,
+        Kind::ObjCAttribute
+            | Kind::NonObjCAttribute
+            | Kind::DynamicAttribute
+            | Kind::DirectMethodReferenceAttribute
+            | Kind::PartialApplyForwarder
+            | Kind::PartialApplyObjCForwarder
+            | Kind::MergedFunction
+            | Kind::AsyncFunctionPointer
+    )
+}
+
+fn is_protocol_node(ty: &Node) -> bool {
+    match ty.kind() {
+        Kind::Type => ty.child(0).is_some_and(is_protocol_node),
+        Kind::Protocol => true,
+        _ => false,
+    }
+}
@@ -0,0 +1,170 @@
+//! Swift 5 symbol demangling.
+//!
+//! Symbols and type names are parsed into a tree of [`Node`]s shaped like
+//! `Demangle::Node` in the Swift runtime, which is printed like
+//! `swift-demangle`.
+//!
+//! Symbols may have the prefixes of Swift 4.0 (`_T0`), Swift 4.2 (`$S`) or
+//! Swift 5 (`$s`), optionally with the leading underscore of C symbols on
+//! Apple platforms. Symbols from before Swift 4 are not supported.
+//!
+//! # Examples
+//!
+//! ```
+//! use swift_sys::mangling;
+//!
+//! let node = mangling::demangle_symbol("$sSS9repeating5countS2S_SitcfC").unwrap();
+//! assert_eq!(
+//!     node.to_string(),
+//!     "Swift.String.init(repeating: Swift.String, count: Swift.Int) -> Swift.String",
+//! );
+//! ```
+
+mod demangler;
+mod node;
+mod printer;
+mod symbol;
+
+#[cfg(test)]
+mod tests;
+
+use demangler::Demangler;
+use std::convert::TryFrom;
+
+pub use node::{Node, NodeKind};
+pub use symbol::SymbolKind;
+
+/// The prefixes of symbols that can be demangled, from the oldest mangling.
+const PREFIXES: &[&str] = &["_T0", "$S", "_$S", "$s", "_$s"];
+
+/// Returns `symbol` without its mangling prefix, or `None` if it doesn't
+/// have one.
+pub fn strip_prefix(symbol: &str) -> Option<&str> {
+    PREFIXES
+        .iter()
+        .find_map(|prefix| symbol.strip_prefix(prefix))
+}
+
+/// Returns `true` if `symbol` has the prefix of a Swift symbol.
+#[inline]
+pub fn is_swift_symbol(symbol: &str) -> bool {
+    strip_prefix(symbol).is_some()
+}
+
+/// Demangles `symbol`, which starts with one of the supported prefixes, such
+/// as `$s`.
+///
+/// Returns `None` if `symbol` is not a valid Swift symbol.
+pub fn demangle_symbol(symbol: &str) -> Option<Node> {
+    let mangled = strip_prefix(symbol)?;
+    let demangler = Demangler::new(mangled.as_bytes());
+    match symbol.starts_with("_T0") {
+        true => demangler.with_old_function_types().demangle_symbol(),
+        false => demangler.demangle_symbol(),
+    }
+}
+
+/// Returns what `symbol` refers to, or `None` if it can't be demangled.
+#[inline]
+pub fn classify_symbol(symbol: &str) -> Option<SymbolKind> {
+    demangle_symbol(symbol).map(|node| SymbolKind::of(&node))
+}
+
+/// Demangles a type name that has no prefix, such as the mangled names in
+/// reflection metadata.
+///
+/// Returns `None` if `mangled` is not a single valid type.
+pub fn demangle_type(mangled: &str) -> Option<Node> {
+    Demangler::new(mangled.as_bytes()).demangle_type()
+}
+
+/// Demangles a type name that may contain symbolic references, which are
+/// turned into nodes by `resolver`.
+///
+/// A reference to a type should resolve to a [`NodeKind::Type`] node so that
+/// it can be substituted later in the name. Returns `None` if `resolver`
+/// does.
+pub fn demangle_type_with(
+    mangled: &[u8],
+    mut resolver: impl FnMut(SymbolicReference) -> Option<Node>,
+) -> Option<Node> {
+    Demangler::new(mangled)
+        .with_resolver(&mut resolver)
+        .demangle_type()
+}
+
+/// A reference within a mangled name to an entity by its address rather than
+/// by its name.
+///
+/// See `docs/ABI/Mangling.rst` for the kinds of references.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SymbolicReference {
+    /// The byte that starts the reference, where `0x01..=0x17` are followed
+    /// by a 32-bit offset relative to the offset itself, and `0x18..=0x1F`
+    /// by an absolute pointer.
+    ///
+    /// For example, `0x01` refers directly to a context descriptor and
+    /// `0x02` refers to a pointer to one.
+    pub control: u8,
+
+    /// The unaligned address of the offset or pointer after `control`.
+    pub payload: *const u8,
+}
+
+macro_rules! value_witnesses {
+    ($($kind:ident = $code:literal $name:literal,)+) => {
+        /// A function of a value witness table, in mangling order.
+        #[derive(Clone, Copy)]
+        enum ValueWitnessKind {
+            $($kind,)+
+        }
+
+        impl ValueWitnessKind {
+            const ALL: &'static [Self] = &[$(Self::$kind,)+];
+
+            fn from_code(code: &[u8; 2]) -> Option<Self> {
+                match code {
+                    $($code => Some(Self::$kind),)+
+                    _ => None,
+                }
+            }
+
+            fn from_index(index: u64) -> Option<Self> {
+                Self::ALL.get(usize::try_from(index).ok()?).copied()
+            }
+
+            fn name(self) -> &'static str {
+                match self {
+                    $(Self::$kind => $name,)+
+                }
+            }
+        }
+    };
+}
+
+value_witnesses! {
+    AllocateBuffer = b"al" "allocateBuffer",
+    AssignWithCopy = b"ca" "assignWithCopy",
+    AssignWithTake = b"ta" "assignWithTake",
+    DeallocateBuffer = b"de" "deallocateBuffer",
+    Destroy = b"xx" "destroy",
+    DestroyBuffer = b"XX" "destroyBuffer",
+    DestroyArray = b"Xx" "destroyArray",
+    InitializeBufferWithCopyOfBuffer = b"CP" "initializeBufferWithCopyOfBuffer",
+    InitializeBufferWithCopy = b"Cp" "initializeBufferWithCopy",
+    InitializeWithCopy = b"cp" "initializeWithCopy",
+    InitializeBufferWithTake = b"Tk" "initializeBufferWithTake",
+    InitializeWithTake = b"tk" "initializeWithTake",
+    ProjectBuffer = b"pr" "projectBuffer",
+    InitializeBufferWithTakeOfBuffer = b"TK" "initializeBufferWithTakeOfBuffer",
+    InitializeArrayWithCopy = b"Cc" "initializeArrayWithCopy",
+    InitializeArrayWithTakeFrontToBack = b"Tt" "initializeArrayWithTakeFrontToBack",
+    InitializeArrayWithTakeBackToFront = b"tT" "initializeArrayWithTakeBackToFront",
+    StoreExtraInhabitant = b"xs" "storeExtraInhabitant",
+    GetExtraInhabitantIndex = b"xg" "getExtraInhabitantIndex",
+    GetEnumTag = b"ug" "getEnumTag",
+    DestructiveProjectEnumData = b"up" "destructiveProjectEnumData",
+    DestructiveInjectEnumTag = b"ui" "destructiveInjectEnumTag",
+    GetEnumTagSinglePayload = b"et" "getEnumTagSinglePayload",
+    StoreEnumTagSinglePayload = b"st" "storeEnumTagSinglePayload",
+}
@@ -0,0 +1,479 @@
+use std::fmt;
+
+/// A node in the tree of a demangled symbol, shaped like `Demangle::Node` in
+/// the Swift runtime.
+///
+/// A node has either text, an index, or children. Its
+/// [`Display`](fmt::Display) implementation matches the output of
+/// `swift-demangle`.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Node {
+    kind: NodeKind,
+    payload: Payload,
+    pub(super) children: Vec<Node>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Payload {
+    None,
+    Text(String),
+    Index(u64),
+}
+
+impl fmt::Debug for Node {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut tuple = f.debug_tuple(&format!("{:?}", self.kind));
+        match &self.payload {
+            Payload::None => {}
+            Payload::Text(text) => {
+                tuple.field(text);
+            }
+            Payload::Index(index) => {
+                tuple.field(index);
+            }
+        }
+        for child in &self.children {
+            tuple.field(child);
+        }
+        tuple.finish()
+    }
+}
+
+impl Node {
+    /// Creates a node of `kind` without text, an index or children.
+    pub fn new(kind: NodeKind) -> Self {
+        Self {
+            kind,
+            payload: Payload::None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Creates a node of `kind` with `text`, such as an identifier.
+    pub fn with_text(kind: NodeKind, text: impl Into<String>) -> Self {
+        Self {
+            payload: Payload::Text(text.into()),
+            ..Self::new(kind)
+        }
+    }
+
+    /// Creates a node of `kind` with `index`.
+    pub fn with_index(kind: NodeKind, index: u64) -> Self {
+        Self {
+            payload: Payload::Index(index),
+            ..Self::new(kind)
+        }
+    }
+
+    /// Creates a node of `kind` with `children`.
+    pub fn with_children(kind: NodeKind, children: Vec<Node>) -> Self {
+        Self {
+            children,
+            ..Self::new(kind)
+        }
+    }
+
+    /// Returns a node of `kind` with the same contents.
+    pub(super) fn into_kind(self, kind: NodeKind) -> Self {
+        Self { kind, ..self }
+    }
+
+    /// Appends `child` to the children of the node.
+    pub fn push(&mut self, child: Node) {
+        self.children.push(child);
+    }
+
+    pub(super) fn reverse_children(&mut self, from: usize) {
+        if from < self.children.len() {
+            self.children[from..].reverse();
+        }
+    }
+
+    /// Returns what the node represents.
+    #[inline]
+    pub fn kind(&self) -> NodeKind {
+        self.kind
+    }
+
+    /// Returns the text of the node, such as the name of an identifier.
+    #[inline]
+    pub fn text(&self) -> Option<&str> {
+        match &self.payload {
+            Payload::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Returns the index of the node, such as the depth of a generic
+    /// parameter.
+    #[inline]
+    pub fn index(&self) -> Option<u64> {
+        match self.payload {
+            Payload::Index(index) => Some(index),
+            _ => None,
+        }
+    }
+
+    /// Returns the child nodes.
+    #[inline]
+    pub fn children(&self) -> &[Node] {
+        &self.children
+    }
+
+    /// Returns the child node at `index`.
+    #[inline]
+    pub fn child(&self, index: usize) -> Option<&Node> {
+        self.children.get(index)
+    }
+
+    /// Returns the first child node of `kind`.
+    #[inline]
+    pub fn child_of_kind(&self, kind: NodeKind) -> Option<&Node> {
+        self.children.iter().find(|child| child.kind == kind)
+    }
+}
+
+macro_rules! node_kinds {
+    ($($(#[$meta:meta])* $kind:ident,)+) => {
+        /// The kind of a demangled [`Node`], named as in the Swift runtime.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[non_exhaustive]
+        pub enum NodeKind {
+            $($(#[$meta])* $kind,)+
+        }
+    };
+}
+
+node_kinds! {
+    /// The root of a demangled symbol.
+    Global,
+    /// Text after the mangled part of a symbol.
+    Suffix,
+    /// A module name.
+    Module,
+    /// A name.
+    Identifier,
+    /// The name of a `private` declaration and the discriminator of its file.
+    PrivateDeclName,
+    /// The name of a declaration in a local context and its discriminator.
+    LocalDeclName,
+    /// The name of an entity related to a C declaration.
+    RelatedEntityDeclName,
+    /// The name of an infix operator.
+    InfixOperator,
+    /// The name of a prefix operator.
+    PrefixOperator,
+    /// The name of a postfix operator.
+    PostfixOperator,
+
+    /// A `struct`.
+    Structure,
+    /// An `enum`.
+    Enum,
+    /// A `class`.
+    Class,
+    /// A `protocol`.
+    Protocol,
+    /// A `typealias`.
+    TypeAlias,
+    /// A nominal type imported from C.
+    OtherNominalType,
+    /// An `extension`, with its module, extended type and generic signature.
+    Extension,
+    /// A generic `struct` applied to arguments.
+    BoundGenericStructure,
+    /// A generic `enum` applied to arguments.
+    BoundGenericEnum,
+    /// A generic `class` applied to arguments.
+    BoundGenericClass,
+    /// A protocol applied to a conforming type.
+    BoundGenericProtocol,
+    /// A generic `typealias` applied to arguments.
+    BoundGenericTypeAlias,
+    /// A generic nominal type imported from C applied to arguments.
+    BoundGenericOtherNominalType,
+    /// A generic function applied to arguments.
+    BoundGenericFunction,
+
+    /// A type.
+    Type,
+    /// A list of types.
+    TypeList,
+    /// The argument labels of a function.
+    LabelList,
+    /// An empty list, or a list without labels.
+    EmptyList,
+    /// The first element of a list.
+    FirstElementMarker,
+    /// The marker of a variadic parameter.
+    VariadicMarker,
+    /// A number.
+    Index,
+    /// A tuple type.
+    Tuple,
+    /// An element of a tuple type.
+    TupleElement,
+    /// The label of a tuple element.
+    TupleElementName,
+
+    /// An escaping function type.
+    FunctionType,
+    /// A non-escaping function type.
+    NoEscapeFunctionType,
+    /// An `@autoclosure` function type.
+    AutoClosureType,
+    /// An escaping `@autoclosure` function type.
+    EscapingAutoClosureType,
+    /// A `@convention(thin)` function type.
+    ThinFunctionType,
+    /// A `@convention(c)` function type.
+    CFunctionPointer,
+    /// A `@convention(block)` function type.
+    ObjCBlock,
+    /// An escaping `@convention(block)` function type.
+    EscapingObjCBlock,
+    /// The type of a method that takes `self` first.
+    UncurriedFunctionType,
+    /// The parameters of a function type.
+    ArgumentTuple,
+    /// The result of a function type.
+    ReturnType,
+    /// The `throws` of a function type.
+    ThrowsAnnotation,
+    /// The `async` of a function type.
+    AsyncAnnotation,
+    /// The `@Sendable` of a function type.
+    ConcurrentFunctionType,
+    /// The global actor of a function type.
+    GlobalActorFunctionType,
+
+    /// An `inout` parameter.
+    InOut,
+    /// A `__shared` parameter.
+    Shared,
+    /// A `__owned` parameter.
+    Owned,
+    /// A `weak` reference.
+    Weak,
+    /// An `unowned` reference.
+    Unowned,
+    /// An `unowned(unsafe)` reference.
+    Unmanaged,
+    /// The dynamic `Self` type.
+    DynamicSelf,
+    /// A metatype, `T.Type`.
+    Metatype,
+    /// The representation of a metatype.
+    MetatypeRepresentation,
+    /// The metatype of an existential, `P.Type`.
+    ExistentialMetatype,
+    /// An existential of zero or more protocols.
+    ProtocolList,
+    /// An existential of protocols and a superclass.
+    ProtocolListWithClass,
+    /// An existential of protocols that is class-bound.
+    ProtocolListWithAnyObject,
+    /// A type of the `Builtin` module.
+    BuiltinTypeName,
+
+    /// A generic parameter, with its depth and index.
+    DependentGenericParamType,
+    /// A generic signature.
+    DependentGenericSignature,
+    /// The number of generic parameters at a depth.
+    DependentGenericParamCount,
+    /// A requirement that a type conforms to a protocol or subclasses a
+    /// class.
+    DependentGenericConformanceRequirement,
+    /// A requirement that two types are the same.
+    DependentGenericSameTypeRequirement,
+    /// A requirement on the layout of a type.
+    DependentGenericLayoutRequirement,
+    /// A type within a generic signature.
+    DependentGenericType,
+    /// An associated type of a type, such as `A.Element`.
+    DependentMemberType,
+    /// The name of an associated type.
+    DependentAssociatedTypeRef,
+    /// An associated type of an archetype.
+    AssociatedTypeRef,
+    /// An opaque result type, `some P`.
+    OpaqueReturnType,
+
+    /// A `func`.
+    Function,
+    /// The allocating entry point of an `init`.
+    Allocator,
+    /// The initializing entry point of an `init`.
+    Constructor,
+    /// A `deinit`.
+    Destructor,
+    /// The deallocating entry point of a `deinit`.
+    Deallocator,
+    /// The initializer of the instance variables of a class.
+    IVarInitializer,
+    /// The destroyer of the instance variables of a class.
+    IVarDestroyer,
+    /// The initial value of a variable.
+    Initializer,
+    /// The default value of a parameter.
+    DefaultArgumentInitializer,
+    /// A closure expression.
+    ExplicitClosure,
+    /// A closure that the compiler made from an expression.
+    ImplicitClosure,
+    /// The initializer of the backing storage of a property wrapper.
+    PropertyWrapperBackingInitializer,
+    /// A `var` or `let`.
+    Variable,
+    /// A `subscript`.
+    Subscript,
+    /// A `static` or `class` member.
+    Static,
+
+    /// A `get` accessor.
+    Getter,
+    /// A `set` accessor.
+    Setter,
+    /// The getter of a global variable.
+    GlobalGetter,
+    /// A `willSet` observer.
+    WillSet,
+    /// A `didSet` observer.
+    DidSet,
+    /// A `_read` coroutine.
+    ReadAccessor,
+    /// A `_modify` coroutine.
+    ModifyAccessor,
+    /// A `materializeForSet` accessor.
+    MaterializeForSet,
+    /// An `init` accessor.
+    InitAccessor,
+    /// An `unsafeAddress` accessor.
+    UnsafeAddressor,
+    /// An `unsafeMutableAddress` accessor.
+    UnsafeMutableAddressor,
+    /// An addressor that returns an owner.
+    OwningAddressor,
+    /// A mutable addressor that returns an owner.
+    OwningMutableAddressor,
+    /// An addressor that returns a native owner.
+    NativeOwningAddressor,
+    /// A mutable addressor that returns a native owner.
+    NativeOwningMutableAddressor,
+    /// An addressor that returns a pinned native owner.
+    NativePinningAddressor,
+    /// A mutable addressor that returns a pinned native owner.
+    NativePinningMutableAddressor,
+
+    /// A type mangled as a symbol.
+    TypeMangling,
+    /// Type metadata.
+    TypeMetadata,
+    /// A type metadata access function.
+    TypeMetadataAccessFunction,
+    /// Full type metadata, including its value witness table.
+    FullTypeMetadata,
+    /// The Objective-C metaclass of a class.
+    Metaclass,
+    /// A nominal type descriptor.
+    NominalTypeDescriptor,
+    /// A protocol descriptor.
+    ProtocolDescriptor,
+    /// A protocol conformance descriptor.
+    ProtocolConformanceDescriptor,
+    /// The descriptor of a protocol's conformance to itself.
+    ProtocolSelfConformanceDescriptor,
+    /// A type metadata completion function.
+    TypeMetadataCompletionFunction,
+    /// A type metadata instantiation cache.
+    TypeMetadataInstantiationCache,
+    /// A type metadata instantiation function.
+    TypeMetadataInstantiationFunction,
+    /// A type metadata singleton initialization cache.
+    TypeMetadataSingletonInitializationCache,
+    /// A lazy cache variable for type metadata.
+    TypeMetadataLazyCache,
+    /// A demangling cache variable for type metadata.
+    TypeMetadataDemanglingCache,
+    /// A generic type metadata pattern.
+    GenericTypeMetadataPattern,
+    /// The offset of the metadata of a class after its superclasses'.
+    ClassMetadataBaseOffset,
+    /// A method lookup function.
+    MethodLookupFunction,
+    /// An Objective-C metadata update function.
+    ObjCMetadataUpdateFunction,
+    /// An Objective-C resilient class stub.
+    ObjCResilientClassStub,
+    /// A full Objective-C resilient class stub.
+    FullObjCResilientClassStub,
+    /// A property descriptor.
+    PropertyDescriptor,
+    /// A reflection metadata field descriptor.
+    ReflectionMetadataFieldDescriptor,
+    /// A reflection metadata builtin descriptor.
+    ReflectionMetadataBuiltinDescriptor,
+    /// A reflection metadata associated type descriptor.
+    ReflectionMetadataAssocTypeDescriptor,
+    /// A reflection metadata superclass descriptor.
+    ReflectionMetadataSuperclassDescriptor,
+
+    /// A conformance of a type to a protocol in a module.
+    ProtocolConformance,
+    /// A value witness table.
+    ValueWitnessTable,
+    /// A value witness function.
+    ValueWitness,
+    /// A protocol witness table.
+    ProtocolWitnessTable,
+    /// A protocol witness table accessor.
+    ProtocolWitnessTableAccessor,
+    /// A protocol witness table pattern.
+    ProtocolWitnessTablePattern,
+    /// A generic protocol witness table.
+    GenericProtocolWitnessTable,
+    /// The instantiation function of a generic protocol witness table.
+    GenericProtocolWitnessTableInstantiationFunction,
+    /// A resilient protocol witness table.
+    ResilientProtocolWitnessTable,
+    /// A lazy protocol witness table accessor.
+    LazyProtocolWitnessTableAccessor,
+    /// A lazy protocol witness table cache variable.
+    LazyProtocolWitnessTableCacheVariable,
+    /// An associated type metadata accessor.
+    AssociatedTypeMetadataAccessor,
+    /// A field offset.
+    FieldOffset,
+    /// Whether a field offset is direct.
+    Directness,
+    /// An enum case.
+    EnumCase,
+
+    /// A protocol witness.
+    ProtocolWitness,
+    /// A dispatch thunk.
+    DispatchThunk,
+    /// A method descriptor.
+    MethodDescriptor,
+    /// A curry thunk.
+    CurryThunk,
+    /// A vtable thunk.
+    VTableThunk,
+    /// An async function pointer.
+    AsyncFunctionPointer,
+    /// A function merged with an identical one.
+    MergedFunction,
+    /// An `@objc` thunk.
+    ObjCAttribute,
+    /// A `@nonobjc` thunk.
+    NonObjCAttribute,
+    /// A `dynamic` thunk.
+    DynamicAttribute,
+    /// A `super` method reference.
+    DirectMethodReferenceAttribute,
+    /// A partial apply forwarder.
+    PartialApplyForwarder,
+    /// An Objective-C partial apply forwarder.
+    PartialApplyObjCForwarder,
+}
@@ -0,0 +1,1174 @@
+use super::{
+    node::{Node, NodeKind as Kind},
+    ValueWitnessKind,
+};
+use std::fmt::{self, Write};
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut printer = Printer::default();
+        printer.print(self, false);
+        f.write_str(&printer.out)
+    }
+}
+
+/// How the type of an entity is printed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TypePrinting {
+    NoType,
+    WithColon,
+    FunctionStyle,
+}
+
+/// A port of the Swift runtime's `NodePrinter` with the options used by
+/// `swift-demangle`.
+#[derive(Default)]
+struct Printer {
+    out: String,
+}
+
+impl Printer {
+    fn text(&mut self, node: &Node) {
+        self.out.push_str(node.text().unwrap_or_default());
+    }
+
+    fn print_children(&mut self, node: &Node, separator: &str) {
+        for (i, child) in node.children().iter().enumerate() {
+            if i != 0 {
+                self.out.push_str(separator);
+            }
+            self.print(child, false);
+        }
+    }
+
+    fn print_child(&mut self, node: &Node, index: usize) {
+        if let Some(child) = node.child(index) {
+            self.print(child, false);
+        }
+    }
+
+    fn prefixed(&mut self, prefix: &str, node: &Node) {
+        self.out.push_str(prefix);
+        self.print_child(node, 0);
+    }
+
+    /// Prints `node` and returns its context if it couldn't be printed as a
+    /// prefix, in which case the caller prints it after ` in `.
+    fn print<'n>(&mut self, node: &'n Node, as_prefix_context: bool) -> Option<&'n Node> {
+        use TypePrinting::*;
+
+        match node.kind() {
+            Kind::Global | Kind::TypeList => self.print_children(node, ""),
+            Kind::Type => self.print_child(node, 0),
+            Kind::Suffix => {
+                self.out.push_str(" with unmangled suffix ");
+                quote(&mut self.out, node.text().unwrap_or_default());
+            }
+            Kind::Module
+            | Kind::Identifier
+            | Kind::TupleElementName
+            | Kind::BuiltinTypeName
+            | Kind::MetatypeRepresentation => self.text(node),
+            Kind::Index => {
+                let _ = write!(self.out, "{}", node.index().unwrap_or_default());
+            }
+            Kind::LabelList => {}
+            Kind::EmptyList => self.out.push_str(" empty-list "),
+            Kind::FirstElementMarker => self.out.push_str(" first-element-marker "),
+            Kind::VariadicMarker => self.out.push_str(" variadic-marker "),
+            Kind::InfixOperator => {
+                self.text(node);
+                self.out.push_str(" infix");
+            }
+            Kind::PrefixOperator => {
+                self.text(node);
+                self.out.push_str(" prefix");
+            }
+            Kind::PostfixOperator => {
+                self.text(node);
+                self.out.push_str(" postfix");
+            }
+            Kind::PrivateDeclName => match node.children() {
+                [discriminator, name] => {
+                    self.out.push('(');
+                    self.print(name, false);
+                    self.out.push_str(" in ");
+                    self.text(discriminator);
+                    self.out.push(')');
+                }
+                [discriminator] => {
+                    self.out.push_str("(in ");
+                    self.text(discriminator);
+                    self.out.push(')');
+                }
+                _ => {}
+            },
+            Kind::LocalDeclName => {
+                self.print_child(node, 1);
+                let index = node.child(0).and_then(Node::index).unwrap_or_default();
+                let _ = write!(self.out, " #{}", index + 1);
+            }
+            Kind::RelatedEntityDeclName => {
+                self.out.push_str("related decl '");
+                if let Some(kind) = node.child(0) {
+                    self.text(kind);
+                }
+                self.out.push_str("' for ");
+                self.print_child(node, 1);
+            }
+
+            Kind::Structure
+            | Kind::Enum
+            | Kind::Class
+            | Kind::Protocol
+            | Kind::TypeAlias
+            | Kind::OtherNominalType => {
+                return self.print_entity(node, as_prefix_context, NoType, true, "", None, "");
+            }
+            Kind::Extension => {
+                self.out.push_str("(extension in ");
+                if let Some(module) = node.child(0) {
+                    self.print(module, true);
+                }
+                self.out.push_str("):");
+                self.print_child(node, 1);
+                if node.children().len() == 3 {
+                    self.print_child(node, 2);
+                }
+            }
+            Kind::BoundGenericStructure
+            | Kind::BoundGenericEnum
+            | Kind::BoundGenericClass
+            | Kind::BoundGenericProtocol
+            | Kind::BoundGenericTypeAlias
+            | Kind::BoundGenericOtherNominalType => self.print_bound_generic(node),
+
+            Kind::Tuple => {
+                self.out.push('(');
+                self.print_children(node, ", ");
+                self.out.push(')');
+            }
+            Kind::TupleElement => {
+                if let Some(label) = node.child_of_kind(Kind::TupleElementName) {
+                    self.text(label);
+                    self.out.push_str(": ");
+                }
+                if let Some(ty) = node.child_of_kind(Kind::Type) {
+                    self.print(ty, false);
+                }
+                if node.child_of_kind(Kind::VariadicMarker).is_some() {
+                    self.out.push_str("...");
+                }
+            }
+
+            Kind::FunctionType
+            | Kind::NoEscapeFunctionType
+            | Kind::AutoClosureType
+            | Kind::EscapingAutoClosureType
+            | Kind::ThinFunctionType
+            | Kind::CFunctionPointer
+            | Kind::ObjCBlock
+            | Kind::EscapingObjCBlock
+            | Kind::UncurriedFunctionType => self.print_function_type(None, node),
+            Kind::ArgumentTuple => self.print_function_parameters(None, node),
+            Kind::ReturnType => {
+                self.out.push_str(" -> ");
+                self.print_children(node, "");
+            }
+            Kind::ThrowsAnnotation => self.out.push_str(" throws"),
+            Kind::AsyncAnnotation => self.out.push_str(" async"),
+            Kind::ConcurrentFunctionType => self.out.push_str("@Sendable "),
+            Kind::GlobalActorFunctionType => {
+                if let Some(actor) = node.child(0) {
+                    self.out.push('@');
+                    self.print(actor, false);
+                    self.out.push(' ');
+                }
+            }
+
+            Kind::InOut => self.prefixed("inout ", node),
+            Kind::Shared => self.prefixed("__shared ", node),
+            Kind::Owned => self.prefixed("__owned ", node),
+            Kind::Weak => self.prefixed("weak ", node),
+            Kind::Unowned => self.prefixed("unowned ", node),
+            Kind::Unmanaged => self.prefixed("unowned(unsafe) ", node),
+            Kind::DynamicSelf => self.out.push_str("Self"),
+            Kind::Metatype => {
+                let mut index = 0;
+                if node.children().len() == 2 {
+                    self.print_child(node, 0);
+                    self.out.push(' ');
+                    index = 1;
+                }
+                if let Some(instance) = node.child(index).and_then(|ty| ty.child(0)) {
+                    self.print_with_parens(instance);
+                    match is_existential_type(instance) {
+                        true => self.out.push_str(".Protocol"),
+                        false => self.out.push_str(".Type"),
+                    }
+                }
+            }
+            Kind::ExistentialMetatype => {
+                let mut index = 0;
+                if node.children().len() == 2 {
+                    self.print_child(node, 0);
+                    self.out.push(' ');
+                    index = 1;
+                }
+                self.print_child(node, index);
+                self.out.push_str(".Type");
+            }
+            Kind::ProtocolList => {
+                if let Some(list) = node.child(0) {
+                    match list.children().is_empty() {
+                        true => self.out.push_str("Any"),
+                        false => self.print_children(list, " & "),
+                    }
+                }
+            }
+            Kind::ProtocolListWithClass => {
+                self.print_child(node, 1);
+                self.out.push_str(" & ");
+                if let Some(list) = node.child(0).and_then(|protocols| protocols.child(0)) {
+                    self.print_children(list, " & ");
+                }
+            }
+            Kind::ProtocolListWithAnyObject => {
+                if let Some(list) = node.child(0).and_then(|protocols| protocols.child(0)) {
+                    if !list.children().is_empty() {
+                        self.print_children(list, " & ");
+                        self.out.push_str(" & ");
+                    }
+                }
+                self.out.push_str("Swift.AnyObject");
+            }
+
+            Kind::DependentGenericParamType => {
+                let depth = node.child(0).and_then(Node::index).unwrap_or_default();
+                let index = node.child(1).and_then(Node::index).unwrap_or_default();
+                self.out.push_str(&generic_param_name(depth, index));
+            }
+            Kind::DependentGenericSignature => self.print_generic_signature(node),
+            Kind::DependentGenericParamCount => {}
+            Kind::DependentGenericConformanceRequirement => {
+                self.print_child(node, 0);
+                self.out.push_str(": ");
+                self.print_child(node, 1);
+            }
+            Kind::DependentGenericSameTypeRequirement => {
+                self.print_child(node, 0);
+                self.out.push_str(" == ");
+                self.print_child(node, 1);
+            }
+            Kind::DependentGenericLayoutRequirement => {
+                self.print_child(node, 0);
+                self.out.push_str(": ");
+                let layout = match node.child(1).and_then(Node::text) {
+                    Some("U") => "_UnknownLayout",
+                    Some("R") => "_RefCountedObject",
+                    Some("N") => "_NativeRefCountedObject",
+                    Some("C") => "AnyObject",
+                    Some("D") => "_NativeClass",
+                    Some("T") | Some("E") | Some("e") => "_Trivial",
+                    Some("M") | Some("m") => "_TrivialAtMost",
+                    _ => "",
+                };
+                self.out.push_str(layout);
+                if node.children().len() > 2 {
+                    self.out.push('(');
+                    self.print_child(node, 2);
+                    if node.children().len() > 3 {
+                        self.out.push_str(", ");
+                        self.print_child(node, 3);
+                    }
+                    self.out.push(')');
+                }
+            }
+            Kind::DependentGenericType => {
+                self.print_child(node, 0);
+                if let Some(ty) = node.child(1) {
+                    if need_space_before_type(ty) {
+                        self.out.push(' ');
+                    }
+                    self.print(ty, false);
+                }
+            }
+            Kind::DependentMemberType => {
+                self.print_child(node, 0);
+                self.out.push('.');
+                self.print_child(node, 1);
+            }
+            Kind::DependentAssociatedTypeRef => {
+                if node.children().len() > 1 {
+                    self.print_child(node, 1);
+                    self.out.push('.');
+                }
+                self.print_child(node, 0);
+            }
+            Kind::AssociatedTypeRef => {
+                self.print_child(node, 0);
+                self.out.push('.');
+                self.print_child(node, 1);
+            }
+            Kind::OpaqueReturnType => self.out.push_str("some"),
+
+            Kind::Function | Kind::BoundGenericFunction => {
+                return self.print_entity(
+                    node,
+                    as_prefix_context,
+                    FunctionStyle,
+                    true,
+                    "",
+                    None,
+                    "",
+                );
+            }
+            Kind::Allocator => {
+                let name = match is_class_type(node.child(0)) {
+                    true => "__allocating_init",
+                    false => "init",
+                };
+                return self.print_entity(
+                    node,
+                    as_prefix_context,
+                    FunctionStyle,
+                    false,
+                    name,
+                    None,
+                    "",
+                );
+            }
+            Kind::Constructor => {
+                let has_name = node.children().len() > 2;
+                return self.print_entity(
+                    node,
+                    as_prefix_context,
+                    FunctionStyle,
+                    has_name,
+                    "init",
+                    None,
+                    "",
+                );
+            }
+            Kind::Destructor => {
+                return self.print_entity(
+                    node,
+                    as_prefix_context,
+                    NoType,
+                    false,
+                    "deinit",
+                    None,
+                    "",
+                );
+            }
+            Kind::Deallocator => {
+                let name = match is_class_type(node.child(0)) {
+                    true => "__deallocating_deinit",
+                    false => "deinit",
+                };
+                return self.print_entity(node, as_prefix_context, NoType, false, name, None, "");
+            }
+            Kind::IVarInitializer => {
+                return self.print_entity(
+                    node,
+                    as_prefix_context,
+                    NoType,
+                    false,
+                    "__ivar_initializer",
+                    None,
+                    "",
+                );
+            }
+            Kind::IVarDestroyer => {
+                return self.print_entity(
+                    node,
+                    as_prefix_context,
+                    NoType,
+                    false,
+                    "__ivar_destroyer",
+                    None,
+                    "",
+                );
+            }
+            Kind::Initializer => {
+                return self.print_entity(
+                    node,
+                    as_prefix_context,
+                    NoType,
+                    false,
+                    "variable initialization expression",
+                    None,
+                    "",
+                );
+            }
+            Kind::PropertyWrapperBackingInitializer => {
+                return self.print_entity(
+                    node,
+                    as_prefix_context,
+                    NoType,
+                    false,
+                    "property wrapper backing initializer",
+                    None,
+                    "",
+                );
+            }
+            Kind::DefaultArgumentInitializer => {
+                let index = node.child(1).and_then(Node::index);
+                return self.print_entity(
+                    node,
+                    as_prefix_context,
+                    NoType,
+                    false,
+                    "default argument ",
+                    index,
+                    "",
+                );
+            }
+            Kind::ExplicitClosure | Kind::ImplicitClosure => {
+                let name = match node.kind() {
+                    Kind::ExplicitClosure => "closure #",
+                    _ => "implicit closure #",
+                };
+                let index = node.child(1).and_then(Node::index).map(|index| index + 1);
+                return self.print_entity(
+                    node,
+                    as_prefix_context,
+                    FunctionStyle,
+                    false,
+                    name,
+                    index,
+                    "",
+                );
+            }
+            Kind::Variable => {
+                return self.print_entity(node, as_prefix_context, WithColon, true, "", None, "");
+            }
+            Kind::Subscript => {
+                return self.print_entity(
+                    node,
+                    as_prefix_context,
+                    WithColon,
+                    false,
+                    "",
+                    None,
+                    "subscript",
+                );
+            }
+            Kind::Static => self.prefixed("static ", node),
+
+            Kind::Getter | Kind::GlobalGetter => {
+                return self.print_abstract_storage(node, as_prefix_context, "getter")
+            }
+            Kind::Setter => return self.print_abstract_storage(node, as_prefix_context, "setter"),
+            Kind::WillSet => {
+                return self.print_abstract_storage(node, as_prefix_context, "willset")
+            }
+            Kind::DidSet => return self.print_abstract_storage(node, as_prefix_context, "didset"),
+            Kind::ReadAccessor => {
+                return self.print_abstract_storage(node, as_prefix_context, "read")
+            }
+            Kind::ModifyAccessor => {
+                return self.print_abstract_storage(node, as_prefix_context, "modify")
+            }
+            Kind::MaterializeForSet => {
+                return self.print_abstract_storage(node, as_prefix_context, "materializeForSet")
+            }
+            Kind::InitAccessor => {
+                return self.print_abstract_storage(node, as_prefix_context, "init")
+            }
+            Kind::UnsafeAddressor => {
+                return self.print_abstract_storage(node, as_prefix_context, "unsafeAddressor")
+            }
+            Kind::UnsafeMutableAddressor => {
+                return self.print_abstract_storage(
+                    node,
+                    as_prefix_context,
+                    "unsafeMutableAddressor",
+                )
+            }
+            Kind::OwningAddressor => {
+                return self.print_abstract_storage(node, as_prefix_context, "owningAddressor")
+            }
+            Kind::OwningMutableAddressor => {
+                return self.print_abstract_storage(
+                    node,
+                    as_prefix_context,
+                    "owningMutableAddressor",
+                )
+            }
+            Kind::NativeOwningAddressor => {
+                return self.print_abstract_storage(
+                    node,
+                    as_prefix_context,
+                    "nativeOwningAddressor",
+                )
+            }
+            Kind::NativeOwningMutableAddressor => {
+                return self.print_abstract_storage(
+                    node,
+                    as_prefix_context,
+                    "nativeOwningMutableAddressor",
+                )
+            }
+            Kind::NativePinningAddressor => {
+                return self.print_abstract_storage(
+                    node,
+                    as_prefix_context,
+                    "nativePinningAddressor",
+                )
+            }
+            Kind::NativePinningMutableAddressor => {
+                return self.print_abstract_storage(
+                    node,
+                    as_prefix_context,
+                    "nativePinningMutableAddressor",
+                )
+            }
+
+            Kind::TypeMangling => match node.children() {
+                [labels, ty] if labels.kind() == Kind::LabelList => {
+                    if let Some(function) = ty.child(0) {
+                        self.print_function_type(Some(labels), function);
+                    }
+                }
+                _ => self.print_child(node, 0),
+            },
+            Kind::TypeMetadata => self.prefixed("type metadata for ", node),
+            Kind::TypeMetadataAccessFunction => self.prefixed("type metadata accessor for ", node),
+            Kind::FullTypeMetadata => self.prefixed("full type metadata for ", node),
+            Kind::Metaclass => self.prefixed("metaclass for ", node),
+            Kind::NominalTypeDescriptor => self.prefixed("nominal type descriptor for ", node),
+            Kind::ProtocolDescriptor => self.prefixed("protocol descriptor for ", node),
+            Kind::ProtocolConformanceDescriptor => {
+                self.prefixed("protocol conformance descriptor for ", node)
+            }
+            Kind::ProtocolSelfConformanceDescriptor => {
+                self.prefixed("protocol self-conformance descriptor for ", node)
+            }
+            Kind::TypeMetadataCompletionFunction => {
+                self.prefixed("type metadata completion function for ", node)
+            }
+            Kind::TypeMetadataInstantiationCache => {
+                self.prefixed("type metadata instantiation cache for ", node)
+            }
+            Kind::TypeMetadataInstantiationFunction => {
+                self.prefixed("type metadata instantiation function for ", node)
+            }
+            Kind::TypeMetadataSingletonInitializationCache => {
+                self.prefixed("type metadata singleton initialization cache for ", node)
+            }
+            Kind::TypeMetadataLazyCache => {
+                self.prefixed("lazy cache variable for type metadata for ", node)
+            }
+            Kind::TypeMetadataDemanglingCache => {
+                self.prefixed("demangling cache variable for type metadata for ", node)
+            }
+            Kind::GenericTypeMetadataPattern => {
+                self.prefixed("generic type metadata pattern for ", node)
+            }
+            Kind::ClassMetadataBaseOffset => self.prefixed("class metadata base offset for ", node),
+            Kind::MethodLookupFunction => self.prefixed("method lookup function for ", node),
+            Kind::ObjCMetadataUpdateFunction => {
+                self.prefixed("ObjC metadata update function for ", node)
+            }
+            Kind::ObjCResilientClassStub => self.prefixed("ObjC resilient class stub for ", node),
+            Kind::FullObjCResilientClassStub => {
+                self.prefixed("full ObjC resilient class stub for ", node)
+            }
+            Kind::PropertyDescriptor => self.prefixed("property descriptor for ", node),
+            Kind::ReflectionMetadataFieldDescriptor => {
+                self.prefixed("reflection metadata field descriptor ", node)
+            }
+            Kind::ReflectionMetadataBuiltinDescriptor => {
+                self.prefixed("reflection metadata builtin descriptor ", node)
+            }
+            Kind::ReflectionMetadataAssocTypeDescriptor => {
+                self.prefixed("reflection metadata associated type descriptor ", node)
+            }
+            Kind::ReflectionMetadataSuperclassDescriptor => {
+                self.prefixed("reflection metadata superclass descriptor ", node)
+            }
+
+            Kind::ProtocolConformance => {
+                self.print_child(node, 0);
+                self.out.push_str(" : ");
+                self.print_child(node, 1);
+                self.out.push_str(" in ");
+                self.print_child(node, 2);
+            }
+            Kind::ValueWitnessTable => self.prefixed("value witness table for ", node),
+            Kind::ValueWitness => {
+                let kind = node.child(0).and_then(Node::index);
+                if let Some(kind) = kind.and_then(ValueWitnessKind::from_index) {
+                    self.out.push_str(kind.name());
+                }
+                self.out.push_str(" value witness for ");
+                self.print_child(node, 1);
+            }
+            Kind::ProtocolWitnessTable => self.prefixed("protocol witness table for ", node),
+            Kind::ProtocolWitnessTableAccessor => {
+                self.prefixed("protocol witness table accessor for ", node)
+            }
+            Kind::ProtocolWitnessTablePattern => {
+                self.prefixed("protocol witness table pattern for ", node)
+            }
+            Kind::GenericProtocolWitnessTable => {
+                self.prefixed("generic protocol witness table for ", node)
+            }
+            Kind::GenericProtocolWitnessTableInstantiationFunction => self.prefixed(
+                "instantiation function for generic protocol witness table for ",
+                node,
+            ),
+            Kind::ResilientProtocolWitnessTable => {
+                self.prefixed("resilient protocol witness table for ", node)
+            }
+            Kind::LazyProtocolWitnessTableAccessor
+            | Kind::LazyProtocolWitnessTableCacheVariable => {
+                match node.kind() {
+                    Kind::LazyProtocolWitnessTableAccessor => self
+                        .out
+                        .push_str("lazy protocol witness table accessor for type "),
+                    _ => self
+                        .out
+                        .push_str("lazy protocol witness table cache variable for type "),
+                }
+                self.print_child(node, 0);
+                self.out.push_str(" and conformance ");
+                self.print_child(node, 1);
+            }
+            Kind::AssociatedTypeMetadataAccessor => {
+                self.out.push_str("associated type metadata accessor for ");
+                self.print_child(node, 1);
+                self.out.push_str(" in ");
+                self.print_child(node, 0);
+            }
+            Kind::FieldOffset => {
+                self.print_child(node, 0);
+                self.out.push_str("field offset for ");
+                self.print_child(node, 1);
+            }
+            Kind::Directness => match node.index() {
+                Some(0) => self.out.push_str("direct "),
+                _ => self.out.push_str("indirect "),
+            },
+            Kind::EnumCase => self.prefixed("enum case for ", node),
+
+            Kind::ProtocolWitness => {
+                self.out.push_str("protocol witness for ");
+                self.print_child(node, 1);
+                self.out.push_str(" in conformance ");
+                self.print_child(node, 0);
+            }
+            Kind::DispatchThunk => self.prefixed("dispatch thunk of ", node),
+            Kind::MethodDescriptor => self.prefixed("method descriptor for ", node),
+            Kind::CurryThunk => self.prefixed("curry thunk of ", node),
+            Kind::VTableThunk => {
+                self.out.push_str("vtable thunk for ");
+                self.print_child(node, 1);
+                self.out.push_str(" dispatching to ");
+                self.print_child(node, 0);
+            }
+            Kind::AsyncFunctionPointer => self.out.push_str("async function pointer to "),
+            Kind::MergedFunction => self.out.push_str("merged "),
+            Kind::ObjCAttribute => self.out.push_str("@objc "),
+            Kind::NonObjCAttribute => self.out.push_str("@nonobjc "),
+            Kind::DynamicAttribute => self.out.push_str("dynamic "),
+            Kind::DirectMethodReferenceAttribute => self.out.push_str("super "),
+            Kind::PartialApplyForwarder | Kind::PartialApplyObjCForwarder => {
+                match node.kind() {
+                    Kind::PartialApplyForwarder => self.out.push_str("partial apply forwarder"),
+                    _ => self.out.push_str("partial apply ObjC forwarder"),
+                }
+                if !node.children().is_empty() {
+                    self.prefixed(" for ", node);
+                }
+            }
+        }
+        None
+    }
+
+    /// Prints an entity as `<context>.<name><type>`, or returns its context
+    /// to be printed after it as `<name><type> in <context>`.
+    #[allow(clippy::too_many_arguments)]
+    fn print_entity<'n>(
+        &mut self,
+        mut entity: &'n Node,
+        as_prefix_context: bool,
+        mut type_printing: TypePrinting,
+        has_name: bool,
+        mut extra_name: &str,
+        mut extra_index: Option<u64>,
+        overwrite_name: &str,
+    ) -> Option<&'n Node> {
+        let mut generic_args = None;
+        if entity.kind() == Kind::BoundGenericFunction {
+            generic_args = entity.child(1);
+            entity = entity.child(0)?;
+        }
+
+        // Names with spaces, and local names, read better with their context
+        // after them.
+        let mut multi_word_name = extra_name.contains(' ');
+        if has_name && entity.child(1)?.kind() == Kind::LocalDeclName {
+            multi_word_name = true;
+        }
+        if as_prefix_context && (type_printing != TypePrinting::NoType || multi_word_name) {
+            return Some(entity);
+        }
+
+        let context = entity.child(0)?;
+        let mut postfix_context = match multi_word_name {
+            true => Some(context),
+            false => {
+                let start = self.out.len();
+                let postfix_context = self.print(context, true);
+                if self.out.len() != start {
+                    self.out.push('.');
+                }
+                postfix_context
+            }
+        };
+
+        if has_name || !overwrite_name.is_empty() {
+            if !extra_name.is_empty() && multi_word_name {
+                self.out.push_str(extra_name);
+                if let Some(index) = extra_index {
+                    let _ = write!(self.out, "{}", index);
+                }
+                self.out.push_str(" of ");
+                extra_name = "";
+                extra_index = None;
+            }
+            let start = self.out.len();
+            if !overwrite_name.is_empty() {
+                self.out.push_str(overwrite_name);
+            } else {
+                let name = entity.child(1)?;
+                if name.kind() != Kind::PrivateDeclName {
+                    self.print(name, false);
+                }
+                if let Some(private_name) = entity.child_of_kind(Kind::PrivateDeclName) {
+                    self.print(private_name, false);
+                }
+            }
+            if self.out.len() != start && !extra_name.is_empty() {
+                self.out.push('.');
+            }
+        }
+        if !extra_name.is_empty() {
+            self.out.push_str(extra_name);
+            if let Some(index) = extra_index {
+                let _ = write!(self.out, "{}", index);
+            }
+        }
+
+        if type_printing != TypePrinting::NoType {
+            let ty = entity.child_of_kind(Kind::Type)?.child(0)?;
+            if type_printing == TypePrinting::FunctionStyle {
+                let mut function = ty;
+                while function.kind() == Kind::DependentGenericType {
+                    function = function.child(1)?.child(0)?;
+                }
+                if !is_function_type(function) {
+                    type_printing = TypePrinting::WithColon;
+                }
+            }
+            if type_printing == TypePrinting::WithColon {
+                self.out.push_str(" : ");
+            } else if multi_word_name || need_space_before_type(ty) {
+                self.out.push(' ');
+            }
+            self.print_entity_type(entity, ty, generic_args);
+        }
+
+        if !as_prefix_context {
+            if let Some(context) = postfix_context.take() {
+                match entity.kind() {
+                    Kind::DefaultArgumentInitializer
+                    | Kind::Initializer
+                    | Kind::PropertyWrapperBackingInitializer => self.out.push_str(" of "),
+                    _ => self.out.push_str(" in "),
+                }
+                self.print(context, false);
+            }
+        }
+        postfix_context
+    }
+
+    fn print_entity_type(&mut self, entity: &Node, mut ty: &Node, generic_args: Option<&Node>) {
+        let labels = entity.child_of_kind(Kind::LabelList);
+        if labels.is_none() && generic_args.is_none() {
+            self.print(ty, false);
+            return;
+        }
+
+        if let Some(args) = generic_args {
+            self.out.push('<');
+            self.print_children(args, ", ");
+            self.out.push('>');
+        }
+        if ty.kind() == Kind::DependentGenericType {
+            if generic_args.is_none() {
+                self.print_child(ty, 0);
+            }
+            if let Some(dependent) = ty.child(1) {
+                if need_space_before_type(dependent) {
+                    self.out.push(' ');
+                }
+                match dependent.child(0) {
+                    Some(inner) => ty = inner,
+                    None => return,
+                }
+            }
+        }
+        match is_function_type(ty) {
+            true => self.print_function_type(labels, ty),
+            false => {
+                self.print(ty, false);
+            }
+        }
+    }
+
+    fn print_abstract_storage<'n>(
+        &mut self,
+        accessor: &'n Node,
+        as_prefix_context: bool,
+        extra_name: &str,
+    ) -> Option<&'n Node> {
+        use TypePrinting::*;
+
+        let storage = accessor.child(0)?;
+        match storage.kind() {
+            Kind::Variable => self.print_entity(
+                storage,
+                as_prefix_context,
+                WithColon,
+                true,
+                extra_name,
+                None,
+                "",
+            ),
+            Kind::Subscript => self.print_entity(
+                storage,
+                as_prefix_context,
+                WithColon,
+                false,
+                extra_name,
+                None,
+                "subscript",
+            ),
+            _ => None,
+        }
+    }
+
+    fn print_function_type(&mut self, labels: Option<&Node>, function: &Node) {
+        let children = function.children();
+        if children.len() < 2 {
+            return;
+        }
+        match function.kind() {
+            Kind::AutoClosureType | Kind::EscapingAutoClosureType => {
+                self.out.push_str("@autoclosure ")
+            }
+            Kind::ThinFunctionType => self.out.push_str("@convention(thin) "),
+            Kind::CFunctionPointer => self.out.push_str("@convention(c) "),
+            Kind::EscapingObjCBlock => self.out.push_str("@escaping @convention(block) "),
+            Kind::ObjCBlock => self.out.push_str("@convention(block) "),
+            _ => {}
+        }
+
+        let mut attrs = &children[..children.len() - 2];
+        let mut take = |kind| match attrs.first() {
+            Some(attr) if attr.kind() == kind => {
+                attrs = &attrs[1..];
+                Some(attr)
+            }
+            _ => None,
+        };
+        let global_actor = take(Kind::GlobalActorFunctionType);
+        let throws = take(Kind::ThrowsAnnotation);
+        let sendable = take(Kind::ConcurrentFunctionType);
+        let is_async = take(Kind::AsyncAnnotation).is_some();
+
+        if let Some(global_actor) = global_actor {
+            self.print(global_actor, false);
+        }
+        if let Some(sendable) = sendable {
+            self.print(sendable, false);
+        }
+        self.print_function_parameters(labels, &children[children.len() - 2]);
+        if is_async {
+            self.out.push_str(" async");
+        }
+        if let Some(throws) = throws {
+            self.print(throws, false);
+        }
+        self.print(&children[children.len() - 1], false);
+    }
+
+    fn print_function_parameters(&mut self, labels: Option<&Node>, params: &Node) {
+        if params.kind() != Kind::ArgumentTuple {
+            return;
+        }
+        let params = match params.child(0).and_then(|ty| ty.child(0)) {
+            Some(params) => params,
+            None => return,
+        };
+        if params.kind() != Kind::Tuple {
+            // A single unlabeled parameter.
+            self.out.push('(');
+            self.print(params, false);
+            self.out.push(')');
+            return;
+        }
+
+        let labels = labels.filter(|labels| !labels.children().is_empty());
+        self.out.push('(');
+        for (i, param) in params.children().iter().enumerate() {
+            if i != 0 {
+                self.out.push_str(", ");
+            }
+            if let Some(labels) = labels {
+                match labels.child(i) {
+                    Some(label) if label.kind() == Kind::Identifier => self.text(label),
+                    _ => self.out.push('_'),
+                }
+                self.out.push_str(": ");
+            }
+            self.print(param, false);
+        }
+        self.out.push(')');
+    }
+
+    fn print_generic_signature(&mut self, signature: &Node) {
+        self.out.push('<');
+        let children = signature.children();
+        let counts = children
+            .iter()
+            .take_while(|child| child.kind() == Kind::DependentGenericParamCount)
+            .count();
+        for (depth, count) in children[..counts].iter().enumerate() {
+            if depth != 0 {
+                self.out.push_str("><");
+            }
+            let count = count.index().unwrap_or_default();
+            for index in 0..count {
+                if index != 0 {
+                    self.out.push_str(", ");
+                }
+                // Malformed symbols may have huge counts.
+                if index >= 128 {
+                    self.out.push_str("...");
+                    break;
+                }
+                self.out.push_str(&generic_param_name(depth as u64, index));
+            }
+        }
+        if counts != children.len() {
+            self.out.push_str(" where ");
+            for (i, requirement) in children[counts..].iter().enumerate() {
+                if i != 0 {
+                    self.out.push_str(", ");
+                }
+                self.print(requirement, false);
+            }
+        }
+        self.out.push('>');
+    }
+
+    fn print_bound_generic(&mut self, node: &Node) {
+        let (ty, args) = match node.children() {
+            [ty, args] => (ty, args),
+            _ => return,
+        };
+        if node.kind() == Kind::BoundGenericProtocol {
+            // Prints the conforming type "as" the protocol.
+            self.print_children(args, "");
+            self.out.push_str(" as ");
+            self.print(ty, false);
+            return;
+        }
+
+        match find_sugar(node) {
+            Some(Sugar::Optional) => {
+                let wrapped = &args.children()[0];
+                let needs_parens = !is_simple_type(wrapped);
+                if needs_parens {
+                    self.out.push('(');
+                }
+                self.print(wrapped, false);
+                if needs_parens {
+                    self.out.push(')');
+                }
+                self.out.push('?');
+            }
+            Some(Sugar::Array) => {
+                self.out.push('[');
+                self.print(&args.children()[0], false);
+                self.out.push(']');
+            }
+            Some(Sugar::Dictionary) => {
+                self.out.push('[');
+                self.print(&args.children()[0], false);
+                self.out.push_str(" : ");
+                self.print(&args.children()[1], false);
+                self.out.push(']');
+            }
+            None => {
+                self.print(ty, false);
+                self.out.push('<');
+                self.print_children(args, ", ");
+                self.out.push('>');
+            }
+        }
+    }
+
+    fn print_with_parens(&mut self, ty: &Node) {
+        let needs_parens = !is_simple_type(ty);
+        if needs_parens {
+            self.out.push('(');
+        }
+        self.print(ty, false);
+        if needs_parens {
+            self.out.push(')');
+        }
+    }
+}
+
+/// Types printed with syntactic sugar.
+enum Sugar {
+    Optional,
+    Array,
+    Dictionary,
+}
+
+fn find_sugar(node: &Node) -> Option<Sugar> {
+    let (ty, args) = match node.children() {
+        [ty, args] => (ty, args),
+        _ => return None,
+    };
+    let unbound = ty.child(0)?;
+    if unbound.child(0)?.text() != Some("Swift") {
+        return None;
+    }
+    let name = unbound.child(1)?.text()?;
+    match (node.kind(), name, args.children().len()) {
+        (Kind::BoundGenericEnum, "Optional", 1) => Some(Sugar::Optional),
+        (Kind::BoundGenericStructure, "Array", 1) => Some(Sugar::Array),
+        (Kind::BoundGenericStructure, "Dictionary", 2) => Some(Sugar::Dictionary),
+        _ => None,
+    }
+}
+
+fn is_function_type(node: &Node) -> bool {
+    matches!(
+        node.kind(),
+        Kind::FunctionType
+            | Kind::NoEscapeFunctionType
+            | Kind::UncurriedFunctionType
+            | Kind::CFunctionPointer
+            | Kind::ThinFunctionType
+    )
+}
+
+fn is_class_type(node: Option<&Node>) -> bool {
+    node.is_some_and(|node| node.kind() == Kind::Class)
+}
+
+fn is_existential_type(node: &Node) -> bool {
+    matches!(
+        node.kind(),
+        Kind::ExistentialMetatype
+            | Kind::ProtocolList
+            | Kind::ProtocolListWithClass
+            | Kind::ProtocolListWithAnyObject
+    )
+}
+
+fn need_space_before_type(node: &Node) -> bool {
+    match node.kind() {
+        Kind::Type => node.child(0).is_none_or(need_space_before_type),
+        Kind::FunctionType
+        | Kind::NoEscapeFunctionType
+        | Kind::UncurriedFunctionType
+        | Kind::DependentGenericType => false,
+        _ => true,
+    }
+}
+
+/// Returns `true` if `node` doesn't need parentheses before `?` or `.Type`.
+fn is_simple_type(node: &Node) -> bool {
+    match node.kind() {
+        Kind::Type => node.child(0).is_some_and(is_simple_type),
+        Kind::AssociatedTypeRef
+        | Kind::BoundGenericClass
+        | Kind::BoundGenericEnum
+        | Kind::BoundGenericStructure
+        | Kind::BoundGenericProtocol
+        | Kind::BoundGenericOtherNominalType
+        | Kind::BoundGenericTypeAlias
+        | Kind::BoundGenericFunction
+        | Kind::BuiltinTypeName
+        | Kind::Class
+        | Kind::DependentGenericType
+        | Kind::DependentMemberType
+        | Kind::DependentGenericParamType
+        | Kind::DynamicSelf
+        | Kind::Enum
+        | Kind::ExistentialMetatype
+        | Kind::Metatype
+        | Kind::MetatypeRepresentation
+        | Kind::Module
+        | Kind::Tuple
+        | Kind::Protocol
+        | Kind::ReturnType
+        | Kind::Structure
+        | Kind::OtherNominalType
+        | Kind::TupleElementName
+        | Kind::TypeAlias
+        | Kind::TypeList
+        | Kind::LabelList => true,
+        Kind::ProtocolList => node.child(0).is_none_or(|list| list.children().len() <= 1),
+        Kind::ProtocolListWithAnyObject => node
+            .child(0)
+            .and_then(|protocols| protocols.child(0))
+            .is_none_or(|list| list.children().is_empty()),
+        _ => false,
+    }
+}
+
+/// Returns the name of a generic parameter, such as `A`, `B` or `A1`.
+fn generic_param_name(depth: u64, mut index: u64) -> String {
+    let mut name = String::new();
+    loop {
+        name.push((b'A' + (index % 26) as u8) as char);
+        index /= 26;
+        if index == 0 {
+            break;
+        }
+    }
+    if depth != 0 {
+        name.push_str(&depth.to_string());
+    }
+    name
+}
+
+/// Writes `text` in quotes, escaping like the Swift runtime.
+fn quote(out: &mut String, text: &str) {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\0' => out.push_str("\\0"),
+            c if (c as u32) < 0x20 || c as u32 == 0x7F => {
+                let _ = write!(out, "\\x{:02X}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
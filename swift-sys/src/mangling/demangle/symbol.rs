@@ -0,0 +1,167 @@
+use super::{Node, NodeKind as Kind};
+
+/// What a demangled symbol refers to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SymbolKind {
+    /// A function, method, initializer, deinitializer or closure.
+    Function,
+
+    /// An accessor of a property or subscript, such as a getter.
+    Accessor,
+
+    /// A global or static variable.
+    Variable,
+
+    /// A type, such as a type mangled for reflection.
+    Type,
+
+    /// `N`: direct type metadata.
+    TypeMetadata,
+
+    /// `Ma`: a type metadata access function.
+    TypeMetadataAccessor,
+
+    /// Other type metadata records, such as a metadata cache or pattern.
+    OtherMetadata,
+
+    /// `Mn`: a nominal type descriptor.
+    NominalTypeDescriptor,
+
+    /// `Mp`: a protocol descriptor.
+    ProtocolDescriptor,
+
+    /// `Mc`: a protocol conformance descriptor.
+    ProtocolConformanceDescriptor,
+
+    /// `WP`: a protocol witness table, or an accessor or pattern of one.
+    ProtocolWitnessTable,
+
+    /// `WV`: a value witness table.
+    ValueWitnessTable,
+
+    /// `w`: a function of a value witness table.
+    ValueWitness,
+
+    /// `Tq`: a method descriptor.
+    MethodDescriptor,
+
+    /// `Wv`: a field offset.
+    FieldOffset,
+
+    /// A thunk, such as a dispatch thunk, protocol witness or partial apply
+    /// forwarder.
+    Thunk,
+
+    /// A symbol of any other kind, such as reflection metadata.
+    Other,
+}
+
+impl SymbolKind {
+    /// Returns the kind of the symbol demangled as `global`.
+    pub fn of(global: &Node) -> Self {
+        let children = global.children();
+        if children.iter().any(|child| {
+            matches!(
+                child.kind(),
+                Kind::PartialApplyForwarder | Kind::PartialApplyObjCForwarder
+            )
+        }) {
+            return Self::Thunk;
+        }
+
+        // The entity follows function attributes, and precedes a suffix.
+        let mut entity = match children
+            .iter()
+            .rev()
+            .find(|child| child.kind() != Kind::Suffix)
+        {
+            Some(entity) => entity,
+            None => return Self::Other,
+        };
+        while entity.kind() == Kind::Static {
+            entity = match entity.child(0) {
+                Some(child) => child,
+                None => return Self::Other,
+            };
+        }
+        Self::of_kind(entity.kind())
+    }
+
+    fn of_kind(kind: Kind) -> Self {
+        match kind {
+            Kind::Function
+            | Kind::BoundGenericFunction
+            | Kind::Allocator
+            | Kind::Constructor
+            | Kind::Destructor
+            | Kind::Deallocator
+            | Kind::IVarInitializer
+            | Kind::IVarDestroyer
+            | Kind::Initializer
+            | Kind::DefaultArgumentInitializer
+            | Kind::PropertyWrapperBackingInitializer
+            | Kind::ExplicitClosure
+            | Kind::ImplicitClosure => Self::Function,
+
+            Kind::Getter
+            | Kind::Setter
+            | Kind::GlobalGetter
+            | Kind::WillSet
+            | Kind::DidSet
+            | Kind::ReadAccessor
+            | Kind::ModifyAccessor
+            | Kind::MaterializeForSet
+            | Kind::InitAccessor
+            | Kind::UnsafeAddressor
+            | Kind::UnsafeMutableAddressor
+            | Kind::OwningAddressor
+            | Kind::OwningMutableAddressor
+            | Kind::NativeOwningAddressor
+            | Kind::NativeOwningMutableAddressor
+            | Kind::NativePinningAddressor
+            | Kind::NativePinningMutableAddressor => Self::Accessor,
+
+            Kind::Variable => Self::Variable,
+
+            Kind::TypeMangling => Self::Type,
+            Kind::TypeMetadata => Self::TypeMetadata,
+            Kind::TypeMetadataAccessFunction => Self::TypeMetadataAccessor,
+            Kind::FullTypeMetadata
+            | Kind::Metaclass
+            | Kind::TypeMetadataCompletionFunction
+            | Kind::TypeMetadataInstantiationCache
+            | Kind::TypeMetadataInstantiationFunction
+            | Kind::TypeMetadataSingletonInitializationCache
+            | Kind::TypeMetadataLazyCache
+            | Kind::TypeMetadataDemanglingCache
+            | Kind::GenericTypeMetadataPattern
+            | Kind::ClassMetadataBaseOffset
+            | Kind::ObjCMetadataUpdateFunction
+            | Kind::ObjCResilientClassStub
+            | Kind::FullObjCResilientClassStub => Self::OtherMetadata,
+
+            Kind::NominalTypeDescriptor => Self::NominalTypeDescriptor,
+            Kind::ProtocolDescriptor => Self::ProtocolDescriptor,
+            Kind::ProtocolConformanceDescriptor => Self::ProtocolConformanceDescriptor,
+            Kind::ProtocolWitnessTable
+            | Kind::ProtocolWitnessTableAccessor
+            | Kind::ProtocolWitnessTablePattern
+            | Kind::GenericProtocolWitnessTable
+            | Kind::GenericProtocolWitnessTableInstantiationFunction
+            | Kind::ResilientProtocolWitnessTable
+            | Kind::LazyProtocolWitnessTableAccessor
+            | Kind::LazyProtocolWitnessTableCacheVariable => Self::ProtocolWitnessTable,
+            Kind::ValueWitnessTable => Self::ValueWitnessTable,
+            Kind::ValueWitness => Self::ValueWitness,
+            Kind::MethodDescriptor => Self::MethodDescriptor,
+            Kind::FieldOffset => Self::FieldOffset,
+
+            Kind::ProtocolWitness | Kind::DispatchThunk | Kind::CurryThunk | Kind::VTableThunk => {
+                Self::Thunk
+            }
+
+            _ => Self::Other,
+        }
+    }
+}
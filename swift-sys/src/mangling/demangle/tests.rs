@@ -0,0 +1,286 @@
+use super::*;
+use crate::mangling::{
+    self, Context, Entity, FunctionType, Nominal, NominalKind, Param, Type, TypeSymbol,
+};
+
+#[track_caller]
+fn assert_demangles(symbol: &str, expected: &str) {
+    match demangle_symbol(symbol) {
+        Some(node) => assert_eq!(node.to_string(), expected, "{:?}", node),
+        None => panic!("failed to demangle {:?}", symbol),
+    }
+}
+
+#[test]
+fn functions() {
+    assert_demangles(
+        "$ss6HasherV8_combineyySuF",
+        "Swift.Hasher._combine(Swift.UInt) -> ()",
+    );
+    assert_demangles(
+        "$ss6HasherV7combine5bytesySW_tF",
+        "Swift.Hasher.combine(bytes: Swift.UnsafeRawBufferPointer) -> ()",
+    );
+    assert_demangles(
+        "$s6Shapes9ShapeKindO05otherC0yyF",
+        "Shapes.ShapeKind.otherKind() -> ()",
+    );
+    assert_demangles(
+        "$ss5print_9separator10terminatoryypd_S2StF",
+        "Swift.print(_: Any..., separator: Swift.String, terminator: Swift.String) -> ()",
+    );
+}
+
+#[test]
+fn constructors() {
+    assert_demangles(
+        "$sSS9repeating5countS2S_SitcfC",
+        "Swift.String.init(repeating: Swift.String, count: Swift.Int) -> Swift.String",
+    );
+    assert_demangles(
+        "$sSS7cStringSSSPys4Int8VG_tcfC",
+        "Swift.String.init(cString: Swift.UnsafePointer<Swift.Int8>) -> Swift.String",
+    );
+    assert_demangles(
+        "$s5MyLib5ModelCACycfC",
+        "MyLib.Model.__allocating_init() -> MyLib.Model",
+    );
+}
+
+#[test]
+fn accessors() {
+    assert_demangles("$sSS5countSivg", "Swift.String.count.getter : Swift.Int");
+    assert_demangles(
+        "$sSayxSicig",
+        "Swift.Array.subscript.getter : (Swift.Int) -> A",
+    );
+    assert_demangles(
+        "$sSayxSiciM",
+        "Swift.Array.subscript.modify : (Swift.Int) -> A",
+    );
+}
+
+#[test]
+fn generics() {
+    assert_demangles(
+        "$ss4swapyyxz_xztlF",
+        "Swift.swap<A>(inout A, inout A) -> ()",
+    );
+    assert_demangles(
+        "$ss3maxyxx_xtSLRzlF",
+        "Swift.max<A where A: Swift.Comparable>(A, A) -> A",
+    );
+    assert_demangles(
+        "$ss3zipys12Zip2SequenceVyxq_Gx_q_tSTRzSTR_r0_lF",
+        "Swift.zip<A, B where A: Swift.Sequence, B: Swift.Sequence>(A, B) \
+         -> Swift.Zip2Sequence<A, B>",
+    );
+    assert_demangles("$sqd0_0_mN", "type metadata for B2.Type");
+}
+
+#[test]
+fn extensions() {
+    assert_demangles(
+        "$sSasSQRzlE2eeoiySbSayxG_ABtFZ",
+        "static (extension in Swift):Swift.Array<A where A: Swift.Equatable>.== infix\
+         ([A], [A]) -> Swift.Bool",
+    );
+    assert_demangles(
+        "$sSQsE2neoiySbx_xtFZ",
+        "static (extension in Swift):Swift.Equatable.!= infix(A, A) -> Swift.Bool",
+    );
+}
+
+#[test]
+fn symbols() {
+    assert_demangles("$sSSN", "type metadata for Swift.String");
+    assert_demangles("$sytN", "type metadata for ()");
+    assert_demangles("$sSaySiGN", "type metadata for [Swift.Int]");
+    assert_demangles(
+        "$s6Shapes5PointVMa",
+        "type metadata accessor for Shapes.Point",
+    );
+    assert_demangles("$sSSMn", "nominal type descriptor for Swift.String");
+    assert_demangles("$sSQMp", "protocol descriptor for Swift.Equatable");
+    assert_demangles(
+        "$s6Shapes8DrawableP4drawyyFTj",
+        "dispatch thunk of Shapes.Drawable.draw() -> ()",
+    );
+    assert_demangles(
+        "$s6Shapes5ShapeC4areaSdyFTq",
+        "method descriptor for Shapes.Shape.area() -> Swift.Double",
+    );
+    assert_demangles(
+        "$s6Shapes5ShapeC4nameSSvgTq",
+        "method descriptor for Shapes.Shape.name.getter : Swift.String",
+    );
+    assert_demangles("$sSiWV", "value witness table for Swift.Int");
+    assert_demangles(
+        "$sSS5countSivg.cold",
+        "Swift.String.count.getter : Swift.Int with unmangled suffix \".cold\"",
+    );
+}
+
+#[test]
+fn types() {
+    let demangled = |mangled| demangle_type(mangled).map(|node| node.to_string());
+    assert_eq!(demangled("Si").as_deref(), Some("Swift.Int"));
+    assert_eq!(demangled("SiSg").as_deref(), Some("Swift.Int?"));
+    assert_eq!(
+        demangled("SDySSSiG").as_deref(),
+        Some("[Swift.String : Swift.Int]")
+    );
+    assert_eq!(
+        demangled("Si_SSt").as_deref(),
+        Some("(Swift.Int, Swift.String)")
+    );
+    assert_eq!(demangled("ypXp").as_deref(), Some("Any.Type"));
+    assert_eq!(demangled("SiSi"), None);
+
+    let node = demangle_type("SaySiG").unwrap();
+    assert_eq!(node.kind(), NodeKind::Type);
+    let array = node.child(0).unwrap();
+    assert_eq!(array.kind(), NodeKind::BoundGenericStructure);
+    assert_eq!(array.child(1).unwrap().kind(), NodeKind::TypeList);
+}
+
+#[test]
+fn punycode_identifiers() {
+    let name = |name: &str| {
+        mangling::type_symbol(
+            &Type::Nominal(Nominal::new("Café", name, NominalKind::Struct)),
+            TypeSymbol::Metadata,
+        )
+    };
+    for identifier in &["Proprietà", "日本", "a-b"] {
+        assert_demangles(
+            &name(identifier),
+            &format!("type metadata for Café.{}", identifier),
+        );
+    }
+}
+
+#[test]
+fn round_trips() {
+    let string = Nominal::new("Swift", "String", NominalKind::Struct);
+    let symbol = mangling::entity(&Entity::Constructor {
+        context: Context::Nominal(Box::new(string.clone())),
+        signature: FunctionType::new(
+            vec![Param::new(Type::stdlib("Int")).label("count")],
+            Type::Nominal(string).optional(),
+        ),
+        allocating: true,
+    });
+    assert_demangles(
+        &symbol,
+        "Swift.String.init(count: Swift.Int) -> Swift.String?",
+    );
+}
+
+#[test]
+fn invalid() {
+    for symbol in &["", "$s", "$sSS6count", "_TSSN", "$sSiS", "$s00"] {
+        assert!(demangle_symbol(symbol).is_none(), "{:?}", symbol);
+    }
+}
+
+#[test]
+fn truncated() {
+    // Truncated symbols may fail but must not panic.
+    let symbols = [
+        "$sSasSQRzlE2eeoiySbSayxG_ABtFZ",
+        "$ss3zipys12Zip2SequenceVyxq_Gx_q_tSTRzSTR_r0_lF",
+        "$ss5print_9separator10terminatoryypd_S2StF",
+        "$s6Shapes9ShapeKindO05otherC0yyF",
+    ];
+    for symbol in &symbols {
+        for end in 0..symbol.len() {
+            let _ = demangle_symbol(&symbol[..end]);
+        }
+    }
+}
+
+#[test]
+fn symbolic_references() {
+    let point = || {
+        Node::with_children(
+            NodeKind::Type,
+            vec![Node::with_children(
+                NodeKind::Structure,
+                vec![
+                    Node::with_text(NodeKind::Module, "Shapes"),
+                    Node::with_text(NodeKind::Identifier, "Point"),
+                ],
+            )],
+        )
+    };
+    let mut controls = Vec::new();
+    let mut resolver = |reference: SymbolicReference| {
+        controls.push(reference.control);
+        match reference.control {
+            0x01 => Some(point()),
+            _ => None,
+        }
+    };
+
+    // `Point?` and `(Point, Point)` using a substitution.
+    let optional = demangle_type_with(b"\x01\x10\0\0\0Sg", &mut resolver);
+    assert_eq!(optional.unwrap().to_string(), "Shapes.Point?");
+    let tuple = demangle_type_with(b"\x01\x10\0\0\0_AAt", &mut resolver);
+    assert_eq!(tuple.unwrap().to_string(), "(Shapes.Point, Shapes.Point)");
+
+    assert!(demangle_type_with(b"\x02\x10\0\0\0", &mut resolver).is_none());
+    assert!(demangle_type_with(b"\x01\x10\0", &mut resolver).is_none());
+    assert_eq!(controls, [0x01, 0x01, 0x02]);
+
+    // Without a resolver, references fail to demangle.
+    assert!(demangle_type("\u{1}\u{10}\0\0\0").is_none());
+}
+
+#[test]
+fn prefixes() {
+    for symbol in &["$sSiN", "_$sSiN", "$SSiN", "_$SSiN", "_T0SiN"] {
+        assert_demangles(symbol, "type metadata for Swift.Int");
+    }
+    assert_eq!(strip_prefix("_$sSiN"), Some("SiN"));
+    assert!(!is_swift_symbol(
+        "_ZN4core3ptr13drop_in_place17h0123456789abcdefE"
+    ));
+    assert!(demangle_symbol("SiN").is_none());
+}
+
+#[test]
+fn old_function_types() {
+    // Swift 4.0 mangles labels in the parameter tuple.
+    let node = demangle_symbol("_T04main3fooS2i1x_tF").unwrap();
+    assert_eq!(node.to_string(), "main.foo(x: Swift.Int) -> Swift.Int");
+    let function = node.child(0).unwrap();
+    let labels = function.child_of_kind(NodeKind::LabelList).unwrap();
+    assert_eq!(labels.child(0).unwrap().text(), Some("x"));
+}
+
+#[test]
+fn symbol_kinds() {
+    let kinds = [
+        ("$ss6HasherV8_combineyySuF", SymbolKind::Function),
+        ("$sSS9repeating5countS2S_SitcfC", SymbolKind::Function),
+        ("$sSQsE2neoiySbx_xtFZ", SymbolKind::Function),
+        ("_$sSS5countSivg", SymbolKind::Accessor),
+        ("$sSayxSiciM", SymbolKind::Accessor),
+        ("$sSSN", SymbolKind::TypeMetadata),
+        ("$s6Shapes5PointVMa", SymbolKind::TypeMetadataAccessor),
+        ("$sSSMn", SymbolKind::NominalTypeDescriptor),
+        ("$sSQMp", SymbolKind::ProtocolDescriptor),
+        ("$sSiSQsMc", SymbolKind::ProtocolConformanceDescriptor),
+        ("$sSiSQsWP", SymbolKind::ProtocolWitnessTable),
+        ("$sSiWV", SymbolKind::ValueWitnessTable),
+        ("$sSiwxx", SymbolKind::ValueWitness),
+        ("$s6Shapes5ShapeC4areaSdyFTq", SymbolKind::MethodDescriptor),
+        ("$s6Shapes8DrawableP4drawyyFTj", SymbolKind::Thunk),
+        ("$sSS5countSivg.cold", SymbolKind::Accessor),
+    ];
+    for (symbol, kind) in &kinds {
+        assert_eq!(classify_symbol(symbol), Some(*kind), "{}", symbol);
+    }
+    assert_eq!(classify_symbol("main"), None);
+}
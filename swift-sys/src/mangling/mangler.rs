@@ -0,0 +1,689 @@
+use super::{
+    punycode, Context, DeclName, Entity, Fixity, FunctionType, GenericParam, GenericSignature,
+    Nominal, NominalKind, Ownership, Param, Requirement, Type, PREFIX,
+};
+use std::collections::HashMap;
+
+/// The maximum number of words that may be substituted.
+const MAX_WORDS: usize = 26;
+
+/// The maximum repeat count of a merged substitution, such as `A5B`.
+const MAX_REPEAT_COUNT: usize = 2048;
+
+/// Entities that may be referenced by a substitution.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Substitutable {
+    Identifier(String),
+    Type(Type),
+}
+
+/// A word that may be substituted within identifiers.
+#[derive(Clone, Copy, Debug)]
+struct Word {
+    /// Position of the word in the buffer, or in the current identifier until
+    /// it has been written.
+    start: usize,
+    len: usize,
+}
+
+/// State for merging adjacent substitutions, such as `AB` + `AC` into `AbC`.
+#[derive(Default)]
+struct SubstitutionMerging {
+    last_position: usize,
+    last_size: usize,
+    last_repeat: usize,
+    last_is_standard: bool,
+}
+
+pub(crate) struct Mangler {
+    buffer: String,
+    substitutions: HashMap<Substitutable, usize>,
+    words: Vec<Word>,
+    merging: SubstitutionMerging,
+}
+
+impl Mangler {
+    pub fn new() -> Self {
+        Self {
+            buffer: PREFIX.to_owned(),
+            substitutions: HashMap::new(),
+            words: Vec::new(),
+            merging: SubstitutionMerging::default(),
+        }
+    }
+
+    pub fn finish(self) -> String {
+        self.buffer
+    }
+
+    pub fn append_operator(&mut self, op: &str) {
+        self.buffer.push_str(op);
+    }
+
+    // Substitutions -----------------------------------------------------------
+
+    fn add_substitution(&mut self, entity: Substitutable) {
+        let index = self.substitutions.len();
+        self.substitutions.entry(entity).or_insert(index);
+    }
+
+    fn try_substitution(&mut self, entity: &Substitutable) -> bool {
+        match self.substitutions.get(entity) {
+            Some(&index) => {
+                self.append_substitution(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn append_substitution(&mut self, index: usize) {
+        if index >= 26 {
+            self.buffer.push('A');
+            self.append_index(index - 26);
+            return;
+        }
+        let subst = (b'A' + index as u8) as char;
+        if !self.try_merge_substitution(subst, false) {
+            self.buffer.push('A');
+            self.buffer.push(subst);
+            self.set_last_substitution(self.buffer.len() - 1, 1, false);
+        }
+    }
+
+    fn try_merge_substitution(&mut self, subst: char, is_standard: bool) -> bool {
+        let merging = &mut self.merging;
+        if merging.last_repeat == 0
+            || self.buffer.len() != merging.last_position + merging.last_size
+            || merging.last_is_standard != is_standard
+        {
+            return false;
+        }
+
+        let last = self.buffer.pop().unwrap();
+        if last != subst && !is_standard {
+            // 'AB' + 'AC' -> 'AbC'
+            self.buffer.push(last.to_ascii_lowercase());
+            merging.last_position = self.buffer.len();
+            merging.last_size = 1;
+            merging.last_repeat = 1;
+            self.buffer.push(subst);
+            return true;
+        }
+        if last == subst && merging.last_repeat < MAX_REPEAT_COUNT {
+            // 'AB' + 'AB' -> 'A2B'
+            merging.last_repeat += 1;
+            self.buffer.truncate(merging.last_position);
+            self.buffer.push_str(&merging.last_repeat.to_string());
+            self.buffer.push(subst);
+            merging.last_size = self.buffer.len() - merging.last_position;
+            return true;
+        }
+        self.buffer.push(last);
+        false
+    }
+
+    fn set_last_substitution(&mut self, position: usize, size: usize, is_standard: bool) {
+        self.merging = SubstitutionMerging {
+            last_position: position,
+            last_size: size,
+            last_repeat: 1,
+            last_is_standard: is_standard,
+        };
+    }
+
+    /// Appends `S<kind>` for types with a known standard substitution.
+    fn try_standard_substitution(&mut self, nominal: &Nominal) -> bool {
+        let subst = match standard_substitution(nominal) {
+            Some(subst) => subst,
+            None => return false,
+        };
+        if !self.try_merge_substitution(subst, true) {
+            self.buffer.push('S');
+            self.buffer.push(subst);
+            self.set_last_substitution(self.buffer.len() - 1, 1, true);
+        }
+        true
+    }
+
+    /// Appends `_` for 0 or `<n - 1>_` otherwise.
+    fn append_index(&mut self, index: usize) {
+        if index != 0 {
+            self.buffer.push_str(&(index - 1).to_string());
+        }
+        self.buffer.push('_');
+    }
+
+    fn append_list_separator(&mut self, is_first: &mut bool) {
+        if *is_first {
+            self.buffer.push('_');
+            *is_first = false;
+        }
+    }
+
+    // Identifiers -------------------------------------------------------------
+
+    pub fn append_identifier(&mut self, ident: &str) {
+        let key = Substitutable::Identifier(ident.to_owned());
+        if self.try_substitution(&key) {
+            return;
+        }
+        self.add_substitution(key);
+        self.mangle_identifier(ident);
+    }
+
+    fn mangle_identifier(&mut self, ident: &str) {
+        if punycode::needs_encoding(ident) {
+            let encoded = punycode::encode(ident);
+            self.buffer.push_str("00");
+            self.buffer.push_str(&encoded.len().to_string());
+            if encoded.starts_with(|c: char| c.is_ascii_digit() || c == '_') {
+                self.buffer.push('_');
+            }
+            self.buffer.push_str(&encoded);
+            return;
+        }
+
+        let bytes = ident.as_bytes();
+        let words_in_buffer = self.words.len();
+
+        // Pairs of (position in identifier, word index).
+        let mut replacements: Vec<(usize, Option<usize>)> = Vec::new();
+
+        let mut word_start = None;
+        for pos in 0..=bytes.len() {
+            let c = bytes.get(pos).copied().unwrap_or(0);
+            if let Some(start) = word_start {
+                if is_word_end(c, bytes[pos - 1]) {
+                    let word = &ident[start..pos];
+                    let lookup = |words: &[Word], text: &str| {
+                        words
+                            .iter()
+                            .position(|w| text.get(w.start..w.start + w.len) == Some(word))
+                    };
+                    let index =
+                        lookup(&self.words[..words_in_buffer], &self.buffer).or_else(|| {
+                            lookup(&self.words[words_in_buffer..], ident)
+                                .map(|i| i + words_in_buffer)
+                        });
+                    match index {
+                        Some(index) => replacements.push((start, Some(index))),
+                        None if word.len() >= 2 && self.words.len() < MAX_WORDS => {
+                            self.words.push(Word {
+                                start,
+                                len: word.len(),
+                            });
+                        }
+                        None => {}
+                    }
+                    word_start = None;
+                }
+            }
+            if word_start.is_none() && is_word_start(c) {
+                word_start = Some(pos);
+            }
+        }
+
+        if !replacements.is_empty() {
+            self.buffer.push('0');
+        }
+        replacements.push((bytes.len(), None));
+
+        let mut new_words = words_in_buffer;
+        let mut pos = 0;
+        let count = replacements.len();
+        for (i, &(repl_pos, index)) in replacements.iter().enumerate() {
+            if pos < repl_pos {
+                self.buffer.push_str(&(repl_pos - pos).to_string());
+                while pos < repl_pos {
+                    // New words now refer to their position in the buffer.
+                    if new_words < self.words.len() && self.words[new_words].start == pos {
+                        self.words[new_words].start = self.buffer.len();
+                        new_words += 1;
+                    }
+                    self.buffer.push(bytes[pos] as char);
+                    pos += 1;
+                }
+            }
+            if let Some(index) = index {
+                pos += self.words[index].len;
+                if i < count - 2 {
+                    self.buffer.push((b'a' + index as u8) as char);
+                } else {
+                    // The last word substitution is uppercase.
+                    self.buffer.push((b'A' + index as u8) as char);
+                    if pos == bytes.len() {
+                        self.buffer.push('0');
+                    }
+                }
+            }
+        }
+    }
+
+    fn append_decl_name(&mut self, name: &DeclName) {
+        match name {
+            DeclName::Identifier(ident) => self.append_identifier(ident),
+            DeclName::Operator(op, fixity) => {
+                let translated: String = op.chars().map(translate_operator_char).collect();
+                self.append_identifier(&translated);
+                self.append_operator(match fixity {
+                    Fixity::Infix => "oi",
+                    Fixity::Prefix => "op",
+                    Fixity::Postfix => "oP",
+                });
+            }
+        }
+    }
+
+    // Contexts and types ------------------------------------------------------
+
+    pub fn append_context(&mut self, context: &Context) {
+        match context {
+            Context::Module(module) => self.append_module(module),
+            Context::Nominal(nominal) => self.append_nominal(nominal),
+            Context::Extension {
+                extended,
+                module,
+                generic_signature,
+            } => {
+                self.append_nominal(extended);
+                self.append_module(module);
+                if let Some(signature) = generic_signature {
+                    self.append_generic_signature(signature);
+                }
+                self.append_operator("E");
+            }
+        }
+    }
+
+    fn append_module(&mut self, module: &str) {
+        match module {
+            "Swift" => self.append_operator("s"),
+            "__C" => self.append_operator("So"),
+            _ => self.append_identifier(module),
+        }
+    }
+
+    fn append_nominal(&mut self, nominal: &Nominal) {
+        if self.try_standard_substitution(nominal) {
+            return;
+        }
+        let key = Substitutable::Type(Type::Nominal(nominal.clone()));
+        if self.try_substitution(&key) {
+            return;
+        }
+        self.append_context(&nominal.context);
+        self.append_identifier(&nominal.name);
+        self.append_operator(match nominal.kind {
+            NominalKind::Struct => "V",
+            NominalKind::Enum => "O",
+            NominalKind::Class => "C",
+            NominalKind::Protocol => "P",
+        });
+        self.add_substitution(key);
+    }
+
+    /// Appends a protocol within a protocol list, which has no kind suffix.
+    pub fn append_protocol_name(&mut self, protocol: &Nominal) {
+        if self.try_standard_substitution(protocol) {
+            return;
+        }
+        self.append_context(&protocol.context);
+        self.append_identifier(&protocol.name);
+    }
+
+    pub fn append_type(&mut self, ty: &Type) {
+        match ty {
+            Type::Nominal(nominal) => self.append_nominal(nominal),
+            Type::BoundGeneric(nominal, args) => {
+                let key = Substitutable::Type(ty.clone());
+                if self.try_substitution(&key) {
+                    return;
+                }
+                if nominal.is_stdlib("Optional") && args.len() == 1 {
+                    self.append_type(&args[0]);
+                    self.append_operator("Sg");
+                } else {
+                    self.append_nominal(nominal);
+                    self.append_operator("y");
+                    for arg in args {
+                        self.append_type(arg);
+                    }
+                    self.append_operator("G");
+                }
+                self.add_substitution(key);
+            }
+            Type::Tuple(elements) => {
+                if elements.is_empty() {
+                    self.append_operator("y");
+                }
+                let mut is_first = true;
+                for element in elements {
+                    self.append_type(&element.ty);
+                    if let Some(label) = &element.label {
+                        self.append_identifier(label);
+                    }
+                    self.append_list_separator(&mut is_first);
+                }
+                self.append_operator("t");
+            }
+            Type::Function(function) => {
+                self.append_function_signature(function);
+                self.append_operator(if function.is_escaping { "c" } else { "XE" });
+            }
+            Type::Metatype(instance) => {
+                self.append_type(instance);
+                self.append_operator("m");
+            }
+            Type::Existential {
+                protocols,
+                class_bound,
+            } => {
+                let mut is_first = true;
+                for protocol in protocols {
+                    self.append_protocol_name(protocol);
+                    self.append_list_separator(&mut is_first);
+                }
+                if is_first {
+                    self.append_operator("y");
+                }
+                self.append_operator(if *class_bound { "Xl" } else { "p" });
+            }
+            Type::GenericParam(param) => {
+                if *param == GenericParam::new(0, 0) {
+                    self.append_operator("x");
+                } else {
+                    self.append_operator("q");
+                    self.append_generic_param_index(*param);
+                }
+            }
+        }
+    }
+
+    /// Appends `z` for the first parameter, `<index>` for others at depth 0,
+    /// and `d<depth><index>` otherwise.
+    fn append_generic_param_index(&mut self, param: GenericParam) {
+        match (param.depth, param.index) {
+            (0, 0) => self.append_operator("z"),
+            (0, index) => self.append_index(index - 1),
+            (depth, index) => {
+                self.append_operator("d");
+                self.append_index(depth - 1);
+                self.append_index(index);
+            }
+        }
+    }
+
+    fn append_generic_signature(&mut self, signature: &GenericSignature) {
+        for requirement in &signature.requirements {
+            let (param, op) = match requirement {
+                Requirement::Conformance(param, protocol) => {
+                    self.append_protocol_name(protocol);
+                    (param, "R")
+                }
+                Requirement::Superclass(param, ty) => {
+                    self.append_type(ty);
+                    (param, "Rb")
+                }
+                Requirement::SameType(param, ty) => {
+                    self.append_type(ty);
+                    (param, "Rs")
+                }
+            };
+            self.append_operator(op);
+            self.append_generic_param_index(*param);
+        }
+        // A single parameter is implied.
+        if signature.param_counts != [1] {
+            self.append_operator("r");
+            for &count in &signature.param_counts {
+                match count {
+                    0 => self.append_operator("z"),
+                    count => self.append_index(count - 1),
+                }
+            }
+        }
+        self.append_operator("l");
+    }
+
+    // Functions ---------------------------------------------------------------
+
+    /// Appends argument labels, or `y` if there are parameters but no labels.
+    fn append_labels(&mut self, params: &[Param]) {
+        if params.iter().any(|param| param.label.is_some()) {
+            for param in params {
+                match &param.label {
+                    Some(label) => self.append_identifier(label),
+                    None => self.append_operator("_"),
+                }
+            }
+        } else if !params.is_empty() {
+            self.append_operator("y");
+        }
+    }
+
+    fn append_function_signature(&mut self, function: &FunctionType) {
+        if function.result.is_void() {
+            self.append_operator("y");
+        } else {
+            self.append_type(&function.result);
+        }
+        self.append_function_input(&function.params);
+        if function.is_async {
+            self.append_operator("Ya");
+        }
+        if function.throws {
+            self.append_operator("K");
+        }
+    }
+
+    /// Appends the type of an initializer or subscript, which is generic if
+    /// it has a generic signature.
+    fn append_function_type(&mut self, function: &FunctionType) {
+        self.append_function_signature(function);
+        self.append_operator("c");
+        if let Some(generic_signature) = &function.generic_signature {
+            self.append_generic_signature(generic_signature);
+            self.append_operator("u");
+        }
+    }
+
+    fn append_function_input(&mut self, params: &[Param]) {
+        match params {
+            [] => self.append_operator("y"),
+            // A sole unlabeled, non-tuple parameter is encoded directly.
+            [param]
+                if param.label.is_none()
+                    && !param.is_variadic
+                    && !matches!(param.ty, Type::Tuple(_)) =>
+            {
+                self.append_param(param)
+            }
+            _ => {
+                let mut is_first = true;
+                for param in params {
+                    self.append_param(param);
+                    self.append_list_separator(&mut is_first);
+                }
+                self.append_operator("t");
+            }
+        }
+    }
+
+    /// Appends a parameter, where variadic parameters are their element type
+    /// followed by `d`.
+    fn append_param(&mut self, param: &Param) {
+        self.append_type(&param.ty);
+        match param.ownership {
+            Ownership::Default => {}
+            Ownership::InOut => self.append_operator("z"),
+            Ownership::Shared => self.append_operator("h"),
+            Ownership::Owned => self.append_operator("n"),
+        }
+        if param.is_variadic {
+            self.append_operator("d");
+        }
+    }
+
+    // Entities ----------------------------------------------------------------
+
+    pub fn append_entity(&mut self, entity: &Entity) {
+        match entity {
+            Entity::Function {
+                context,
+                name,
+                signature,
+                is_static,
+            } => {
+                self.append_context(context);
+                self.append_decl_name(name);
+                self.append_labels(&signature.params);
+                self.append_function_signature(signature);
+                if let Some(generic_signature) = &signature.generic_signature {
+                    self.append_generic_signature(generic_signature);
+                }
+                self.append_operator("F");
+                if *is_static {
+                    self.append_operator("Z");
+                }
+            }
+            Entity::Constructor {
+                context,
+                signature,
+                allocating,
+            } => {
+                self.append_context(context);
+                self.append_labels(&signature.params);
+                self.append_function_type(signature);
+                self.append_operator(if *allocating { "fC" } else { "fc" });
+            }
+            Entity::Variable {
+                context,
+                name,
+                ty,
+                accessor,
+                is_static,
+            } => {
+                self.append_context(context);
+                self.append_identifier(name);
+                self.append_type(ty);
+                self.append_operator("v");
+                self.append_operator(accessor.code());
+                if *is_static {
+                    self.append_operator("Z");
+                }
+            }
+            Entity::Subscript {
+                context,
+                signature,
+                accessor,
+                is_static,
+            } => {
+                self.append_context(context);
+                self.append_labels(&signature.params);
+                self.append_function_type(signature);
+                self.append_operator("i");
+                self.append_operator(accessor.code());
+                if *is_static {
+                    self.append_operator("Z");
+                }
+            }
+        }
+    }
+}
+
+impl Type {
+    fn is_void(&self) -> bool {
+        matches!(self, Type::Tuple(elements) if elements.is_empty())
+    }
+}
+
+fn is_word_start(c: u8) -> bool {
+    !c.is_ascii_digit() && c != b'_' && c != 0
+}
+
+fn is_word_end(c: u8, prev: u8) -> bool {
+    c == b'_' || c == 0 || (!prev.is_ascii_uppercase() && c.is_ascii_uppercase())
+}
+
+fn translate_operator_char(c: char) -> char {
+    match c {
+        '&' => 'a',
+        '@' => 'c',
+        '/' => 'd',
+        '=' => 'e',
+        '>' => 'g',
+        '<' => 'l',
+        '*' => 'm',
+        '!' => 'n',
+        '|' => 'o',
+        '+' => 'p',
+        '?' => 'q',
+        '%' => 'r',
+        '-' => 's',
+        '~' => 't',
+        '^' => 'x',
+        '.' => 'z',
+        c => c,
+    }
+}
+
+/// Returns the `S`-prefixed substitution for well-known standard library
+/// types.
+fn standard_substitution(nominal: &Nominal) -> Option<char> {
+    if nominal.context != Context::Module("Swift".to_owned()) {
+        return None;
+    }
+    let subst = match nominal.name.as_str() {
+        "AutoreleasingUnsafeMutablePointer" => 'A',
+        "Array" => 'a',
+        "BinaryFloatingPoint" => 'B',
+        "Bool" => 'b',
+        "Dictionary" => 'D',
+        "Double" => 'd',
+        "Encodable" => 'E',
+        "Decodable" => 'e',
+        "FloatingPoint" => 'F',
+        "Float" => 'f',
+        "RandomNumberGenerator" => 'G',
+        "Hashable" => 'H',
+        "Set" => 'h',
+        "DefaultIndices" => 'I',
+        "Int" => 'i',
+        "Character" => 'J',
+        "Numeric" => 'j',
+        "BidirectionalCollection" => 'K',
+        "RandomAccessCollection" => 'k',
+        "Comparable" => 'L',
+        "Collection" => 'l',
+        "MutableCollection" => 'M',
+        "RangeReplaceableCollection" => 'm',
+        "ClosedRange" => 'N',
+        "Range" => 'n',
+        "ObjectIdentifier" => 'O',
+        "UnsafePointer" => 'P',
+        "UnsafeMutablePointer" => 'p',
+        "Equatable" => 'Q',
+        "Optional" => 'q',
+        "UnsafeBufferPointer" => 'R',
+        "UnsafeMutableBufferPointer" => 'r',
+        "String" => 'S',
+        "Substring" => 's',
+        "Sequence" => 'T',
+        "IteratorProtocol" => 't',
+        "UnsignedInteger" => 'U',
+        "UInt" => 'u',
+        "UnsafeRawPointer" => 'V',
+        "UnsafeMutableRawPointer" => 'v',
+        "UnsafeRawBufferPointer" => 'W',
+        "UnsafeMutableRawBufferPointer" => 'w',
+        "RangeExpression" => 'X',
+        "Strideable" => 'x',
+        "RawRepresentable" => 'Y',
+        "StringProtocol" => 'y',
+        "SignedInteger" => 'Z',
+        "BinaryInteger" => 'z',
+        _ => return None,
+    };
+    Some(subst)
+}
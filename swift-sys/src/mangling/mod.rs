@@ -0,0 +1,904 @@
+//! Swift 5 symbol mangling and demangling.
+//!
+//! Entities are described structurally and then encoded following
+//! [`docs/ABI/Mangling.rst`](https://github.com/apple/swift/blob/main/docs/ABI/Mangling.rst),
+//! including identifier, word and standard type substitutions. Symbols are
+//! decoded by [`demangle_symbol`] into a [`Node`] tree.
+//!
+//! This needs no runtime, so symbols can be computed ahead of time, such as
+//! when generating bindings.
+//!
+//! # Examples
+//!
+//! ```
+//! use swift_sys::mangling::{self, Context, Entity, FunctionType, Nominal, NominalKind, Param, Type};
+//!
+//! let string = Nominal::new("Swift", "String", NominalKind::Struct);
+//! let init = Entity::Constructor {
+//!     context: Context::Nominal(Box::new(string.clone())),
+//!     signature: FunctionType::new(
+//!         vec![
+//!             Param::new(Type::Nominal(string.clone())).label("repeating"),
+//!             Param::new(Type::stdlib("Int")).label("count"),
+//!         ],
+//!         Type::Nominal(string),
+//!     ),
+//!     allocating: true,
+//! };
+//! assert_eq!(mangling::entity(&init), "$sSS9repeating5countS2S_SitcfC");
+//! ```
+
+mod demangle;
+mod mangler;
+mod punycode;
+
+pub use demangle::*;
+use mangler::Mangler;
+
+/// The `$s` prefix of Swift 5 symbols.
+pub const PREFIX: &str = "$s";
+
+/// The context in which an entity is declared.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Context {
+    /// A top-level declaration in a module.
+    Module(String),
+
+    /// A member of a nominal type, or of an extension of it within the type's
+    /// own module.
+    Nominal(Box<Nominal>),
+
+    /// A member of an extension declared in a different module than the
+    /// extended type, or with requirements on its generic parameters.
+    Extension {
+        /// The extended type.
+        extended: Box<Nominal>,
+
+        /// The module declaring the extension.
+        module: String,
+
+        /// The signature of a constrained extension, which includes the
+        /// generic parameters of the extended type.
+        generic_signature: Option<GenericSignature>,
+    },
+}
+
+/// A nominal type declaration.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Nominal {
+    /// The context in which the type is declared.
+    pub context: Context,
+
+    /// The name of the type.
+    pub name: String,
+
+    /// The kind of type.
+    pub kind: NominalKind,
+}
+
+impl Nominal {
+    /// Creates a top-level type in `module`.
+    pub fn new(module: &str, name: &str, kind: NominalKind) -> Self {
+        Self {
+            context: Context::Module(module.to_owned()),
+            name: name.to_owned(),
+            kind,
+        }
+    }
+
+    /// Creates a type nested within `self`.
+    pub fn nested(&self, name: &str, kind: NominalKind) -> Self {
+        Self {
+            context: Context::Nominal(Box::new(self.clone())),
+            name: name.to_owned(),
+            kind,
+        }
+    }
+
+    /// Returns `true` if this is a top-level type in the standard library.
+    pub fn is_stdlib(&self, name: &str) -> bool {
+        self.name == name && self.context == Context::Module("Swift".to_owned())
+    }
+}
+
+/// The kind of a [`Nominal`] type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NominalKind {
+    /// A `struct`.
+    Struct,
+
+    /// An `enum`.
+    Enum,
+
+    /// A `class` or `actor`.
+    Class,
+
+    /// A `protocol`.
+    Protocol,
+}
+
+/// A canonical type.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Type {
+    /// A non-generic nominal type.
+    Nominal(Nominal),
+
+    /// A generic nominal type applied to arguments.
+    BoundGeneric(Nominal, Vec<Type>),
+
+    /// A tuple; `()` is the empty tuple.
+    Tuple(Vec<TupleElement>),
+
+    /// A function type.
+    Function(Box<FunctionType>),
+
+    /// A metatype, `T.Type`.
+    Metatype(Box<Type>),
+
+    /// An existential of zero or more protocols, such as `Any` or
+    /// `Equatable & Hashable`.
+    Existential {
+        /// The protocols that the value conforms to.
+        protocols: Vec<Nominal>,
+
+        /// Whether the value is a class instance, as with `AnyObject`.
+        class_bound: bool,
+    },
+
+    /// A generic parameter.
+    GenericParam(GenericParam),
+}
+
+impl Type {
+    /// The empty tuple type, `()`.
+    pub const VOID: Self = Self::Tuple(Vec::new());
+
+    /// Returns a top-level standard library struct type, such as `Int`.
+    pub fn stdlib(name: &str) -> Self {
+        Self::Nominal(Nominal::new("Swift", name, NominalKind::Struct))
+    }
+
+    /// Returns `Optional<self>`.
+    pub fn optional(self) -> Self {
+        Self::BoundGeneric(
+            Nominal::new("Swift", "Optional", NominalKind::Enum),
+            vec![self],
+        )
+    }
+}
+
+/// A generic parameter by depth and index, where the outermost generic
+/// context has depth 0.
+///
+/// `Self` of a protocol is `(0, 0)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GenericParam {
+    /// The number of generic contexts that enclose the parameter's.
+    pub depth: usize,
+
+    /// The position of the parameter within its context.
+    pub index: usize,
+}
+
+impl GenericParam {
+    /// Creates a parameter at `depth` and `index`.
+    pub const fn new(depth: usize, index: usize) -> Self {
+        Self { depth, index }
+    }
+}
+
+/// An element of a tuple type.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TupleElement {
+    /// The element's label, if any.
+    pub label: Option<String>,
+
+    /// The type of the element.
+    pub ty: Type,
+}
+
+/// A function type or the signature of a function declaration.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FunctionType {
+    /// The parameters, in order.
+    pub params: Vec<Param>,
+
+    /// The result type, which is [`Type::VOID`] for none.
+    pub result: Type,
+
+    /// Whether the function is `async`.
+    pub is_async: bool,
+
+    /// Whether the function `throws`.
+    pub throws: bool,
+
+    /// Whether a closure of this type is `@escaping`.
+    pub is_escaping: bool,
+
+    /// The generic parameters that a declaration introduces, which are only
+    /// mangled for declarations.
+    pub generic_signature: Option<GenericSignature>,
+}
+
+impl FunctionType {
+    /// Creates a synchronous, non-throwing signature.
+    pub fn new(params: Vec<Param>, result: Type) -> Self {
+        Self {
+            params,
+            result,
+            is_async: false,
+            throws: false,
+            is_escaping: false,
+            generic_signature: None,
+        }
+    }
+}
+
+/// A function parameter.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Param {
+    /// The argument label, which is only mangled for declarations.
+    pub label: Option<String>,
+
+    /// The type of the parameter.
+    pub ty: Type,
+
+    /// How the argument is passed.
+    pub ownership: Ownership,
+
+    /// Whether the parameter is variadic, such as `Int...`.
+    pub is_variadic: bool,
+}
+
+impl Param {
+    /// Creates an unlabeled parameter with the default convention.
+    pub fn new(ty: Type) -> Self {
+        Self {
+            label: None,
+            ty,
+            ownership: Ownership::Default,
+            is_variadic: false,
+        }
+    }
+
+    /// Sets the argument label.
+    pub fn label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_owned());
+        self
+    }
+}
+
+/// The explicit ownership convention of a parameter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Ownership {
+    /// No explicit convention.
+    Default,
+
+    /// `inout`.
+    InOut,
+
+    /// `__owned` or `consuming`.
+    Owned,
+
+    /// `__shared` or `borrowing`.
+    Shared,
+}
+
+impl Default for Ownership {
+    #[inline]
+    fn default() -> Self {
+        Self::Default
+    }
+}
+
+/// The generic parameters and requirements introduced by a declaration.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GenericSignature {
+    /// The number of parameters at each depth introduced by the declaration,
+    /// from the outermost.
+    pub param_counts: Vec<usize>,
+
+    /// The requirements on the parameters, ordered by parameter.
+    pub requirements: Vec<Requirement>,
+}
+
+impl GenericSignature {
+    /// Creates a signature with `count` unconstrained parameters at a single
+    /// depth.
+    pub fn new(count: usize) -> Self {
+        Self {
+            param_counts: vec![count],
+            requirements: Vec::new(),
+        }
+    }
+
+    /// Adds `requirement`.
+    pub fn requirement(mut self, requirement: Requirement) -> Self {
+        self.requirements.push(requirement);
+        self
+    }
+}
+
+/// A requirement on a generic parameter.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Requirement {
+    /// The parameter conforms to a protocol, as in `T: Equatable`.
+    Conformance(GenericParam, Nominal),
+
+    /// The parameter is a subclass of a class, as in `T: NSObject`.
+    Superclass(GenericParam, Type),
+
+    /// The parameter is the same as a type, as in `T == Int`.
+    SameType(GenericParam, Type),
+}
+
+/// The name of a declaration.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DeclName {
+    /// An identifier such as `count`.
+    Identifier(String),
+
+    /// An operator such as `==`.
+    Operator(String, Fixity),
+}
+
+/// The fixity of an operator function.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Fixity {
+    /// An operator between its operands, such as `a + b`.
+    Infix,
+
+    /// An operator before its operand, such as `-a`.
+    Prefix,
+
+    /// An operator after its operand, such as `a!`.
+    Postfix,
+}
+
+/// A property or subscript accessor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Accessor {
+    /// `get`.
+    Getter,
+
+    /// `set`.
+    Setter,
+
+    /// `_read`, a coroutine that yields a borrowed value.
+    Read,
+
+    /// `_modify`, a coroutine that yields a mutable value.
+    Modify,
+
+    /// The address of a stored global, which lazily initializes it.
+    UnsafeMutableAddressor,
+}
+
+impl Accessor {
+    fn code(self) -> &'static str {
+        match self {
+            Self::Getter => "g",
+            Self::Setter => "s",
+            Self::Read => "r",
+            Self::Modify => "M",
+            Self::UnsafeMutableAddressor => "au",
+        }
+    }
+}
+
+/// A declaration that has a symbol.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Entity {
+    /// A `func`.
+    Function {
+        /// Where the function is declared.
+        context: Context,
+
+        /// The base name of the function.
+        name: DeclName,
+
+        /// The parameters and result of the function.
+        signature: FunctionType,
+
+        /// Whether the function is `static` or `class`.
+        is_static: bool,
+    },
+
+    /// An `init`, returning `signature.result`.
+    Constructor {
+        /// The type that the initializer is declared in.
+        context: Context,
+
+        /// The parameters and result of the initializer.
+        signature: FunctionType,
+
+        /// Whether this is the entry point that allocates the instance,
+        /// rather than the one that initializes `self`.
+        allocating: bool,
+    },
+
+    /// A `var` or `let` accessor.
+    Variable {
+        /// Where the variable is declared.
+        context: Context,
+
+        /// The name of the variable.
+        name: String,
+
+        /// The type of the variable.
+        ty: Type,
+
+        /// The accessor of the variable.
+        accessor: Accessor,
+
+        /// Whether the variable is `static` or `class`.
+        is_static: bool,
+    },
+
+    /// A `subscript` accessor.
+    Subscript {
+        /// The type that the subscript is declared in.
+        context: Context,
+
+        /// The indices and element type of the subscript.
+        signature: FunctionType,
+
+        /// The accessor of the subscript.
+        accessor: Accessor,
+
+        /// Whether the subscript is `static` or `class`.
+        is_static: bool,
+    },
+}
+
+/// A symbol for a type, such as metadata or its accessor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TypeSymbol {
+    /// `N`: direct type metadata.
+    Metadata,
+
+    /// `Ma`: the type metadata access function.
+    MetadataAccessor,
+
+    /// `Mn`: the nominal type descriptor.
+    NominalTypeDescriptor,
+}
+
+impl TypeSymbol {
+    fn suffix(self) -> &'static str {
+        match self {
+            Self::Metadata => "N",
+            Self::MetadataAccessor => "Ma",
+            Self::NominalTypeDescriptor => "Mn",
+        }
+    }
+}
+
+/// Returns the symbol for `entity`.
+pub fn entity(entity: &Entity) -> String {
+    let mut mangler = Mangler::new();
+    mangler.append_entity(entity);
+    mangler.finish()
+}
+
+/// Returns the symbol of `kind` for `ty`.
+pub fn type_symbol(ty: &Type, kind: TypeSymbol) -> String {
+    let mut mangler = Mangler::new();
+    mangler.append_type(ty);
+    mangler.append_operator(kind.suffix());
+    mangler.finish()
+}
+
+/// Returns the symbol of the descriptor of `protocol`.
+pub fn protocol_descriptor(protocol: &Nominal) -> String {
+    let mut mangler = Mangler::new();
+    mangler.append_protocol_name(protocol);
+    mangler.append_operator("Mp");
+    mangler.finish()
+}
+
+/// Returns the symbol of the dispatch thunk for the protocol requirement
+/// `entity`, which resilient modules export to call through witness tables.
+pub fn dispatch_thunk(entity: &Entity) -> String {
+    let mut mangler = Mangler::new();
+    mangler.append_entity(entity);
+    mangler.append_operator("Tj");
+    mangler.finish()
+}
+
+/// Returns the symbol of the method descriptor for the class member
+/// `entity`, which locates its entry in the vtable.
+pub fn method_descriptor(entity: &Entity) -> String {
+    let mut mangler = Mangler::new();
+    mangler.append_entity(entity);
+    mangler.append_operator("Tq");
+    mangler.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string() -> Nominal {
+        Nominal::new("Swift", "String", NominalKind::Struct)
+    }
+
+    fn hasher() -> Nominal {
+        Nominal::new("Swift", "Hasher", NominalKind::Struct)
+    }
+
+    fn signature(params: Vec<Param>, result: Type) -> FunctionType {
+        FunctionType::new(params, result)
+    }
+
+    fn generic_signature(
+        params: Vec<Param>,
+        result: Type,
+        generics: GenericSignature,
+    ) -> FunctionType {
+        FunctionType {
+            generic_signature: Some(generics),
+            ..signature(params, result)
+        }
+    }
+
+    fn protocol(name: &str) -> Nominal {
+        Nominal::new("Swift", name, NominalKind::Protocol)
+    }
+
+    fn param(index: usize) -> GenericParam {
+        GenericParam::new(0, index)
+    }
+
+    fn method(context: Nominal, name: &str, params: Vec<Param>, result: Type) -> String {
+        entity(&Entity::Function {
+            context: Context::Nominal(Box::new(context)),
+            name: DeclName::Identifier(name.to_owned()),
+            signature: signature(params, result),
+            is_static: false,
+        })
+    }
+
+    #[test]
+    fn stdlib_functions() {
+        assert_eq!(
+            method(
+                hasher(),
+                "_combine",
+                vec![Param::new(Type::stdlib("UInt"))],
+                Type::VOID
+            ),
+            "$ss6HasherV8_combineyySuF"
+        );
+        assert_eq!(
+            method(
+                hasher(),
+                "_combine",
+                vec![Param::new(Type::stdlib("UInt16"))],
+                Type::VOID
+            ),
+            "$ss6HasherV8_combineyys6UInt16VF"
+        );
+        assert_eq!(
+            method(hasher(), "finalize", vec![], Type::stdlib("Int")),
+            "$ss6HasherV8finalizeSiyF"
+        );
+        assert_eq!(
+            method(
+                hasher(),
+                "combine",
+                vec![Param::new(Type::stdlib("UnsafeRawBufferPointer")).label("bytes")],
+                Type::VOID,
+            ),
+            "$ss6HasherV7combine5bytesySW_tF"
+        );
+    }
+
+    #[test]
+    fn stdlib_operators() {
+        let string_ty = Type::Nominal(string());
+        for (op, symbol) in &[("==", "$sSS2eeoiySbSS_SStFZ"), ("<", "$sSS1loiySbSS_SStFZ")] {
+            let mangled = entity(&Entity::Function {
+                context: Context::Nominal(Box::new(string())),
+                name: DeclName::Operator(op.to_string(), Fixity::Infix),
+                signature: signature(
+                    vec![Param::new(string_ty.clone()), Param::new(string_ty.clone())],
+                    Type::stdlib("Bool"),
+                ),
+                is_static: true,
+            });
+            assert_eq!(mangled, *symbol);
+        }
+    }
+
+    #[test]
+    fn stdlib_constructors() {
+        let string_ty = Type::Nominal(string());
+        let constructor = |context: Nominal, params: Vec<Param>| {
+            let result = Type::Nominal(context.clone());
+            entity(&Entity::Constructor {
+                context: Context::Nominal(Box::new(context)),
+                signature: signature(params, result),
+                allocating: true,
+            })
+        };
+
+        assert_eq!(
+            constructor(
+                string(),
+                vec![
+                    Param::new(string_ty.clone()).label("repeating"),
+                    Param::new(Type::stdlib("Int")).label("count"),
+                ],
+            ),
+            "$sSS9repeating5countS2S_SitcfC"
+        );
+        assert_eq!(
+            constructor(
+                string(),
+                vec![Param::new(Type::Nominal(
+                    Nominal::new("Swift", "Unicode", NominalKind::Enum)
+                        .nested("Scalar", NominalKind::Struct)
+                ))],
+            ),
+            "$sSSySSs7UnicodeO6ScalarVcfC"
+        );
+        assert_eq!(
+            constructor(
+                string(),
+                vec![Param::new(Type::BoundGeneric(
+                    Nominal::new("Swift", "UnsafePointer", NominalKind::Struct),
+                    vec![Type::stdlib("Int8")],
+                ))
+                .label("cString")],
+            ),
+            "$sSS7cStringSSSPys4Int8VG_tcfC"
+        );
+        assert_eq!(constructor(hasher(), vec![]), "$ss6HasherVABycfC");
+    }
+
+    #[test]
+    fn stdlib_properties() {
+        let getter = |name: &str, ty: &str| {
+            entity(&Entity::Variable {
+                context: Context::Nominal(Box::new(string())),
+                name: name.to_owned(),
+                ty: Type::stdlib(ty),
+                accessor: Accessor::Getter,
+                is_static: false,
+            })
+        };
+        assert_eq!(getter("count", "Int"), "$sSS5countSivg");
+        assert_eq!(getter("isEmpty", "Bool"), "$sSS7isEmptySbvg");
+    }
+
+    #[test]
+    fn type_symbols() {
+        let metadata = |ty: Type| type_symbol(&ty, TypeSymbol::Metadata);
+        assert_eq!(metadata(Type::Nominal(string())), "$sSSN");
+        assert_eq!(metadata(Type::stdlib("Int8")), "$ss4Int8VN");
+        assert_eq!(metadata(Type::VOID), "$sytN");
+        assert_eq!(
+            type_symbol(
+                &Type::Nominal(Nominal::new("Shapes", "Point", NominalKind::Struct)),
+                TypeSymbol::MetadataAccessor
+            ),
+            "$s6Shapes5PointVMa"
+        );
+    }
+
+    #[test]
+    fn protocol_symbols() {
+        let equatable = Nominal::new("Swift", "Equatable", NominalKind::Protocol);
+        assert_eq!(protocol_descriptor(&equatable), "$sSQMp");
+
+        let drawable = Nominal::new("Shapes", "Drawable", NominalKind::Protocol);
+        assert_eq!(protocol_descriptor(&drawable), "$s6Shapes8DrawableMp");
+        assert_eq!(
+            dispatch_thunk(&Entity::Function {
+                context: Context::Nominal(Box::new(drawable)),
+                name: DeclName::Identifier("draw".to_owned()),
+                signature: signature(vec![], Type::VOID),
+                is_static: false,
+            }),
+            "$s6Shapes8DrawableP4drawyyFTj"
+        );
+
+        let self_ty = Type::GenericParam(param(0));
+        assert_eq!(
+            dispatch_thunk(&Entity::Function {
+                context: Context::Nominal(Box::new(equatable)),
+                name: DeclName::Operator("==".to_owned(), Fixity::Infix),
+                signature: signature(
+                    vec![Param::new(self_ty.clone()), Param::new(self_ty)],
+                    Type::stdlib("Bool"),
+                ),
+                is_static: true,
+            }),
+            "$sSQ2eeoiySbx_xtFZTj"
+        );
+    }
+
+    #[test]
+    fn method_descriptors() {
+        let shape = Nominal::new("Shapes", "Shape", NominalKind::Class);
+        assert_eq!(
+            method_descriptor(&Entity::Function {
+                context: Context::Nominal(Box::new(shape.clone())),
+                name: DeclName::Identifier("area".to_owned()),
+                signature: signature(vec![], Type::stdlib("Double")),
+                is_static: false,
+            }),
+            "$s6Shapes5ShapeC4areaSdyFTq"
+        );
+        assert_eq!(
+            method_descriptor(&Entity::Variable {
+                context: Context::Nominal(Box::new(shape)),
+                name: "name".to_owned(),
+                ty: Type::stdlib("String"),
+                accessor: Accessor::Getter,
+                is_static: false,
+            }),
+            "$s6Shapes5ShapeC4nameSSvgTq"
+        );
+    }
+
+    #[test]
+    fn user_constructor_substitution() {
+        let model = Nominal::new("MyLib", "Model", NominalKind::Class);
+        let mangled = entity(&Entity::Constructor {
+            context: Context::Nominal(Box::new(model.clone())),
+            signature: signature(vec![], Type::Nominal(model)),
+            allocating: true,
+        });
+        assert_eq!(mangled, "$s5MyLib5ModelCACycfC");
+    }
+
+    #[test]
+    fn word_substitutions() {
+        // "Shape" is a new word since words only match whole words.
+        let kind = Nominal::new("Shapes", "ShapeKind", NominalKind::Enum);
+        assert_eq!(
+            type_symbol(&Type::Nominal(kind.clone()), TypeSymbol::Metadata),
+            "$s6Shapes9ShapeKindON"
+        );
+
+        // "Kind" was seen in "ShapeKind".
+        let mangled = method(kind, "otherKind", vec![], Type::VOID);
+        assert_eq!(mangled, "$s6Shapes9ShapeKindO05otherC0yyF");
+    }
+
+    #[test]
+    fn stdlib_generic_functions() {
+        let function = |name: &str, signature: FunctionType| {
+            entity(&Entity::Function {
+                context: Context::Module("Swift".to_owned()),
+                name: DeclName::Identifier(name.to_owned()),
+                signature,
+                is_static: false,
+            })
+        };
+        let t = Type::GenericParam(param(0));
+        let u = Type::GenericParam(param(1));
+
+        let mut inout = Param::new(t.clone());
+        inout.ownership = Ownership::InOut;
+        assert_eq!(
+            function(
+                "swap",
+                generic_signature(
+                    vec![inout.clone(), inout],
+                    Type::VOID,
+                    GenericSignature::new(1)
+                ),
+            ),
+            "$ss4swapyyxz_xztlF"
+        );
+        assert_eq!(
+            function(
+                "max",
+                generic_signature(
+                    vec![Param::new(t.clone()), Param::new(t.clone())],
+                    t.clone(),
+                    GenericSignature::new(1)
+                        .requirement(Requirement::Conformance(param(0), protocol("Comparable"))),
+                ),
+            ),
+            "$ss3maxyxx_xtSLRzlF"
+        );
+
+        let zip = Nominal::new("Swift", "Zip2Sequence", NominalKind::Struct);
+        let sequence = protocol("Sequence");
+        assert_eq!(
+            function(
+                "zip",
+                generic_signature(
+                    vec![Param::new(t.clone()), Param::new(u.clone())],
+                    Type::BoundGeneric(zip, vec![t, u]),
+                    GenericSignature::new(2)
+                        .requirement(Requirement::Conformance(param(0), sequence.clone()))
+                        .requirement(Requirement::Conformance(param(1), sequence)),
+                ),
+            ),
+            "$ss3zipys12Zip2SequenceVyxq_Gx_q_tSTRzSTR_r0_lF"
+        );
+    }
+
+    #[test]
+    fn stdlib_extensions() {
+        let array = Nominal::new("Swift", "Array", NominalKind::Struct);
+        let array_ty = Type::BoundGeneric(array.clone(), vec![Type::GenericParam(param(0))]);
+        let equatable = GenericSignature::new(1)
+            .requirement(Requirement::Conformance(param(0), protocol("Equatable")));
+        assert_eq!(
+            entity(&Entity::Function {
+                context: Context::Extension {
+                    extended: Box::new(array.clone()),
+                    module: "Swift".to_owned(),
+                    generic_signature: Some(equatable),
+                },
+                name: DeclName::Operator("==".to_owned(), Fixity::Infix),
+                signature: signature(
+                    vec![Param::new(array_ty.clone()), Param::new(array_ty)],
+                    Type::stdlib("Bool"),
+                ),
+                is_static: true,
+            }),
+            "$sSasSQRzlE2eeoiySbSayxG_ABtFZ"
+        );
+
+        // Members of protocol extensions use `Self` as the first parameter.
+        let self_ty = Type::GenericParam(param(0));
+        assert_eq!(
+            entity(&Entity::Function {
+                context: Context::Extension {
+                    extended: Box::new(protocol("Equatable")),
+                    module: "Swift".to_owned(),
+                    generic_signature: None,
+                },
+                name: DeclName::Operator("!=".to_owned(), Fixity::Infix),
+                signature: signature(
+                    vec![Param::new(self_ty.clone()), Param::new(self_ty)],
+                    Type::stdlib("Bool"),
+                ),
+                is_static: true,
+            }),
+            "$sSQsE2neoiySbx_xtFZ"
+        );
+    }
+
+    #[test]
+    fn stdlib_subscripts() {
+        let array = Nominal::new("Swift", "Array", NominalKind::Struct);
+        let subscript = |accessor| {
+            entity(&Entity::Subscript {
+                context: Context::Nominal(Box::new(array.clone())),
+                signature: signature(
+                    vec![Param::new(Type::stdlib("Int"))],
+                    Type::GenericParam(param(0)),
+                ),
+                accessor,
+                is_static: false,
+            })
+        };
+        assert_eq!(subscript(Accessor::Getter), "$sSayxSicig");
+        assert_eq!(subscript(Accessor::Modify), "$sSayxSiciM");
+    }
+
+    #[test]
+    fn generic_param_indices() {
+        let mangled = |depth, index| {
+            type_symbol(
+                &Type::Metatype(Box::new(Type::GenericParam(GenericParam::new(
+                    depth, index,
+                )))),
+                TypeSymbol::Metadata,
+            )
+        };
+        assert_eq!(mangled(0, 0), "$sxmN");
+        assert_eq!(mangled(0, 1), "$sq_mN");
+        assert_eq!(mangled(0, 2), "$sq0_mN");
+        assert_eq!(mangled(1, 0), "$sqd__mN");
+        assert_eq!(mangled(2, 1), "$sqd0_0_mN");
+    }
+}
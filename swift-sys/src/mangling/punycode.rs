@@ -0,0 +1,208 @@
+//! Swift's variant of Punycode ([RFC 3492](https://tools.ietf.org/html/rfc3492))
+//! for identifiers containing non-symbol characters.
+//!
+//! Swift uses `_` as the delimiter, encodes digits 26 through 35 as `A`
+//! through `J`, and maps non-symbol ASCII characters to `0xD800 + c` so that
+//! they are encoded rather than copied.
+
+const BASE: u32 = 36;
+const T_MIN: u32 = 1;
+const T_MAX: u32 = 26;
+const SKEW: u32 = 38;
+const DAMP: u32 = 700;
+const INITIAL_BIAS: u32 = 72;
+const INITIAL_N: u32 = 128;
+const DELIMITER: char = '_';
+
+/// Offset for non-symbol ASCII characters.
+const NON_SYMBOL_OFFSET: u32 = 0xD800;
+
+fn is_symbol_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '$'
+}
+
+/// Returns `true` if `ident` contains characters that can't appear in a symbol
+/// as-is.
+pub(crate) fn needs_encoding(ident: &str) -> bool {
+    !ident.chars().all(is_symbol_char)
+}
+
+fn digit(value: u32) -> char {
+    debug_assert!(value < BASE);
+    if value < 26 {
+        (b'a' + value as u8) as char
+    } else {
+        (b'A' + (value - 26) as u8) as char
+    }
+}
+
+fn adapt(mut delta: u32, num_points: u32, first_time: bool) -> u32 {
+    delta /= if first_time { DAMP } else { 2 };
+    delta += delta / num_points;
+    let mut k = 0;
+    while delta > ((BASE - T_MIN) * T_MAX) / 2 {
+        delta /= BASE - T_MIN;
+        k += BASE;
+    }
+    k + (((BASE - T_MIN + 1) * delta) / (delta + SKEW))
+}
+
+fn digit_value(c: u8) -> Option<u32> {
+    match c {
+        b'a'..=b'z' => Some((c - b'a') as u32),
+        b'A'..=b'J' => Some((c - b'A') as u32 + 26),
+        _ => None,
+    }
+}
+
+/// Encodes `ident`.
+pub(crate) fn encode(ident: &str) -> String {
+    let input: Vec<u32> = ident
+        .chars()
+        .map(|c| {
+            if c.is_ascii() && !is_symbol_char(c) {
+                c as u32 + NON_SYMBOL_OFFSET
+            } else {
+                c as u32
+            }
+        })
+        .collect();
+
+    let mut output: String = input
+        .iter()
+        .filter(|&&c| c < 0x80)
+        .map(|&c| c as u8 as char)
+        .collect();
+    let basic_len = output.len() as u32;
+    if basic_len > 0 {
+        output.push(DELIMITER);
+    }
+
+    let mut n = INITIAL_N;
+    let mut delta = 0;
+    let mut bias = INITIAL_BIAS;
+    let mut handled = basic_len;
+
+    while (handled as usize) < input.len() {
+        let m = input.iter().copied().filter(|&c| c >= n).min().unwrap();
+        delta += (m - n) * (handled + 1);
+        n = m;
+        for &c in &input {
+            if c < n {
+                delta += 1;
+            }
+            if c == n {
+                let mut q = delta;
+                let mut k = BASE;
+                loop {
+                    let t = if k <= bias {
+                        T_MIN
+                    } else if k >= bias + T_MAX {
+                        T_MAX
+                    } else {
+                        k - bias
+                    };
+                    if q < t {
+                        break;
+                    }
+                    output.push(digit(t + (q - t) % (BASE - t)));
+                    q = (q - t) / (BASE - t);
+                    k += BASE;
+                }
+                output.push(digit(q));
+                bias = adapt(delta, handled + 1, handled == basic_len);
+                delta = 0;
+                handled += 1;
+            }
+        }
+        delta += 1;
+        n += 1;
+    }
+    output
+}
+
+/// Decodes `encoded`, or returns `None` if it isn't valid.
+pub(crate) fn decode(encoded: &str) -> Option<String> {
+    let (basic, mut input) = match encoded.rfind(DELIMITER) {
+        Some(delimiter) => (&encoded[..delimiter], &encoded.as_bytes()[delimiter + 1..]),
+        None => ("", encoded.as_bytes()),
+    };
+    if !basic.is_ascii() {
+        return None;
+    }
+    let mut output: Vec<u32> = basic.bytes().map(u32::from).collect();
+
+    let mut n = INITIAL_N;
+    let mut i: u32 = 0;
+    let mut bias = INITIAL_BIAS;
+    while !input.is_empty() {
+        let old_i = i;
+        let mut w: u32 = 1;
+        let mut k = BASE;
+        loop {
+            let (&c, rest) = input.split_first()?;
+            input = rest;
+            let digit = digit_value(c)?;
+            i = i.checked_add(digit.checked_mul(w)?)?;
+            let t = if k <= bias {
+                T_MIN
+            } else if k >= bias + T_MAX {
+                T_MAX
+            } else {
+                k - bias
+            };
+            if digit < t {
+                break;
+            }
+            w = w.checked_mul(BASE - t)?;
+            k += BASE;
+        }
+        let len = output.len() as u32 + 1;
+        bias = adapt(i - old_i, len, old_i == 0);
+        n = n.checked_add(i / len)?;
+        i %= len;
+        if n < 0x80 {
+            return None;
+        }
+        output.insert(i as usize, n);
+        i += 1;
+    }
+
+    output
+        .into_iter()
+        .map(|c| match c {
+            c if (NON_SYMBOL_OFFSET..NON_SYMBOL_OFFSET + 0x80).contains(&c) => {
+                Some((c - NON_SYMBOL_OFFSET) as u8 as char)
+            }
+            c => char::from_u32(c),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_unicode() {
+        assert_eq!(encode("ü"), "tda");
+        assert_eq!(encode("café"), "caf_dma");
+        assert_eq!(encode("Proprietà"), "Propriet_Gya");
+        assert_eq!(encode("日本"), "wgvHBa");
+    }
+
+    #[test]
+    fn decode_unicode() {
+        for ident in &["ü", "café", "Proprietà", "日本", "a-b", "+"] {
+            assert_eq!(decode(&encode(ident)).as_deref(), Some(*ident));
+        }
+        assert_eq!(decode("caf_d"), None);
+        assert_eq!(decode("caf_dm!"), None);
+    }
+
+    #[test]
+    fn non_symbol_chars() {
+        assert!(needs_encoding("a-b"));
+        assert!(!needs_encoding("a_b$1"));
+    }
+}
@@ -1,5 +1,5 @@
 use crate::metadata::{MetadataRequest, MetadataResponse};
-use std::fmt;
+use std::{ffi::c_void, fmt, mem};
 
 /// Pointer to a metadata access function.
 #[repr(transparent)]
@@ -23,6 +23,161 @@ impl fmt::Pointer for MetadataAccessFunction {
     }
 }
 
+type AccessFunction0 = unsafe extern "C" fn(MetadataRequest) -> MetadataResponse;
+
+type AccessFunction1 = unsafe extern "C" fn(MetadataRequest, *const c_void) -> MetadataResponse;
+
+type AccessFunction2 =
+    unsafe extern "C" fn(MetadataRequest, *const c_void, *const c_void) -> MetadataResponse;
+
+type AccessFunction3 = unsafe extern "C" fn(
+    MetadataRequest,
+    *const c_void,
+    *const c_void,
+    *const c_void,
+) -> MetadataResponse;
+
+type AccessFunctionN =
+    unsafe extern "C" fn(MetadataRequest, *const *const c_void) -> MetadataResponse;
+
 impl MetadataAccessFunction {
-    // TODO: Implement methods to call `function`.
+    /// The number of arguments that are passed directly. Functions with more
+    /// arguments take them in an array.
+    pub const MAX_DIRECT_ARGS: usize = 3;
+
+    /// Creates an instance from `function`, which is cast to the signature
+    /// for the number of arguments when called.
+    #[inline]
+    pub const fn new(function: unsafe extern "C" fn(MetadataRequest) -> MetadataResponse) -> Self {
+        Self(function)
+    }
+
+    /// Returns the address of the function.
+    #[inline]
+    pub fn as_ptr(self) -> *const c_void {
+        self.0 as *const c_void
+    }
+
+    /// Calls the function with `args`.
+    ///
+    /// # Safety
+    ///
+    /// `args` must be the key arguments of the type's generic context: the
+    /// metadata of each key generic parameter, followed by the witness table
+    /// of each protocol conformance requirement.
+    #[inline]
+    pub unsafe fn call(self, request: MetadataRequest, args: &[*const c_void]) -> MetadataResponse {
+        match *args {
+            [] => self.call0(request),
+            [arg0] => self.call1(request, arg0),
+            [arg0, arg1] => self.call2(request, arg0, arg1),
+            [arg0, arg1, arg2] => self.call3(request, arg0, arg1, arg2),
+            _ => self.call_many(request, args.as_ptr()),
+        }
+    }
+
+    /// Calls the function of a type with no key arguments.
+    ///
+    /// # Safety
+    ///
+    /// The type must not be generic.
+    #[inline]
+    pub unsafe fn call0(self, request: MetadataRequest) -> MetadataResponse {
+        (self.0)(request)
+    }
+
+    /// Calls the function of a type with one key argument.
+    ///
+    /// # Safety
+    ///
+    /// See [`call`](Self::call).
+    #[inline]
+    pub unsafe fn call1(self, request: MetadataRequest, arg0: *const c_void) -> MetadataResponse {
+        mem::transmute::<AccessFunction0, AccessFunction1>(self.0)(request, arg0)
+    }
+
+    /// Calls the function of a type with two key arguments.
+    ///
+    /// # Safety
+    ///
+    /// See [`call`](Self::call).
+    #[inline]
+    pub unsafe fn call2(
+        self,
+        request: MetadataRequest,
+        arg0: *const c_void,
+        arg1: *const c_void,
+    ) -> MetadataResponse {
+        mem::transmute::<AccessFunction0, AccessFunction2>(self.0)(request, arg0, arg1)
+    }
+
+    /// Calls the function of a type with three key arguments.
+    ///
+    /// # Safety
+    ///
+    /// See [`call`](Self::call).
+    #[inline]
+    pub unsafe fn call3(
+        self,
+        request: MetadataRequest,
+        arg0: *const c_void,
+        arg1: *const c_void,
+        arg2: *const c_void,
+    ) -> MetadataResponse {
+        mem::transmute::<AccessFunction0, AccessFunction3>(self.0)(request, arg0, arg1, arg2)
+    }
+
+    /// Calls the function of a type with more than
+    /// [`MAX_DIRECT_ARGS`](Self::MAX_DIRECT_ARGS) key arguments, which are
+    /// passed in an array.
+    ///
+    /// # Safety
+    ///
+    /// `args` must point to the key arguments described in
+    /// [`call`](Self::call).
+    #[inline]
+    pub unsafe fn call_many(
+        self,
+        request: MetadataRequest,
+        args: *const *const c_void,
+    ) -> MetadataResponse {
+        mem::transmute::<AccessFunction0, AccessFunctionN>(self.0)(request, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::MetadataState;
+
+    /// Stands in for an access function, and returns its first argument.
+    unsafe extern "C" fn first_arg(
+        request: MetadataRequest,
+        arg0: *const c_void,
+    ) -> MetadataResponse {
+        MetadataResponse {
+            value: arg0.cast(),
+            state: request.state(),
+        }
+    }
+
+    #[test]
+    fn call() {
+        let function = MetadataAccessFunction::new(unsafe {
+            mem::transmute::<AccessFunction1, AccessFunction0>(first_arg)
+        });
+        let args: Vec<*const c_void> = (1..=5).map(|i| i as *const c_void).collect();
+        let request = MetadataRequest::blocking(MetadataState::COMPLETE);
+
+        // Up to three arguments are passed directly.
+        for n in 1..=MetadataAccessFunction::MAX_DIRECT_ARGS {
+            let response = unsafe { function.call(request, &args[..n]) };
+            assert_eq!(response.value, args[0].cast());
+            assert_eq!(response.state, MetadataState::COMPLETE);
+        }
+
+        // More are passed in an array.
+        let response = unsafe { function.call(request, &args) };
+        assert_eq!(response.value, args.as_ptr().cast());
+    }
 }
@@ -0,0 +1,132 @@
+use crate::{ctx_desc::ClassDescriptor, metadata::Metadata};
+use std::{ffi::c_void, fmt};
+
+/// Metadata for classes.
+///
+/// The fields of the root class's metadata follow this header, then those of
+/// each subclass in turn. These are its generic arguments, field offsets and
+/// vtable entries.
+///
+/// This type deliberately does not implement [`Copy`] in order to avoid
+/// accidentally dereferencing from the wrong location.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ClassMetadata {
+    /// The base metadata, whose kind is the `isa` pointer when interoperating
+    /// with Objective-C.
+    pub base: Metadata,
+
+    /// The metadata of the superclass, or null for root classes.
+    pub superclass: *const ClassMetadata,
+
+    /// The Objective-C method cache.
+    #[cfg(target_vendor = "apple")]
+    pub cache_data: [*mut c_void; 2],
+
+    /// The Objective-C class data, whose low two bits flag Swift classes.
+    #[cfg(target_vendor = "apple")]
+    pub data: usize,
+
+    /// Swift-specific class flags.
+    pub flags: ClassFlags,
+
+    /// The address point of instances of this type.
+    pub instance_address_point: u32,
+
+    /// The required size of instances of this type.
+    pub instance_size: u32,
+
+    /// The alignment mask of the address point of instances of this type.
+    pub instance_align_mask: u16,
+
+    /// Reserved for runtime use.
+    pub reserved: u16,
+
+    /// The total size of the class object, including prefix and suffix
+    /// extents.
+    pub class_size: u32,
+
+    /// The offset of the address point within the class object.
+    pub class_address_point: u32,
+
+    /// An out-of-line description of the type.
+    pub description: *const ClassDescriptor,
+
+    /// A function for destroying instance variables, used to clean up after
+    /// an early return from a constructor.
+    pub ivar_destroyer: *const c_void,
+}
+
+/// Swift-specific flags of [`ClassMetadata`].
+#[repr(transparent)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ClassFlags(u32);
+
+impl fmt::Debug for ClassFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ClassFlags")
+            .field("is_swift_pre_stable_abi", &self.is_swift_pre_stable_abi())
+            .field("uses_swift_refcounting", &self.uses_swift_refcounting())
+            .field("has_custom_objc_name", &self.has_custom_objc_name())
+            .field("is_static_specialization", &self.is_static_specialization())
+            .field(
+                "is_canonical_static_specialization",
+                &self.is_canonical_static_specialization(),
+            )
+            .finish()
+    }
+}
+
+impl ClassFlags {
+    /// Creates a new instance without any bits set.
+    #[inline]
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Creates a new instance from `bits`.
+    #[inline]
+    pub const fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the underlying bits of `self`.
+    #[inline]
+    pub const fn into_bits(self) -> u32 {
+        self.0
+    }
+
+    /// Returns `true` if the class was compiled before the Swift ABI was
+    /// stable.
+    #[inline]
+    pub const fn is_swift_pre_stable_abi(self) -> bool {
+        self.0 & 0x1 != 0
+    }
+
+    /// Returns `true` if instances of the class use Swift reference counting
+    /// rather than Objective-C's.
+    #[inline]
+    pub const fn uses_swift_refcounting(self) -> bool {
+        self.0 & 0x2 != 0
+    }
+
+    /// Returns `true` if the class has an explicit `@objc` name.
+    #[inline]
+    pub const fn has_custom_objc_name(self) -> bool {
+        self.0 & 0x4 != 0
+    }
+
+    /// Returns `true` if the metadata is a specialization of a generic class
+    /// that was emitted statically.
+    #[inline]
+    pub const fn is_static_specialization(self) -> bool {
+        self.0 & 0x8 != 0
+    }
+
+    /// Returns `true` if the metadata is a static specialization that is also
+    /// the canonical metadata of its arguments.
+    #[inline]
+    pub const fn is_canonical_static_specialization(self) -> bool {
+        self.0 & 0x10 != 0
+    }
+}
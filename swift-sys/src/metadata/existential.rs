@@ -0,0 +1,296 @@
+use crate::{ctx_desc::ProtocolContextDescriptor, metadata::Metadata};
+use std::{ffi::c_void, fmt};
+
+/// Metadata for existential types, such as `Any`, `AnyObject` and `any P`.
+///
+/// The metadata of the superclass constraint follows this header if
+/// [`ExistentialTypeFlags::has_superclass_constraint`], and then the
+/// [`ProtocolDescriptorRef`] of each protocol.
+///
+/// This type deliberately does not implement [`Copy`] in order to avoid
+/// accidentally dereferencing from the wrong location.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ExistentialTypeMetadata {
+    /// The base metadata.
+    pub base: Metadata,
+
+    /// Flags describing the existential type.
+    pub flags: ExistentialTypeFlags,
+
+    /// The number of protocols that the existential type is composed of.
+    pub num_protocols: u32,
+}
+
+/// Metadata for the metatype of an existential type, such as `Any.Type`.
+///
+/// This type deliberately does not implement [`Copy`] in order to avoid
+/// accidentally dereferencing from the wrong location.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ExistentialMetatypeMetadata {
+    /// The base metadata.
+    pub base: Metadata,
+
+    /// The metadata of the instance type, which is either an existential type
+    /// or an existential metatype.
+    pub instance_type: *const Metadata,
+
+    /// The flags of the existential type at the root of the instance type.
+    pub flags: ExistentialTypeFlags,
+}
+
+/// The container of a value of an opaque existential type, such as `Any`.
+///
+/// The witness table of each protocol that the existential type is composed
+/// of follows the container.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OpaqueExistentialContainer {
+    /// The inline buffer, which holds either the value or a pointer to the
+    /// heap box that holds the value.
+    pub buffer: [*mut c_void; 3],
+
+    /// The metadata of the type of the value.
+    pub ty: *const Metadata,
+}
+
+/// The representation of values of an existential type.
+///
+/// This is semantically an `enum`. However, it is defined as a `struct` in
+/// order to be future-compatible.
+#[repr(transparent)]
+#[derive(Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct ExistentialTypeRepresentation(u8);
+
+impl fmt::Debug for ExistentialTypeRepresentation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Format like an `enum`.
+        let known_representation = match *self {
+            Self::OPAQUE => "OPAQUE",
+            Self::CLASS => "CLASS",
+            Self::ERROR => "ERROR",
+            _ => return f.debug_tuple("UNKNOWN").field(&self.0).finish(),
+        };
+
+        f.write_str(known_representation)
+    }
+}
+
+impl ExistentialTypeRepresentation {
+    /// An [`OpaqueExistentialContainer`] followed by witness tables.
+    pub const OPAQUE: Self = Self(0);
+
+    /// A class reference followed by witness tables.
+    pub const CLASS: Self = Self(1);
+
+    /// A reference to the box of a `Swift.Error`.
+    pub const ERROR: Self = Self(2);
+
+    /// Returns this representation's inner value.
+    #[inline]
+    pub const fn value(self) -> u8 {
+        self.0
+    }
+}
+
+/// The special protocol of an existential type, which may give it a special
+/// representation.
+///
+/// This is semantically an `enum`. However, it is defined as a `struct` in
+/// order to be future-compatible.
+#[repr(transparent)]
+#[derive(Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct SpecialProtocol(u8);
+
+impl fmt::Debug for SpecialProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Format like an `enum`.
+        let known_protocol = match *self {
+            Self::NONE => "NONE",
+            Self::ERROR => "ERROR",
+            _ => return f.debug_tuple("UNKNOWN").field(&self.0).finish(),
+        };
+
+        f.write_str(known_protocol)
+    }
+}
+
+impl SpecialProtocol {
+    /// Not a special protocol.
+    pub const NONE: Self = Self(0);
+
+    /// `Swift.Error`.
+    pub const ERROR: Self = Self(1);
+
+    /// Returns this protocol's inner value.
+    #[inline]
+    pub const fn value(self) -> u8 {
+        self.0
+    }
+}
+
+/// Flags for [`ExistentialTypeMetadata`].
+#[repr(transparent)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ExistentialTypeFlags(u32);
+
+impl fmt::Debug for ExistentialTypeFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ExistentialTypeFlags")
+            .field("num_witness_tables", &self.num_witness_tables())
+            .field("is_class_constrained", &self.is_class_constrained())
+            .field(
+                "has_superclass_constraint",
+                &self.has_superclass_constraint(),
+            )
+            .field("special_protocol", &self.special_protocol())
+            .finish()
+    }
+}
+
+impl ExistentialTypeFlags {
+    const NUM_WITNESS_TABLES_MASK: u32 = 0x00FFFFFF;
+    // Set for `Any` and clear for `Class`.
+    const CLASS_CONSTRAINT: u32 = 0x80000000;
+    const HAS_SUPERCLASS: u32 = 0x40000000;
+    const SPECIAL_PROTOCOL_MASK: u32 = 0x3F000000;
+    const SPECIAL_PROTOCOL_SHIFT: u32 = 24;
+
+    /// Creates a new instance from `bits`.
+    #[inline]
+    pub const fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the underlying bits of `self`.
+    #[inline]
+    pub const fn into_bits(self) -> u32 {
+        self.0
+    }
+
+    /// Returns the number of witness tables that values of the existential
+    /// type store.
+    #[inline]
+    pub const fn num_witness_tables(self) -> usize {
+        (self.0 & Self::NUM_WITNESS_TABLES_MASK) as usize
+    }
+
+    /// Returns `true` if values of the existential type are class instances,
+    /// such as `AnyObject`.
+    #[inline]
+    pub const fn is_class_constrained(self) -> bool {
+        self.0 & Self::CLASS_CONSTRAINT == 0
+    }
+
+    /// Returns `true` if the metadata of a superclass that values must
+    /// inherit from follows the existential type metadata.
+    #[inline]
+    pub const fn has_superclass_constraint(self) -> bool {
+        self.0 & Self::HAS_SUPERCLASS != 0
+    }
+
+    /// Returns the special protocol of the existential type.
+    #[inline]
+    pub const fn special_protocol(self) -> SpecialProtocol {
+        SpecialProtocol(
+            ((self.0 & Self::SPECIAL_PROTOCOL_MASK) >> Self::SPECIAL_PROTOCOL_SHIFT) as u8,
+        )
+    }
+
+    /// Returns the representation of values of the existential type.
+    #[inline]
+    pub const fn representation(self) -> ExistentialTypeRepresentation {
+        if self.special_protocol().0 == SpecialProtocol::ERROR.0 {
+            ExistentialTypeRepresentation::ERROR
+        } else if self.is_class_constrained() {
+            ExistentialTypeRepresentation::CLASS
+        } else {
+            ExistentialTypeRepresentation::OPAQUE
+        }
+    }
+}
+
+/// A reference to a Swift or Objective-C protocol.
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProtocolDescriptorRef(usize);
+
+impl fmt::Debug for ProtocolDescriptorRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.swift_protocol() {
+            Some(protocol) => f.debug_tuple("Swift").field(&protocol).finish(),
+            None => f.debug_tuple("ObjC").field(&self.objc_protocol()).finish(),
+        }
+    }
+}
+
+impl ProtocolDescriptorRef {
+    // Only set with Objective-C interop.
+    const IS_OBJC: usize = 0x1;
+
+    /// Creates a new instance from `bits`.
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the underlying bits of `self`.
+    #[inline]
+    pub const fn into_bits(self) -> usize {
+        self.0
+    }
+
+    /// Returns `true` if this refers to an Objective-C protocol.
+    #[inline]
+    pub const fn is_objc(self) -> bool {
+        cfg!(target_vendor = "apple") && self.0 & Self::IS_OBJC != 0
+    }
+
+    /// Returns the descriptor of the Swift protocol, or `None` if this refers
+    /// to an Objective-C protocol.
+    #[inline]
+    pub fn swift_protocol(self) -> Option<*const ProtocolContextDescriptor> {
+        if self.is_objc() {
+            None
+        } else {
+            Some(self.0 as *const ProtocolContextDescriptor)
+        }
+    }
+
+    /// Returns the Objective-C protocol, or null if this refers to a Swift
+    /// protocol.
+    #[inline]
+    pub fn objc_protocol(self) -> *const c_void {
+        if self.is_objc() {
+            (self.0 & !Self::IS_OBJC) as *const c_void
+        } else {
+            std::ptr::null()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn representation() {
+        // `Any`, `AnyObject`, `Error` and `any Hashable & AnyObject`.
+        let any = ExistentialTypeFlags::from_bits(0x8000_0000);
+        let any_object = ExistentialTypeFlags::from_bits(0);
+        let error = ExistentialTypeFlags::from_bits(0x8100_0001);
+        let hashable_object = ExistentialTypeFlags::from_bits(0x0000_0001);
+
+        assert_eq!(any.representation(), ExistentialTypeRepresentation::OPAQUE);
+        assert_eq!(
+            any_object.representation(),
+            ExistentialTypeRepresentation::CLASS
+        );
+        assert_eq!(error.representation(), ExistentialTypeRepresentation::ERROR);
+        assert_eq!(error.special_protocol(), SpecialProtocol::ERROR);
+        assert_eq!(hashable_object.num_witness_tables(), 1);
+        assert!(hashable_object.is_class_constrained());
+        assert!(!any.is_class_constrained() && !any.has_superclass_constraint());
+    }
+}
@@ -1,10 +1,17 @@
 //! Public runtime functions.
-
-// #![cfg(feature = "link")]
+//!
+//! These are declared without the `link` feature so that crates like
+//! `swift-rt` can refer to them, but `swiftCore` is only linked with it.
 
 use crate::{
-    ctx_desc::TypeContextDescriptor,
-    metadata::{Metadata, MetadataRequest, MetadataResponse},
+    ctx_desc::{
+        ClassDescriptor, ContextDescriptor, GenericEnvironment, MethodDescriptor,
+        ProtocolContextDescriptor, TypeContextDescriptor,
+    },
+    metadata::{
+        ClassMetadata, FunctionTypeFlags, FunctionTypeMetadata, Metadata, MetadataRequest,
+        MetadataResponse, ParameterFlags, TupleTypeFlags, ValueWitnessTable, WitnessTable,
+    },
 };
 use std::os::raw::{c_char, c_void};
 
@@ -22,7 +29,7 @@ pub struct TypeNamePair {
 //
 // TODO: Support the Swift calling convention in rustc
 // See https://github.com/rust-lang/rust/pull/64582
-#[link(name = "swiftCore", kind = "dylib")]
+#[cfg_attr(feature = "link", link(name = "swiftCore", kind = "dylib"))]
 // TODO: `extern "Swift"`
 extern "C" {
     /// Fetch a uniqued metadata object for a generic nominal type.
@@ -32,6 +39,59 @@ extern "C" {
         description: *const TypeContextDescriptor,
     ) -> MetadataResponse;
 
+    /// Fetch a uniqued metadata object for a function type.
+    ///
+    /// `parameters` has the number of parameters in `flags`, and so does
+    /// `parameter_flags` if `flags` has parameter flags.
+    pub fn swift_getFunctionTypeMetadata(
+        flags: FunctionTypeFlags,
+        parameters: *const *const Metadata,
+        parameter_flags: *const ParameterFlags,
+        result: *const Metadata,
+    ) -> *const FunctionTypeMetadata;
+
+    /// Fetch a uniqued metadata object for a tuple type.
+    ///
+    /// `elements` has the number of elements in `flags`. `labels` is either
+    /// null or a string of space-terminated labels for each element, such as
+    /// `"x  z "` for `(x: Int, Int, z: Int)`, which must outlive the metadata
+    /// unless `flags` has non-constant labels.
+    ///
+    /// `proposed_witnesses` may be null to have the runtime create a
+    /// value-witness table for the tuple.
+    pub fn swift_getTupleTypeMetadata(
+        request: MetadataRequest,
+        flags: TupleTypeFlags,
+        elements: *const *const Metadata,
+        labels: *const c_char,
+        proposed_witnesses: *const ValueWitnessTable,
+    ) -> MetadataResponse;
+
+    /// Fetch a uniqued metadata object for a tuple type with two elements.
+    ///
+    /// See [`swift_getTupleTypeMetadata`] for the requirements on `labels`
+    /// and `proposed_witnesses`. Labels are assumed to be constant.
+    pub fn swift_getTupleTypeMetadata2(
+        request: MetadataRequest,
+        element0: *const Metadata,
+        element1: *const Metadata,
+        labels: *const c_char,
+        proposed_witnesses: *const ValueWitnessTable,
+    ) -> MetadataResponse;
+
+    /// Fetch a uniqued metadata object for a tuple type with three elements.
+    ///
+    /// See [`swift_getTupleTypeMetadata`] for the requirements on `labels`
+    /// and `proposed_witnesses`. Labels are assumed to be constant.
+    pub fn swift_getTupleTypeMetadata3(
+        request: MetadataRequest,
+        element0: *const Metadata,
+        element1: *const Metadata,
+        element2: *const Metadata,
+        labels: *const c_char,
+        proposed_witnesses: *const ValueWitnessTable,
+    ) -> MetadataResponse;
+
     /// Returns the name of a Swift type represented by a metadata object.
     pub fn swift_getTypeName(ty: *const Metadata, qualified: bool) -> TypeNamePair;
 
@@ -43,6 +103,45 @@ extern "C" {
     /// **Swift:** 5.3
     pub fn swift_getMangledTypeName(ty: *const Metadata) -> TypeNamePair;
 
+    /// Returns the metadata of the type with a mangled name, or null if it
+    /// can't be found.
+    ///
+    /// Generic parameters in the name refer to `generic_args`, which are the
+    /// arguments of `context`.
+    pub fn swift_getTypeByMangledNameInContext(
+        type_name_start: *const c_char,
+        type_name_length: usize,
+        context: *const ContextDescriptor,
+        generic_args: *const *const c_void,
+    ) -> *const Metadata;
+
+    /// Returns the metadata of the type with a mangled name, or null if it
+    /// can't be found.
+    ///
+    /// Generic parameters in the name refer to `generic_args`, which are the
+    /// arguments of `environment`.
+    pub fn swift_getTypeByMangledNameInEnvironment(
+        type_name_start: *const c_char,
+        type_name_length: usize,
+        environment: *const GenericEnvironment,
+        generic_args: *const *const c_void,
+    ) -> *const Metadata;
+
     /// Returns the context descriptor for a type metadata.
     pub fn swift_getTypeContextDescriptor(ty: *const Metadata) -> *const TypeContextDescriptor;
+
+    /// Returns the witness table of the conformance of a type to a protocol,
+    /// or null if the type doesn't conform to it.
+    pub fn swift_conformsToProtocol(
+        ty: *const Metadata,
+        protocol: *const ProtocolContextDescriptor,
+    ) -> *const WitnessTable;
+
+    /// Returns the implementation of a method in the vtable of class
+    /// metadata, which may be an override of the method in `description`.
+    pub fn swift_lookUpClassMethod(
+        metadata: *const ClassMetadata,
+        method: *const MethodDescriptor,
+        description: *const ClassDescriptor,
+    ) -> *const c_void;
 }
@@ -0,0 +1,419 @@
+use crate::metadata::Metadata;
+use std::fmt;
+
+/// Metadata for function types.
+///
+/// The metadata of each parameter follows this header, then the
+/// [`ParameterFlags`] of each parameter if [`FunctionTypeFlags::has_param_flags`],
+/// then the differentiability kind if
+/// [`FunctionTypeFlags::is_differentiable`], and then the global actor's
+/// metadata if [`FunctionTypeFlags::has_global_actor`].
+///
+/// This type deliberately does not implement [`Copy`] in order to avoid
+/// accidentally dereferencing from the wrong location.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FunctionTypeMetadata {
+    /// The base metadata.
+    pub base: Metadata,
+
+    /// Flags describing the function type.
+    pub flags: FunctionTypeFlags,
+
+    /// The metadata of the result type.
+    pub result_type: *const Metadata,
+}
+
+/// The calling convention of a function type.
+///
+/// This is semantically an `enum`. However, it is defined as a `struct` in
+/// order to be future-compatible.
+#[repr(transparent)]
+#[derive(Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct FunctionMetadataConvention(u8);
+
+impl fmt::Debug for FunctionMetadataConvention {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Format like an `enum`.
+        let known_convention = match *self {
+            Self::SWIFT => "SWIFT",
+            Self::BLOCK => "BLOCK",
+            Self::THIN => "THIN",
+            Self::C => "C",
+            _ => return f.debug_tuple("UNKNOWN").field(&self.0).finish(),
+        };
+
+        f.write_str(known_convention)
+    }
+}
+
+impl FunctionMetadataConvention {
+    /// A Swift closure, which has a context.
+    pub const SWIFT: Self = Self(0);
+
+    /// An Objective-C block.
+    pub const BLOCK: Self = Self(1);
+
+    /// A Swift function without a context, such as `@convention(thin)`.
+    pub const THIN: Self = Self(2);
+
+    /// A C function pointer, such as `@convention(c)`.
+    pub const C: Self = Self(3);
+
+    /// Creates a new instance from `value`.
+    #[inline]
+    pub const fn new(value: u8) -> Self {
+        Self(value)
+    }
+
+    /// Returns this convention's inner value.
+    #[inline]
+    pub const fn value(self) -> u8 {
+        self.0
+    }
+}
+
+/// Flags for [`FunctionTypeMetadata`].
+#[repr(transparent)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FunctionTypeFlags(usize);
+
+impl fmt::Debug for FunctionTypeFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FunctionTypeFlags")
+            .field("num_params", &self.num_params())
+            .field("convention", &self.convention())
+            .field("is_throwing", &self.is_throwing())
+            .field("has_param_flags", &self.has_param_flags())
+            .field("is_escaping", &self.is_escaping())
+            .field("is_differentiable", &self.is_differentiable())
+            .field("has_global_actor", &self.has_global_actor())
+            .field("is_async", &self.is_async())
+            .field("is_sendable", &self.is_sendable())
+            .finish()
+    }
+}
+
+impl FunctionTypeFlags {
+    const NUM_PARAMS_MASK: usize = 0x0000FFFF;
+    const CONVENTION_MASK: usize = 0x00FF0000;
+    const CONVENTION_SHIFT: usize = 16;
+    const THROWS: usize = 0x01000000;
+    const PARAM_FLAGS: usize = 0x02000000;
+    const ESCAPING: usize = 0x04000000;
+    const DIFFERENTIABLE: usize = 0x08000000;
+    const GLOBAL_ACTOR: usize = 0x10000000;
+    const ASYNC: usize = 0x20000000;
+    const SENDABLE: usize = 0x40000000;
+
+    /// Creates a new instance without any bits set.
+    #[inline]
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Creates a new instance from `bits`.
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the underlying bits of `self`.
+    #[inline]
+    pub const fn into_bits(self) -> usize {
+        self.0
+    }
+
+    #[inline]
+    const fn with_flag(self, flag: usize, value: bool) -> Self {
+        Self((self.0 & !flag) | (flag * value as usize))
+    }
+
+    /// Returns the number of parameters.
+    #[inline]
+    pub const fn num_params(self) -> usize {
+        self.0 & Self::NUM_PARAMS_MASK
+    }
+
+    /// Returns the calling convention.
+    #[inline]
+    pub const fn convention(self) -> FunctionMetadataConvention {
+        FunctionMetadataConvention(
+            ((self.0 & Self::CONVENTION_MASK) >> Self::CONVENTION_SHIFT) as u8,
+        )
+    }
+
+    /// Returns `true` if the function `throws`.
+    #[inline]
+    pub const fn is_throwing(self) -> bool {
+        self.0 & Self::THROWS != 0
+    }
+
+    /// Returns `true` if [`ParameterFlags`] follow the parameters.
+    #[inline]
+    pub const fn has_param_flags(self) -> bool {
+        self.0 & Self::PARAM_FLAGS != 0
+    }
+
+    /// Returns `true` if the function is `@escaping`.
+    #[inline]
+    pub const fn is_escaping(self) -> bool {
+        self.0 & Self::ESCAPING != 0
+    }
+
+    /// Returns `true` if the function is `@differentiable`.
+    #[inline]
+    pub const fn is_differentiable(self) -> bool {
+        self.0 & Self::DIFFERENTIABLE != 0
+    }
+
+    /// Returns `true` if the function is isolated to a global actor.
+    #[inline]
+    pub const fn has_global_actor(self) -> bool {
+        self.0 & Self::GLOBAL_ACTOR != 0
+    }
+
+    /// Returns `true` if the function is `async`.
+    #[inline]
+    pub const fn is_async(self) -> bool {
+        self.0 & Self::ASYNC != 0
+    }
+
+    /// Returns `true` if the function is `@Sendable`.
+    #[inline]
+    pub const fn is_sendable(self) -> bool {
+        self.0 & Self::SENDABLE != 0
+    }
+
+    /// Returns `self` with `num_params` set.
+    ///
+    /// Only the low 16 bits of `num_params` are used.
+    #[inline]
+    pub const fn with_num_params(self, num_params: usize) -> Self {
+        Self((self.0 & !Self::NUM_PARAMS_MASK) | (num_params & Self::NUM_PARAMS_MASK))
+    }
+
+    /// Returns `self` with `convention` set.
+    #[inline]
+    pub const fn with_convention(self, convention: FunctionMetadataConvention) -> Self {
+        Self(
+            (self.0 & !Self::CONVENTION_MASK) | ((convention.0 as usize) << Self::CONVENTION_SHIFT),
+        )
+    }
+
+    /// Returns `self` with `is_throwing` set.
+    #[inline]
+    pub const fn with_throwing(self, is_throwing: bool) -> Self {
+        self.with_flag(Self::THROWS, is_throwing)
+    }
+
+    /// Returns `self` with `has_param_flags` set.
+    #[inline]
+    pub const fn with_param_flags(self, has_param_flags: bool) -> Self {
+        self.with_flag(Self::PARAM_FLAGS, has_param_flags)
+    }
+
+    /// Returns `self` with `is_escaping` set.
+    #[inline]
+    pub const fn with_escaping(self, is_escaping: bool) -> Self {
+        self.with_flag(Self::ESCAPING, is_escaping)
+    }
+
+    /// Returns `self` with `is_async` set.
+    #[inline]
+    pub const fn with_async(self, is_async: bool) -> Self {
+        self.with_flag(Self::ASYNC, is_async)
+    }
+
+    /// Returns `self` with `is_sendable` set.
+    #[inline]
+    pub const fn with_sendable(self, is_sendable: bool) -> Self {
+        self.with_flag(Self::SENDABLE, is_sendable)
+    }
+}
+
+/// The ownership of a function parameter.
+///
+/// This is semantically an `enum`. However, it is defined as a `struct` in
+/// order to be future-compatible.
+#[repr(transparent)]
+#[derive(Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct ValueOwnership(u8);
+
+impl fmt::Debug for ValueOwnership {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Format like an `enum`.
+        let known_ownership = match *self {
+            Self::DEFAULT => "DEFAULT",
+            Self::INOUT => "INOUT",
+            Self::SHARED => "SHARED",
+            Self::OWNED => "OWNED",
+            _ => return f.debug_tuple("UNKNOWN").field(&self.0).finish(),
+        };
+
+        f.write_str(known_ownership)
+    }
+}
+
+impl ValueOwnership {
+    /// The default ownership of the parameter's convention.
+    pub const DEFAULT: Self = Self(0);
+
+    /// An `inout` parameter.
+    pub const INOUT: Self = Self(1);
+
+    /// A `__shared` (borrowed) parameter.
+    pub const SHARED: Self = Self(2);
+
+    /// A `__owned` (consumed) parameter.
+    pub const OWNED: Self = Self(3);
+
+    /// Creates a new instance from `value`.
+    #[inline]
+    pub const fn new(value: u8) -> Self {
+        Self(value)
+    }
+
+    /// Returns this ownership's inner value.
+    #[inline]
+    pub const fn value(self) -> u8 {
+        self.0
+    }
+}
+
+/// Flags for a parameter of a [`FunctionTypeMetadata`].
+#[repr(transparent)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ParameterFlags(u32);
+
+impl fmt::Debug for ParameterFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ParameterFlags")
+            .field("ownership", &self.ownership())
+            .field("is_variadic", &self.is_variadic())
+            .field("is_autoclosure", &self.is_autoclosure())
+            .field("is_no_derivative", &self.is_no_derivative())
+            .field("is_isolated", &self.is_isolated())
+            .finish()
+    }
+}
+
+impl ParameterFlags {
+    const OWNERSHIP_MASK: u32 = 0x7F;
+    const VARIADIC: u32 = 0x80;
+    const AUTOCLOSURE: u32 = 0x100;
+    const NO_DERIVATIVE: u32 = 0x200;
+    const ISOLATED: u32 = 0x400;
+
+    /// Creates a new instance without any bits set.
+    #[inline]
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Creates a new instance from `bits`.
+    #[inline]
+    pub const fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the underlying bits of `self`.
+    #[inline]
+    pub const fn into_bits(self) -> u32 {
+        self.0
+    }
+
+    /// Returns the ownership of the parameter.
+    #[inline]
+    pub const fn ownership(self) -> ValueOwnership {
+        ValueOwnership((self.0 & Self::OWNERSHIP_MASK) as u8)
+    }
+
+    /// Returns `true` if the parameter is `inout`.
+    #[inline]
+    pub const fn is_inout(self) -> bool {
+        self.ownership().0 == ValueOwnership::INOUT.0
+    }
+
+    /// Returns `true` if the parameter is `__owned`.
+    #[inline]
+    pub const fn is_owned(self) -> bool {
+        self.ownership().0 == ValueOwnership::OWNED.0
+    }
+
+    /// Returns `true` if the parameter is variadic, such as `Int...`.
+    #[inline]
+    pub const fn is_variadic(self) -> bool {
+        self.0 & Self::VARIADIC != 0
+    }
+
+    /// Returns `true` if the parameter is `@autoclosure`.
+    #[inline]
+    pub const fn is_autoclosure(self) -> bool {
+        self.0 & Self::AUTOCLOSURE != 0
+    }
+
+    /// Returns `true` if the parameter is `@noDerivative`.
+    #[inline]
+    pub const fn is_no_derivative(self) -> bool {
+        self.0 & Self::NO_DERIVATIVE != 0
+    }
+
+    /// Returns `true` if the parameter is `isolated`.
+    #[inline]
+    pub const fn is_isolated(self) -> bool {
+        self.0 & Self::ISOLATED != 0
+    }
+
+    /// Returns `self` with `ownership` set.
+    #[inline]
+    pub const fn with_ownership(self, ownership: ValueOwnership) -> Self {
+        Self((self.0 & !Self::OWNERSHIP_MASK) | (ownership.0 as u32 & Self::OWNERSHIP_MASK))
+    }
+
+    /// Returns `self` with `is_variadic` set.
+    #[inline]
+    pub const fn with_variadic(self, is_variadic: bool) -> Self {
+        Self((self.0 & !Self::VARIADIC) | (Self::VARIADIC * is_variadic as u32))
+    }
+
+    /// Returns `self` with `is_autoclosure` set.
+    #[inline]
+    pub const fn with_autoclosure(self, is_autoclosure: bool) -> Self {
+        Self((self.0 & !Self::AUTOCLOSURE) | (Self::AUTOCLOSURE * is_autoclosure as u32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn function_type_flags() {
+        let flags = FunctionTypeFlags::empty()
+            .with_num_params(3)
+            .with_convention(FunctionMetadataConvention::C)
+            .with_escaping(true)
+            .with_throwing(true);
+        assert_eq!(flags.into_bits(), 0x0503_0003);
+        assert_eq!(flags.num_params(), 3);
+        assert_eq!(flags.convention(), FunctionMetadataConvention::C);
+        assert!(flags.is_escaping() && flags.is_throwing());
+        assert!(!flags.is_async() && !flags.has_param_flags());
+
+        let flags = flags.with_throwing(false).with_num_params(0x1_0001);
+        assert_eq!(flags.num_params(), 1);
+        assert!(!flags.is_throwing());
+    }
+
+    #[test]
+    fn parameter_flags() {
+        let flags = ParameterFlags::empty()
+            .with_ownership(ValueOwnership::INOUT)
+            .with_variadic(true);
+        assert_eq!(flags.into_bits(), 0x81);
+        assert!(flags.is_inout() && flags.is_variadic());
+        assert!(!flags.is_owned() && !flags.is_autoclosure());
+        assert!(ParameterFlags::from_bits(0x3).is_owned());
+    }
+}
@@ -1,6 +1,10 @@
 use crate::{
-    ctx_desc::TypeContextDescriptor,
-    metadata::{fns, MetadataKind, MetadataRequest, MetadataResponse, ValueWitnessTable},
+    ctx_desc::{
+        ContextDescriptor, GenericEnvironment, ProtocolContextDescriptor, TypeContextDescriptor,
+    },
+    metadata::{
+        fns, MetadataKind, MetadataRequest, MetadataResponse, ValueWitnessTable, WitnessTable,
+    },
 };
 use std::{ffi::c_void, ptr, slice, str};
 
@@ -34,6 +38,48 @@ impl Metadata {
         fns::swift_getGenericMetadata(request, arguments, description)
     }
 
+    /// Returns the metadata of the type with a mangled name, or null if it
+    /// can't be found.
+    ///
+    /// # Safety
+    ///
+    /// - `name` must point to `len` bytes of a mangled name in its original
+    ///   location, since symbolic references are relative to it.
+    ///
+    /// - `context` must be null or reference a valid context descriptor, and
+    ///   `generic_args` must point to its generic arguments if it's generic.
+    #[inline]
+    #[doc(alias = "swift_getTypeByMangledNameInContext")]
+    pub unsafe fn from_mangled_name_in_context(
+        name: *const u8,
+        len: usize,
+        context: *const ContextDescriptor,
+        generic_args: *const *const c_void,
+    ) -> *const Self {
+        fns::swift_getTypeByMangledNameInContext(name.cast(), len, context, generic_args)
+    }
+
+    /// Returns the metadata of the type with a mangled name, or null if it
+    /// can't be found.
+    ///
+    /// # Safety
+    ///
+    /// - `name` must point to `len` bytes of a mangled name in its original
+    ///   location, since symbolic references are relative to it.
+    ///
+    /// - `environment` must be null or reference a valid generic environment,
+    ///   and `generic_args` must point to its generic arguments.
+    #[inline]
+    #[doc(alias = "swift_getTypeByMangledNameInEnvironment")]
+    pub unsafe fn from_mangled_name_in_environment(
+        name: *const u8,
+        len: usize,
+        environment: *const GenericEnvironment,
+        generic_args: *const *const c_void,
+    ) -> *const Self {
+        fns::swift_getTypeByMangledNameInEnvironment(name.cast(), len, environment, generic_args)
+    }
+
     /// Returns the name of a Swift type represented by a metadata object.
     #[inline]
     #[doc(alias = "swift_getTypeName")]
@@ -80,6 +126,22 @@ impl Metadata {
         fns::swift_getTypeContextDescriptor(this)
     }
 
+    /// Returns the witness table of the type's conformance to `protocol`, or
+    /// null if it doesn't conform.
+    ///
+    /// # Safety
+    ///
+    /// The raw pointers must reference valid type metadata and a valid
+    /// protocol descriptor.
+    #[inline]
+    #[doc(alias = "swift_conformsToProtocol")]
+    pub unsafe fn conformance(
+        this: *const Self,
+        protocol: *const ProtocolContextDescriptor,
+    ) -> *const WitnessTable {
+        fns::swift_conformsToProtocol(this, protocol)
+    }
+
     /// Returns the kind of this metadata.
     #[inline]
     pub fn kind(&self) -> MetadataKind {
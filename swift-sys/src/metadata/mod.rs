@@ -10,7 +10,10 @@
 pub mod fns;
 
 mod access_function;
+mod class;
 mod enum_;
+mod existential;
+mod function;
 mod kind;
 mod metadata;
 mod metatype;
@@ -20,9 +23,13 @@ mod state;
 mod struct_;
 mod tuple;
 mod value_witness;
+mod witness_table;
 
 pub use access_function::*;
+pub use class::*;
 pub use enum_::*;
+pub use existential::*;
+pub use function::*;
 pub use kind::*;
 pub use metadata::*;
 pub use metatype::*;
@@ -32,3 +39,4 @@ pub use state::*;
 pub use struct_::*;
 pub use tuple::*;
 pub use value_witness::*;
+pub use witness_table::*;
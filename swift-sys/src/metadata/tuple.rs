@@ -1,5 +1,5 @@
 use crate::metadata::Metadata;
-use std::{os::raw::c_char, slice};
+use std::{fmt, os::raw::c_char, slice};
 
 /// Metadata for tuples.
 ///
@@ -76,3 +76,85 @@ def_element_offset! {
     ///
     /// This is a [`usize`] on Apple targets, and a [`u32`] everywhere else.
 }
+
+/// Flags for requesting [`TupleMetadata`].
+#[repr(transparent)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TupleTypeFlags(usize);
+
+impl fmt::Debug for TupleTypeFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TupleTypeFlags")
+            .field("num_elements", &self.num_elements())
+            .field("has_non_constant_labels", &self.has_non_constant_labels())
+            .finish()
+    }
+}
+
+impl TupleTypeFlags {
+    const NUM_ELEMENTS_MASK: usize = 0x0000FFFF;
+    const NON_CONSTANT_LABELS: usize = 0x00010000;
+
+    /// Creates a new instance without any bits set.
+    #[inline]
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Creates a new instance from `bits`.
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the underlying bits of `self`.
+    #[inline]
+    pub const fn into_bits(self) -> usize {
+        self.0
+    }
+
+    /// Returns the number of elements.
+    #[inline]
+    pub const fn num_elements(self) -> usize {
+        self.0 & Self::NUM_ELEMENTS_MASK
+    }
+
+    /// Returns `true` if the labels string may be deallocated after the
+    /// request, in which case the runtime copies it into new metadata.
+    #[inline]
+    pub const fn has_non_constant_labels(self) -> bool {
+        self.0 & Self::NON_CONSTANT_LABELS != 0
+    }
+
+    /// Returns `self` with `num_elements` set.
+    ///
+    /// Only the low 16 bits of `num_elements` are used.
+    #[inline]
+    pub const fn with_num_elements(self, num_elements: usize) -> Self {
+        Self((self.0 & !Self::NUM_ELEMENTS_MASK) | (num_elements & Self::NUM_ELEMENTS_MASK))
+    }
+
+    /// Returns `self` with whether the labels string is non-constant set.
+    #[inline]
+    pub const fn with_non_constant_labels(self, value: bool) -> Self {
+        Self((self.0 & !Self::NON_CONSTANT_LABELS) | (Self::NON_CONSTANT_LABELS * value as usize))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags() {
+        let flags = TupleTypeFlags::empty()
+            .with_num_elements(3)
+            .with_non_constant_labels(true);
+        assert_eq!(flags.into_bits(), 0x1_0003);
+        assert_eq!(flags.num_elements(), 3);
+        assert!(flags.has_non_constant_labels());
+        assert!(!flags
+            .with_non_constant_labels(false)
+            .has_non_constant_labels());
+    }
+}
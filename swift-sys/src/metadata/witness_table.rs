@@ -0,0 +1,22 @@
+use std::ffi::c_void;
+
+/// A table of the witnesses that satisfy the requirements of a protocol for a
+/// conforming type.
+///
+/// The conformance descriptor is followed by a pointer for each requirement of
+/// the protocol, in the order they're laid out by the compiler.
+#[repr(C)]
+#[derive(Debug)]
+pub struct WitnessTable {
+    /// The protocol conformance descriptor.
+    pub description: *const c_void,
+}
+
+impl WitnessTable {
+    /// Returns a pointer to the witnesses that follow the conformance
+    /// descriptor, from the pointer to a witness table.
+    #[inline]
+    pub fn witnesses_ptr(this: *const Self) -> *const *const c_void {
+        this.cast::<*const c_void>().wrapping_add(1)
+    }
+}
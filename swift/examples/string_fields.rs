@@ -1,8 +1,29 @@
 use swift::String;
-use swift_rt::metadata::Type;
+use swift_rt::{mangling::Mangled, metadata::Type};
 
 fn main() {
-    let fields = String::get_metadata().type_descriptor().fields().unwrap();
+    let descriptor = String::get_metadata().type_descriptor();
+    let fields = descriptor.fields().unwrap();
 
+    // Print the stored properties like a Swift declaration.
+    match descriptor.demangle() {
+        Some(name) => println!("struct {} {{", name),
+        None => println!("struct {{"),
+    }
+    for record in fields.field_records() {
+        let keyword = if record.flags().is_var() {
+            "var"
+        } else {
+            "let"
+        };
+        let name = record.field_name().unwrap_or("_");
+        match record.type_name().and_then(Mangled::demangle) {
+            Some(ty) => println!("    {} {}: {}", keyword, name, ty),
+            None => println!("    {} {}", keyword, name),
+        }
+    }
+    println!("}}");
+
+    println!();
     println!("{:#?}", fields);
 }
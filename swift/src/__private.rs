@@ -0,0 +1,7 @@
+//! Items used by macro expansions. Not public API.
+
+use swift_rt::metadata::Type;
+
+/// Fails to compile if `T` has no Swift metadata.
+#[inline]
+pub fn assert_type<T: Type>() {}
@@ -0,0 +1,206 @@
+use crate::AnyType;
+use std::{
+    ffi::c_void,
+    fmt,
+    mem::{self, MaybeUninit},
+    ptr::{self, NonNull},
+};
+use swift_rt::metadata::{ExistentialTypeMetadata, Metadata, OpaqueExistentialContainer, Type};
+use swift_sys::heap::fns::swift_allocBox;
+
+/// The protocol to which all types implicitly conform.
+///
+/// See [documentation](https://developer.apple.com/documentation/swift/any).
+///
+/// # Discussion
+///
+/// A value of type `Any` is stored in a three-word buffer alongside the
+/// metadata of its type. Values that don't fit in the buffer are stored in a
+/// heap box, which is shared between copies.
+///
+/// ```swift
+/// let values: [Any] = [1, "two", 3.0]
+/// ```
+#[repr(transparent)]
+pub struct Any {
+    raw: OpaqueExistentialContainer,
+}
+
+impl Drop for Any {
+    #[inline]
+    fn drop(&mut self) {
+        let metadata = Self::get_metadata().as_metadata();
+
+        unsafe { metadata.vw_destroy(self) };
+    }
+}
+
+impl Clone for Any {
+    #[inline]
+    fn clone(&self) -> Self {
+        let metadata = Self::get_metadata().as_metadata();
+
+        unsafe {
+            let mut clone = MaybeUninit::<Self>::uninit();
+            metadata.vw_initialize_with_copy(clone.as_mut_ptr(), self);
+            clone.assume_init()
+        }
+    }
+}
+
+impl Type for Any {
+    type Metadata = ExistentialTypeMetadata;
+
+    #[inline]
+    fn get_metadata() -> &'static Self::Metadata {
+        // TODO: Expose full metadata type.
+        #[repr(C)]
+        struct FullMetadata {
+            header: usize,
+            metadata: ExistentialTypeMetadata,
+        }
+
+        extern "C" {
+            #[link_name = "$sypN"]
+            static METADATA: FullMetadata;
+        }
+
+        unsafe { &METADATA.metadata }
+    }
+
+    #[inline]
+    fn get_metadata_blocking(_blocking: bool) -> Option<&'static Self::Metadata> {
+        Some(Self::get_metadata())
+    }
+}
+
+impl fmt::Debug for Any {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Any").field(&self.get_type()).finish()
+    }
+}
+
+impl Any {
+    /// Wraps `value` in an existential container, boxing it if it doesn't fit
+    /// inline.
+    pub fn new<T: Type>(value: T) -> Self {
+        let metadata = T::get_metadata().as_ref();
+        let mut raw = OpaqueExistentialContainer {
+            buffer: [ptr::null_mut(); 3],
+            ty: metadata.as_raw(),
+        };
+
+        unsafe {
+            let dest: *mut T = if metadata.value_witnesses().is_value_inline() {
+                raw.buffer.as_mut_ptr().cast()
+            } else {
+                let pair = swift_allocBox(metadata.as_raw());
+                raw.buffer[0] = pair.object;
+                pair.buffer.cast()
+            };
+            dest.write(value);
+        }
+
+        Self { raw }
+    }
+
+    /// Returns a reference to the inner raw existential container.
+    #[inline]
+    pub const fn as_raw(&self) -> &OpaqueExistentialContainer {
+        &self.raw
+    }
+
+    /// Returns the metadata of the type of the contained value.
+    #[inline]
+    pub fn metadata(&self) -> &'static Metadata {
+        unsafe { &*self.raw.ty.cast() }
+    }
+
+    /// Returns the type of the contained value.
+    ///
+    /// Unlike [`type(of:)`][docs], this is the type that the value had when it
+    /// was wrapped, so class instances are not inspected for a subclass.
+    ///
+    /// [docs]: https://developer.apple.com/documentation/swift/2885064-type
+    #[inline]
+    pub fn get_type(&self) -> AnyType {
+        unsafe { AnyType::from_metadata(NonNull::from(self.metadata())) }
+    }
+
+    /// Returns `true` if the contained value is of type `T`.
+    #[inline]
+    pub fn is<T: Type>(&self) -> bool {
+        ptr::eq(self.metadata(), T::get_metadata().as_ref())
+    }
+
+    /// Returns a pointer to the contained value, which is either inline or in
+    /// a heap box.
+    #[inline]
+    pub fn as_ptr(&self) -> *const c_void {
+        let value_witnesses = self.metadata().value_witnesses();
+        if value_witnesses.is_value_inline() {
+            return self.raw.buffer.as_ptr().cast();
+        }
+
+        // The value follows the header of the box, which is a metadata
+        // pointer and a reference count.
+        let header_size = 2 * mem::size_of::<usize>();
+        let align_mask = value_witnesses.flags.align_mask();
+        let offset = (header_size + align_mask) & !align_mask;
+        unsafe { self.raw.buffer[0].cast::<u8>().add(offset).cast() }
+    }
+
+    /// Returns a reference to the contained value if it is of type `T`.
+    #[inline]
+    pub fn downcast_ref<T: Type>(&self) -> Option<&T> {
+        if self.is::<T>() {
+            Some(unsafe { &*self.as_ptr().cast() })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a copy of the contained value if it is of type `T`, or `self`
+    /// otherwise.
+    ///
+    /// The value is copied with its value witnesses, since a boxed value may
+    /// be shared with other containers.
+    #[inline]
+    pub fn downcast<T: Type>(self) -> Result<T, Self> {
+        if !self.is::<T>() {
+            return Err(self);
+        }
+
+        unsafe {
+            let mut value = MaybeUninit::<T>::uninit();
+            self.metadata()
+                .vw_initialize_with_copy(value.as_mut_ptr(), self.as_ptr().cast::<T>());
+            Ok(value.assume_init())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::String;
+
+    #[test]
+    fn inline() {
+        let any = Any::new(42isize);
+        assert!(any.is::<isize>());
+        assert!(!any.is::<String>());
+        assert_eq!(any.downcast_ref::<isize>(), Some(&42));
+        assert_eq!(any.clone().downcast::<isize>().ok(), Some(42));
+        assert!(any.downcast::<u8>().is_err());
+    }
+
+    #[test]
+    fn clone_drop() {
+        let any = Any::new(String::from('a'));
+        let clone = any.clone();
+        drop(any);
+        assert!(clone.downcast_ref::<String>() == Some(&String::from('a')));
+    }
+}
@@ -1,4 +1,6 @@
 use crate::{Equatable, Hasher, ObjectIdentifier};
+use std::ffi::c_void;
+use swift_rt::call::SwiftCall;
 
 /// A type that can be hashed into a [`Hasher`] to produce an integer hash
 /// value.
@@ -15,33 +17,39 @@ pub unsafe trait Hashable: Equatable {
     /// given hasher.
     ///
     /// See [documentation](https://developer.apple.com/documentation/swift/hashable/2995575-hash).
-    ///
-    /// # Panics
-    ///
-    /// Implementations of this function may panic if the `asm` feature is not
-    /// enabled.
     fn hash(self, hasher: &mut Hasher);
 }
 
 mod sys {
     use super::*;
 
-    #[cfg_attr(not(feature = "asm"), allow(unused))]
     extern "C" {
         #[link_name = "$ss6HasherV8_combineyySuF"]
-        pub fn combine_UInt(hasher: *mut Hasher, value: usize);
+        pub fn combine_UInt();
 
         #[link_name = "$ss6HasherV8_combineyys5UInt8VF"]
-        pub fn combine_UInt8(hasher: *mut Hasher, value: u8);
+        pub fn combine_UInt8();
 
         #[link_name = "$ss6HasherV8_combineyys6UInt16VF"]
-        pub fn combine_UInt16(hasher: *mut Hasher, value: u16);
+        pub fn combine_UInt16();
 
         #[link_name = "$ss6HasherV8_combineyys6UInt32VF"]
-        pub fn combine_UInt32(hasher: *mut Hasher, value: u32);
+        pub fn combine_UInt32();
 
         #[link_name = "$ss6HasherV8_combineyys6UInt64VF"]
-        pub fn combine_UInt64(hasher: *mut Hasher, value: u64);
+        pub fn combine_UInt64();
+    }
+
+    /// Calls a `Hasher._combine` method, which takes the hasher in the context
+    /// register.
+    #[inline]
+    pub fn combine(hasher: &mut Hasher, combine: unsafe extern "C" fn(), value: u64) {
+        unsafe {
+            SwiftCall::new(combine as *const c_void)
+                .context(hasher)
+                .int(value)
+                .call();
+        }
     }
 }
 
@@ -52,26 +60,10 @@ unsafe impl Hashable for ObjectIdentifier {
     }
 }
 
-#[cfg_attr(not(feature = "asm"), allow(unused))]
 unsafe impl Hashable for usize {
     #[inline]
     fn hash(self, hasher: &mut Hasher) {
-        unsafe {
-            arch_asm! {
-                "aarch64" => {
-                    "bl {}",
-                    sym sys::combine_UInt,
-                    in("x20") hasher,
-                    in("x1") self,
-                }
-                "x86_64" => {
-                    "call {}",
-                    sym sys::combine_UInt,
-                    in("r13") hasher,
-                    in("rsi") self,
-                }
-            }
-        }
+        sys::combine(hasher, sys::combine_UInt, self as u64);
     }
 }
 
@@ -82,26 +74,10 @@ unsafe impl Hashable for isize {
     }
 }
 
-#[cfg_attr(not(feature = "asm"), allow(unused))]
 unsafe impl Hashable for u8 {
     #[inline]
     fn hash(self, hasher: &mut Hasher) {
-        unsafe {
-            arch_asm! {
-                "aarch64" => {
-                    "bl {}",
-                    sym sys::combine_UInt8,
-                    in("x20") hasher,
-                    in("x1") self,
-                }
-                "x86_64" => {
-                    "call {}",
-                    sym sys::combine_UInt8,
-                    in("r13") hasher,
-                    in("sil") self, // 8-bit rsi
-                }
-            }
-        }
+        sys::combine(hasher, sys::combine_UInt8, self as u64);
     }
 }
 
@@ -112,26 +88,10 @@ unsafe impl Hashable for i8 {
     }
 }
 
-#[cfg_attr(not(feature = "asm"), allow(unused))]
 unsafe impl Hashable for u16 {
     #[inline]
     fn hash(self, hasher: &mut Hasher) {
-        unsafe {
-            arch_asm! {
-                "aarch64" => {
-                    "bl {}",
-                    sym sys::combine_UInt16,
-                    in("x20") hasher,
-                    in("x1") self,
-                }
-                "x86_64" => {
-                    "call {}",
-                    sym sys::combine_UInt16,
-                    in("r13") hasher,
-                    in("si") self, // 16-bit rsi
-                }
-            }
-        }
+        sys::combine(hasher, sys::combine_UInt16, self as u64);
     }
 }
 
@@ -142,26 +102,10 @@ unsafe impl Hashable for i16 {
     }
 }
 
-#[cfg_attr(not(feature = "asm"), allow(unused))]
 unsafe impl Hashable for u32 {
     #[inline]
     fn hash(self, hasher: &mut Hasher) {
-        unsafe {
-            arch_asm! {
-                "aarch64" => {
-                    "bl {}",
-                    sym sys::combine_UInt32,
-                    in("x20") hasher,
-                    in("x1") self,
-                }
-                "x86_64" => {
-                    "call {}",
-                    sym sys::combine_UInt32,
-                    in("r13") hasher,
-                    in("esi") self, // 32-bit rsi
-                }
-            }
-        }
+        sys::combine(hasher, sys::combine_UInt32, self as u64);
     }
 }
 
@@ -172,26 +116,10 @@ unsafe impl Hashable for i32 {
     }
 }
 
-#[cfg_attr(not(feature = "asm"), allow(unused))]
 unsafe impl Hashable for u64 {
     #[inline]
     fn hash(self, hasher: &mut Hasher) {
-        unsafe {
-            arch_asm! {
-                "aarch64" => {
-                    "bl {}",
-                    sym sys::combine_UInt64,
-                    in("x20") hasher,
-                    in("x1") self,
-                }
-                "x86_64" => {
-                    "call {}",
-                    sym sys::combine_UInt64,
-                    in("r13") hasher,
-                    in("rsi") self,
-                }
-            }
-        }
+        sys::combine(hasher, sys::combine_UInt64, self);
     }
 }
 
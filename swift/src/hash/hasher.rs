@@ -1,5 +1,9 @@
 use crate::{Hashable, Int, UnsafeRawBufferPointer};
-use std::mem::{self, MaybeUninit};
+use std::{
+    ffi::c_void,
+    mem::{self, MaybeUninit},
+};
+use swift_rt::call::SwiftCall;
 
 /// The universal hash function used by [`Set`](crate::Set) and
 /// [`Dictionary`](crate::Dictionary).
@@ -95,11 +99,6 @@ impl Hasher {
     /// hasher state.
     ///
     /// See [documentation](https://developer.apple.com/documentation/swift/hasher/2995578-combine).
-    ///
-    /// # Panics
-    ///
-    /// Implementations of this function may panic if the `asm` feature is not
-    /// enabled.
     #[inline]
     pub fn combine<H: Hashable>(&mut self, value: H) {
         value.hash(self);
@@ -109,85 +108,44 @@ impl Hasher {
     /// hasher state.
     ///
     /// See [documentation](https://developer.apple.com/documentation/swift/hasher/2995579-combine).
-    ///
-    /// # Panics
-    ///
-    /// Implementations of this function may panic if the `asm` feature is not
-    /// enabled.
     #[inline]
     pub fn combine_bytes(&mut self, bytes: &[u8]) {
-        #[allow(unused)]
         extern "C" {
             #[link_name = "$ss6HasherV7combine5bytesySW_tF"]
-            fn combine(hasher: *mut Hasher, bytes: UnsafeRawBufferPointer);
+            fn combine();
         }
 
         unsafe {
-            #[allow(unused)]
-            let [arg0, arg1]: [usize; 2] = mem::transmute(UnsafeRawBufferPointer::from(bytes));
-
-            arch_asm! {
-                "aarch64" => {
-                    "bl {}",
-                    sym combine,
-                    in("x20") self,
-                    in("x1") arg0,
-                    in("x2") arg1,
-                }
-                "x86_64" => {
-                    "call {}",
-                    sym combine,
-                    in("r13") self,
-                    in("rsi") arg0,
-                    in("rdx") arg1,
-                }
-            }
+            let [start, count]: [u64; 2] = mem::transmute(UnsafeRawBufferPointer::from(bytes));
+
+            SwiftCall::new(combine as *const c_void)
+                .context(self)
+                .int(start)
+                .int(count)
+                .call();
         }
     }
 
     /// Finalizes the hasher state and returns the hash value.
     ///
     /// See [documentation](https://developer.apple.com/documentation/swift/hasher/2995580-finalize).
-    ///
-    /// # Panics
-    ///
-    /// Implementations of this function may panic if the `asm` feature is not
-    /// enabled.
     #[inline]
     pub fn finalize(self) -> Int {
-        // TODO: Remove when `asm!` is stabilized.
-        // See https://github.com/rust-lang/rust/issues/72016.
-        #![cfg_attr(not(feature = "asm"), allow(unused, unreachable_code))]
-
         extern "C" {
             #[link_name = "$ss6HasherV8finalizeSiyF"]
-            fn finalize(hasher: *mut Hasher) -> Int;
+            fn finalize();
         }
 
         // `finalize` consumes the `Hasher` instance; so we assume that its
         // memory will be left uninitialized.
         let mut hasher = MaybeUninit::new(self);
-        let hasher_ptr = hasher.as_mut_ptr();
 
         unsafe {
-            let mut result: Int;
-
-            arch_asm! {
-                "aarch64" => {
-                    "bl {}",
-                    sym finalize,
-                    in("x20") hasher_ptr,
-                    out("x0") result,
-                }
-                "x86_64" => {
-                    "call {}",
-                    sym finalize,
-                    in("r13") hasher_ptr,
-                    out("rax") result,
-                }
-            }
-
-            result
+            let result = SwiftCall::new(finalize as *const c_void)
+                .context(hasher.as_mut_ptr())
+                .call();
+
+            result.int(0) as Int
         }
     }
 }
@@ -197,7 +155,6 @@ mod tests {
     use super::*;
     use std::mem;
 
-    #[cfg(feature = "asm")]
     fn hash_i32(i: i32) -> Int {
         let mut hasher = Hasher::default();
         hasher.combine(i);
@@ -214,12 +171,10 @@ mod tests {
     fn default() {
         assert_eq!(Hasher::default()._core, Hasher::default()._core);
 
-        #[cfg(feature = "asm")]
         assert_eq!(Hasher::default().finalize(), Hasher::default().finalize());
     }
 
     #[test]
-    #[cfg(feature = "asm")]
     fn hash_bytes() {
         fn hash(bytes: &[u8]) -> Int {
             let mut hasher = Hasher::default();
@@ -235,7 +190,6 @@ mod tests {
     }
 
     #[test]
-    #[cfg(feature = "asm")]
     fn different_results() {
         fn generate() -> (i32, Int) {
             let value = rand::random();
@@ -260,7 +214,6 @@ mod tests {
     }
 
     #[test]
-    #[cfg(feature = "asm")]
     fn same_results() {
         for _ in 0..100 {
             let value = rand::random();
@@ -14,16 +14,18 @@
 
 #![warn(missing_docs)]
 #![allow(clippy::module_inception)]
-#![cfg_attr(feature = "asm", feature(asm))]
 
 pub use swift_rt as rt;
 pub use swift_sys as sys;
 
-#[macro_use]
-mod macros;
+pub use swift_macros::export;
 
 pub(crate) mod util;
 
+#[doc(hidden)]
+pub mod __private;
+
+mod any;
 mod any_object;
 mod array;
 mod hash;
@@ -35,6 +37,7 @@ mod ptr;
 mod string;
 mod ty;
 
+pub use any::*;
 pub use any_object::*;
 pub use array::*;
 pub use hash::*;
@@ -1,5 +1,5 @@
 use std::{fmt, ptr::NonNull};
-use swift_rt::metadata::{Metadata, MetadataKind};
+use swift_rt::metadata::{ClassMetadata, Metadata, MetadataKind};
 use swift_sys::casting;
 
 /// The metatype for [`Any`](crate::Any), also known as `Any.Type`.
@@ -189,6 +189,54 @@ impl AnyClass {
     pub fn mangled_name(&self) -> &'static str {
         self.0.mangled_name()
     }
+
+    /// Returns the Swift class metadata of this type, or `None` for classes
+    /// defined in Objective-C.
+    #[inline]
+    pub fn class_metadata(&self) -> Option<&'static ClassMetadata> {
+        self.0.metadata().as_class()
+    }
+}
+
+/// Class hierarchy.
+impl AnyClass {
+    /// Returns the superclass of this class, or `None` for root classes.
+    ///
+    /// This is equivalent to `class_getSuperclass` in Objective-C, except
+    /// that superclasses defined in Objective-C are `None`.
+    #[inline]
+    pub fn superclass(self) -> Option<AnyClass> {
+        let superclass = self.class_metadata()?.superclass()?;
+        unsafe {
+            Some(AnyClass::from_metadata(NonNull::from(
+                superclass.as_metadata(),
+            )))
+        }
+    }
+
+    /// Returns an iterator over the superclasses of this class, starting with
+    /// the nearest.
+    #[inline]
+    pub fn superclasses(self) -> impl Iterator<Item = AnyClass> {
+        std::iter::successors(self.superclass(), |class| class.superclass())
+    }
+
+    /// Returns the size in bytes of instances of this class, including the
+    /// object header.
+    ///
+    /// Returns `None` for classes defined in Objective-C.
+    #[inline]
+    pub fn instance_size(self) -> Option<usize> {
+        Some(self.class_metadata()?.instance_size() as usize)
+    }
+
+    /// Returns the alignment in bytes of instances of this class.
+    ///
+    /// Returns `None` for classes defined in Objective-C.
+    #[inline]
+    pub fn instance_align(self) -> Option<usize> {
+        Some(self.class_metadata()?.instance_align_mask() as usize + 1)
+    }
 }
 
 /// Type casting.